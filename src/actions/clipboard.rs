@@ -0,0 +1,67 @@
+use crate::error::{PrismError, Result};
+use base64::Engine;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Platform clipboard tools tried in order, before falling back to OSC 52
+#[cfg(target_os = "linux")]
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+];
+
+#[cfg(target_os = "macos")]
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[&["pbcopy"]];
+
+#[cfg(target_os = "windows")]
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[&["clip"]];
+
+/// How text reached the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Copied {
+    /// A clipboard tool took it
+    Tool,
+    /// The terminal was asked via OSC 52. Many ignore it without a word, so
+    /// the text may not be on the clipboard at all.
+    Terminal,
+}
+
+/// Copy text to the system clipboard. Uses a platform clipboard tool when one
+/// is installed, otherwise asks the terminal to set it via OSC 52.
+pub fn copy_to_clipboard(text: &str) -> Result<Copied> {
+    for command in CLIPBOARD_COMMANDS {
+        if pipe_to_command(command, text).is_ok() {
+            return Ok(Copied::Tool);
+        }
+    }
+    copy_with_osc52(text).map(|()| Copied::Terminal)
+}
+
+fn pipe_to_command(command: &[&str], text: &str) -> std::io::Result<()> {
+    let mut child = Command::new(command[0])
+        .args(&command[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other("clipboard command failed"))
+    }
+}
+
+fn copy_with_osc52(text: &str) -> Result<()> {
+    let encoded = base64::engine::general_purpose::STANDARD.encode(text);
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encoded)
+        .and_then(|_| stdout.flush())
+        .map_err(|e| PrismError::Other(format!("Failed to copy to clipboard: {}", e)))
+}
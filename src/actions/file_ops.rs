@@ -4,6 +4,15 @@ use std::path::Path;
 use std::process::Command;
 
 pub fn open_folder(path: &Path) -> Result<()> {
+    open_with_system(path, "folder")
+}
+
+/// Open a file in whatever the desktop opens its type with
+pub fn open_file(path: &Path) -> Result<()> {
+    open_with_system(path, "file")
+}
+
+fn open_with_system(path: &Path, what: &str) -> Result<()> {
     #[cfg(target_os = "linux")]
     let opener = "xdg-open";
 
@@ -16,11 +25,44 @@ pub fn open_folder(path: &Path) -> Result<()> {
     Command::new(opener)
         .arg(path)
         .spawn()
-        .map_err(|e| PrismError::Other(format!("Failed to open folder: {}", e)))?;
+        .map_err(|e| PrismError::Other(format!("Failed to open {}: {}", what, e)))?;
 
     Ok(())
 }
 
+/// Open a web URL in the default browser. Only http(s) URLs are accepted so
+/// text pulled from data files can't be used to launch local programs, and
+/// the URL goes to the opener as a single argument, never through a shell.
+pub fn open_url(url: &str) -> Result<()> {
+    if !is_web_url(url) {
+        return Err(PrismError::Other(format!("Not a web URL: {}", url)));
+    }
+    url_command(url)
+        .spawn()
+        .map_err(|e| PrismError::Other(format!("Failed to open URL: {}", e)))?;
+    Ok(())
+}
+
+/// The opener for `url`. On Windows `cmd /C start` would read `&`, `|` and
+/// `^` in it as shell syntax, so the URL handler is called directly.
+fn url_command(url: &str) -> Command {
+    #[cfg(target_os = "linux")]
+    let mut cmd = Command::new("xdg-open");
+
+    #[cfg(target_os = "macos")]
+    let mut cmd = Command::new("open");
+
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        let mut cmd = Command::new("rundll32");
+        cmd.arg("url.dll,FileProtocolHandler");
+        cmd
+    };
+
+    cmd.arg(url);
+    cmd
+}
+
 pub fn open_in_editor(path: &Path) -> Result<()> {
     // Try $EDITOR first, then fall back to xdg-open/platform opener
     let editor = env::var("EDITOR").ok();
@@ -49,3 +91,56 @@ pub fn open_in_editor(path: &Path) -> Result<()> {
 
     Ok(())
 }
+
+fn is_web_url(text: &str) -> bool {
+    text.starts_with("https://") || text.starts_with("http://")
+}
+
+/// Find the first http(s) URL in free-form text
+pub fn find_url(text: &str) -> Option<&str> {
+    text.split_whitespace()
+        .map(|word| word.trim_matches(|c: char| matches!(c, '(' | ')' | '<' | '>' | ',' | '"')))
+        .find(|word| is_web_url(word) && word.len() > "https://".len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_url() {
+        assert_eq!(
+            find_url("My Server (https://example.com/home)"),
+            Some("https://example.com/home")
+        );
+        assert_eq!(find_url("see http://a.b for rules"), Some("http://a.b"));
+        assert_eq!(find_url("no link here"), None);
+        assert_eq!(find_url("https://"), None);
+    }
+
+    #[test]
+    fn test_urls_never_reach_a_shell() {
+        for url in [
+            "https://x&calc",
+            "https://x|calc",
+            "https://x^calc",
+            "https://x\"&calc",
+        ] {
+            let cmd = url_command(url);
+            let program = cmd.get_program().to_string_lossy().to_lowercase();
+            assert!(
+                !["cmd", "cmd.exe", "sh", "bash", "powershell"].contains(&program.as_str()),
+                "{} went through {}",
+                url,
+                program
+            );
+            assert_eq!(cmd.get_args().last(), Some(std::ffi::OsStr::new(url)));
+        }
+    }
+
+    #[test]
+    fn test_open_url_rejects_non_web() {
+        assert!(open_url("/etc/passwd").is_err());
+        assert!(open_url("file:///etc/passwd").is_err());
+    }
+}
@@ -1,31 +1,78 @@
 use crate::error::{PrismError, Result};
+use std::collections::BTreeMap;
 use std::process::{Command, Stdio};
 
-pub fn launch_instance(
-    instance_id: &str,
-    account: Option<&str>,
-    server: Option<&str>,
-) -> Result<()> {
-    let mut cmd = Command::new("prismlauncher");
+pub const LAUNCHER_BINARY: &str = "prismlauncher";
 
-    // Detach process output from TUI
-    cmd.stdin(Stdio::null())
-        .stdout(Stdio::null())
-        .stderr(Stdio::null());
+/// Per-launch settings besides the instance itself
+#[derive(Debug, Clone, Default)]
+pub struct LaunchOptions {
+    pub account: Option<String>,
+    pub server: Option<String>,
+    /// Extra environment variables for the launcher process
+    pub env: BTreeMap<String, String>,
+    /// Command the launcher is run through, e.g. `gamemoderun`
+    pub wrapper: Vec<String>,
+}
 
-    cmd.arg("--launch").arg(instance_id);
+/// Arguments passed to PrismLauncher to launch an instance
+pub fn launch_args(instance_id: &str, options: &LaunchOptions) -> Vec<String> {
+    let mut args = vec!["--launch".to_string(), instance_id.to_string()];
 
-    if let Some(profile) = account {
-        cmd.arg("--profile").arg(profile);
+    if let Some(profile) = &options.account {
+        args.push("--profile".to_string());
+        args.push(profile.clone());
     }
 
-    if let Some(server_addr) = server {
-        cmd.arg("--server").arg(server_addr);
+    if let Some(server_addr) = &options.server {
+        args.push("--server".to_string());
+        args.push(server_addr.clone());
     }
 
+    args
+}
+
+/// Full command for scripts and desktop entries: environment variables go
+/// through `env` so the result is a plain argument list
+#[cfg(unix)]
+pub fn launch_argv(instance_id: &str, options: &LaunchOptions) -> Vec<String> {
+    let mut argv = Vec::new();
+    if !options.env.is_empty() {
+        argv.push("env".to_string());
+        argv.extend(options.env.iter().map(|(k, v)| format!("{}={}", k, v)));
+    }
+    argv.extend(options.wrapper.iter().cloned());
+    argv.push(LAUNCHER_BINARY.to_string());
+    argv.extend(launch_args(instance_id, options));
+    argv
+}
+
+pub fn launch_instance(instance_id: &str, options: &LaunchOptions) -> Result<()> {
+    let mut cmd = match options.wrapper.split_first() {
+        Some((program, args)) => {
+            let mut cmd = Command::new(program);
+            cmd.args(args).arg(LAUNCHER_BINARY);
+            cmd
+        }
+        None => Command::new(LAUNCHER_BINARY),
+    };
+
+    // Detach process output from TUI
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    cmd.envs(&options.env);
+    cmd.args(launch_args(instance_id, options));
+
     cmd.spawn().map_err(|e| {
         if e.kind() == std::io::ErrorKind::NotFound {
-            PrismError::LaunchFailed("prismlauncher not found in PATH".into())
+            let program = options
+                .wrapper
+                .first()
+                .map(String::as_str)
+                .unwrap_or(LAUNCHER_BINARY);
+            PrismError::LaunchFailed(format!("{} not found in PATH", program))
         } else {
             PrismError::LaunchFailed(e.to_string())
         }
@@ -33,3 +80,112 @@ pub fn launch_instance(
 
     Ok(())
 }
+
+/// Open PrismLauncher's own window, e.g. to create instances or add accounts
+pub fn open_launcher() -> Result<()> {
+    Command::new(LAUNCHER_BINARY)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                PrismError::LaunchFailed(format!("{} not found in PATH", LAUNCHER_BINARY))
+            } else {
+                PrismError::LaunchFailed(e.to_string())
+            }
+        })?;
+    Ok(())
+}
+
+/// Shell command line equivalent to `launch_instance`, for scripts and desktop entries
+pub fn launch_command_line(instance_id: &str, options: &LaunchOptions) -> String {
+    let env = options
+        .env
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, shell_quote(v)));
+    let command = options
+        .wrapper
+        .iter()
+        .cloned()
+        .chain(std::iter::once(LAUNCHER_BINARY.to_string()))
+        .chain(launch_args(instance_id, options))
+        .map(|arg| shell_quote(&arg));
+    env.chain(command).collect::<Vec<_>>().join(" ")
+}
+
+/// Quote an argument for POSIX shells when it contains special characters
+pub fn shell_quote(arg: &str) -> String {
+    let is_safe = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:@+=,".contains(c));
+    if is_safe {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', r"'\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_launch_command_line_minimal() {
+        assert_eq!(
+            launch_command_line("ATM9", &LaunchOptions::default()),
+            "prismlauncher --launch ATM9"
+        );
+    }
+
+    #[test]
+    fn test_launch_command_line_quotes_args() {
+        let options = LaunchOptions {
+            account: Some("Steve".to_string()),
+            server: Some("mc.example.com:25565".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            launch_command_line("My Pack", &options),
+            "prismlauncher --launch 'My Pack' --profile Steve --server mc.example.com:25565"
+        );
+        assert_eq!(shell_quote("it's"), r"'it'\''s'");
+    }
+
+    #[test]
+    fn test_launch_command_line_env() {
+        let mut options = LaunchOptions::default();
+        options.env.insert("DRI_PRIME".to_string(), "1".to_string());
+        options
+            .env
+            .insert("MESA_LOADER".to_string(), "a b".to_string());
+        assert_eq!(
+            launch_command_line("ATM9", &options),
+            "DRI_PRIME=1 MESA_LOADER='a b' prismlauncher --launch ATM9"
+        );
+    }
+
+    #[test]
+    fn test_launch_command_line_wrapper() {
+        let options = LaunchOptions {
+            wrapper: vec!["gamemoderun".to_string(), "mangohud".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            launch_command_line("ATM9", &options),
+            "gamemoderun mangohud prismlauncher --launch ATM9"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_launch_argv_uses_env() {
+        let mut options = LaunchOptions::default();
+        options.env.insert("DRI_PRIME".to_string(), "1".to_string());
+        assert_eq!(
+            launch_argv("ATM9", &options),
+            ["env", "DRI_PRIME=1", "prismlauncher", "--launch", "ATM9"]
+        );
+    }
+}
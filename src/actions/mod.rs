@@ -1,5 +1,11 @@
+pub mod clipboard;
 pub mod file_ops;
 pub mod launch;
+pub mod process;
+pub mod shortcut;
 
-pub use file_ops::{open_folder, open_in_editor};
-pub use launch::launch_instance;
+pub use clipboard::{Copied, copy_to_clipboard};
+pub use file_ops::{find_url, open_file, open_folder, open_in_editor, open_url};
+pub use launch::{LaunchOptions, launch_command_line, launch_instance, open_launcher};
+pub use process::{find_java_processes, lower_priority, scan_java_processes};
+pub use shortcut::create_shortcut;
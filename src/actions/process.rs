@@ -0,0 +1,92 @@
+use crate::error::{PrismError, Result};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, UpdateKind};
+
+/// Lower a running process's scheduling priority. `nice` follows Unix
+/// niceness (higher is lower priority); Windows uses "below normal".
+pub fn lower_priority(pid: u32, nice: i32) -> Result<()> {
+    let mut command = priority_command(pid, nice);
+    let status = command
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(|e| PrismError::Other(format!("Failed to run priority command: {}", e)))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(PrismError::Other(format!(
+            "Could not change priority of process {}",
+            pid
+        )))
+    }
+}
+
+#[cfg(unix)]
+fn priority_command(pid: u32, nice: i32) -> Command {
+    let mut command = Command::new("renice");
+    command.args(["-n", &nice.to_string(), "-p", &pid.to_string()]);
+    command
+}
+
+#[cfg(windows)]
+fn priority_command(pid: u32, _nice: i32) -> Command {
+    let mut command = Command::new("powershell");
+    command.args([
+        "-NoProfile",
+        "-Command",
+        &format!("(Get-Process -Id {}).PriorityClass = 'BelowNormal'", pid),
+    ]);
+    command
+}
+
+/// Scan for Java processes and match them to known instances (id, path) by path.
+pub fn scan_java_processes(
+    system: &mut sysinfo::System,
+    instances: &[(String, PathBuf)],
+) -> HashMap<String, sysinfo::Pid> {
+    let refresh_kind = ProcessRefreshKind::nothing().with_cmd(UpdateKind::OnlyIfNotSet);
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind);
+    find_java_processes(system, instances)
+}
+
+/// Match already refreshed Java processes to instances by path
+pub fn find_java_processes(
+    system: &sysinfo::System,
+    instances: &[(String, PathBuf)],
+) -> HashMap<String, sysinfo::Pid> {
+    let mut result = HashMap::new();
+
+    for (pid, process) in system.processes() {
+        let cmd = process.cmd();
+        if cmd.is_empty() {
+            continue;
+        }
+
+        let is_java = cmd.iter().any(|arg| {
+            let s = arg.to_string_lossy();
+            s.contains("java") || s.ends_with("/java") || s.ends_with("\\java.exe")
+        });
+        if !is_java {
+            continue;
+        }
+
+        let full_cmd: String = cmd
+            .iter()
+            .map(|a| a.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        for (id, path) in instances {
+            let inst_path = path.to_string_lossy();
+            if full_cmd.contains(&*inst_path) {
+                result.insert(id.clone(), *pid);
+                break;
+            }
+        }
+    }
+
+    result
+}
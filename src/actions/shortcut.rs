@@ -0,0 +1,208 @@
+use crate::actions::launch::LaunchOptions;
+#[cfg(unix)]
+use crate::actions::launch::launch_argv;
+#[cfg(target_os = "macos")]
+use crate::actions::launch::shell_quote;
+#[cfg(target_os = "windows")]
+use crate::actions::launch::{LAUNCHER_BINARY, launch_args};
+use crate::data::Instance;
+use crate::error::{PrismError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Icon used when the instance has no custom icon
+#[cfg(target_os = "linux")]
+const FALLBACK_ICON: &str = "org.prismlauncher.PrismLauncher";
+
+/// Extensions PrismLauncher accepts for custom instance icons
+#[cfg(target_os = "linux")]
+const ICON_EXTENSIONS: &[&str] = &["png", "svg", "jpg", "jpeg", "ico", "gif"];
+
+/// Create an OS launcher entry that starts the instance directly.
+/// Returns the path of the created file.
+pub fn create_shortcut(
+    instance: &Instance,
+    data_dir: &Path,
+    options: &LaunchOptions,
+) -> Result<PathBuf> {
+    write_shortcut(instance, data_dir, options)
+}
+
+#[cfg(target_os = "linux")]
+fn write_shortcut(
+    instance: &Instance,
+    data_dir: &Path,
+    options: &LaunchOptions,
+) -> Result<PathBuf> {
+    let apps_dir = dirs::data_dir()
+        .ok_or_else(|| PrismError::Other("No data directory for desktop entries".into()))?
+        .join("applications");
+    fs::create_dir_all(&apps_dir)?;
+
+    let icon = extract_icon(instance, data_dir)?
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|| FALLBACK_ICON.to_string());
+
+    // Desktop entries have their own quoting rules, so build Exec separately
+    let exec = launch_argv(&instance.id, options)
+        .iter()
+        .map(|arg| desktop_exec_quote(arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let entry = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name={name}\n\
+         Comment=Launch {name} with PrismLauncher\n\
+         Exec={exec}\n\
+         Icon={icon}\n\
+         Terminal=false\n\
+         Categories=Game;\n",
+        name = instance.name.replace('\n', " "),
+        exec = exec,
+        icon = icon,
+    );
+
+    let path = apps_dir.join(format!("prism-tui-{}.desktop", file_safe(&instance.id)));
+    fs::write(&path, entry)?;
+    Ok(path)
+}
+
+#[cfg(target_os = "macos")]
+fn write_shortcut(
+    instance: &Instance,
+    _data_dir: &Path,
+    options: &LaunchOptions,
+) -> Result<PathBuf> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let command = launch_argv(&instance.id, options)
+        .iter()
+        .map(|a| shell_quote(a))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let path = desktop_dir()?.join(format!("{}.command", file_safe(&instance.name)));
+    fs::write(&path, format!("#!/bin/sh\n{}\n", command))?;
+    fs::set_permissions(&path, fs::Permissions::from_mode(0o755))?;
+    Ok(path)
+}
+
+#[cfg(target_os = "windows")]
+fn write_shortcut(
+    instance: &Instance,
+    _data_dir: &Path,
+    options: &LaunchOptions,
+) -> Result<PathBuf> {
+    let path = desktop_dir()?.join(format!("{}.cmd", file_safe(&instance.name)));
+    let quoted: Vec<String> = launch_args(&instance.id, options)
+        .iter()
+        .map(|a| format!("\"{}\"", a))
+        .collect();
+    let env: String = options
+        .env
+        .iter()
+        .map(|(k, v)| format!("set \"{}={}\"\r\n", k, v))
+        .collect();
+    let program: Vec<&str> = options
+        .wrapper
+        .iter()
+        .map(String::as_str)
+        .chain(std::iter::once(LAUNCHER_BINARY))
+        .collect();
+    let script = format!(
+        "@echo off\r\n{}start \"\" {} {}\r\n",
+        env,
+        program.join(" "),
+        quoted.join(" ")
+    );
+    fs::write(&path, script)?;
+    Ok(path)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn desktop_dir() -> Result<PathBuf> {
+    dirs::desktop_dir().ok_or_else(|| PrismError::Other("Desktop folder not found".into()))
+}
+
+/// Copy the instance's custom icon out of the PrismLauncher icons folder so
+/// the entry keeps working if the icon is renamed in the launcher
+#[cfg(target_os = "linux")]
+fn extract_icon(instance: &Instance, data_dir: &Path) -> Result<Option<PathBuf>> {
+    let Some(key) = &instance.icon_key else {
+        return Ok(None);
+    };
+
+    let icons_dir = data_dir.join("icons");
+    let Some((source, ext)) = ICON_EXTENSIONS
+        .iter()
+        .map(|ext| (icons_dir.join(format!("{}.{}", key, ext)), ext))
+        .find(|(path, _)| path.is_file())
+    else {
+        return Ok(None);
+    };
+
+    let target_dir = dirs::data_dir()
+        .ok_or_else(|| PrismError::Other("No data directory for icons".into()))?
+        .join("prism-tui")
+        .join("icons");
+    fs::create_dir_all(&target_dir)?;
+
+    let target = target_dir.join(format!("{}.{}", file_safe(&instance.id), ext));
+    fs::copy(&source, &target)?;
+    Ok(Some(target))
+}
+
+/// Quote an Exec argument per the desktop entry specification
+#[cfg(target_os = "linux")]
+fn desktop_exec_quote(arg: &str) -> String {
+    let needs_quotes = arg
+        .chars()
+        .any(|c| c.is_whitespace() || "\"'\\><~|&;$*?#()`".contains(c));
+    let escaped = arg.replace('%', "%%");
+    if needs_quotes {
+        let inner: String = escaped
+            .chars()
+            .flat_map(|c| match c {
+                '"' | '`' | '$' | '\\' => vec!['\\', c],
+                _ => vec![c],
+            })
+            .collect();
+        format!("\"{}\"", inner)
+    } else {
+        escaped
+    }
+}
+
+/// Replace characters that are awkward in file names
+fn file_safe(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || "-_.".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_safe() {
+        assert_eq!(file_safe("All The Mods 9"), "All_The_Mods_9");
+        assert_eq!(file_safe("a/b\\c"), "a_b_c");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_desktop_exec_quote() {
+        assert_eq!(desktop_exec_quote("ATM9"), "ATM9");
+        assert_eq!(desktop_exec_quote("My Pack"), "\"My Pack\"");
+        assert_eq!(desktop_exec_quote("100%"), "100%%");
+        assert_eq!(desktop_exec_quote("$HOME pack"), "\"\\$HOME pack\"");
+    }
+}
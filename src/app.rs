@@ -1,14 +1,185 @@
-use crate::data::{Account, AppConfig, Instance, LogEntry, PrismConfig, Server};
-use crate::error::Result;
+use crate::actions::LaunchOptions;
+use crate::command::Command;
+use crate::data::atomic::fingerprint;
+use crate::data::storage::free_space;
+use crate::data::{
+    Account, AppConfig, CleanupItem, CrashHint, Instance, InstanceCfgEdit, InstanceStats,
+    JoinConflict, JunkItem, LaunchPreset, LogEntry, ModFile, PrismConfig, PrismSettings,
+    ResourcePack, Screenshot, SearchFolders, SearchItem, SearchKind, SearchTarget, Server,
+    StorageInfo, StorageUsage, TASKS_FILE, Task, Template, WorldBackup, load_crash_reports,
+    load_log_entries, load_log_entries_with_crashes, load_servers, save_tasks, search_files_stamp,
+};
+use crate::error::{PrismError, Result, error_chain, remedy};
 use crate::message::Message;
+use crate::net::lan::LanGame;
+use crate::net::loaders::{self, Loader, LoaderCatalog, LoaderVersion};
+use crate::net::metrics::SharedState;
+use crate::net::mod_source::{ModHit, ModUpdate};
+use crate::net::modrinth::PackUpdate;
+use crate::net::mojang::{ManifestVersion, VersionManifest, VersionType};
+use crate::schedule::Scheduler;
+use crate::tui::{ImagePreview, ImageProtocol};
+use crate::view::TOUR_STEP_COUNT;
 use ratatui::layout::Rect;
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
-use std::time::Instant;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// LAN games are dropped when not announced for this long
+const LAN_GAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Tags each background instance load, so a reload can ignore the rest of
+/// an earlier one
+static NEXT_INSTANCE_LOAD: AtomicU64 = AtomicU64::new(0);
+
+/// How often the free space on the data directory's drive is read again
+pub const FREE_SPACE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How often a followed log file is checked for new lines
+pub const LOG_FOLLOW_INTERVAL: Duration = Duration::from_secs(1);
+
+/// A storage scan younger than this is shown without scanning again
+pub const STORAGE_USAGE_MAX_AGE: Duration = Duration::from_secs(60 * 60);
+
+pub struct DiscoveredLanGame {
+    pub game: LanGame,
+    pub last_seen: Instant,
+}
+
+/// Numbers shown on the debug overlay (F12)
+#[derive(Debug, Default)]
+pub struct DebugStats {
+    pub frames: u64,
+    pub last_render: Duration,
+    /// Moving average over roughly the last 16 frames
+    pub avg_render: Duration,
+    pub max_render: Duration,
+    pub input_messages: u64,
+    pub background_messages: u64,
+    /// Messages from background tasks still waiting in the channel
+    pub queued: usize,
+}
+
+impl DebugStats {
+    pub fn record_frame(&mut self, took: Duration) {
+        self.frames += 1;
+        self.last_render = took;
+        self.max_render = self.max_render.max(took);
+        self.avg_render = if self.frames == 1 {
+            took
+        } else {
+            (self.avg_render * 15 + took) / 16
+        };
+    }
+
+    pub fn record_message(&mut self, msg: &Message) {
+        match msg {
+            Message::Tick => {}
+            msg if msg.is_background() => self.background_messages += 1,
+            _ => self.input_messages += 1,
+        }
+    }
+}
+
+/// What a destructive action would do, listed instead of doing it in
+/// dry-run mode
+#[derive(Debug, Clone)]
+pub struct DryRunPreview {
+    pub title: String,
+    pub lines: Vec<String>,
+    pub scroll: usize,
+    /// Carries the action out once confirmed
+    pub confirm: Message,
+}
+
+/// The full text of an error, opened with Enter on its toast
+#[derive(Debug, Clone)]
+pub struct ErrorDetails {
+    pub chain: Vec<String>,
+    pub path: Option<PathBuf>,
+    pub remedy: Option<&'static str>,
+    pub scroll: usize,
+}
+
+impl ErrorDetails {
+    pub fn new(message: &str, path: Option<PathBuf>) -> Self {
+        Self {
+            chain: error_chain(message),
+            path,
+            remedy: remedy(message),
+            scroll: 0,
+        }
+    }
+
+    /// Plain text for the clipboard or a bug report
+    pub fn to_text(&self) -> String {
+        let mut lines = self.chain.clone();
+        if let Some(path) = &self.path {
+            lines.push(format!("File: {}", path.display()));
+        }
+        if let Some(remedy) = self.remedy {
+            lines.push(format!("Try: {}", remedy));
+        }
+        lines.join("\n")
+    }
+}
+
+/// The log file open on the Logs screen and how far down it was read
+#[derive(Debug, Clone)]
+pub struct LogPosition {
+    pub file: String,
+    pub scroll: usize,
+}
+
+/// A save held back because the file changed on disk after it was loaded
+#[derive(Debug, Clone)]
+pub enum ExternalChange {
+    Servers,
+    InstanceCfg(InstanceCfgEdit),
+}
+
+/// Steps of the new-instance screen, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CreateStep {
+    /// Start from a saved template instead, offered when there are any
+    Template,
+    #[default]
+    MinecraftVersion,
+    Loader,
+    LoaderVersion,
+}
+
+/// Choices made so far on the new-instance screen
+#[derive(Debug, Clone, Default)]
+pub struct InstanceDraft {
+    pub step: CreateStep,
+    /// Offer snapshots and old alphas and betas besides releases
+    pub all_versions: bool,
+    pub minecraft_version: Option<String>,
+    pub loader: Option<Loader>,
+    pub loader_version: Option<String>,
+    /// Cursor in the current step's list
+    pub selected: usize,
+    /// First list row on screen
+    pub scroll: usize,
+}
+
+/// A launch held back until low disk space is confirmed
+pub struct PendingLaunch {
+    pub instance_id: String,
+    pub options: LaunchOptions,
+    pub low_priority: bool,
+}
 
 pub struct RunningInstance {
     pub pid: Option<sysinfo::Pid>,
     pub launched_at: Instant,
+    /// Lower the game's priority once its Java process is found
+    pub low_priority: bool,
+    pub priority_lowered: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -28,13 +199,40 @@ pub enum Screen {
     Servers,
     Logs,
     InstanceDetails,
+    Settings,
     Help,
+    CreateInstance,
+    Mods,
+    Screenshots,
+    ResourcePacks,
+    Worlds,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum LogSource {
     Instance,
+    /// Only the selected instance's crash reports
+    CrashReports,
     Launcher,
+    /// prism-tui's own log
+    App,
+}
+
+impl LogSource {
+    /// Sources that belong to the selected instance
+    pub fn is_per_instance(self) -> bool {
+        matches!(self, LogSource::Instance | LogSource::CrashReports)
+    }
+
+    /// The source after this one on the Logs screen's switcher
+    pub fn next(self) -> LogSource {
+        match self {
+            LogSource::Instance => LogSource::CrashReports,
+            LogSource::CrashReports => LogSource::Launcher,
+            LogSource::Launcher => LogSource::App,
+            LogSource::App => LogSource::Instance,
+        }
+    }
 }
 
 pub struct ClickRegion {
@@ -42,16 +240,43 @@ pub struct ClickRegion {
     pub action: ClickAction,
 }
 
+/// Read by every JVM at startup, so a preset's arguments reach the game
+/// without touching instance.cfg
+const JVM_OPTIONS_VAR: &str = "JAVA_TOOL_OPTIONS";
+
+/// Bounds and default for the log file column width, in percent
+pub const LOG_LIST_MIN_PERCENT: u16 = 15;
+pub const LOG_LIST_MAX_PERCENT: u16 = 60;
+pub const LOG_LIST_DEFAULT_PERCENT: u16 = 30;
+
+/// A list whose scrollbar can be clicked or dragged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScrollTarget {
+    Instances,
+    Accounts,
+    Servers,
+    LogFiles,
+    LogPreview,
+}
+
 #[derive(Debug, Clone)]
 pub enum ClickAction {
+    Scrollbar(ScrollTarget),
     SwitchTab(usize),
     SelectItem(usize),
     GroupHeader(String),
     FooterAction(Message),
     JoinCheckbox,
+    /// A server's join marker in the list
+    JoinServer(usize),
+    CycleAccount,
+    ToggleLogLevel(LogLevel),
     GoBack,
     DismissOverlay,
     SelectLogFile(usize),
+    /// A row in the Worlds screen's backup list
+    SelectBackup(usize),
+    SearchResult(usize),
     ScrollLogPreview,
     Noop,
 }
@@ -65,7 +290,41 @@ pub enum InputMode {
     AddServerAddress,
     EditServerName,
     EditServerAddress,
+    ExportServers,
+    ImportServers,
     ConfirmDelete,
+    ConfirmLogCleanup,
+    ConfirmJunkCleanup,
+    ConfirmDisableMod,
+    ConfirmDeleteScreenshot,
+    ConfirmDeleteResourcePack,
+    ConfirmExternalChange,
+    PickLogSource,
+    PickInstanceFolder,
+    PickLaunchPreset,
+    ConfirmJavaLaunch,
+    ConfirmLowSpaceLaunch,
+    ConfirmServerLaunch,
+    ConfirmSyncSettings,
+    SaveTemplate,
+    NewFromTemplate,
+    SearchMods,
+    PickModHit,
+    SearchPacks,
+    PickPackHit,
+    NameNewInstance,
+    DryRunPreview,
+    ErrorDetails,
+    StorageOverview,
+    SetAlias,
+    EditEnv,
+    EditWrapper,
+    EditPrismWrapper,
+    EditPrismSetting,
+    AddTask,
+    EditTask,
+    GlobalSearch,
+    SetDataDir,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -108,6 +367,17 @@ pub enum LogLevel {
 }
 
 impl LogLevel {
+    pub const ALL: [LogLevel; 4] = [
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Info,
+        LogLevel::Debug,
+    ];
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|l| l.label() == label)
+    }
+
     pub fn label(self) -> &'static str {
         match self {
             LogLevel::Error => "ERROR",
@@ -121,7 +391,15 @@ impl LogLevel {
 #[derive(Debug, Clone)]
 pub struct GroupedInstances {
     pub group_name: Option<String>,
-    pub instances: Vec<Instance>,
+    /// Positions of the group's instances in `App::instances`, in list order
+    pub indices: Vec<usize>,
+}
+
+impl GroupedInstances {
+    /// Key used in `collapsed_groups`
+    pub fn key(&self) -> &str {
+        self.group_name.as_deref().unwrap_or("Ungrouped")
+    }
 }
 
 pub struct App {
@@ -134,22 +412,68 @@ pub struct App {
     // Data
     pub data_dir: PathBuf,
     pub instances: Vec<Instance>,
+    /// Background load the instances are coming from, and how many folders
+    /// it is reading
+    pub instance_load: u64,
+    pub instances_expected: usize,
+    pub instances_loading: bool,
+    /// Instances were loaded since the list was last sorted and grouped
+    pub instances_unplaced: bool,
+    /// Instance to select once all are loaded, unless a key was pressed first
+    pub select_after_load: Option<String>,
+    /// Run once all instances are loaded, e.g. --instance and startup checks
+    pub after_instances_load: Vec<Message>,
     pub grouped_instances: Vec<GroupedInstances>,
+    /// Position of each instance id in `instances`, rebuilt with the groups
+    pub instance_index: HashMap<String, usize>,
     pub accounts: Vec<Account>,
     pub servers: Vec<Server>,
+    /// Why instgroups.json, accounts.json or the current servers.dat
+    /// couldn't be parsed; instances are shown ungrouped, and the accounts
+    /// or servers empty, until a retry (Ctrl+R) succeeds
+    pub groups_error: Option<String>,
+    pub accounts_error: Option<String>,
+    pub servers_error: Option<String>,
+    /// servers.dat as last read or written by us
+    pub servers_fingerprint: Option<u64>,
+    /// The server list as last read or written by us, what a merge with a
+    /// changed servers.dat compares both sides against
+    pub servers_base: Vec<Server>,
+    /// Save waiting for the user to choose how to handle an outside edit
+    pub external_change: Option<ExternalChange>,
 
     // Selection state
     pub selected_instance_index: usize,
+    /// First instance list row on screen, kept so the list only scrolls
+    /// when the selection would leave it
+    pub instance_scroll: usize,
+    /// Instance rows that fit in the list, the step for paging
+    pub instance_page_rows: usize,
     pub selected_account_index: usize,
     pub selected_server_index: usize,
+    pub show_hidden_servers: bool,
+    /// Where the Servers and Logs screens were left, by instance id, so
+    /// opening them again picks up there
+    pub server_positions: HashMap<String, usize>,
+    pub log_positions: HashMap<(LogSource, Option<String>), LogPosition>,
+
+    // LAN worlds announced while the Servers screen is open
+    pub lan_games: Vec<DiscoveredLanGame>,
+    pub selected_lan_index: usize,
+    pub lan_listener: Option<tokio::task::JoinHandle<()>>,
+    pub lan_error: Option<String>,
 
     // Input buffer for dialogs
     pub input_buffer: String,
     pub edit_server_name: String,
     pub edit_server_address: String,
 
-    // Error display
+    // Error and status display
     pub error_message: Option<String>,
+    /// The file the current error is about
+    pub error_path: Option<PathBuf>,
+    pub error_details: Option<ErrorDetails>,
+    pub status_message: Option<String>,
 
     // Active account
     pub active_account: Option<Account>,
@@ -165,6 +489,14 @@ pub struct App {
     pub log_content: Vec<String>,
     pub log_scroll_offset: usize,
     pub log_source: LogSource,
+    /// Source the Logs tab reopens, once any logs were opened
+    pub last_log_source: Option<LogSource>,
+    /// Instance whose logs were shown before picking another on the Logs screen
+    pub previous_log_instance: Option<String>,
+    /// Saved templates while creating an instance from one, and the pick
+    pub templates: Vec<Template>,
+    pub chosen_template: Option<usize>,
+    pub draft: InstanceDraft,
     pub pending_key: Option<char>,
 
     // Sorting
@@ -173,48 +505,170 @@ pub struct App {
 
     // Collapsible groups
     pub collapsed_groups: HashSet<String>,
+    /// Instance ids marked with Space for bulk actions
+    pub marked_instances: HashSet<String>,
 
     // Log search
     pub log_search_query: String,
     pub log_search_matches: Vec<usize>,
     pub log_search_current: usize,
 
+    // Old logs the retention policy would remove, shown while confirming
+    pub log_cleanup_plan: Vec<CleanupItem>,
+    /// Crash reports and caches an instance cleanup would remove
+    pub junk_cleanup_plan: Vec<JunkItem>,
+
+    // Known failures recognized in the loaded log
+    pub log_hints: Vec<CrashHint>,
+    /// Installed jar of the first mod the hints point at
+    pub suspect_mod: Option<PathBuf>,
+
     // Log level filter
     pub log_level_filter: HashSet<LogLevel>,
+    pub show_log_filter_panel: bool,
+    /// Width of the log file column, in percent of the screen
+    pub log_list_percent: u16,
+    pub log_wrap: bool,
+    /// Reload the previewed file as it grows and stay at its end
+    pub log_follow: bool,
+    /// Size of the previewed file when it was loaded, to notice it growing
+    pub log_loaded_size: Option<u64>,
 
     // App config
     pub app_config: AppConfig,
+    /// Refuse anything that changes files or processes, see `Message::is_mutating`
+    pub read_only: bool,
+    /// Preview deletes and cleanups before running them
+    pub dry_run: bool,
+    pub dry_run_preview: Option<DryRunPreview>,
+
+    // Developer overlay
+    pub show_debug_overlay: bool,
+    pub debug_stats: DebugStats,
+
+    // PrismLauncher's own settings (prismlauncher.cfg)
+    pub prism_settings: PrismSettings,
+    pub selected_setting_index: usize,
+
+    // The selected instance's mods folder
+    pub mods: Vec<ModFile>,
+    pub selected_mod_index: usize,
+    /// Mods marked to turn on or off together, by name
+    pub marked_mods: HashSet<String>,
+    /// First mod row on screen
+    pub mod_scroll: usize,
+    /// Results of the last Modrinth/CurseForge search, picked by number
+    pub mod_hits: Vec<ModHit>,
+    /// Newer Modrinth files for the mods shown, from the last check
+    pub mod_updates: Vec<ModUpdate>,
+
+    // The selected instance's screenshots folder
+    pub screenshots: Vec<Screenshot>,
+    pub selected_screenshot_index: usize,
+    pub screenshot_scroll: usize,
+    /// How the terminal draws images, None where it cannot
+    pub image_protocol: Option<ImageProtocol>,
+    /// Image the last frame wants drawn over it
+    pub image_preview: Option<ImagePreview>,
+
+    // The selected instance's resourcepacks folder
+    pub resource_packs: Vec<ResourcePack>,
+    pub selected_resource_pack_index: usize,
+    pub resource_pack_scroll: usize,
+
+    // The selected instance's worlds and the backups that can be restored
+    pub worlds: Vec<String>,
+    pub selected_world_index: usize,
+    pub world_scroll: usize,
+    pub backups: Vec<WorldBackup>,
+    pub selected_backup_index: usize,
+    pub backup_scroll: usize,
+    /// Until the backups' archives have been read
+    pub backups_loading: bool,
+    /// Keys move through the backups rather than the worlds
+    pub backups_focused: bool,
+
+    // Global search (Ctrl+P); the query lives in input_buffer
+    pub search_index: Vec<SearchItem>,
+    /// Servers and logs found by the last background index, and the
+    /// `search_files_stamp` they were read at
+    pub search_files: Vec<SearchItem>,
+    pub search_files_stamp: Option<u64>,
+    pub search_indexing: bool,
+    pub search_results: Vec<usize>,
+    pub selected_search_result: usize,
+
+    // Task list of the instance shown in details
+    pub tasks: Vec<Task>,
+    pub selected_task_index: usize,
 
     // Help scroll
     pub help_scroll_offset: usize,
+    /// Current step of the onboarding tour while it is shown
+    pub tour_step: Option<usize>,
 
     // Group selection (for Tab collapse)
     pub selected_group_index: usize,
+    /// The cursor is on the selected group's header rather than an instance
+    pub group_header_selected: bool,
 
     // Click regions for mouse support
     pub click_regions: Vec<ClickRegion>,
     pub last_click_time: Option<Instant>,
     pub last_click_pos: (u16, u16),
+    /// Scrollbar being dragged and the track it was grabbed on
+    pub scrollbar_drag: Option<(ScrollTarget, Rect)>,
+
+    // Cached folder counts per instance id (refreshed explicitly)
+    pub instance_stats: HashMap<String, InstanceStats>,
+    /// Where each instance's folder really lives, refreshed with its stats
+    pub instance_storage: HashMap<String, StorageInfo>,
+    /// The last scan of the data directory, kept across runs in the cache
+    pub storage_usage: Option<StorageUsage>,
+    pub storage_scanning: bool,
 
     // Running instance processes
     pub running_instances: HashMap<String, RunningInstance>,
-    pub last_process_scan: Instant,
-    pub system: sysinfo::System,
+    /// instance.cfg changes waiting for the game to exit, by instance id
+    pub pending_cfg_edits: HashMap<String, Vec<InstanceCfgEdit>>,
+    /// When the periodic tick work (process scan, free space) runs next
+    pub scheduler: Scheduler,
+    /// Free space on the data directory's drive, read every
+    /// FREE_SPACE_CHECK_INTERVAL
+    pub data_dir_free_space: Option<u64>,
+    pub pending_launch: Option<PendingLaunch>,
+    /// Why the pending launch's server will likely refuse the game
+    pub server_launch_warning: Option<String>,
+    pub process_scan_pending: bool,
+    pub system: Arc<Mutex<sysinfo::System>>,
+    /// Instance states read by the metrics endpoint, when it is enabled
+    pub metrics: Option<SharedState>,
+    /// Hash of what was last handed to the metrics endpoint
+    pub metrics_published: Option<u64>,
+
+    // Modpack update check results per instance id
+    pub pack_updates: HashMap<String, PackUpdate>,
+    pub pack_update_checks_pending: usize,
+    pub http_client: reqwest::Client,
+
+    // Mojang version manifest, fetched on demand
+    pub version_manifest: Option<VersionManifest>,
+    pub version_manifest_pending: bool,
+
+    // Loader version catalogs per (loader, Minecraft version), fetched on demand
+    pub loader_catalogs: HashMap<(Loader, String), LoaderCatalog>,
+    pub loader_catalogs_pending: HashSet<(Loader, String)>,
+
+    // Channel for background tasks to post messages back to the update loop
+    pub message_tx: UnboundedSender<Message>,
+    /// Side effects queued while handling a message, run once `update`
+    /// returns them
+    pub commands: Vec<Command>,
 }
 
 impl App {
-    pub fn new(config: PrismConfig) -> Result<Self> {
-        use crate::data::{load_accounts, load_groups, load_instances};
-
-        let instances_dir = config.instances_dir();
-        let groups = load_groups(&instances_dir)?;
-        let instances = load_instances(&instances_dir, &groups)?;
-        let accounts = load_accounts(&config.accounts_path())?;
-
-        let active_account = accounts.iter().find(|a| a.is_active).cloned();
-
-        let app_config = AppConfig::load();
-
+    pub fn new(config: PrismConfig, message_tx: UnboundedSender<Message>) -> Result<Self> {
+        let (app_config, config_warnings) = AppConfig::load_checked();
         let sort_mode = app_config.default_sort_mode();
         let sort_ascending = app_config.sort_ascending;
 
@@ -223,19 +677,44 @@ impl App {
             screen: Screen::Instances,
             previous_screen: None,
             input_mode: InputMode::Normal,
-            data_dir: config.data_dir,
-            instances,
+            data_dir: config.data_dir.clone(),
+            instances: Vec::new(),
+            instance_load: 0,
+            instances_expected: 0,
+            instances_loading: false,
+            instances_unplaced: false,
+            select_after_load: None,
+            after_instances_load: Vec::new(),
             grouped_instances: Vec::new(),
-            accounts,
+            instance_index: HashMap::new(),
+            accounts: Vec::new(),
             servers: Vec::new(),
+            groups_error: None,
+            accounts_error: None,
+            servers_error: None,
+            servers_fingerprint: None,
+            servers_base: Vec::new(),
+            external_change: None,
             selected_instance_index: 0,
+            instance_scroll: 0,
+            instance_page_rows: 10,
             selected_account_index: 0,
             selected_server_index: 0,
+            show_hidden_servers: false,
+            server_positions: HashMap::new(),
+            log_positions: HashMap::new(),
+            lan_games: Vec::new(),
+            selected_lan_index: 0,
+            lan_listener: None,
+            lan_error: None,
             input_buffer: String::new(),
             edit_server_name: String::new(),
             edit_server_address: String::new(),
             error_message: None,
-            active_account,
+            error_path: None,
+            error_details: None,
+            status_message: None,
+            active_account: None,
             search_query: String::new(),
             filtered_instance_indices: Vec::new(),
             filtered_account_indices: Vec::new(),
@@ -244,40 +723,234 @@ impl App {
             log_content: Vec::new(),
             log_scroll_offset: 0,
             log_source: LogSource::Instance,
+            last_log_source: None,
+            previous_log_instance: None,
+            templates: Vec::new(),
+            chosen_template: None,
+            draft: InstanceDraft::default(),
             pending_key: None,
             sort_mode,
             sort_ascending,
             collapsed_groups: HashSet::new(),
+            marked_instances: HashSet::new(),
             log_search_query: String::new(),
             log_search_matches: Vec::new(),
             log_search_current: 0,
-            log_level_filter: HashSet::new(),
+            log_cleanup_plan: Vec::new(),
+            junk_cleanup_plan: Vec::new(),
+            log_hints: Vec::new(),
+            suspect_mod: None,
+            log_level_filter: app_config
+                .log_view
+                .levels
+                .iter()
+                .filter_map(|l| LogLevel::from_label(l))
+                .collect(),
+            show_log_filter_panel: app_config.log_view.filter_panel,
+            log_list_percent: app_config
+                .log_view
+                .list_percent
+                .clamp(LOG_LIST_MIN_PERCENT, LOG_LIST_MAX_PERCENT),
+            log_wrap: app_config.log_view.wrap,
+            log_follow: app_config.log_view.follow,
+            log_loaded_size: None,
+            read_only: app_config.read_only,
+            dry_run: app_config.dry_run,
+            dry_run_preview: None,
+            show_debug_overlay: false,
+            debug_stats: DebugStats::default(),
             app_config,
+            prism_settings: PrismSettings::default(),
+            selected_setting_index: 0,
+            mods: Vec::new(),
+            selected_mod_index: 0,
+            marked_mods: HashSet::new(),
+            mod_hits: Vec::new(),
+            mod_updates: Vec::new(),
+            mod_scroll: 0,
+            screenshots: Vec::new(),
+            selected_screenshot_index: 0,
+            screenshot_scroll: 0,
+            image_protocol: None,
+            image_preview: None,
+            resource_packs: Vec::new(),
+            selected_resource_pack_index: 0,
+            resource_pack_scroll: 0,
+            worlds: Vec::new(),
+            selected_world_index: 0,
+            world_scroll: 0,
+            backups: Vec::new(),
+            selected_backup_index: 0,
+            backup_scroll: 0,
+            backups_loading: false,
+            backups_focused: false,
+            search_index: Vec::new(),
+            search_files: Vec::new(),
+            search_files_stamp: None,
+            search_indexing: false,
+            search_results: Vec::new(),
+            selected_search_result: 0,
+            tasks: Vec::new(),
+            selected_task_index: 0,
             help_scroll_offset: 0,
+            tour_step: None,
             selected_group_index: 0,
+            group_header_selected: false,
             click_regions: Vec::new(),
             last_click_time: None,
             last_click_pos: (0, 0),
+            scrollbar_drag: None,
+            instance_stats: HashMap::new(),
+            instance_storage: HashMap::new(),
+            storage_usage: None,
+            storage_scanning: false,
             running_instances: HashMap::new(),
-            last_process_scan: Instant::now(),
-            system: sysinfo::System::new(),
+            pending_cfg_edits: HashMap::new(),
+            scheduler: Scheduler::default(),
+            data_dir_free_space: None,
+            pending_launch: None,
+            server_launch_warning: None,
+            process_scan_pending: false,
+            system: Arc::new(Mutex::new(sysinfo::System::new())),
+            metrics: None,
+            metrics_published: None,
+            pack_updates: HashMap::new(),
+            pack_update_checks_pending: 0,
+            http_client: crate::net::build_client()?,
+            version_manifest: None,
+            version_manifest_pending: false,
+            loader_catalogs: HashMap::new(),
+            loader_catalogs_pending: HashSet::new(),
+            message_tx,
+            commands: Vec::new(),
         };
 
-        app.sort_and_group_instances();
-
-        let instance_count = app
-            .grouped_instances
-            .iter()
-            .map(|g| g.instances.len())
-            .sum();
-        app.filtered_instance_indices = (0..instance_count).collect();
-        app.filtered_account_indices = (0..app.accounts.len()).collect();
+        app.load_data(config)?;
 
-        app.selected_account_index = app.accounts.iter().position(|a| a.is_active).unwrap_or(0);
+        if !app.app_config.tour_seen {
+            let last = TOUR_STEP_COUNT - 1;
+            app.tour_step = Some(app.app_config.tour_step.min(last));
+        }
+        let warnings: Vec<&String> = [&app.groups_error, &app.accounts_error]
+            .into_iter()
+            .flatten()
+            .chain(&config_warnings)
+            .collect();
+        if let Some(first) = warnings.first() {
+            let more = match warnings.len() {
+                1 => String::new(),
+                n => format!(" (and {} more)", n - 1),
+            };
+            app.set_error(format!("{}{}", first, more));
+        }
 
         Ok(app)
     }
 
+    /// Read instances, groups, accounts and launcher settings from
+    /// `config`'s data directory, dropping everything read from the previous
+    /// one. UI state, settings and running games are left alone.
+    pub fn load_data(&mut self, config: PrismConfig) -> Result<()> {
+        use crate::data::{instance_dirs, load_accounts, load_groups, load_instance_dirs};
+
+        // A corrupt groups or accounts file costs only what it holds
+        let instances_dir = config.instances_dir();
+        let (groups, groups_error) = match load_groups(&instances_dir) {
+            Ok(groups) => (groups, None),
+            Err(e) => (
+                HashMap::new(),
+                Some(unreadable(&instances_dir.join("instgroups.json"), &e)),
+            ),
+        };
+        // Instances arrive one by one from a background load, so a large
+        // folder on a slow disk doesn't hold up the first frame
+        let dirs = instance_dirs(&instances_dir)?;
+        let instance_load = NEXT_INSTANCE_LOAD.fetch_add(1, Ordering::Relaxed);
+        let instances_expected = dirs.len();
+        let loader_tx = self.message_tx.clone();
+        std::thread::spawn(move || {
+            load_instance_dirs(&dirs, &groups, |instance| {
+                let _ = loader_tx.send(Message::InstanceLoaded {
+                    load: instance_load,
+                    instance: Box::new(instance),
+                });
+            });
+            let _ = loader_tx.send(Message::InstancesLoaded {
+                load: instance_load,
+            });
+        });
+        let (accounts, accounts_error) = match load_accounts(&config.accounts_path()) {
+            Ok(accounts) => (accounts, None),
+            Err(e) => (Vec::new(), Some(unreadable(&config.accounts_path(), &e))),
+        };
+
+        self.prism_settings = PrismSettings::load(&config.data_dir).unwrap_or_default();
+        self.data_dir = config.data_dir;
+        self.instances.clear();
+        self.instance_load = instance_load;
+        self.instances_expected = instances_expected;
+        self.instances_loading = true;
+        self.instances_unplaced = false;
+        self.select_after_load = config.selected_instance;
+        self.instance_stats.clear();
+        self.instance_storage.clear();
+        self.storage_usage = None;
+        self.pack_updates.clear();
+        self.search_index.clear();
+        self.search_files.clear();
+        self.search_files_stamp = None;
+        self.marked_instances.clear();
+        self.selected_instance_index = 0;
+        self.selected_group_index = 0;
+        self.group_header_selected = false;
+        self.instance_scroll = 0;
+        self.servers.clear();
+        self.servers_error = None;
+        self.servers_fingerprint = None;
+        self.servers_base.clear();
+        self.external_change = None;
+        self.log_entries.clear();
+        self.log_content.clear();
+        self.groups_error = groups_error;
+        self.accounts_error = accounts_error;
+        self.active_account = accounts.iter().find(|a| a.is_active).cloned();
+        self.selected_account_index = accounts.iter().position(|a| a.is_active).unwrap_or(0);
+        self.filtered_account_indices = (0..accounts.len()).collect();
+        self.accounts = accounts;
+
+        self.sort_and_group_instances();
+        self.refresh_free_space();
+        Ok(())
+    }
+
+    /// Add an instance from the background load. It shows once
+    /// `place_loaded_instances` sorts it in, so a burst of them is sorted
+    /// and grouped once.
+    pub fn add_loaded_instance(&mut self, instance: Instance) {
+        self.instances.push(instance);
+        self.instances_unplaced = true;
+    }
+
+    /// Sort and group the instances added since the last time, keeping the
+    /// selection on the instance it was on
+    pub fn place_loaded_instances(&mut self) {
+        if !self.instances_unplaced {
+            return;
+        }
+        self.instances_unplaced = false;
+        let selected = (!self.group_header_selected)
+            .then(|| self.selected_instance().map(|i| i.id.clone()))
+            .flatten();
+        self.sort_and_group_instances();
+        if !self.search_query.is_empty() {
+            self.update_search(self.search_query.clone());
+        }
+        if let Some(idx) = selected.and_then(|id| self.visual_index_of(&id)) {
+            self.selected_instance_index = idx;
+            self.selected_group_index = self.group_index_for_instance(idx);
+        }
+    }
+
     pub fn selected_instance(&self) -> Option<&Instance> {
         self.flat_instance_index()
             .and_then(|idx| self.instances.get(idx))
@@ -290,69 +963,162 @@ impl App {
 
     /// Get an instance reference by its visual index (skipping collapsed groups)
     pub fn instance_by_visual_idx(&self, target: usize) -> Option<&Instance> {
-        let mut visual_count = 0;
-        for group in &self.grouped_instances {
-            let group_key = group
-                .group_name
-                .as_deref()
-                .unwrap_or("Ungrouped")
-                .to_string();
-            if self.collapsed_groups.contains(&group_key) {
-                continue;
-            }
-            for instance in &group.instances {
-                if visual_count == target {
-                    return Some(instance);
-                }
-                visual_count += 1;
-            }
-        }
-        None
+        self.flat_index_of_visual(target)
+            .and_then(|idx| self.instances.get(idx))
+    }
+
+    pub fn instance_by_id(&self, id: &str) -> Option<&Instance> {
+        self.instance_index
+            .get(id)
+            .and_then(|&idx| self.instances.get(idx))
+    }
+
+    pub fn instance_by_id_mut(&mut self, id: &str) -> Option<&mut Instance> {
+        self.instance_index
+            .get(id)
+            .and_then(|&idx| self.instances.get_mut(idx))
+    }
+
+    /// Groups that aren't collapsed, in list order
+    fn expanded_groups(&self) -> impl Iterator<Item = &GroupedInstances> {
+        self.grouped_instances
+            .iter()
+            .filter(|g| !self.collapsed_groups.contains(g.key()))
+    }
+
+    /// Position in `instances` of the instance at a visual index
+    fn flat_index_of_visual(&self, target: usize) -> Option<usize> {
+        self.expanded_groups()
+            .flat_map(|g| g.indices.iter().copied())
+            .nth(target)
     }
 
     /// Convert the visual selection index to flat instances index,
     /// accounting for collapsed groups
     fn flat_instance_index(&self) -> Option<usize> {
-        let mut visual_count = 0;
-        for group in &self.grouped_instances {
-            let group_key = group
-                .group_name
-                .as_deref()
-                .unwrap_or("Ungrouped")
-                .to_string();
-            let is_collapsed = self.collapsed_groups.contains(&group_key);
+        // A group's header stands for no instance in particular, so instance
+        // actions leave it alone
+        if self.group_header_selected {
+            return None;
+        }
+        self.flat_index_of_visual(self.selected_instance_index)
+    }
 
-            if is_collapsed {
-                continue;
+    /// Select an instance by id, expanding its group and clearing any
+    /// search that would hide it
+    pub fn select_instance_by_id(&mut self, id: &str) -> bool {
+        let Some(instance) = self.instance_by_id(id) else {
+            return false;
+        };
+        let group_key = instance.group.as_deref().unwrap_or("Ungrouped").to_string();
+        self.collapsed_groups.remove(&group_key);
+        self.clear_search();
+
+        match self.visual_index_of(id) {
+            Some(idx) => {
+                self.selected_instance_index = idx;
+                self.selected_group_index = self.group_index_for_instance(idx);
+                self.group_header_selected = false;
+                true
             }
+            None => false,
+        }
+    }
 
-            for instance in &group.instances {
-                if visual_count == self.selected_instance_index {
-                    return self.instances.iter().position(|i| i.id == instance.id);
-                }
-                visual_count += 1;
-            }
+    /// Position of an instance among those in expanded groups
+    pub fn visual_index_of(&self, id: &str) -> Option<usize> {
+        let target = *self.instance_index.get(id)?;
+        self.expanded_groups()
+            .flat_map(|g| g.indices.iter())
+            .position(|&idx| idx == target)
+    }
+
+    /// Everything the global search can find: instances and accounts, plus
+    /// every instance's servers and all log files as last indexed
+    pub fn build_search_index(&self) -> Vec<SearchItem> {
+        let mut items = Vec::new();
+        for instance in &self.instances {
+            let alias = self.app_config.alias_for(&instance.id).unwrap_or_default();
+            items.push(SearchItem {
+                kind: SearchKind::Instance,
+                label: instance.name.clone(),
+                detail: format!(
+                    "{} {} {}",
+                    instance.minecraft_version,
+                    instance.mod_loader.as_deref().unwrap_or(""),
+                    alias
+                ),
+                target: SearchTarget::Instance(instance.id.clone()),
+            });
         }
-        None
+        for (idx, account) in self.accounts.iter().enumerate() {
+            items.push(SearchItem {
+                kind: SearchKind::Account,
+                label: account.username.clone(),
+                detail: String::new(),
+                target: SearchTarget::Account(idx),
+            });
+        }
+        items.extend(self.search_files.iter().cloned());
+        items
     }
 
-    pub fn total_instance_count(&self) -> usize {
-        self.grouped_instances
+    /// Index the servers and logs in the background unless the last index
+    /// is still current or one is already running
+    pub fn refresh_search_files(&mut self) {
+        let instances: Vec<SearchFolders> = self
+            .instances
             .iter()
-            .map(|g| g.instances.len())
-            .sum()
+            .map(|instance| SearchFolders {
+                instance_id: instance.id.clone(),
+                instance_name: instance.name.clone(),
+                servers_dat: instance.servers_dat_path(),
+                logs_dir: instance.logs_dir(),
+                crash_reports_dir: instance.crash_reports_dir(),
+            })
+            .collect();
+        let launcher_logs = self.data_dir.join("logs");
+        let stamp = search_files_stamp(&instances, &launcher_logs);
+        if self.search_indexing || self.search_files_stamp == Some(stamp) {
+            return;
+        }
+        self.search_indexing = true;
+        self.commands.push(Command::IndexSearchFiles {
+            instances,
+            launcher_logs,
+            stamp,
+        });
+    }
+
+    /// Instances for the Logs screen's picker, the one last switched away
+    /// from first so two packs can be compared back and forth
+    pub fn build_log_instance_index(&self) -> Vec<SearchItem> {
+        let mut instances: Vec<&Instance> = self.instances.iter().collect();
+        if let Some(previous) = &self.previous_log_instance {
+            instances.sort_by_key(|i| &i.id != previous);
+        }
+        instances
+            .into_iter()
+            .map(|instance| SearchItem {
+                kind: SearchKind::LogInstance,
+                label: instance.name.clone(),
+                detail: format!(
+                    "{} {}",
+                    instance.minecraft_version,
+                    instance.mod_loader.as_deref().unwrap_or("")
+                ),
+                target: SearchTarget::InstanceLogs(instance.id.clone()),
+            })
+            .collect()
+    }
+
+    pub fn total_instance_count(&self) -> usize {
+        self.instances.len()
     }
 
     /// Count visible (non-collapsed) instances
     pub fn visible_instance_count(&self) -> usize {
-        self.grouped_instances
-            .iter()
-            .filter(|g| {
-                let key = g.group_name.as_deref().unwrap_or("Ungrouped").to_string();
-                !self.collapsed_groups.contains(&key)
-            })
-            .map(|g| g.instances.len())
-            .sum()
+        self.expanded_groups().map(|g| g.indices.len()).sum()
     }
 
     pub fn selected_account(&self) -> Option<&Account> {
@@ -363,37 +1129,228 @@ impl App {
         self.servers.get(self.selected_server_index)
     }
 
-    pub fn load_servers_for_instance(&mut self) -> Result<()> {
-        use crate::data::load_servers;
+    /// Note where the Servers or Logs screen is, for when it is opened
+    /// again for the same instance
+    pub fn remember_position(&mut self) {
+        let id = self.selected_instance().map(|i| i.id.clone());
+        match self.screen {
+            Screen::Servers => {
+                if let Some(id) = id {
+                    self.server_positions.insert(id, self.selected_server_index);
+                }
+            }
+            Screen::Logs => {
+                let Some(entry) = self.log_entries.get(self.selected_log_index) else {
+                    return;
+                };
+                let id = id.filter(|_| self.log_source.is_per_instance());
+                let position = LogPosition {
+                    file: entry.name.clone(),
+                    scroll: self.log_scroll_offset,
+                };
+                self.log_positions.insert((self.log_source, id), position);
+            }
+            _ => {}
+        }
+    }
 
+    pub fn load_servers_for_instance(&mut self) -> Result<()> {
         if let Some(instance) = self.selected_instance() {
             let servers_path = instance.servers_dat_path();
-            self.servers = load_servers(&servers_path)?;
-            self.selected_server_index = 0;
+            let id = instance.id.clone();
+            self.servers_fingerprint = fingerprint(&servers_path);
+            match load_servers(&servers_path) {
+                Ok(servers) => {
+                    self.servers_base = servers.clone();
+                    self.servers = servers;
+                    self.servers_error = None;
+                }
+                Err(e) => {
+                    let error = unreadable(&servers_path, &e);
+                    self.servers.clear();
+                    self.servers_base.clear();
+                    self.servers_error = Some(error.clone());
+                    return Err(PrismError::Other(error));
+                }
+            }
+            self.selected_server_index = self.server_positions.get(&id).copied().unwrap_or(0);
+            self.clamp_server_selection();
         }
         Ok(())
     }
 
-    pub fn save_servers_for_instance(&self) -> Result<()> {
+    pub fn selected_lan_game(&self) -> Option<&LanGame> {
+        self.lan_games.get(self.selected_lan_index).map(|d| &d.game)
+    }
+
+    /// Record an announcement, refreshing an already known game
+    pub fn record_lan_game(&mut self, game: LanGame) {
+        let now = Instant::now();
+        match self
+            .lan_games
+            .iter_mut()
+            .find(|d| d.game.address == game.address)
+        {
+            Some(known) => {
+                known.game = game;
+                known.last_seen = now;
+            }
+            None => self.lan_games.push(DiscoveredLanGame {
+                game,
+                last_seen: now,
+            }),
+        }
+    }
+
+    /// Drop games that stopped announcing
+    pub fn prune_lan_games(&mut self) {
+        self.lan_games
+            .retain(|d| d.last_seen.elapsed() < LAN_GAME_TIMEOUT);
+        if self.selected_lan_index >= self.lan_games.len() {
+            self.selected_lan_index = 0;
+        }
+    }
+
+    /// Indices of servers shown in the list; hidden entries are skipped
+    /// unless `show_hidden_servers` is set
+    pub fn visible_server_indices(&self) -> Vec<usize> {
+        self.servers
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| self.show_hidden_servers || !s.hidden)
+            .map(|(idx, _)| idx)
+            .collect()
+    }
+
+    pub fn hidden_server_count(&self) -> usize {
+        self.servers.iter().filter(|s| s.hidden).count()
+    }
+
+    /// Visible server `offset` rows away from the selection
+    pub fn adjacent_server_index(&self, offset: isize) -> Option<usize> {
+        let visible = self.visible_server_indices();
+        let pos = visible
+            .iter()
+            .position(|&i| i == self.selected_server_index)?;
+        visible.get(pos.checked_add_signed(offset)?).copied()
+    }
+
+    /// Move the selection onto a visible server if it sits on a hidden one
+    pub fn clamp_server_selection(&mut self) {
+        let visible = self.visible_server_indices();
+        if !visible.contains(&self.selected_server_index) {
+            self.selected_server_index = visible
+                .iter()
+                .copied()
+                .find(|&i| i > self.selected_server_index)
+                .or_else(|| visible.last().copied())
+                .unwrap_or(0);
+        }
+    }
+
+    /// Write the server list. Unless `overwrite` is set, this fails with
+    /// `ChangedOnDisk` when servers.dat changed since it was loaded.
+    pub fn save_servers_for_instance(&mut self, overwrite: bool) -> Result<()> {
         use crate::data::save_servers;
 
+        if let Some(error) = &self.servers_error {
+            // Writing the list shown would replace every server in the file
+            return Err(PrismError::Other(format!("{}; it was left alone", error)));
+        }
         if let Some(instance) = self.selected_instance() {
             let servers_path = instance.servers_dat_path();
+            if !overwrite && fingerprint(&servers_path) != self.servers_fingerprint {
+                return Err(PrismError::ChangedOnDisk(servers_path));
+            }
             save_servers(&servers_path, &self.servers)?;
+            self.servers_fingerprint = fingerprint(&servers_path);
+            self.servers_base = self.servers.clone();
         }
         Ok(())
     }
 
+    /// Join-on-launch conflict for the selected instance, checked against
+    /// the currently loaded server list
+    pub fn join_conflict(&self) -> Option<JoinConflict> {
+        self.selected_instance()
+            .and_then(|i| i.join_conflict(&self.servers))
+    }
+
+    /// Compute folder counts and storage for the selected instance.
+    /// With `force` unset, an existing cache entry is kept.
+    pub fn refresh_instance_stats(&mut self, force: bool) {
+        if let Some(instance) = self.selected_instance() {
+            if !force && self.instance_stats.contains_key(&instance.id) {
+                return;
+            }
+            let id = instance.id.clone();
+            let stats = instance.stats();
+            // Listing the drives is slow enough to notice, so the folder
+            // shows without its storage line until that answers
+            self.commands.push(Command::ReadStorageInfo {
+                id: id.clone(),
+                path: instance.path.clone(),
+                data_dir: self.data_dir.clone(),
+            });
+            self.instance_stats.insert(id, stats);
+        }
+        if let Some(instance) = self.selected_instance_mut() {
+            instance.refresh_unloaded_mods();
+        }
+    }
+
+    pub fn selected_instance_stats(&self) -> Option<InstanceStats> {
+        self.selected_instance()
+            .and_then(|i| self.instance_stats.get(&i.id).copied())
+    }
+
+    pub fn refresh_free_space(&mut self) {
+        self.data_dir_free_space = free_space(&self.data_dir);
+    }
+
+    /// Whether the data directory's drive is below `low_disk_space_mb`
+    pub fn disk_space_low(&self) -> bool {
+        self.data_dir_free_space
+            .zip(self.app_config.low_disk_space())
+            .is_some_and(|(free, limit)| free < limit)
+    }
+
+    pub fn selected_instance_storage(&self) -> Option<&StorageInfo> {
+        self.selected_instance()
+            .and_then(|i| self.instance_storage.get(&i.id))
+    }
+
     pub fn set_error(&mut self, msg: String) {
+        tracing::warn!("{}", msg);
         self.error_message = Some(msg);
+        self.error_path = None;
+    }
+
+    /// Show an error about a particular file, named in its details
+    pub fn set_error_at(&mut self, msg: String, path: &Path) {
+        self.set_error(msg);
+        self.error_path = Some(path.to_path_buf());
     }
 
     pub fn clear_error(&mut self) {
         self.error_message = None;
+        self.error_path = None;
+        self.status_message = None;
+    }
+
+    /// Show a non-error notice, e.g. the result of a successful action
+    pub fn set_status(&mut self, msg: String) {
+        tracing::debug!("{}", msg);
+        self.status_message = Some(msg);
     }
 
     pub fn update_search(&mut self, query: String) {
-        self.search_query = query.to_lowercase();
+        let query = query.to_lowercase();
+        // A longer query only narrows what the shorter one matched
+        let narrowing = !self.search_query.is_empty()
+            && query.len() > self.search_query.len()
+            && query.starts_with(&self.search_query);
+        self.search_query = query;
 
         if self.search_query.is_empty() {
             // Reset to all indices
@@ -401,42 +1358,28 @@ impl App {
             self.filtered_instance_indices = (0..instance_count).collect();
             self.filtered_account_indices = (0..self.accounts.len()).collect();
         } else {
-            // Filter instances - match against name, version, mod_loader, group
-            let mut idx = 0;
-            self.filtered_instance_indices.clear();
-            for group in &self.grouped_instances {
-                let group_key = group
-                    .group_name
-                    .as_deref()
-                    .unwrap_or("Ungrouped")
-                    .to_string();
-                let is_collapsed = self.collapsed_groups.contains(&group_key);
-
-                if is_collapsed {
-                    continue;
-                }
-
-                for instance in &group.instances {
-                    let matches = instance.name.to_lowercase().contains(&self.search_query)
-                        || instance
-                            .minecraft_version
-                            .to_lowercase()
-                            .contains(&self.search_query)
-                        || instance
-                            .mod_loader
-                            .as_ref()
-                            .is_some_and(|l| l.to_lowercase().contains(&self.search_query))
-                        || instance
-                            .group
-                            .as_ref()
-                            .is_some_and(|g| g.to_lowercase().contains(&self.search_query));
-
-                    if matches {
-                        self.filtered_instance_indices.push(idx);
-                    }
-                    idx += 1;
-                }
-            }
+            // Filter instances on their precomputed search keys and aliases
+            let visible: Vec<&Instance> = self
+                .expanded_groups()
+                .flat_map(|g| g.indices.iter().map(|&idx| &self.instances[idx]))
+                .collect();
+            let candidates = if narrowing {
+                std::mem::take(&mut self.filtered_instance_indices)
+            } else {
+                (0..visible.len()).collect()
+            };
+            self.filtered_instance_indices = candidates
+                .into_iter()
+                .filter(|&idx| {
+                    visible.get(idx).is_some_and(|instance| {
+                        instance.search_key.contains(&self.search_query)
+                            || self
+                                .app_config
+                                .alias_for(&instance.id)
+                                .is_some_and(|a| a.contains(&self.search_query))
+                    })
+                })
+                .collect();
 
             // Filter accounts
             self.filtered_account_indices = self
@@ -450,6 +1393,7 @@ impl App {
 
         // Reset selection to first filtered item
         self.selected_instance_index = self.filtered_instance_indices.first().copied().unwrap_or(0);
+        self.group_header_selected = false;
         self.selected_account_index = self.filtered_account_indices.first().copied().unwrap_or(0);
     }
 
@@ -484,6 +1428,12 @@ impl App {
         });
 
         self.grouped_instances = group_instances(&self.instances);
+        self.instance_index = self
+            .instances
+            .iter()
+            .enumerate()
+            .map(|(idx, instance)| (instance.id.clone(), idx))
+            .collect();
 
         // Clamp selected group index
         if !self.grouped_instances.is_empty()
@@ -538,6 +1488,24 @@ impl App {
         self.log_scroll_offset = self.log_search_matches[self.log_search_current];
     }
 
+    /// Position in the filtered content of the next (or previous) line at
+    /// `level`, relative to the top of the preview
+    pub fn adjacent_log_level_line(&self, level: LogLevel, forward: bool) -> Option<usize> {
+        let mut positions = self
+            .filtered_log_content()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, (_, line))| detect_log_level(line) == Some(level))
+            .map(|(pos, _)| pos);
+        if forward {
+            positions.find(|&pos| pos > self.log_scroll_offset)
+        } else {
+            positions
+                .take_while(|&pos| pos < self.log_scroll_offset)
+                .last()
+        }
+    }
+
     pub fn filtered_log_content(&self) -> Vec<(usize, &String)> {
         if self.log_level_filter.is_empty() {
             return self.log_content.iter().enumerate().collect();
@@ -560,22 +1528,22 @@ impl App {
     pub fn selected_group_key(&self) -> Option<String> {
         self.grouped_instances
             .get(self.selected_group_index)
-            .map(|g| g.group_name.as_deref().unwrap_or("Ungrouped").to_string())
+            .map(|g| g.key().to_string())
+    }
+
+    pub fn selected_group_collapsed(&self) -> bool {
+        self.selected_group_key()
+            .is_some_and(|key| self.collapsed_groups.contains(&key))
     }
 
     /// Find which group index a visual instance index belongs to
     pub fn group_index_for_instance(&self, instance_visual_idx: usize) -> usize {
         let mut visual_count = 0;
         for (group_idx, group) in self.grouped_instances.iter().enumerate() {
-            let group_key = group
-                .group_name
-                .as_deref()
-                .unwrap_or("Ungrouped")
-                .to_string();
-            if self.collapsed_groups.contains(&group_key) {
+            if self.collapsed_groups.contains(group.key()) {
                 continue;
             }
-            let group_end = visual_count + group.instances.len();
+            let group_end = visual_count + group.indices.len();
             if instance_visual_idx < group_end {
                 return group_idx;
             }
@@ -588,25 +1556,20 @@ impl App {
     pub fn first_instance_in_group(&self, group_idx: usize) -> Option<usize> {
         let mut visual_count = 0;
         for (idx, group) in self.grouped_instances.iter().enumerate() {
-            let group_key = group
-                .group_name
-                .as_deref()
-                .unwrap_or("Ungrouped")
-                .to_string();
-            if self.collapsed_groups.contains(&group_key) {
+            if self.collapsed_groups.contains(group.key()) {
                 if idx == group_idx {
                     return None;
                 }
                 continue;
             }
             if idx == group_idx {
-                return if group.instances.is_empty() {
+                return if group.indices.is_empty() {
                     None
                 } else {
                     Some(visual_count)
                 };
             }
-            visual_count += group.instances.len();
+            visual_count += group.indices.len();
         }
         None
     }
@@ -615,11 +1578,218 @@ impl App {
         self.click_regions.push(ClickRegion { rect, action });
     }
 
+    /// Tick interval for the event loop: fast while games are running
+    /// (process tracking), slower when idle to save power
+    pub fn tick_rate(&self) -> Duration {
+        // Loading instances are placed in the list on each tick
+        if self.running_instances.is_empty() && !self.instances_loading {
+            self.app_config.idle_tick_rate()
+        } else {
+            self.app_config.tick_rate()
+        }
+    }
+
     pub fn is_instance_running(&self, instance_id: &str) -> bool {
         self.running_instances.contains_key(instance_id)
     }
 
+    /// Latest Minecraft release when a vanilla instance is on an older one
+    pub fn newer_minecraft_release(&self, instance: &Instance) -> Option<&str> {
+        if instance.mod_loader.is_some() {
+            return None;
+        }
+        self.version_manifest
+            .as_ref()?
+            .newer_release(&instance.minecraft_version)
+    }
+
+    /// Newest stable loader version when the instance's loader is older
+    pub fn newer_loader_version(&self, instance: &Instance) -> Option<&LoaderVersion> {
+        let loader = Loader::from_name(instance.mod_loader.as_deref()?)?;
+        let installed = instance.mod_loader_version.as_deref()?;
+        let catalog = self
+            .loader_catalogs
+            .get(&(loader, instance.minecraft_version.clone()))?;
+        loaders::newer_stable(catalog, installed)
+    }
+
+    /// The CurseForge API key, if one is set
+    pub fn curseforge_key(&self) -> Option<String> {
+        self.app_config
+            .curseforge_api_key
+            .clone()
+            .filter(|key| !key.trim().is_empty())
+    }
+
+    /// Launch settings for an instance: active account, env overrides and
+    /// the given server
+    pub fn launch_options(&self, instance_id: &str, server: Option<String>) -> LaunchOptions {
+        LaunchOptions {
+            account: self.active_account.as_ref().map(|a| a.username.clone()),
+            server,
+            env: self.app_config.env_for(instance_id),
+            wrapper: self.app_config.wrapper_for(instance_id),
+        }
+    }
+
+    /// `launch_options` with a preset's server, account, wrapper and JVM
+    /// arguments in place of the usual ones
+    pub fn preset_launch_options(
+        &self,
+        instance_id: &str,
+        server: Option<String>,
+        preset: &LaunchPreset,
+    ) -> LaunchOptions {
+        let mut options = self.launch_options(instance_id, preset.server.clone().or(server));
+        if let Some(account) = &preset.account {
+            options.account = Some(account.clone());
+        }
+        if let Some(wrapper) = &preset.wrapper {
+            options.wrapper = wrapper.split_whitespace().map(str::to_string).collect();
+        }
+        if let Some(args) = &preset.jvm_args {
+            let value = match options.env.get(JVM_OPTIONS_VAR) {
+                Some(existing) => format!("{} {}", existing, args),
+                None => args.clone(),
+            };
+            options.env.insert(JVM_OPTIONS_VAR.to_string(), value);
+        }
+        options
+    }
+
+    /// Presets configured for the selected instance, by name
+    /// Minecraft versions the new-instance screen offers, newest first
+    pub fn draft_versions(&self) -> Vec<&ManifestVersion> {
+        self.version_manifest
+            .iter()
+            .flat_map(|m| &m.versions)
+            .filter(|v| self.draft.all_versions || v.kind == VersionType::Release)
+            .collect()
+    }
+
+    /// Loader choices on the new-instance screen, vanilla first
+    pub fn draft_loaders(&self) -> Vec<Option<Loader>> {
+        std::iter::once(None).chain(Loader::ALL.map(Some)).collect()
+    }
+
+    /// Versions of the drafted loader for the drafted Minecraft version,
+    /// once they are fetched
+    pub fn draft_loader_versions(&self) -> Option<&LoaderCatalog> {
+        let loader = self.draft.loader?;
+        let minecraft_version = self.draft.minecraft_version.clone()?;
+        self.loader_catalogs.get(&(loader, minecraft_version))
+    }
+
+    /// Rows in the new-instance screen's current step
+    pub fn draft_len(&self) -> usize {
+        match self.draft.step {
+            // "Blank instance", then the templates
+            CreateStep::Template => self.templates.len() + 1,
+            CreateStep::MinecraftVersion => self.draft_versions().len(),
+            CreateStep::Loader => self.draft_loaders().len(),
+            CreateStep::LoaderVersion => self.draft_loader_versions().map_or(0, |c| c.len()),
+        }
+    }
+
+    pub fn selected_launch_presets(&self) -> Vec<(&str, &LaunchPreset)> {
+        self.selected_instance()
+            .and_then(|i| self.app_config.launch_presets.get(&i.id))
+            .map(|presets| presets.iter().map(|(n, p)| (n.as_str(), p)).collect())
+            .unwrap_or_default()
+    }
+
+    pub fn selected_instance_alias(&self) -> Option<&str> {
+        self.selected_instance()
+            .and_then(|i| self.app_config.alias_for(&i.id))
+    }
+
+    /// Copy the log viewer state into the config and save it, once the
+    /// Logs screen is left
+    pub fn save_log_view(&mut self) {
+        let view = &mut self.app_config.log_view;
+        view.levels = LogLevel::ALL
+            .iter()
+            .filter(|l| self.log_level_filter.contains(l))
+            .map(|l| l.label().to_string())
+            .collect();
+        view.filter_panel = self.show_log_filter_panel;
+        view.list_percent = self.log_list_percent;
+        view.wrap = self.log_wrap;
+        view.follow = self.log_follow;
+        self.save_config();
+    }
+
+    /// Note the loaded log file as the last one opened for its source,
+    /// saved with the rest by `save_log_view`
+    pub fn note_log_file(&mut self) {
+        let Some(entry) = self.log_entries.get(self.selected_log_index) else {
+            return;
+        };
+        let name = entry.name.clone();
+        match self.log_source {
+            LogSource::Launcher => self.app_config.log_view.launcher_file = Some(name),
+            LogSource::App | LogSource::CrashReports => {}
+            LogSource::Instance => {
+                if let Some(id) = self.selected_instance().map(|i| i.id.clone()) {
+                    self.app_config.log_view.instance_files.insert(id, name);
+                }
+            }
+        }
+    }
+
+    /// Log files for a source; instance logs include its crash reports
+    pub fn load_log_entries_for(&self, source: LogSource) -> Result<Vec<LogEntry>> {
+        match source {
+            LogSource::Instance => match self.selected_instance() {
+                Some(instance) => load_log_entries_with_crashes(
+                    &instance.logs_dir(),
+                    &instance.crash_reports_dir(),
+                ),
+                None => Ok(Vec::new()),
+            },
+            LogSource::CrashReports => match self.selected_instance() {
+                Some(instance) => load_crash_reports(&instance.crash_reports_dir()),
+                None => Ok(Vec::new()),
+            },
+            LogSource::Launcher => load_log_entries(&self.data_dir.join("logs")),
+            LogSource::App => load_log_entries(&crate::logging::log_dir()),
+        }
+    }
+
+    /// Name of the log file last opened for the current log source
+    pub fn last_log_file(&self) -> Option<&str> {
+        let view = &self.app_config.log_view;
+        match self.log_source {
+            LogSource::Launcher => view.launcher_file.as_deref(),
+            LogSource::App | LogSource::CrashReports => None,
+            LogSource::Instance => self
+                .selected_instance()
+                .and_then(|i| view.instance_files.get(&i.id))
+                .map(String::as_str),
+        }
+    }
+
+    /// Write the details task list back to the instance's sidecar file
+    pub fn save_tasks(&mut self) {
+        if self.read_only {
+            return;
+        }
+        let Some(path) = self.selected_instance().map(|i| i.path.clone()) else {
+            return;
+        };
+        if let Err(e) = save_tasks(&path, &self.tasks) {
+            self.set_error_at(
+                format!("Failed to save tasks: {}", e),
+                &path.join(TASKS_FILE),
+            );
+        }
+    }
+
     pub fn save_config(&self) {
+        // Choices made in read-only mode last until prism-tui exits
+        if self.read_only {
+            return;
+        }
         let mut config = self.app_config.clone();
         config.default_sort = self.sort_mode.label().to_string();
         config.sort_ascending = self.sort_ascending;
@@ -634,29 +1804,18 @@ impl App {
         let mut visual_idx = 0;
 
         for group in &self.grouped_instances {
-            let group_key = group
-                .group_name
-                .as_deref()
-                .unwrap_or("Ungrouped")
-                .to_string();
-            let is_collapsed = self.collapsed_groups.contains(&group_key);
+            let is_collapsed = self.collapsed_groups.contains(group.key());
 
             // Check if group header should be shown
-            let show_header = if is_collapsed {
-                true
-            } else {
-                group
-                    .instances
-                    .iter()
-                    .enumerate()
-                    .any(|(i, _)| filtered_set.contains(&(visual_idx + i)))
-            };
+            let show_header = is_collapsed
+                || (visual_idx..visual_idx + group.indices.len())
+                    .any(|i| filtered_set.contains(&i));
 
             if show_header {
                 rows.push(VisualRow::GroupHeader {
-                    key: group_key,
+                    key: group.key().to_string(),
                     collapsed: is_collapsed,
-                    count: group.instances.len(),
+                    count: group.indices.len(),
                 });
             }
 
@@ -664,7 +1823,7 @@ impl App {
                 continue;
             }
 
-            for _ in &group.instances {
+            for _ in &group.indices {
                 if filtered_set.contains(&visual_idx) {
                     rows.push(VisualRow::Instance(visual_idx));
                 }
@@ -676,7 +1835,16 @@ impl App {
     }
 }
 
-fn detect_log_level(line: &str) -> Option<LogLevel> {
+/// Warning for a PrismLauncher file that failed to load
+fn unreadable(path: &Path, error: &PrismError) -> String {
+    format!(
+        "Couldn't read {}: {} (Ctrl+R retries)",
+        path.display(),
+        error
+    )
+}
+
+pub(crate) fn detect_log_level(line: &str) -> Option<LogLevel> {
     if line.contains("ERROR") || line.contains("[ERROR]") {
         Some(LogLevel::Error)
     } else if line.contains("WARN") || line.contains("[WARN]") {
@@ -693,20 +1861,20 @@ fn detect_log_level(line: &str) -> Option<LogLevel> {
 fn group_instances(instances: &[Instance]) -> Vec<GroupedInstances> {
     use std::collections::HashMap;
 
-    let mut groups: HashMap<Option<String>, Vec<Instance>> = HashMap::new();
+    let mut groups: HashMap<Option<&str>, Vec<usize>> = HashMap::new();
 
-    for instance in instances {
+    for (idx, instance) in instances.iter().enumerate() {
         groups
-            .entry(instance.group.clone())
+            .entry(instance.group.as_deref())
             .or_default()
-            .push(instance.clone());
+            .push(idx);
     }
 
     let mut result: Vec<GroupedInstances> = groups
         .into_iter()
-        .map(|(group_name, instances)| GroupedInstances {
-            group_name,
-            instances,
+        .map(|(group_name, indices)| GroupedInstances {
+            group_name: group_name.map(str::to_string),
+            indices,
         })
         .collect();
 
@@ -727,17 +1895,28 @@ mod tests {
     use std::path::PathBuf;
 
     fn create_test_instance(id: &str, name: &str, group: Option<&str>) -> Instance {
-        Instance {
+        let mut instance = Instance {
             id: id.to_string(),
             name: name.to_string(),
             path: PathBuf::from(format!("/tmp/{}", id)),
             group: group.map(|s| s.to_string()),
             minecraft_version: "1.20.1".to_string(),
             mod_loader: None,
+            mod_loader_version: None,
             total_time_played: 0,
             last_launch: None,
             server_join: None,
-        }
+            world_join: None,
+            managed_pack: None,
+            icon_key: None,
+            wrapper_command: None,
+            setting_overrides: HashMap::new(),
+            cfg_values: HashMap::new(),
+            unloaded_mods: 0,
+            search_key: String::new(),
+        };
+        instance.refresh_search_key();
+        instance
     }
 
     #[test]
@@ -768,7 +1947,7 @@ mod tests {
         let grouped = group_instances(&instances);
 
         assert_eq!(grouped.len(), 1);
-        assert_eq!(grouped[0].instances.len(), 2);
+        assert_eq!(grouped[0].indices, [0, 1]);
     }
 
     #[test]
@@ -0,0 +1,122 @@
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(version, about)]
+pub struct Cli {
+    /// PrismLauncher data directory, skipping automatic discovery
+    #[arg(long, global = true, value_name = "DIR")]
+    pub data_dir: Option<PathBuf>,
+
+    /// Open the TUI with this instance (alias, folder id, or name) selected
+    #[arg(long, short)]
+    pub instance: Option<String>,
+
+    /// Open the TUI on this screen
+    #[arg(long, short, value_enum)]
+    pub screen: Option<StartScreen>,
+
+    /// Show a read-only status view of running instances and recent
+    /// launches instead of the TUI, e.g. for a tmux pane
+    #[arg(long)]
+    pub dashboard: bool,
+
+    /// Print the dashboard once as plain text and exit
+    #[arg(long)]
+    pub once: bool,
+
+    /// Seconds between dashboard refreshes
+    #[arg(long, value_name = "SECS", default_value_t = 2)]
+    pub interval: u64,
+
+    /// Draw with ANSI 16 colors and ASCII only
+    #[arg(long)]
+    pub plain: bool,
+
+    /// Print selection changes and messages as plain lines instead of
+    /// drawing the interface, for screen readers
+    #[arg(long)]
+    pub screen_reader: bool,
+
+    /// Browse and launch only: no edits, kills or deletes, and settings
+    /// are not saved. `clean-logs` only lists what it would do.
+    #[arg(long, global = true)]
+    pub read_only: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Screens the TUI can be opened on
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StartScreen {
+    Instances,
+    Details,
+    Servers,
+    Accounts,
+    Logs,
+    LauncherLogs,
+    /// prism-tui's own log
+    AppLogs,
+    Settings,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Launch an instance by alias, id, or name without opening the TUI
+    Launch {
+        /// Instance alias, folder id, or name
+        instance: String,
+        /// Server address to join on launch
+        #[arg(long)]
+        server: Option<String>,
+        /// Ping the server first and stop if it is offline or runs another
+        /// Minecraft version
+        #[arg(long, requires = "server")]
+        ping: bool,
+    },
+    /// Print a Markdown report of an instance for support threads
+    Report {
+        /// Instance alias, folder id, or name
+        instance: String,
+        /// Write the report to this file instead of stdout
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Send a command to a running prism-tui, e.g. `ctl launch atm9` or
+    /// `ctl focus logs atm9`
+    Ctl {
+        #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+    /// Move prism-tui's settings between machines
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+    /// Apply the log retention policy from config.toml
+    CleanLogs {
+        /// Only this instance (alias, folder id, or name) instead of all
+        instance: Option<String>,
+        /// List what would be removed without touching anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigAction {
+    /// Write the settings (aliases, wrappers, quick launch...) to one file
+    Export {
+        /// Write to this file instead of stdout
+        #[arg(long, short, value_name = "FILE")]
+        output: Option<PathBuf>,
+    },
+    /// Merge an exported file into the settings on this machine
+    Import {
+        file: PathBuf,
+        /// Discard the current settings instead of merging
+        #[arg(long)]
+        replace: bool,
+    },
+}
@@ -0,0 +1,617 @@
+//! Side effects asked for by `update`. Handlers only describe the work as a
+//! `Command`; the main loop hands each one to a `Runner`, which does it on a
+//! tokio task and posts the outcome back as a `Message`.
+
+use crate::actions::scan_java_processes;
+use crate::app::App;
+use crate::data::{
+    CleanupItem, Instance, JunkItem, PackPlatform, STORAGE_USAGE_CACHE, SearchFolders, Template,
+    WorldBackup, apply_junk_cleanup, apply_log_cleanup, backup_world, create_from_template,
+    find_backups, index_search_files, load_log_entries, plan_junk_cleanup, plan_log_cleanup,
+    restore_backup, save_template, split_address, storage_info, storage_usage, verify_instance,
+};
+use crate::error::PrismError;
+use crate::message::Message;
+use crate::net::loaders::{self, Loader};
+use crate::net::mod_source::{self, ModHit, ModUpdate};
+use crate::net::{blocklist, cache, curseforge, modpack, modrinth, mojang, ping};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio::sync::mpsc::UnboundedSender;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    /// Ask Modrinth or CurseForge whether a managed pack has a newer
+    /// version. Answered by `Message::PackUpdateChecked`.
+    CheckPackUpdate {
+        instance_id: String,
+        platform: PackPlatform,
+        project_id: String,
+        version_id: String,
+        curseforge_key: Option<String>,
+    },
+    /// Load the Mojang version manifest, cached on disk.
+    /// Answered by `Message::VersionManifestLoaded`.
+    FetchVersionManifest,
+    /// Load the versions of a loader for one Minecraft version.
+    /// Answered by `Message::LoaderVersionsLoaded`.
+    FetchLoaderVersions {
+        loader: Loader,
+        minecraft_version: String,
+    },
+    /// Resolve a server address and compare it with the other servers'
+    /// (name, address), optionally against Mojang's blocklist too.
+    /// Answered by `Message::ServerCheckComplete`.
+    CheckServer {
+        address: String,
+        others: Vec<(String, String)>,
+        check_blocklist: bool,
+        report_ok: bool,
+    },
+    /// Look for the Java processes of these (id, folder) instances.
+    /// Answered by `Message::ProcessScanComplete`.
+    ScanProcesses { instances: Vec<(String, PathBuf)> },
+    /// Ask a game's process to stop with SIGTERM, or kill it outright where
+    /// that signal can't be sent, as on Windows. Not answered; the instance
+    /// is already marked stopped.
+    KillProcess { pid: sysinfo::Pid },
+    /// Remove or compress the `planned` logs, then those older than each
+    /// target folder's maximum age. Answered by `Message::LogCleanupFinished`.
+    CleanLogs {
+        /// Files already planned, and shown, as they are
+        planned: Vec<CleanupItem>,
+        targets: Vec<(PathBuf, Duration)>,
+        compress: bool,
+    },
+    /// Ask a server for its version and players, before launching into it
+    /// or to show on the Servers screen. Answered by `Message::ServerPinged`.
+    PingServer {
+        address: String,
+        protocol: Option<i32>,
+        for_launch: bool,
+    },
+    /// Search Modrinth, and CurseForge given a key, for mods.
+    /// Answered by `Message::ModSearchDone`.
+    SearchMods {
+        query: String,
+        minecraft_version: String,
+        loader: Loader,
+        curseforge_key: Option<String>,
+    },
+    /// Download a mod found by `SearchMods` into `mods_dir`.
+    /// Answered by `Message::ModInstalled`.
+    InstallMod {
+        hit: ModHit,
+        minecraft_version: String,
+        loader: Loader,
+        mods_dir: PathBuf,
+        curseforge_key: Option<String>,
+    },
+    /// Find the Modrinth page of the mod file at `path`.
+    /// Answered by `Message::ModPageFound`.
+    FindModPage { name: String, path: PathBuf },
+    /// Look up the mods in `mods_dir` on Modrinth for newer files.
+    /// Answered by `Message::ModUpdatesChecked`.
+    CheckModUpdates {
+        mods_dir: PathBuf,
+        minecraft_version: String,
+        loader: Loader,
+    },
+    /// Swap mods for the newer files found by `CheckModUpdates`, one at a
+    /// time. Answered by `Message::ModsUpdated`.
+    UpdateMods {
+        updates: Vec<ModUpdate>,
+        mods_dir: PathBuf,
+    },
+    /// Read the backup mods' archives in `game_dir` for restorable worlds.
+    /// Answered by `Message::BackupsFound`.
+    FindBackups {
+        instance_id: String,
+        game_dir: PathBuf,
+    },
+    /// Zip the world in `world_dir` into the game folder's backups/.
+    /// Answered by `Message::WorldBackedUp`.
+    BackupWorld {
+        world_dir: PathBuf,
+        game_dir: PathBuf,
+    },
+    /// Unpack a world backup into `saves_dir`.
+    /// Answered by `Message::BackupRestored`.
+    RestoreBackup {
+        backup: WorldBackup,
+        saves_dir: PathBuf,
+    },
+    /// Find what a cleanup of `instance` would remove and size it.
+    /// Answered by `Message::JunkCleanupPlanned`.
+    PlanJunkCleanup {
+        instance: Box<Instance>,
+        crash_report_age: Option<Duration>,
+    },
+    /// Remove a cleanup plan's files from the instance `name`.
+    /// Answered by `Message::JunkCleaned`.
+    CleanJunk { name: String, plan: Vec<JunkItem> },
+    /// Read the servers and log files the global search lists.
+    /// Answered by `Message::SearchFilesIndexed`.
+    IndexSearchFiles {
+        instances: Vec<SearchFolders>,
+        launcher_logs: PathBuf,
+        stamp: u64,
+    },
+    /// Hash the libraries and assets of the instance in `instance_path`.
+    /// Answered by `Message::InstanceVerified`.
+    VerifyInstance {
+        name: String,
+        instance_path: PathBuf,
+        data_dir: PathBuf,
+    },
+    /// Size the data directory, each instance and the shared folders, and
+    /// cache the result. Answered by `Message::StorageScanned`.
+    ScanStorage {
+        data_dir: PathBuf,
+        instances_dir: PathBuf,
+        /// Name and folder of every instance
+        instances: Vec<(String, PathBuf)>,
+    },
+    /// Save the instance in `instance_path` as the template `name` in `dir`.
+    /// Answered by `Message::TemplateSaved`.
+    SaveTemplate {
+        instance_path: PathBuf,
+        mods_dir: PathBuf,
+        dir: PathBuf,
+        name: String,
+    },
+    /// Create the instance `name` in `instances_dir` from a template.
+    /// Answered by `Message::InstanceCreated`.
+    CreateFromTemplate {
+        template: Template,
+        instances_dir: PathBuf,
+        name: String,
+    },
+    /// Search Modrinth, and CurseForge given a key, for modpacks.
+    /// Answered by `Message::PackSearchDone`.
+    SearchPacks {
+        query: String,
+        curseforge_key: Option<String>,
+    },
+    /// Install a modpack found by `SearchPacks` as a new instance in
+    /// `instances_dir`. Answered by `Message::PackInstalled`.
+    InstallPack {
+        hit: ModHit,
+        instances_dir: PathBuf,
+        curseforge_key: Option<String>,
+    },
+    /// Find the real folder and drive of an instance.
+    /// Answered by `Message::StorageInfoRead`.
+    ReadStorageInfo {
+        id: String,
+        path: PathBuf,
+        data_dir: PathBuf,
+    },
+}
+
+/// What commands share while they run, and where they post their results
+pub struct Runner {
+    client: reqwest::Client,
+    system: Arc<Mutex<sysinfo::System>>,
+    tx: UnboundedSender<Message>,
+}
+
+impl Runner {
+    pub fn new(app: &App) -> Self {
+        Self {
+            client: app.http_client.clone(),
+            system: Arc::clone(&app.system),
+            tx: app.message_tx.clone(),
+        }
+    }
+
+    /// Start `command` without waiting for it. Blocking work such as file
+    /// system walks goes to tokio's blocking pool.
+    pub fn run(&self, command: Command) {
+        let client = self.client.clone();
+        let tx = self.tx.clone();
+        match command {
+            Command::CheckPackUpdate {
+                instance_id,
+                platform,
+                project_id,
+                version_id,
+                curseforge_key,
+            } => {
+                tokio::spawn(async move {
+                    let result = if platform == PackPlatform::CurseForge {
+                        let key = curseforge_key.unwrap_or_default();
+                        curseforge::check_pack_update(&client, &key, &project_id, &version_id).await
+                    } else {
+                        modrinth::check_pack_update(&client, &project_id, &version_id).await
+                    }
+                    .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::PackUpdateChecked {
+                        instance_id,
+                        result,
+                    });
+                });
+            }
+            Command::FetchVersionManifest => {
+                tokio::spawn(async move {
+                    let result = mojang::version_manifest(&client)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::VersionManifestLoaded(result));
+                });
+            }
+            Command::FetchLoaderVersions {
+                loader,
+                minecraft_version,
+            } => {
+                tokio::spawn(async move {
+                    let result = loaders::loader_versions(&client, loader, &minecraft_version)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::LoaderVersionsLoaded {
+                        loader,
+                        minecraft_version,
+                        result,
+                    });
+                });
+            }
+            Command::CheckServer {
+                address,
+                others,
+                check_blocklist,
+                report_ok,
+            } => {
+                tokio::spawn(async move {
+                    let warnings =
+                        server_warnings(&client, &address, &others, check_blocklist).await;
+                    let _ = tx.send(Message::ServerCheckComplete {
+                        address,
+                        warnings,
+                        report_ok,
+                    });
+                });
+            }
+            Command::ScanProcesses { instances } => {
+                let system = Arc::clone(&self.system);
+                tokio::task::spawn_blocking(move || {
+                    let found_pids = match system.lock() {
+                        Ok(mut system) => scan_java_processes(&mut system, &instances),
+                        Err(_) => HashMap::new(),
+                    };
+                    let _ = tx.send(Message::ProcessScanComplete(found_pids));
+                });
+            }
+            Command::KillProcess { pid } => {
+                let system = Arc::clone(&self.system);
+                tokio::task::spawn_blocking(move || {
+                    if let Ok(system) = system.lock()
+                        && let Some(process) = system.process(pid)
+                    {
+                        let killed = process.kill_with(sysinfo::Signal::Term).unwrap_or(false);
+                        if !killed {
+                            process.kill();
+                        }
+                    }
+                });
+            }
+            Command::CleanLogs {
+                planned,
+                targets,
+                compress,
+            } => {
+                tokio::task::spawn_blocking(move || {
+                    let result = apply_log_cleanup(&planned)
+                        .and_then(|cleaned| {
+                            targets.iter().try_fold(cleaned, |total, (dir, max_age)| {
+                                let entries = load_log_entries(dir)?;
+                                let plan = plan_log_cleanup(
+                                    &entries,
+                                    *max_age,
+                                    compress,
+                                    SystemTime::now(),
+                                );
+                                Ok::<_, PrismError>(total + apply_log_cleanup(&plan)?)
+                            })
+                        })
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::LogCleanupFinished(result));
+                });
+            }
+            Command::PingServer {
+                address,
+                protocol,
+                for_launch,
+            } => {
+                tokio::spawn(async move {
+                    let result = ping::ping(&address, protocol)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::ServerPinged {
+                        address,
+                        result,
+                        for_launch,
+                    });
+                });
+            }
+            Command::SearchMods {
+                query,
+                minecraft_version,
+                loader,
+                curseforge_key,
+            } => {
+                tokio::spawn(async move {
+                    let result = mod_source::search_mods(
+                        &client,
+                        curseforge_key.as_deref(),
+                        &query,
+                        &minecraft_version,
+                        loader,
+                    )
+                    .await
+                    .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::ModSearchDone(result));
+                });
+            }
+            Command::InstallMod {
+                hit,
+                minecraft_version,
+                loader,
+                mods_dir,
+                curseforge_key,
+            } => {
+                tokio::spawn(async move {
+                    let result = mod_source::install_mod(
+                        &client,
+                        curseforge_key.as_deref(),
+                        &hit,
+                        &minecraft_version,
+                        loader,
+                        &mods_dir,
+                    )
+                    .await
+                    .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::ModInstalled {
+                        name: hit.name,
+                        result,
+                    });
+                });
+            }
+            Command::FindModPage { name, path } => {
+                tokio::spawn(async move {
+                    let result = mod_source::mod_page(&client, &path)
+                        .await
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::ModPageFound { name, result });
+                });
+            }
+            Command::CheckModUpdates {
+                mods_dir,
+                minecraft_version,
+                loader,
+            } => {
+                tokio::spawn(async move {
+                    let result = mod_source::check_mod_updates(
+                        &client,
+                        &mods_dir,
+                        &minecraft_version,
+                        loader,
+                    )
+                    .await
+                    .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::ModUpdatesChecked(result));
+                });
+            }
+            Command::UpdateMods { updates, mods_dir } => {
+                tokio::spawn(async move {
+                    let mut updated = Vec::new();
+                    let mut errors = Vec::new();
+                    for update in &updates {
+                        match mod_source::apply_mod_update(&client, &mods_dir, update).await {
+                            Ok(_) => updated.push(update.installed.name.clone()),
+                            Err(e) => errors.push(format!("{}: {}", update.installed.name, e)),
+                        }
+                    }
+                    let _ = tx.send(Message::ModsUpdated { updated, errors });
+                });
+            }
+            Command::FindBackups {
+                instance_id,
+                game_dir,
+            } => {
+                tokio::task::spawn_blocking(move || {
+                    let backups = find_backups(&game_dir);
+                    let _ = tx.send(Message::BackupsFound {
+                        instance_id,
+                        backups,
+                    });
+                });
+            }
+            Command::BackupWorld {
+                world_dir,
+                game_dir,
+            } => {
+                tokio::task::spawn_blocking(move || {
+                    let world = world_dir
+                        .file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .into_owned();
+                    let result = backup_world(&world_dir, &game_dir).map_err(|e| e.to_string());
+                    let _ = tx.send(Message::WorldBackedUp { world, result });
+                });
+            }
+            Command::RestoreBackup { backup, saves_dir } => {
+                tokio::task::spawn_blocking(move || {
+                    let result = restore_backup(&backup, &saves_dir).map_err(|e| e.to_string());
+                    let _ = tx.send(Message::BackupRestored {
+                        world: backup.world,
+                        result,
+                    });
+                });
+            }
+            Command::PlanJunkCleanup {
+                instance,
+                crash_report_age,
+            } => {
+                tokio::task::spawn_blocking(move || {
+                    let plan = plan_junk_cleanup(&instance, crash_report_age, SystemTime::now());
+                    let _ = tx.send(Message::JunkCleanupPlanned {
+                        instance_id: instance.id,
+                        plan,
+                    });
+                });
+            }
+            Command::CleanJunk { name, plan } => {
+                tokio::task::spawn_blocking(move || {
+                    let result = apply_junk_cleanup(&plan).map_err(|e| e.to_string());
+                    let _ = tx.send(Message::JunkCleaned { name, result });
+                });
+            }
+            Command::IndexSearchFiles {
+                instances,
+                launcher_logs,
+                stamp,
+            } => {
+                tokio::task::spawn_blocking(move || {
+                    let items = index_search_files(&instances, &launcher_logs);
+                    let _ = tx.send(Message::SearchFilesIndexed { stamp, items });
+                });
+            }
+            Command::VerifyInstance {
+                name,
+                instance_path,
+                data_dir,
+            } => {
+                tokio::task::spawn_blocking(move || {
+                    let result =
+                        verify_instance(&instance_path, &data_dir).map_err(|e| e.to_string());
+                    let _ = tx.send(Message::InstanceVerified { name, result });
+                });
+            }
+            Command::ScanStorage {
+                data_dir,
+                instances_dir,
+                instances,
+            } => {
+                tokio::task::spawn_blocking(move || {
+                    let usage = storage_usage(&data_dir, &instances_dir, &instances);
+                    cache::store(STORAGE_USAGE_CACHE, &usage);
+                    let _ = tx.send(Message::StorageScanned(usage));
+                });
+            }
+            Command::SaveTemplate {
+                instance_path,
+                mods_dir,
+                dir,
+                name,
+            } => {
+                tokio::task::spawn_blocking(move || {
+                    let result = save_template(&instance_path, &mods_dir, &dir, &name)
+                        .map(|_| ())
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::TemplateSaved { name, result });
+                });
+            }
+            Command::CreateFromTemplate {
+                template,
+                instances_dir,
+                name,
+            } => {
+                tokio::task::spawn_blocking(move || {
+                    let result = create_from_template(&template, &instances_dir, &name)
+                        .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::InstanceCreated { name, result });
+                });
+            }
+            Command::SearchPacks {
+                query,
+                curseforge_key,
+            } => {
+                tokio::spawn(async move {
+                    let result =
+                        mod_source::search_packs(&client, curseforge_key.as_deref(), &query)
+                            .await
+                            .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::PackSearchDone(result));
+                });
+            }
+            Command::InstallPack {
+                hit,
+                instances_dir,
+                curseforge_key,
+            } => {
+                tokio::spawn(async move {
+                    let result = modpack::install_pack(
+                        &client,
+                        curseforge_key.as_deref(),
+                        &hit,
+                        &instances_dir,
+                    )
+                    .await
+                    .map_err(|e| e.to_string());
+                    let _ = tx.send(Message::PackInstalled {
+                        name: hit.name,
+                        result,
+                    });
+                });
+            }
+            Command::ReadStorageInfo { id, path, data_dir } => {
+                tokio::task::spawn_blocking(move || {
+                    let info = storage_info(&path, &data_dir);
+                    let _ = tx.send(Message::StorageInfoRead { id, info });
+                });
+            }
+        }
+    }
+}
+
+/// Problems with `address`: not resolving, pointing at the same host as one
+/// of `others`, or being on Mojang's blocklist
+async fn server_warnings(
+    client: &reqwest::Client,
+    address: &str,
+    others: &[(String, String)],
+    check_blocklist: bool,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let (host, _) = split_address(address);
+
+    let resolved = resolve(address).await;
+    if resolved.is_empty() {
+        warnings.push("address does not resolve".to_string());
+    } else {
+        let other_addrs = futures::future::join_all(others.iter().map(|(_, ip)| resolve(ip))).await;
+        if let Some(((name, _), _)) = others
+            .iter()
+            .zip(other_addrs)
+            .find(|(_, addrs)| addrs.iter().any(|a| resolved.contains(a)))
+        {
+            warnings.push(format!("resolves to the same host as '{}'", name));
+        }
+    }
+
+    if check_blocklist {
+        match blocklist::blocked_hashes(client).await {
+            Ok(hashes) => {
+                if let Some(pattern) = blocklist::blocked_pattern(&hashes, &host) {
+                    warnings.push(format!(
+                        "blocked by Mojang ({}), connections will be refused",
+                        pattern
+                    ));
+                }
+            }
+            Err(e) => warnings.push(format!("blocklist check failed: {}", e)),
+        }
+    }
+    warnings
+}
+
+async fn resolve(address: &str) -> Vec<std::net::SocketAddr> {
+    let (host, port) = ping::server_target(address).await;
+    tokio::net::lookup_host((host.as_str(), port))
+        .await
+        .map(|addrs| addrs.collect())
+        .unwrap_or_default()
+}
@@ -0,0 +1,162 @@
+use crate::actions::find_java_processes;
+use crate::data::{Instance, PrismConfig, load_groups, load_instances};
+use crate::error::Result;
+use crate::tui::{Event, EventStream, Terminal};
+use crate::view::{dashboard_height, render_dashboard};
+use chrono::{DateTime, Local};
+use crossterm::event::{KeyCode, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use std::path::PathBuf;
+use std::time::Duration;
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+/// A game process matched to its instance
+pub struct RunningStats {
+    pub name: String,
+    pub minecraft_version: String,
+    pub pid: u32,
+    /// Percent of one core
+    pub cpu: f32,
+    pub memory: u64,
+    pub uptime: u64,
+}
+
+/// State behind the read-only `--dashboard` view
+pub struct Dashboard {
+    instances_dir: PathBuf,
+    system: System,
+    pub instances: Vec<Instance>,
+    pub running: Vec<RunningStats>,
+    pub used_memory: u64,
+    pub total_memory: u64,
+    pub updated: DateTime<Local>,
+}
+
+impl Dashboard {
+    pub fn new(config: &PrismConfig) -> Self {
+        let mut dashboard = Self {
+            instances_dir: config.instances_dir(),
+            system: System::new(),
+            instances: Vec::new(),
+            running: Vec::new(),
+            used_memory: 0,
+            total_memory: 0,
+            updated: Local::now(),
+        };
+        dashboard.refresh();
+        dashboard
+    }
+
+    /// Reload instances and sample the game processes. CPU usage is
+    /// measured since the previous refresh, so the first sample reads 0.
+    pub fn refresh(&mut self) {
+        // Keep the last good list if the folder is mid-write
+        if let Ok(instances) = load_groups(&self.instances_dir)
+            .and_then(|groups| load_instances(&self.instances_dir, &groups))
+        {
+            self.instances = instances;
+        }
+
+        let paths: Vec<(String, PathBuf)> = self
+            .instances
+            .iter()
+            .map(|i| (i.id.clone(), i.path.clone()))
+            .collect();
+        // One refresh for everything, since CPU usage is measured between refreshes
+        self.system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_cmd(UpdateKind::OnlyIfNotSet)
+                .with_cpu()
+                .with_memory(),
+        );
+        let found = find_java_processes(&self.system, &paths);
+        self.system.refresh_memory();
+
+        self.running = self
+            .instances
+            .iter()
+            .filter_map(|instance| {
+                let pid = *found.get(&instance.id)?;
+                let process = self.system.process(pid)?;
+                Some(RunningStats {
+                    name: instance.name.clone(),
+                    minecraft_version: instance.minecraft_version.clone(),
+                    pid: pid.as_u32(),
+                    cpu: process.cpu_usage(),
+                    memory: process.memory(),
+                    uptime: process.run_time(),
+                })
+            })
+            .collect();
+        self.used_memory = self.system.used_memory();
+        self.total_memory = self.system.total_memory();
+        self.updated = Local::now();
+    }
+
+    /// Instances by most recent launch, never-launched ones left out
+    pub fn recent_launches(&self) -> Vec<&Instance> {
+        let mut recent: Vec<&Instance> = self
+            .instances
+            .iter()
+            .filter(|i| i.last_launch.is_some_and(|ts| ts > 0))
+            .collect();
+        recent.sort_by_key(|i| std::cmp::Reverse(i.last_launch));
+        recent
+    }
+}
+
+/// Redraw the dashboard every `interval` until q, Esc or Ctrl+C
+pub async fn run(config: &PrismConfig, interval: Duration) -> color_eyre::Result<()> {
+    let mut dashboard = Dashboard::new(config);
+    let mut terminal = Terminal::new()?;
+    let mut events = EventStream::new(interval);
+
+    loop {
+        terminal.draw(|frame| {
+            let area = frame.area();
+            render_dashboard(&dashboard, area, frame.buffer_mut());
+        })?;
+
+        match events.next().await {
+            Some(Event::Tick) => dashboard.refresh(),
+            Some(Event::Key(key))
+                if matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+                    || (key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL)) =>
+            {
+                break;
+            }
+            // Anything else, resizes included, just redraws
+            Some(_) => {}
+            None => break,
+        }
+    }
+
+    Ok(())
+}
+
+/// The dashboard as plain text, sized to the terminal width when there is one
+pub fn snapshot(config: &PrismConfig) -> Result<String> {
+    let mut dashboard = Dashboard::new(config);
+    // A second sample so CPU usage has something to compare against
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
+    dashboard.refresh();
+
+    let width = crossterm::terminal::size().map_or(80, |(w, _)| w);
+    let area = Rect::new(0, 0, width, dashboard_height(&dashboard));
+    let mut buf = Buffer::empty(area);
+    render_dashboard(&dashboard, area, &mut buf);
+
+    let mut text = String::new();
+    for y in area.top()..area.bottom() {
+        let line: String = (area.left()..area.right())
+            .map(|x| buf[(x, y)].symbol())
+            .collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    Ok(text)
+}
@@ -1,7 +1,19 @@
-use crate::app::SortMode;
+use crate::app::{LOG_LIST_DEFAULT_PERCENT, SortMode};
+use crate::error::{self, PrismError};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
+
+/// Lower bound for tick intervals so a bad config can't spin the event loop
+const MIN_TICK_RATE_MS: u64 = 16;
+
+/// Version of the `config export` format, bumped on incompatible changes
+pub const CONFIG_SCHEMA_VERSION: i64 = 1;
+
+/// Machine-local state left out of exports
+const LOCAL_ONLY_KEYS: &[&str] = &["log_view"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
@@ -9,6 +21,206 @@ pub struct AppConfig {
     pub default_sort: String,
     #[serde(default = "default_true")]
     pub sort_ascending: bool,
+    /// Show the mod loader version next to the loader in the instance table
+    #[serde(default)]
+    pub show_loader_version: bool,
+    /// Check managed modpacks for updates when the app starts
+    #[serde(default)]
+    pub check_pack_updates_on_start: bool,
+    /// Show line numbers and a level gutter in the log preview
+    #[serde(default)]
+    pub log_line_numbers: bool,
+    /// Load latest.log (or the newest file) when the Logs screen opens,
+    /// rather than the file read last time
+    #[serde(default = "default_true")]
+    pub open_latest_log: bool,
+    /// Event tick interval while instances are running (ms)
+    #[serde(default = "default_tick_rate_ms")]
+    pub tick_rate_ms: u64,
+    /// Event tick interval when nothing is running (ms)
+    #[serde(default = "default_idle_tick_rate_ms")]
+    pub idle_tick_rate_ms: u64,
+    /// Interval between scans for running game processes (ms)
+    #[serde(default = "default_process_scan_interval_ms")]
+    pub process_scan_interval_ms: u64,
+    /// Ask before launching when the data directory's drive has less free
+    /// space than this (MB, 0 = never)
+    #[serde(default = "default_low_disk_space_mb")]
+    pub low_disk_space_mb: u64,
+    /// Short names for instances: alias -> instance id
+    #[serde(default)]
+    pub aliases: BTreeMap<String, String>,
+    /// Start every launch at low priority
+    #[serde(default)]
+    pub launch_low_priority: bool,
+    /// Niceness applied to low priority games (Unix; Windows uses "below normal")
+    #[serde(default = "default_low_priority_nice")]
+    pub low_priority_nice: i32,
+    /// Environment variables per instance id, set for its launches
+    #[serde(default)]
+    pub instance_env: BTreeMap<String, BTreeMap<String, String>>,
+    /// Commands prepended to the launcher per instance id (e.g. "gamemoderun")
+    #[serde(default)]
+    pub instance_wrapper: BTreeMap<String, String>,
+    /// Named launch setups per instance id, picked with `P`
+    #[serde(default)]
+    pub launch_presets: BTreeMap<String, BTreeMap<String, LaunchPreset>>,
+    /// Check new servers against Mojang's blocklist
+    #[serde(default)]
+    pub check_server_blocklist: bool,
+    /// Ping the server before launching into it, asking first if it is
+    /// offline or runs another Minecraft version
+    #[serde(default)]
+    pub ping_before_join: bool,
+    /// Quick launch slots: "1".."9" -> instance alias, id, or name
+    #[serde(default)]
+    pub quick_launch: BTreeMap<String, String>,
+    /// Clean up logs older than this many days; 0 keeps everything
+    #[serde(default)]
+    pub log_retention_days: u64,
+    /// Per-instance overrides of `log_retention_days`, by instance id
+    #[serde(default)]
+    pub instance_log_retention_days: BTreeMap<String, u64>,
+    /// Gzip old plain-text logs instead of deleting them
+    #[serde(default)]
+    pub log_retention_compress: bool,
+    /// Apply the retention policy to every instance at startup
+    #[serde(default)]
+    pub clean_logs_on_start: bool,
+    /// Crash reports an instance cleanup removes once older than this many
+    /// days; 0 keeps them all
+    #[serde(default = "default_crash_report_days")]
+    pub crash_report_days: u64,
+    /// The onboarding tour was finished or skipped
+    #[serde(default)]
+    pub tour_seen: bool,
+    /// Page of an unfinished tour, to carry on from at the next start
+    #[serde(default)]
+    pub tour_step: usize,
+    /// PrismLauncher data directory to use instead of discovery
+    #[serde(default)]
+    pub data_dir: Option<PathBuf>,
+    /// Hold instance.cfg changes for a running game until it exits, since
+    /// PrismLauncher rewrites the file then
+    #[serde(default = "default_true")]
+    pub defer_running_cfg_edits: bool,
+    /// "iso", "locale", "relative" or a strftime pattern such as "%d.%m.%Y %H:%M"
+    #[serde(default)]
+    pub timestamp_format: Option<String>,
+    /// "short", "full" or "decimal"
+    #[serde(default)]
+    pub playtime_format: Option<String>,
+    /// Announce changes as plain lines instead of drawing, like `--screen-reader`
+    #[serde(default)]
+    pub screen_reader: bool,
+    /// Start in dry-run mode: deletes and cleanups show what they would do
+    /// and wait for confirmation
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Level of prism-tui's own log: error, warn, info, debug or trace, or a
+    /// tracing filter; PRISM_TUI_LOG takes precedence
+    #[serde(default)]
+    pub log_level: Option<String>,
+    /// Browse and launch only, like `--read-only`
+    #[serde(default)]
+    pub read_only: bool,
+    /// Color theme: catppuccin, high-contrast, deuteranopia or protanopia
+    #[serde(default)]
+    pub theme: Option<String>,
+    /// Log level colors overriding the theme's, by level (error, warn, info, debug)
+    #[serde(default)]
+    pub log_colors: BTreeMap<String, String>,
+    /// Draw with ANSI 16 colors and ASCII only, like `--plain`
+    #[serde(default)]
+    pub plain_mode: bool,
+    /// Interface language, e.g. "de"; the locale environment decides when unset
+    #[serde(default)]
+    pub language: Option<String>,
+    /// Serve running game metrics over HTTP on this address, e.g. "127.0.0.1:9100"
+    #[serde(default)]
+    pub metrics_address: Option<String>,
+    /// Key for CurseForge's API, from console.curseforge.com; without one
+    /// only Modrinth is searched
+    #[serde(default)]
+    pub curseforge_api_key: Option<String>,
+    /// Log viewer state, restored when logs are reopened
+    #[serde(default)]
+    pub log_view: LogViewConfig,
+}
+
+/// One way to launch an instance; anything left out uses the usual setting
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LaunchPreset {
+    /// Server to join instead of the instance's own
+    #[serde(default)]
+    pub server: Option<String>,
+    /// Account name to launch with instead of the active one
+    #[serde(default)]
+    pub account: Option<String>,
+    /// Extra JVM arguments, handed to Java through JAVA_TOOL_OPTIONS
+    #[serde(default)]
+    pub jvm_args: Option<String>,
+    /// Wrapper command replacing the instance's `instance_wrapper`
+    #[serde(default)]
+    pub wrapper: Option<String>,
+}
+
+impl LaunchPreset {
+    /// e.g. "server mc.example.com, account Alt, JVM -Xmx8G"
+    pub fn summary(&self) -> String {
+        let parts: Vec<String> = [
+            ("server", &self.server),
+            ("account", &self.account),
+            ("wrapper", &self.wrapper),
+            ("JVM", &self.jvm_args),
+        ]
+        .into_iter()
+        .filter_map(|(label, value)| value.as_ref().map(|v| format!("{} {}", label, v)))
+        .collect();
+        if parts.is_empty() {
+            "defaults".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogViewConfig {
+    /// Level filter labels ("ERROR", "WARN", ...); empty shows every level
+    #[serde(default)]
+    pub levels: Vec<String>,
+    #[serde(default = "default_true")]
+    pub filter_panel: bool,
+    /// Width of the file list in percent
+    #[serde(default = "default_log_list_percent")]
+    pub list_percent: u16,
+    /// Wrap long lines instead of cutting them off
+    #[serde(default)]
+    pub wrap: bool,
+    /// Keep the preview at the end of the file as it grows
+    #[serde(default)]
+    pub follow: bool,
+    /// Last opened launcher log file name
+    #[serde(default)]
+    pub launcher_file: Option<String>,
+    /// Last opened log file name per instance id
+    #[serde(default)]
+    pub instance_files: BTreeMap<String, String>,
+}
+
+impl Default for LogViewConfig {
+    fn default() -> Self {
+        Self {
+            levels: Vec::new(),
+            filter_panel: true,
+            list_percent: default_log_list_percent(),
+            wrap: false,
+            follow: false,
+            launcher_file: None,
+            instance_files: BTreeMap::new(),
+        }
+    }
 }
 
 fn default_true() -> bool {
@@ -19,11 +231,83 @@ fn default_sort() -> String {
     "Last Played".to_string()
 }
 
+fn default_tick_rate_ms() -> u64 {
+    250
+}
+
+fn default_idle_tick_rate_ms() -> u64 {
+    1000
+}
+
+/// A retention setting in days as an age, where 0 keeps files forever
+fn retention(days: u64) -> Option<Duration> {
+    (days > 0).then(|| Duration::from_secs(days * 24 * 60 * 60))
+}
+
+fn default_log_list_percent() -> u16 {
+    LOG_LIST_DEFAULT_PERCENT
+}
+
+fn default_low_priority_nice() -> i32 {
+    10
+}
+
+fn default_process_scan_interval_ms() -> u64 {
+    2000
+}
+
+fn default_crash_report_days() -> u64 {
+    30
+}
+
+fn default_low_disk_space_mb() -> u64 {
+    2048
+}
+
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
             default_sort: default_sort(),
             sort_ascending: true,
+            show_loader_version: false,
+            check_pack_updates_on_start: false,
+            log_line_numbers: false,
+            open_latest_log: true,
+            tick_rate_ms: default_tick_rate_ms(),
+            idle_tick_rate_ms: default_idle_tick_rate_ms(),
+            process_scan_interval_ms: default_process_scan_interval_ms(),
+            low_disk_space_mb: default_low_disk_space_mb(),
+            aliases: BTreeMap::new(),
+            quick_launch: BTreeMap::new(),
+            check_server_blocklist: false,
+            ping_before_join: false,
+            instance_env: BTreeMap::new(),
+            instance_wrapper: BTreeMap::new(),
+            launch_presets: BTreeMap::new(),
+            launch_low_priority: false,
+            low_priority_nice: default_low_priority_nice(),
+            log_retention_days: 0,
+            instance_log_retention_days: BTreeMap::new(),
+            log_retention_compress: false,
+            clean_logs_on_start: false,
+            crash_report_days: default_crash_report_days(),
+            tour_seen: false,
+            tour_step: 0,
+            data_dir: None,
+            defer_running_cfg_edits: true,
+            timestamp_format: None,
+            playtime_format: None,
+            screen_reader: false,
+            read_only: false,
+            log_level: None,
+            dry_run: false,
+            theme: None,
+            log_colors: BTreeMap::new(),
+            plain_mode: false,
+            language: None,
+            metrics_address: None,
+            curseforge_api_key: None,
+            log_view: LogViewConfig::default(),
         }
     }
 }
@@ -37,32 +321,166 @@ impl AppConfig {
     }
 
     pub fn load() -> Self {
+        let (config, warnings) = Self::load_checked();
+        for warning in warnings {
+            eprintln!("Warning: {}", warning);
+        }
+        config
+    }
+
+    /// Load the config, returning a description of every setting that was
+    /// skipped. Bad lines and values fall back to their defaults on their own.
+    pub fn load_checked() -> (Self, Vec<String>) {
         let path = Self::config_path();
-        if path.exists() {
-            match fs::read_to_string(&path) {
-                Ok(content) => match toml::from_str(&content) {
-                    Ok(config) => return config,
-                    Err(e) => eprintln!("Warning: Failed to parse config: {}", e),
-                },
-                Err(e) => eprintln!("Warning: Failed to read config: {}", e),
-            }
+        if !path.exists() {
+            return (Self::default(), Vec::new());
+        }
+        match fs::read_to_string(&path) {
+            Ok(content) => parse_lenient(&content),
+            Err(e) => (
+                Self::default(),
+                vec![format!("Failed to read config: {}", e)],
+            ),
         }
-        Self::default()
+    }
+
+    /// How long crash reports are kept when cleaning up an instance
+    pub fn crash_report_retention(&self) -> Option<Duration> {
+        retention(self.crash_report_days)
+    }
+
+    /// How long an instance's logs are kept, if a retention policy applies
+    pub fn log_retention_for(&self, instance_id: &str) -> Option<Duration> {
+        let days = self
+            .instance_log_retention_days
+            .get(instance_id)
+            .copied()
+            .unwrap_or(self.log_retention_days);
+        retention(days)
+    }
+
+    /// How long logs outside any instance, such as PrismLauncher's own, are
+    /// kept, if a retention policy applies
+    pub fn global_log_retention(&self) -> Option<Duration> {
+        retention(self.log_retention_days)
     }
 
     pub fn save(&self) {
+        if let Err(e) = self.try_save() {
+            eprintln!("Warning: Failed to write config: {}", e);
+        }
+    }
+
+    pub fn try_save(&self) -> error::Result<()> {
         let path = Self::config_path();
         if let Some(parent) = path.parent() {
-            let _ = fs::create_dir_all(parent);
+            fs::create_dir_all(parent)?;
         }
-        match toml::to_string_pretty(self) {
-            Ok(content) => {
-                if let Err(e) = fs::write(&path, content) {
-                    eprintln!("Warning: Failed to write config: {}", e);
-                }
-            }
-            Err(e) => eprintln!("Warning: Failed to serialize config: {}", e),
+        let content =
+            toml::to_string_pretty(self).map_err(|e| PrismError::Config(e.to_string()))?;
+        fs::write(&path, content)?;
+        Ok(())
+    }
+
+    /// The settings as a portable file, tagged with the schema version
+    pub fn export(&self) -> error::Result<String> {
+        let mut table = self.to_table()?;
+        for key in LOCAL_ONLY_KEYS {
+            table.remove(*key);
+        }
+        let body = toml::to_string_pretty(&table).map_err(|e| PrismError::Config(e.to_string()))?;
+        Ok(format!(
+            "# prism-tui configuration export\nschema = {}\n\n{}",
+            CONFIG_SCHEMA_VERSION, body
+        ))
+    }
+
+    /// Apply an export on top of these settings. Tables such as aliases are
+    /// merged key by key, so entries only present here are kept. A plain
+    /// `config.toml` without a schema is accepted as the current version.
+    pub fn import(&self, content: &str) -> error::Result<Self> {
+        let mut incoming: toml::Table =
+            toml::from_str(content).map_err(|e| PrismError::Config(e.to_string()))?;
+        let schema = match incoming.remove("schema") {
+            Some(toml::Value::Integer(schema)) => schema,
+            Some(_) => return Err(PrismError::Config("schema must be a number".to_string())),
+            None => CONFIG_SCHEMA_VERSION,
+        };
+        if schema > CONFIG_SCHEMA_VERSION {
+            return Err(PrismError::Config(format!(
+                "exported by a newer prism-tui (schema {}, this version reads {})",
+                schema, CONFIG_SCHEMA_VERSION
+            )));
+        }
+        for key in LOCAL_ONLY_KEYS {
+            incoming.remove(*key);
+        }
+
+        let mut table = self.to_table()?;
+        merge_tables(&mut table, incoming);
+        table
+            .try_into()
+            .map_err(|e: toml::de::Error| PrismError::Config(e.to_string()))
+    }
+
+    fn to_table(&self) -> error::Result<toml::Table> {
+        toml::Table::try_from(self).map_err(|e| PrismError::Config(e.to_string()))
+    }
+
+    pub fn tick_rate(&self) -> Duration {
+        Duration::from_millis(self.tick_rate_ms.max(MIN_TICK_RATE_MS))
+    }
+
+    pub fn idle_tick_rate(&self) -> Duration {
+        Duration::from_millis(self.idle_tick_rate_ms.max(MIN_TICK_RATE_MS))
+    }
+
+    pub fn process_scan_interval(&self) -> Duration {
+        Duration::from_millis(self.process_scan_interval_ms)
+    }
+
+    /// Free space below which launches ask first, in bytes
+    pub fn low_disk_space(&self) -> Option<u64> {
+        (self.low_disk_space_mb > 0).then(|| self.low_disk_space_mb * 1024 * 1024)
+    }
+
+    /// Alias assigned to an instance, if any
+    pub fn alias_for(&self, instance_id: &str) -> Option<&str> {
+        self.aliases
+            .iter()
+            .find(|(_, id)| id.as_str() == instance_id)
+            .map(|(alias, _)| alias.as_str())
+    }
+
+    /// Assign an alias to an instance, replacing its previous alias.
+    /// An empty alias removes it. An alias another instance already has is
+    /// refused with that instance's id.
+    pub fn set_alias(&mut self, instance_id: &str, alias: &str) -> Result<(), String> {
+        let alias = alias.to_lowercase();
+        if let Some(owner) = self.aliases.get(&alias).filter(|id| *id != instance_id) {
+            return Err(owner.clone());
+        }
+        self.aliases.retain(|_, id| id != instance_id);
+        if !alias.is_empty() {
+            self.aliases.insert(alias, instance_id.to_string());
         }
+        Ok(())
+    }
+
+    /// Environment variables configured for an instance
+    pub fn env_for(&self, instance_id: &str) -> BTreeMap<String, String> {
+        self.instance_env
+            .get(instance_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Wrapper command words configured for an instance
+    pub fn wrapper_for(&self, instance_id: &str) -> Vec<String> {
+        self.instance_wrapper
+            .get(instance_id)
+            .map(|w| w.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default()
     }
 
     pub fn default_sort_mode(&self) -> SortMode {
@@ -75,3 +493,302 @@ impl AppConfig {
         }
     }
 }
+
+/// Parse space-separated `KEY=value` assignments as typed in the editor
+pub fn parse_env_assignments(input: &str) -> Result<BTreeMap<String, String>, String> {
+    let mut env = BTreeMap::new();
+    for assignment in input.split_whitespace() {
+        let (key, value) = assignment
+            .split_once('=')
+            .ok_or_else(|| format!("Expected KEY=value, got '{}'", assignment))?;
+        let valid_key = key
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !valid_key {
+            return Err(format!("Invalid variable name '{}'", key));
+        }
+        env.insert(key.to_string(), value.to_string());
+    }
+    Ok(env)
+}
+
+/// Give up on syntax recovery after this many skipped lines
+const MAX_SKIPPED_LINES: usize = 20;
+
+/// Parse a config file, skipping lines that aren't valid TOML and keys whose
+/// values don't fit, so one mistake doesn't reset every other setting
+fn parse_lenient(content: &str) -> (AppConfig, Vec<String>) {
+    let mut warnings = Vec::new();
+
+    // Blank out unparsable lines, keeping numbering intact for later messages
+    let mut lines: Vec<&str> = content.lines().collect();
+    let table = loop {
+        let text = lines.join("\n");
+        match toml::from_str::<toml::Table>(&text) {
+            Ok(table) => break table,
+            Err(e) => {
+                let line = e
+                    .span()
+                    .map(|span| text[..span.start].matches('\n').count());
+                match line {
+                    Some(line) if warnings.len() < MAX_SKIPPED_LINES && !lines[line].is_empty() => {
+                        warnings.push(format!(
+                            "config.toml line {}: {}; line ignored",
+                            line + 1,
+                            one_line(e.message())
+                        ));
+                        lines[line] = "";
+                    }
+                    _ => {
+                        warnings.push(format!(
+                            "config.toml: {}; using defaults",
+                            one_line(e.message())
+                        ));
+                        return (AppConfig::default(), warnings);
+                    }
+                }
+            }
+        }
+    };
+
+    // Check each setting on its own so a bad one only resets itself. Tables
+    // are checked entry by entry, so one bad alias keeps the others.
+    let fits = |key: &str, value: toml::Value| {
+        toml::Table::from_iter([(key.to_string(), value)])
+            .try_into::<AppConfig>()
+            .map_err(|e| one_line(e.message()))
+    };
+    let mut rejected = Vec::new();
+    let mut valid = toml::Table::new();
+    for (key, value) in table {
+        let error = match fits(&key, value.clone()) {
+            Ok(_) => {
+                valid.insert(key, value);
+                continue;
+            }
+            Err(error) => error,
+        };
+        let toml::Value::Table(entries) = value else {
+            rejected.push((key_line(content, &key, None), key, error));
+            continue;
+        };
+        let mut kept = toml::Table::new();
+        for (name, entry) in entries {
+            let single =
+                toml::Value::Table(toml::Table::from_iter([(name.clone(), entry.clone())]));
+            match fits(&key, single) {
+                Ok(_) => {
+                    kept.insert(name, entry);
+                }
+                Err(error) => rejected.push((
+                    key_line(content, &key, Some(&name)),
+                    format!("{}.{}", key, name),
+                    error,
+                )),
+            }
+        }
+        valid.insert(key, toml::Value::Table(kept));
+    }
+
+    rejected.sort_by_key(|(line, _, _)| *line);
+    for (line, key, error) in rejected {
+        let location = line
+            .map(|line| format!(" line {}", line + 1))
+            .unwrap_or_default();
+        warnings.push(format!(
+            "config.toml{}: `{}`: {}; using the default",
+            location, key, error
+        ));
+    }
+    let config = valid.try_into().unwrap_or_default();
+    (config, warnings)
+}
+
+/// toml's messages span several lines, too many for a status line
+fn one_line(message: &str) -> String {
+    message.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Line where a top-level key is set, either `key = ...` or a `[key]` table.
+/// With `entry`, the line setting that entry after the table header.
+fn key_line(content: &str, key: &str, entry: Option<&str>) -> Option<usize> {
+    let assigns = |line: &str, key: &str| {
+        let line = line.trim_start();
+        let key = key.trim_matches('"');
+        [key.to_string(), format!("\"{}\"", key)].iter().any(|k| {
+            line.strip_prefix(k.as_str())
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+    };
+    let header = content.lines().position(|line| {
+        let is_table = line
+            .trim_start()
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_prefix(key))
+            .is_some_and(|rest| rest.starts_with(']') || rest.starts_with('.'));
+        is_table || assigns(line, key)
+    })?;
+    match entry {
+        None => Some(header),
+        Some(entry) => content
+            .lines()
+            .enumerate()
+            .skip(header + 1)
+            .find(|(_, line)| assigns(line, entry))
+            .map(|(i, _)| i),
+    }
+}
+
+/// Overlay `incoming` on `base`, recursing into tables present in both
+fn merge_tables(base: &mut toml::Table, incoming: toml::Table) {
+    for (key, value) in incoming {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(incoming)) => {
+                merge_tables(base, incoming)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Inverse of `parse_env_assignments`, for prefilling the editor
+pub fn format_env_assignments(env: &BTreeMap<String, String>) -> String {
+    env.iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_alias_replaces_previous() {
+        let mut config = AppConfig::default();
+        config.set_alias("atm9-folder", "atm9").unwrap();
+        config.set_alias("atm9-folder", "ATM").unwrap();
+        assert_eq!(config.alias_for("atm9-folder"), Some("atm"));
+        assert_eq!(config.aliases.len(), 1);
+
+        assert_eq!(
+            config.set_alias("vanilla-folder", "Atm"),
+            Err("atm9-folder".to_string())
+        );
+        assert_eq!(config.alias_for("vanilla-folder"), None);
+
+        config.set_alias("atm9-folder", "").unwrap();
+        assert_eq!(config.alias_for("atm9-folder"), None);
+    }
+
+    #[test]
+    fn test_log_view_defaults_when_missing() {
+        let config: AppConfig = toml::from_str("[log_view]\nlevels = [\"ERROR\"]\n").unwrap();
+        assert_eq!(config.log_view.levels, ["ERROR"]);
+        assert!(config.log_view.filter_panel);
+        assert_eq!(config.log_view.list_percent, LOG_LIST_DEFAULT_PERCENT);
+    }
+
+    #[test]
+    fn test_launch_presets_per_instance() {
+        let config: AppConfig = toml::from_str(
+            "[launch_presets.\"All the Mods 9\".testing]\n\
+             server = \"localhost\"\n\
+             jvm_args = \"-Xmx8G\"\n",
+        )
+        .unwrap();
+        let preset = &config.launch_presets["All the Mods 9"]["testing"];
+        assert_eq!(preset.server.as_deref(), Some("localhost"));
+        assert_eq!(preset.account, None);
+        assert_eq!(preset.summary(), "server localhost, JVM -Xmx8G");
+        assert_eq!(LaunchPreset::default().summary(), "defaults");
+    }
+
+    #[test]
+    fn test_parse_env_assignments() {
+        let env = parse_env_assignments("DRI_PRIME=1  __NV_PRIME_RENDER_OFFLOAD=1").unwrap();
+        assert_eq!(env.get("DRI_PRIME").map(String::as_str), Some("1"));
+        assert_eq!(
+            format_env_assignments(&env),
+            "DRI_PRIME=1 __NV_PRIME_RENDER_OFFLOAD=1"
+        );
+
+        assert!(parse_env_assignments("").unwrap().is_empty());
+        assert!(parse_env_assignments("DRI_PRIME").is_err());
+        assert!(parse_env_assignments("1BAD=x").is_err());
+    }
+
+    #[test]
+    fn test_import_merges_tables_and_keeps_local_state() {
+        let mut exported = AppConfig::default();
+        exported.set_alias("atm9-folder", "atm9").unwrap();
+        exported.launch_low_priority = true;
+        exported.log_view.levels = vec!["ERROR".to_string()];
+        let content = exported.export().unwrap();
+        assert!(content.contains("schema = 1"));
+        assert!(!content.contains("log_view"));
+
+        let mut local = AppConfig::default();
+        local.set_alias("vanilla-folder", "van").unwrap();
+        local.log_view.list_percent = 40;
+        let merged = local.import(&content).unwrap();
+        assert_eq!(merged.alias_for("atm9-folder"), Some("atm9"));
+        assert_eq!(merged.alias_for("vanilla-folder"), Some("van"));
+        assert!(merged.launch_low_priority);
+        assert_eq!(merged.log_view.list_percent, 40);
+        assert!(merged.log_view.levels.is_empty());
+    }
+
+    #[test]
+    fn test_import_rejects_newer_schema() {
+        let local = AppConfig::default();
+        assert!(local.import("schema = 99\n").is_err());
+        assert!(local.import("sort_ascending = false\n").is_ok());
+    }
+
+    #[test]
+    fn test_bad_values_only_reset_themselves() {
+        let (config, warnings) = parse_lenient(
+            "sort_ascending = false\ntick_rate_ms = \"fast\"\n[aliases]\natm9 = \"ATM9\"\n",
+        );
+        assert!(!config.sort_ascending);
+        assert_eq!(config.tick_rate_ms, 250);
+        assert_eq!(config.aliases.get("atm9").map(String::as_str), Some("ATM9"));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("config.toml line 2: `tick_rate_ms`:"));
+    }
+
+    #[test]
+    fn test_bad_table_entries_only_reset_themselves() {
+        let (config, warnings) = parse_lenient(
+            "[log_view]\nlist_percent = \"wide\"\nfilter_panel = false\n[aliases]\natm9 = 9\nvan = \"Vanilla\"\n",
+        );
+        assert!(!config.log_view.filter_panel);
+        assert_eq!(config.log_view.list_percent, LOG_LIST_DEFAULT_PERCENT);
+        assert_eq!(config.aliases.len(), 1);
+        assert!(warnings[0].starts_with("config.toml line 2: `log_view.list_percent`:"));
+        assert!(warnings[1].starts_with("config.toml line 5: `aliases.atm9`:"));
+    }
+
+    #[test]
+    fn test_syntax_errors_skip_the_line() {
+        let (config, warnings) =
+            parse_lenient("sort_ascending = false\nshow_loader_version = \ntick_rate_ms = 100\n");
+        assert!(!config.sort_ascending);
+        assert_eq!(config.tick_rate_ms, 100);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("config.toml line 2:"));
+    }
+
+    #[test]
+    fn test_missing_fields_use_defaults() {
+        let config: AppConfig = toml::from_str("sort_ascending = false").unwrap();
+        assert!(!config.sort_ascending);
+        assert_eq!(config.tick_rate_ms, 250);
+        assert!(config.aliases.is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use std::collections::hash_map::DefaultHasher;
+use std::ffi::OsString;
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Replace `path` with `contents` so a crash or full disk can't leave a
+/// half-written file behind for PrismLauncher to read. The new contents go
+/// to a temporary file next to it, which is then renamed over the original;
+/// the previous version is kept as `<name>.bak`. The temporary file's name
+/// is unique to the write, so other writers to the same file, in this
+/// process or another prism-tui, can't replace it before the rename.
+pub fn write_atomic(path: &Path, contents: &[u8]) -> io::Result<()> {
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+    let write = WRITES.fetch_add(1, Ordering::Relaxed);
+    let temp = sibling(path, &format!(".{}-{}.tmp", std::process::id(), write));
+    let result = (|| {
+        let mut file = File::create(&temp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        if path.exists() {
+            fs::copy(path, sibling(path, ".bak"))?;
+        }
+        fs::rename(&temp, path)
+    })();
+    match &result {
+        Ok(()) => tracing::debug!("Wrote {}", path.display()),
+        Err(e) => {
+            tracing::warn!("Writing {} failed: {}", path.display(), e);
+            let _ = fs::remove_file(&temp);
+        }
+    }
+    result
+}
+
+/// Hash of a file's contents, or `None` when it can't be read. Taken when a
+/// file is loaded and compared before saving, to notice edits made by
+/// PrismLauncher or the game in the meantime.
+pub fn fingerprint(path: &Path) -> Option<u64> {
+    let contents = fs::read(path).ok()?;
+    let mut hasher = DefaultHasher::new();
+    contents.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+/// `path` with `suffix` added to the whole file name, e.g. servers.dat.bak
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().map(OsString::from).unwrap_or_default();
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_atomic_keeps_one_backup() {
+        let dir = crate::data::test_dir("atomic");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("instance.cfg");
+
+        write_atomic(&path, b"first").unwrap();
+        assert!(!dir.join("instance.cfg.bak").exists());
+        write_atomic(&path, b"second").unwrap();
+        write_atomic(&path, b"third").unwrap();
+
+        let current = fs::read_to_string(&path).unwrap();
+        let backup = fs::read_to_string(dir.join("instance.cfg.bak")).unwrap();
+        let leftovers = fs::read_dir(&dir).unwrap().count();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(current, "third");
+        assert_eq!(backup, "second");
+        assert_eq!(leftovers, 2);
+    }
+
+    #[test]
+    fn test_concurrent_writers_each_use_their_own_temp_file() {
+        let dir = crate::data::test_dir("atomic-race");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+
+        let writers: Vec<_> = (0..4)
+            .map(|n| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    (0..25).all(|_| write_atomic(&path, format!("writer {}", n).as_bytes()).is_ok())
+                })
+            })
+            .collect();
+        let all_written = writers.into_iter().all(|w| w.join().unwrap());
+        let current = fs::read_to_string(&path).unwrap();
+        let leftovers = fs::read_dir(&dir).unwrap().count();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(all_written);
+        assert!(current.starts_with("writer "));
+        assert_eq!(leftovers, 2);
+    }
+
+    #[test]
+    fn test_fingerprint_follows_contents() {
+        let path = crate::data::test_dir("print");
+        assert_eq!(fingerprint(&path), None);
+
+        fs::write(&path, "one").unwrap();
+        let first = fingerprint(&path);
+        fs::write(&path, "one").unwrap();
+        let same = fingerprint(&path);
+        fs::write(&path, "two").unwrap();
+        let changed = fingerprint(&path);
+        fs::remove_file(&path).unwrap();
+
+        assert!(first.is_some());
+        assert_eq!(first, same);
+        assert_ne!(first, changed);
+    }
+}
@@ -0,0 +1,324 @@
+//! World backups that backup mods keep inside an instance (FTB Backups in
+//! `backups/`, Simple Backups in `simplebackups/`) or that prism-tui makes
+//! itself, and restoring them into `saves/`.
+
+use crate::data::format;
+use crate::data::logs::format_size;
+use crate::data::storage::{dir_size, ensure_free_space};
+use crate::data::templates::{filled_or_removed, unused_folder_name};
+use crate::error::{PrismError, Result};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+/// Comment on the archives prism-tui writes, telling them apart from FTB
+/// Backups' in the same folder
+const ARCHIVE_COMMENT: &str = "prism-tui world backup";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackupMod {
+    FtbBackups,
+    SimpleBackups,
+    /// Made with `b` on the worlds screen
+    PrismTui,
+}
+
+impl BackupMod {
+    /// The mods with a folder of their own
+    pub const ALL: [BackupMod; 2] = [BackupMod::FtbBackups, BackupMod::SimpleBackups];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            BackupMod::FtbBackups => "FTB Backups",
+            BackupMod::SimpleBackups => "Simple Backups",
+            BackupMod::PrismTui => "prism-tui",
+        }
+    }
+
+    /// Where the mod writes its archives, in the game folder
+    fn dir_name(self) -> &'static str {
+        match self {
+            BackupMod::FtbBackups | BackupMod::PrismTui => "backups",
+            BackupMod::SimpleBackups => "simplebackups",
+        }
+    }
+}
+
+/// A zipped world that can be restored
+#[derive(Debug, Clone, PartialEq)]
+pub struct WorldBackup {
+    pub path: PathBuf,
+    pub source: BackupMod,
+    pub world: String,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+}
+
+impl WorldBackup {
+    /// e.g. "New World, 3h ago, 12.0 MB (Simple Backups)"
+    pub fn summary(&self) -> String {
+        let when = self
+            .modified
+            .map(|time| {
+                format!(
+                    ", {}",
+                    format::age(chrono::DateTime::<chrono::Local>::from(time))
+                )
+            })
+            .unwrap_or_default();
+        format!(
+            "{}{}, {} ({})",
+            self.world,
+            when,
+            format_size(self.size),
+            self.source.label()
+        )
+    }
+}
+
+/// Backups found in the backup mods' folders under `game_dir`, newest first.
+/// Archives without a level.dat are not worlds and are left out.
+pub fn find_backups(game_dir: &Path) -> Vec<WorldBackup> {
+    let mut backups: Vec<WorldBackup> = BackupMod::ALL
+        .into_iter()
+        .flat_map(|source| {
+            fs::read_dir(game_dir.join(source.dir_name()))
+                .into_iter()
+                .flatten()
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "zip"))
+                .filter_map(move |path| read_backup(path, source))
+        })
+        .collect();
+    backups.sort_by_key(|b| std::cmp::Reverse(b.modified));
+    backups
+}
+
+fn read_backup(path: PathBuf, source: BackupMod) -> Option<WorldBackup> {
+    let metadata = fs::metadata(&path).ok()?;
+    let mut archive = ZipArchive::new(File::open(&path).ok()?).ok()?;
+    let root = world_root(&mut archive)?;
+    let source = if archive.comment() == ARCHIVE_COMMENT.as_bytes() {
+        BackupMod::PrismTui
+    } else {
+        source
+    };
+    // Archives holding the world's files directly are named after the date
+    let world = match root.file_name() {
+        Some(name) => name.to_string_lossy().into_owned(),
+        None => path.file_stem()?.to_string_lossy().into_owned(),
+    };
+    Some(WorldBackup {
+        world,
+        source,
+        size: metadata.len(),
+        modified: metadata.modified().ok(),
+        path,
+    })
+}
+
+/// The folder in the archive that holds level.dat, the shallowest if
+/// there are several
+fn world_root(archive: &mut ZipArchive<File>) -> Option<PathBuf> {
+    (0..archive.len())
+        .filter_map(|i| archive.by_index_raw(i).ok()?.enclosed_name())
+        .filter(|path| path.file_name().is_some_and(|name| name == "level.dat"))
+        .min_by_key(|path| path.components().count())
+        .map(|path| path.parent().map(Path::to_path_buf).unwrap_or_default())
+}
+
+/// The worlds in `saves_dir` by folder name, most recently played first
+pub fn list_worlds(saves_dir: &Path) -> Vec<String> {
+    let mut worlds: Vec<(Option<SystemTime>, String)> = fs::read_dir(saves_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let level = fs::metadata(e.path().join("level.dat")).ok()?;
+            Some((level.modified().ok(), e.file_name().into_string().ok()?))
+        })
+        .collect();
+    worlds.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+    worlds.into_iter().map(|(_, name)| name).collect()
+}
+
+/// Zip the world folder `world_dir` into `game_dir/backups/` as
+/// `<world>_<date>.zip`, with the world's folder at the archive's root.
+/// Returns the archive's file name.
+pub fn backup_world(world_dir: &Path, game_dir: &Path) -> Result<String> {
+    let world = world_dir
+        .file_name()
+        .ok_or_else(|| PrismError::Other(format!("No world at {}", world_dir.display())))?
+        .to_string_lossy()
+        .into_owned();
+    let backups_dir = game_dir.join(BackupMod::PrismTui.dir_name());
+    fs::create_dir_all(&backups_dir)?;
+    ensure_free_space(dir_size(world_dir), &backups_dir)?;
+
+    let stamp = chrono::Local::now().format("%Y-%m-%d_%H-%M-%S");
+    let file_name = format!("{}_{}.zip", world, stamp);
+    let target = backups_dir.join(&file_name);
+    // Written under another name first, so a failed backup is never listed
+    let partial = backups_dir.join(format!("{}.part", file_name));
+    let result = (|| -> Result<()> {
+        let mut zip = ZipWriter::new(File::create(&partial)?);
+        zip.set_comment(ARCHIVE_COMMENT);
+        add_to_zip(&mut zip, world_dir, &world)?;
+        zip.finish()?;
+        fs::rename(&partial, &target)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&partial);
+    }
+    result.map(|()| file_name)
+}
+
+/// Add the files under `dir` to `zip` below `prefix`. session.lock is left
+/// out, as the game recreates it and holds it while running.
+fn add_to_zip(zip: &mut ZipWriter<File>, dir: &Path, prefix: &str) -> Result<()> {
+    let options = SimpleFileOptions::default();
+    zip.add_directory(prefix, options)?;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let path = format!("{}/{}", prefix, name);
+        let kind = entry.file_type()?;
+        if kind.is_dir() {
+            add_to_zip(zip, &entry.path(), &path)?;
+        } else if kind.is_file() && name != "session.lock" {
+            zip.start_file(path, options)?;
+            io::copy(&mut File::open(entry.path())?, zip)?;
+        }
+    }
+    Ok(())
+}
+
+/// Unpack `backup` into a new folder under `saves_dir`, next to any world of
+/// the same name rather than over it. Returns the folder's name.
+pub fn restore_backup(backup: &WorldBackup, saves_dir: &Path) -> Result<String> {
+    let mut archive = ZipArchive::new(File::open(&backup.path)?)?;
+    let root = world_root(&mut archive)
+        .ok_or_else(|| PrismError::Other(format!("No world in {}", backup.path.display())))?;
+    let unpacked_size = (0..archive.len())
+        .filter_map(|i| archive.by_index_raw(i).ok().map(|e| e.size()))
+        .sum();
+    fs::create_dir_all(saves_dir)?;
+    ensure_free_space(unpacked_size, saves_dir)?;
+
+    let folder = unused_folder_name(saves_dir, &backup.world)?;
+    let target = saves_dir.join(&folder);
+    filled_or_removed(&target, || {
+        fs::create_dir_all(&target)?;
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i)?;
+            // Entries outside the world folder, or escaping it, are skipped
+            let Some(relative) = entry
+                .enclosed_name()
+                .and_then(|p| p.strip_prefix(&root).ok().map(Path::to_path_buf))
+            else {
+                continue;
+            };
+            let dest = target.join(relative);
+            if entry.is_dir() {
+                fs::create_dir_all(&dest)?;
+            } else {
+                if let Some(parent) = dest.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                io::copy(&mut entry, &mut File::create(&dest)?)?;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    fn write_zip(path: &Path, files: &[(&str, &str)]) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let mut zip = ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    #[test]
+    fn test_backups_restore_next_to_the_live_world() {
+        let game_dir = crate::data::test_dir("backups");
+        write_zip(
+            &game_dir.join("simplebackups/2025-01-08_12-00-00.zip"),
+            &[
+                ("New World/level.dat", "level"),
+                ("New World/region/r.0.0.mca", "region"),
+                ("../escaped.txt", "nope"),
+            ],
+        );
+        write_zip(
+            &game_dir.join("backups/2025-01-07-12-00-00.zip"),
+            &[("level.dat", "level")],
+        );
+        write_zip(&game_dir.join("backups/notes.zip"), &[("notes.txt", "")]);
+        fs::create_dir_all(game_dir.join("saves/New World")).unwrap();
+
+        let backups = find_backups(&game_dir);
+        let mut worlds: Vec<&str> = backups.iter().map(|b| b.world.as_str()).collect();
+        worlds.sort();
+        assert_eq!(worlds, ["2025-01-07-12-00-00", "New World"]);
+
+        let simple = backups
+            .iter()
+            .find(|b| b.source == BackupMod::SimpleBackups)
+            .unwrap();
+        let folder = restore_backup(simple, &game_dir.join("saves")).unwrap();
+        let restored = game_dir.join("saves").join(&folder);
+        let region = fs::read_to_string(restored.join("region/r.0.0.mca"));
+        let escaped = game_dir.join("saves/escaped.txt").exists();
+        fs::remove_dir_all(&game_dir).unwrap();
+
+        assert_eq!(folder, "New World-2");
+        assert_eq!(region.unwrap(), "region");
+        assert!(!escaped);
+    }
+
+    #[test]
+    fn test_backup_world_restores_under_its_name() {
+        let game_dir = crate::data::test_dir("backup-world");
+        let world = game_dir.join("saves/Survival");
+        fs::create_dir_all(world.join("region")).unwrap();
+        fs::write(world.join("level.dat"), "level").unwrap();
+        fs::write(world.join("region/r.0.0.mca"), "region").unwrap();
+        fs::write(world.join("session.lock"), "").unwrap();
+        fs::create_dir_all(game_dir.join("saves/not-a-world")).unwrap();
+
+        let worlds = list_worlds(&game_dir.join("saves"));
+        let file_name = backup_world(&world, &game_dir).unwrap();
+        let backups = find_backups(&game_dir);
+        fs::remove_dir_all(&world).unwrap();
+        let folder = restore_backup(&backups[0], &game_dir.join("saves")).unwrap();
+        let region = fs::read_to_string(game_dir.join("saves/Survival/region/r.0.0.mca"));
+        let lock = game_dir.join("saves/Survival/session.lock").exists();
+        fs::remove_dir_all(&game_dir).unwrap();
+
+        assert_eq!(worlds, ["Survival"]);
+        assert!(file_name.starts_with("Survival_") && file_name.ends_with(".zip"));
+        assert_eq!(backups.len(), 1);
+        assert_eq!(backups[0].source, BackupMod::PrismTui);
+        assert_eq!(backups[0].world, "Survival");
+        assert_eq!(folder, "Survival");
+        assert_eq!(region.unwrap(), "region");
+        assert!(!lock);
+    }
+}
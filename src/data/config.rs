@@ -5,8 +5,10 @@ use std::path::{Path, PathBuf};
 
 pub struct PrismConfig {
     pub data_dir: PathBuf,
-    #[allow(dead_code)]
+    /// `SelectedInstance`, the instance PrismLauncher's window has selected
     pub selected_instance: Option<String>,
+    /// `InstanceDir` from prismlauncher.cfg, when set
+    instance_dir: Option<String>,
 }
 
 impl PrismConfig {
@@ -14,23 +16,21 @@ impl PrismConfig {
         let config_path = data_dir.join("prismlauncher.cfg");
         let mut config = Ini::new();
 
-        let selected_instance = if config_path.exists() {
+        if config_path.exists() {
             config
                 .load(&config_path)
                 .map_err(|e| PrismError::Config(e.to_string()))?;
-            config.get("General", "SelectedInstance")
-        } else {
-            None
-        };
+        }
 
         Ok(Self {
             data_dir: data_dir.to_path_buf(),
-            selected_instance,
+            selected_instance: config.get("General", "SelectedInstance"),
+            instance_dir: config.get("General", "InstanceDir"),
         })
     }
 
     pub fn instances_dir(&self) -> PathBuf {
-        self.data_dir.join("instances")
+        resolve_data_path(&self.data_dir, self.instance_dir.as_deref(), "instances")
     }
 
     pub fn accounts_path(&self) -> PathBuf {
@@ -38,6 +38,43 @@ impl PrismConfig {
     }
 }
 
+/// A portable install is marked by `portable.txt` (or an existing
+/// prismlauncher.cfg) in the launcher's own directory
+fn is_portable_dir(dir: &Path) -> bool {
+    dir.join("portable.txt").is_file() || dir.join("prismlauncher.cfg").is_file()
+}
+
+/// Portable data directory: the directory we were started from, or the one
+/// holding the `prismlauncher` executable found in PATH
+pub fn find_portable_data_dir() -> Option<PathBuf> {
+    if let Ok(cwd) = env::current_dir()
+        && is_portable_dir(&cwd)
+    {
+        return Some(cwd);
+    }
+
+    let binary = if cfg!(windows) {
+        "prismlauncher.exe"
+    } else {
+        "prismlauncher"
+    };
+    env::split_paths(&env::var_os("PATH")?)
+        .map(|dir| dir.join(binary))
+        .find(|path| path.is_file())
+        .and_then(|path| path.canonicalize().ok())
+        .and_then(|path| path.parent().map(Path::to_path_buf))
+        .filter(|dir| is_portable_dir(dir))
+}
+
+/// Resolve a folder setting from prismlauncher.cfg. PrismLauncher stores
+/// these relative to its data directory unless the user picked an absolute path
+pub fn resolve_data_path(data_dir: &Path, value: Option<&str>, default: &str) -> PathBuf {
+    match value.map(str::trim).filter(|v| !v.is_empty()) {
+        Some(value) => data_dir.join(value),
+        None => data_dir.join(default),
+    }
+}
+
 pub fn find_prism_data_dir() -> Result<PathBuf> {
     // Check environment variable first
     if let Ok(path) = env::var("PRISMLAUNCHER_DATA") {
@@ -47,6 +84,11 @@ pub fn find_prism_data_dir() -> Result<PathBuf> {
         }
     }
 
+    // Portable install: the launcher keeps its data next to the executable
+    if let Some(portable) = find_portable_data_dir() {
+        return Ok(portable);
+    }
+
     // Standard location
     if let Some(data_dir) = dirs::data_dir() {
         let standard = data_dir.join("PrismLauncher");
@@ -68,3 +110,37 @@ pub fn find_prism_data_dir() -> Result<PathBuf> {
 
     Err(PrismError::DataDirNotFound)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_portable_dir() {
+        let dir = crate::data::test_dir("portable");
+        std::fs::create_dir_all(&dir).unwrap();
+        assert!(!is_portable_dir(&dir));
+
+        std::fs::write(dir.join("portable.txt"), "").unwrap();
+        let portable = is_portable_dir(&dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(portable);
+    }
+
+    #[test]
+    fn test_resolve_data_path() {
+        let data_dir = Path::new("/data/PrismLauncher");
+        assert_eq!(
+            resolve_data_path(data_dir, None, "instances"),
+            data_dir.join("instances")
+        );
+        assert_eq!(
+            resolve_data_path(data_dir, Some("my-instances"), "instances"),
+            data_dir.join("my-instances")
+        );
+        assert_eq!(
+            resolve_data_path(data_dir, Some("/mnt/games/instances"), "instances"),
+            PathBuf::from("/mnt/games/instances")
+        );
+    }
+}
@@ -0,0 +1,264 @@
+/// Kinds of failure the crash analyzer recognizes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrashKind {
+    MixinConflict,
+    MissingDependency,
+    DuplicateMod,
+    OutdatedJava,
+    OpenGl,
+    OutOfMemory,
+}
+
+impl CrashKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            CrashKind::MixinConflict => "Mixin",
+            CrashKind::MissingDependency => "Dependency",
+            CrashKind::DuplicateMod => "Duplicate",
+            CrashKind::OutdatedJava => "Java",
+            CrashKind::OpenGl => "OpenGL",
+            CrashKind::OutOfMemory => "Memory",
+        }
+    }
+}
+
+/// A recognized failure with a readable explanation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CrashHint {
+    pub kind: CrashKind,
+    pub message: String,
+    /// Index of the line that matched
+    pub line: usize,
+    /// Mod id that disabling would most likely get the game running again
+    pub suspect: Option<String>,
+}
+
+/// Stop after this many hints; later ones are usually follow-on errors
+const MAX_HINTS: usize = 6;
+
+type Rule = fn(&str) -> Option<(CrashKind, String, Option<String>)>;
+
+const RULES: &[Rule] = &[
+    outdated_java,
+    missing_dependency,
+    mixin_conflict,
+    duplicate_mod,
+    opengl,
+    out_of_memory,
+];
+
+/// Scan a log or crash report for known failure signatures
+pub fn analyze_log(lines: &[String]) -> Vec<CrashHint> {
+    let mut hints: Vec<CrashHint> = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        for rule in RULES {
+            if let Some((kind, message, suspect)) = rule(line)
+                && !hints.iter().any(|h| h.message == message)
+            {
+                hints.push(CrashHint {
+                    kind,
+                    message,
+                    line: idx,
+                    suspect,
+                });
+                if hints.len() == MAX_HINTS {
+                    return hints;
+                }
+            }
+        }
+    }
+    hints
+}
+
+/// Text between `start` and the next `end` after it
+fn between<'a>(line: &'a str, start: &str, end: &str) -> Option<&'a str> {
+    let rest = &line[line.find(start)? + start.len()..];
+    Some(&rest[..rest.find(end)?])
+}
+
+fn outdated_java(line: &str) -> Option<(CrashKind, String, Option<String>)> {
+    if !line.contains("UnsupportedClassVersionError") {
+        return None;
+    }
+    // Class file version 52 is Java 8, each release adds one
+    let version = between(line, "class file version ", ")")?;
+    let major: f32 = version.parse().ok()?;
+    let java = (major as u32).checked_sub(44)?;
+    Some((
+        CrashKind::OutdatedJava,
+        format!(
+            "Needs Java {} or newer; pick a newer Java for this instance",
+            java
+        ),
+        None,
+    ))
+}
+
+fn missing_dependency(line: &str) -> Option<(CrashKind, String, Option<String>)> {
+    // Fabric: Mod 'A' (a) 1.0 requires version 2 or later of 'B' (b), which is missing!
+    if line.contains("which is missing") {
+        let requester = between(line, "Mod '", "'")?;
+        let requester_id = between(line, "' (", ")");
+        let before = &line[..line.find(", which is missing")?];
+        let dependency = before.rsplit('\'').nth(1)?;
+        return Some((
+            CrashKind::MissingDependency,
+            format!(
+                "{} requires {}, which is not installed",
+                requester, dependency
+            ),
+            requester_id.map(str::to_string),
+        ));
+    }
+    // Fabric (newer): - Install fabric-api, version 0.90 or later.
+    if let Some(dependency) = between(line.trim_start(), "- Install ", ",") {
+        return Some((
+            CrashKind::MissingDependency,
+            format!("Install {} to satisfy a mod's dependency", dependency),
+            None,
+        ));
+    }
+    // Forge: Mod ID: 'b', Requested by: 'a', Expected range: ...
+    if line.contains("Requested by:") {
+        let dependency = between(line, "Mod ID: '", "'")?;
+        let requester = between(line, "Requested by: '", "'")?;
+        return Some((
+            CrashKind::MissingDependency,
+            format!(
+                "{} requires {}, which is missing or the wrong version",
+                requester, dependency
+            ),
+            Some(requester.to_string()),
+        ));
+    }
+    None
+}
+
+fn mixin_conflict(line: &str) -> Option<(CrashKind, String, Option<String>)> {
+    let signatures = [
+        "FAILED during APPLY",
+        "Mixin apply failed",
+        "Mixin apply for mod",
+        "MixinApplyError",
+        "InvalidInjectionException",
+    ];
+    if !signatures.iter().any(|s| line.contains(s)) {
+        return None;
+    }
+    // "... from mod sodium]" names the mod; otherwise fall back to the config name
+    let name = between(line, "from mod ", "]")
+        .or_else(|| between(line, "Mixin apply for mod ", " "))
+        .or_else(|| {
+            let config = line
+                .split(['[', ' ', ':'])
+                .find(|w| w.ends_with(".mixins.json"))?;
+            config.strip_suffix(".mixins.json")
+        });
+    Some((
+        CrashKind::MixinConflict,
+        match name {
+            Some(name) => format!(
+                "Mixin from {} failed to apply; it may clash with another mod or be outdated",
+                name
+            ),
+            None => "A mixin failed to apply; two mods likely conflict".to_string(),
+        },
+        name.map(str::to_string),
+    ))
+}
+
+fn duplicate_mod(line: &str) -> Option<(CrashKind, String, Option<String>)> {
+    if line.contains("DuplicateModsFoundException") || line.contains("Found duplicate mods") {
+        return Some((
+            CrashKind::DuplicateMod,
+            "The same mod is installed twice; remove one of the copies".to_string(),
+            None,
+        ));
+    }
+    None
+}
+
+fn opengl(line: &str) -> Option<(CrashKind, String, Option<String>)> {
+    let signatures = [
+        "GLFW error 65542",
+        "GLFW error 65543",
+        "Pixel format not accelerated",
+        "does not appear to support OpenGL",
+        "Could not create context",
+    ];
+    if signatures.iter().any(|s| line.contains(s)) {
+        return Some((
+            CrashKind::OpenGl,
+            "The graphics driver lacks the required OpenGL support; update GPU drivers".to_string(),
+            None,
+        ));
+    }
+    None
+}
+
+fn out_of_memory(line: &str) -> Option<(CrashKind, String, Option<String>)> {
+    if line.contains("java.lang.OutOfMemoryError") {
+        return Some((
+            CrashKind::OutOfMemory,
+            "Ran out of memory; raise the maximum memory allocation".to_string(),
+            None,
+        ));
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn analyze(lines: &[&str]) -> Vec<CrashHint> {
+        let lines: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        analyze_log(&lines)
+    }
+
+    #[test]
+    fn test_outdated_java() {
+        let hints = analyze(&[
+            "java.lang.UnsupportedClassVersionError: net/minecraft/client/main/Main has been \
+             compiled by a more recent version of the Java Runtime (class file version 65.0), \
+             this version of the Java Runtime only recognizes class file versions up to 61.0",
+        ]);
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].kind, CrashKind::OutdatedJava);
+        assert!(hints[0].message.contains("Java 21"));
+    }
+
+    #[test]
+    fn test_missing_dependency_fabric_and_forge() {
+        let hints = analyze(&[
+            "\t - Mod 'Sodium Extra' (sodium-extra) 0.5.1 requires any version of 'Sodium' \
+             (sodium), which is missing!",
+            "\t\tMod ID: 'geckolib', Requested by: 'alexsmobs', Expected range: '[4.2,)'",
+        ]);
+        assert_eq!(hints.len(), 2);
+        assert_eq!(
+            hints[0].message,
+            "Sodium Extra requires Sodium, which is not installed"
+        );
+        assert_eq!(hints[0].suspect.as_deref(), Some("sodium-extra"));
+        assert!(hints[1].message.starts_with("alexsmobs requires geckolib"));
+        assert_eq!(hints[1].suspect.as_deref(), Some("alexsmobs"));
+        assert_eq!(hints[1].line, 1);
+    }
+
+    #[test]
+    fn test_mixin_names_mod_and_dedupes() {
+        let line = "Mixin [sodium.mixins.json:core.MixinWindow from mod sodium] from phase \
+                    [DEFAULT] in config [sodium.mixins.json] FAILED during APPLY";
+        let hints = analyze(&[line, line]);
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].kind, CrashKind::MixinConflict);
+        assert!(hints[0].message.contains("from sodium"));
+        assert_eq!(hints[0].suspect.as_deref(), Some("sodium"));
+    }
+
+    #[test]
+    fn test_unrelated_lines_have_no_hints() {
+        assert!(analyze(&["[Render thread/INFO]: Setting user: Steve"]).is_empty());
+    }
+}
@@ -0,0 +1,259 @@
+//! Timestamp and playtime formatting, set once from the config so every
+//! screen, the report and the dashboard agree.
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local, TimeZone};
+use std::sync::OnceLock;
+
+const ISO: &str = "%Y-%m-%d %H:%M";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimestampFormat {
+    /// A strftime pattern, "%Y-%m-%d %H:%M" by default
+    Pattern(String),
+    /// "3 hours ago", "yesterday"
+    Relative,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PlaytimeFormat {
+    /// Hours in lists ("12h played"), hours and minutes in details
+    Short,
+    /// Hours and minutes everywhere ("12h 34m")
+    Full,
+    /// Fractional hours ("12.6 hours")
+    Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formats {
+    pub timestamp: TimestampFormat,
+    pub playtime: PlaytimeFormat,
+}
+
+impl Default for Formats {
+    fn default() -> Self {
+        Self {
+            timestamp: TimestampFormat::Pattern(ISO.to_string()),
+            playtime: PlaytimeFormat::Short,
+        }
+    }
+}
+
+static FORMATS: OnceLock<Formats> = OnceLock::new();
+
+/// Choose the formats for this run from the config values. Only the first
+/// call has any effect; problems are returned and the defaults kept.
+pub fn init(timestamp: Option<&str>, playtime: Option<&str>) -> Vec<String> {
+    let (formats, warnings) = parse(timestamp, playtime);
+    let _ = FORMATS.set(formats);
+    warnings
+}
+
+fn current() -> &'static Formats {
+    FORMATS.get_or_init(Formats::default)
+}
+
+/// Timestamp formats are "iso", "locale", "relative" or a strftime pattern;
+/// playtime formats are "short", "full" or "decimal"
+fn parse(timestamp: Option<&str>, playtime: Option<&str>) -> (Formats, Vec<String>) {
+    let mut formats = Formats::default();
+    let mut warnings = Vec::new();
+
+    match timestamp {
+        None | Some("iso") => {}
+        Some("relative") => formats.timestamp = TimestampFormat::Relative,
+        Some("locale") => formats.timestamp = TimestampFormat::Pattern(locale_pattern()),
+        Some(pattern) => {
+            if StrftimeItems::new(pattern).any(|item| item == Item::Error) {
+                warnings.push(format!("Invalid timestamp_format '{}'", pattern));
+            } else {
+                formats.timestamp = TimestampFormat::Pattern(pattern.to_string());
+            }
+        }
+    }
+
+    match playtime {
+        None | Some("short") => {}
+        Some("full") => formats.playtime = PlaytimeFormat::Full,
+        Some("decimal") => formats.playtime = PlaytimeFormat::Decimal,
+        Some(other) => warnings.push(format!(
+            "Unknown playtime_format '{}', expected short, full or decimal",
+            other
+        )),
+    }
+
+    (formats, warnings)
+}
+
+/// Date order of the user's region, going by the locale variables
+fn locale_pattern() -> String {
+    let locale = ["LC_ALL", "LC_TIME", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    let region = locale.split(['.', '@']).next().unwrap_or_default();
+    let language = region.split(['_', '-']).next().unwrap_or_default();
+
+    match (language, region) {
+        (_, "en_US") => "%m/%d/%Y %I:%M %p",
+        ("de" | "pl" | "ru" | "cs" | "fi" | "nb" | "da", _) => "%d.%m.%Y %H:%M",
+        ("en" | "fr" | "es" | "it" | "pt" | "nl" | "el", _) => "%d/%m/%Y %H:%M",
+        ("ja" | "zh" | "ko", _) => "%Y/%m/%d %H:%M",
+        _ => ISO,
+    }
+    .to_string()
+}
+
+/// A point in time in the configured format
+pub fn timestamp<Tz: TimeZone>(time: DateTime<Tz>) -> String {
+    let local = time.with_timezone(&Local);
+    match &current().timestamp {
+        TimestampFormat::Pattern(pattern) => local.format(pattern).to_string(),
+        TimestampFormat::Relative => {
+            relative(Local::now().signed_duration_since(local).num_seconds())
+        }
+    }
+}
+
+/// A point in time as a date, even when timestamps are shown relative
+pub fn absolute<Tz: TimeZone>(time: DateTime<Tz>) -> String {
+    let pattern = match &current().timestamp {
+        TimestampFormat::Pattern(pattern) => pattern.as_str(),
+        TimestampFormat::Relative => ISO,
+    };
+    time.with_timezone(&Local).format(pattern).to_string()
+}
+
+/// Compact age for table columns, e.g. "3h ago"
+pub fn age<Tz: TimeZone>(time: DateTime<Tz>) -> String {
+    let local = time.with_timezone(&Local);
+    age_short(Local::now().signed_duration_since(local).num_seconds())
+}
+
+fn age_short(seconds: i64) -> String {
+    const HOUR: i64 = 3600;
+    const DAY: i64 = 24 * HOUR;
+
+    if seconds < 60 {
+        "now".to_string()
+    } else if seconds < HOUR {
+        format!("{}m ago", seconds / 60)
+    } else if seconds < DAY {
+        format!("{}h ago", seconds / HOUR)
+    } else if seconds < 14 * DAY {
+        format!("{}d ago", seconds / DAY)
+    } else if seconds < 60 * DAY {
+        format!("{}w ago", seconds / (7 * DAY))
+    } else if seconds < 365 * DAY {
+        format!("{}mo ago", seconds / (30 * DAY))
+    } else {
+        format!("{}y ago", seconds / (365 * DAY))
+    }
+}
+
+fn relative(seconds: i64) -> String {
+    const MINUTE: i64 = 60;
+    const HOUR: i64 = 60 * MINUTE;
+    const DAY: i64 = 24 * HOUR;
+
+    let plural =
+        |n: i64, unit: &str| format!("{} {}{} ago", n, unit, if n == 1 { "" } else { "s" });
+    // Clock skew shows as the present rather than the future
+    if seconds < MINUTE {
+        "just now".to_string()
+    } else if seconds < HOUR {
+        plural(seconds / MINUTE, "minute")
+    } else if seconds < DAY {
+        plural(seconds / HOUR, "hour")
+    } else if seconds < 2 * DAY {
+        "yesterday".to_string()
+    } else if seconds < 14 * DAY {
+        plural(seconds / DAY, "day")
+    } else if seconds < 60 * DAY {
+        plural(seconds / (7 * DAY), "week")
+    } else if seconds < 365 * DAY {
+        plural(seconds / (30 * DAY), "month")
+    } else {
+        plural(seconds / (365 * DAY), "year")
+    }
+}
+
+/// Playtime where space is short, such as the instance list
+pub fn playtime(seconds: u64) -> String {
+    match current().playtime {
+        PlaytimeFormat::Short => {
+            let hours = seconds / 3600;
+            if hours > 0 {
+                format!("{}h played", hours)
+            } else {
+                format!("{}m played", seconds / 60)
+            }
+        }
+        _ => playtime_full(seconds),
+    }
+}
+
+/// Playtime with room to spare, such as the details screen
+pub fn playtime_full(seconds: u64) -> String {
+    match current().playtime {
+        PlaytimeFormat::Decimal => format!("{:.1} hours", seconds as f64 / 3600.0),
+        PlaytimeFormat::Short | PlaytimeFormat::Full => {
+            let hours = seconds / 3600;
+            let minutes = (seconds % 3600) / 60;
+            if hours > 0 {
+                format!("{}h {}m", hours, minutes)
+            } else {
+                format!("{}m", minutes)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_formats() {
+        let (formats, warnings) = parse(Some("relative"), Some("decimal"));
+        assert_eq!(formats.timestamp, TimestampFormat::Relative);
+        assert_eq!(formats.playtime, PlaytimeFormat::Decimal);
+        assert!(warnings.is_empty());
+
+        let (formats, warnings) = parse(Some("%d.%m.%Y"), None);
+        assert_eq!(
+            formats.timestamp,
+            TimestampFormat::Pattern("%d.%m.%Y".to_string())
+        );
+        assert!(warnings.is_empty());
+
+        let (formats, warnings) = parse(Some("%Q"), Some("weeks"));
+        assert_eq!(formats, Formats::default());
+        assert_eq!(warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_relative_time() {
+        assert_eq!(relative(-5), "just now");
+        assert_eq!(relative(90), "1 minute ago");
+        assert_eq!(relative(3 * 3600 + 5), "3 hours ago");
+        assert_eq!(relative(30 * 3600), "yesterday");
+        assert_eq!(relative(5 * 86400), "5 days ago");
+        assert_eq!(relative(21 * 86400), "3 weeks ago");
+        assert_eq!(relative(100 * 86400), "3 months ago");
+        assert_eq!(relative(800 * 86400), "2 years ago");
+    }
+
+    #[test]
+    fn test_short_age() {
+        assert_eq!(age_short(30), "now");
+        assert_eq!(age_short(45 * 60), "45m ago");
+        assert_eq!(age_short(3 * 3600), "3h ago");
+        assert_eq!(age_short(3 * 86400), "3d ago");
+        assert_eq!(age_short(20 * 86400), "2w ago");
+        assert_eq!(age_short(90 * 86400), "3mo ago");
+        assert_eq!(age_short(1000 * 86400), "2y ago");
+    }
+}
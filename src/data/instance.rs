@@ -1,11 +1,23 @@
+use crate::data::Server;
+use crate::data::atomic::write_atomic;
+use crate::data::format;
+use crate::data::mods::list_mods;
+use crate::data::prism_settings::{
+    JAVA_SETTINGS, OVERRIDE_GROUPS, PrismSettings, SettingSource, instance_overrides,
+};
 use crate::error::{PrismError, Result};
 use configparser::ini::Ini;
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
-#[derive(Debug, Clone)]
+/// Most threads reading instance folders at once
+const MAX_LOAD_THREADS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Instance {
     pub id: String,
     pub name: String,
@@ -13,17 +25,186 @@ pub struct Instance {
     pub group: Option<String>,
     pub minecraft_version: String,
     pub mod_loader: Option<String>,
+    pub mod_loader_version: Option<String>,
     pub total_time_played: u64,
     pub last_launch: Option<i64>,
     pub server_join: Option<ServerJoin>,
+    pub world_join: Option<String>,
+    pub managed_pack: Option<ManagedPack>,
+    pub icon_key: Option<String>,
+    /// PrismLauncher's wrapper command, when the instance overrides commands
+    pub wrapper_command: Option<String>,
+    /// Global settings this instance overrides (key -> value)
+    pub setting_overrides: HashMap<String, String>,
+    /// instance.cfg's `[General]` values as last read or written by us,
+    /// keyed in lowercase, see `cfg_changed_on_disk`
+    pub cfg_values: HashMap<String, String>,
+    /// Enabled jars in mods/ while mmc-pack.json has no mod loader to load
+    /// them, counted when the instance is read
+    pub unloaded_mods: usize,
+    /// Name, version, loader and group in lowercase, matched by the search
+    pub search_key: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ServerJoin {
     pub enabled: bool,
     pub address: String,
 }
 
+/// Platform a managed modpack instance was installed from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackPlatform {
+    Modrinth,
+    CurseForge,
+    Ftb,
+    Technic,
+    ATLauncher,
+    Other(String),
+}
+
+impl PackPlatform {
+    fn from_type(pack_type: &str) -> Self {
+        match pack_type.to_lowercase().as_str() {
+            "modrinth" => PackPlatform::Modrinth,
+            "flame" => PackPlatform::CurseForge,
+            "ftb" => PackPlatform::Ftb,
+            "technic" => PackPlatform::Technic,
+            "atlauncher" => PackPlatform::ATLauncher,
+            other => PackPlatform::Other(other.to_string()),
+        }
+    }
+
+    /// The `ManagedPackType` value PrismLauncher writes for the platform
+    pub fn config_type(&self) -> &str {
+        match self {
+            PackPlatform::Modrinth => "modrinth",
+            PackPlatform::CurseForge => "flame",
+            PackPlatform::Ftb => "ftb",
+            PackPlatform::Technic => "technic",
+            PackPlatform::ATLauncher => "atlauncher",
+            PackPlatform::Other(name) => name,
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            PackPlatform::Modrinth => "Modrinth",
+            PackPlatform::CurseForge => "CurseForge",
+            PackPlatform::Ftb => "FTB",
+            PackPlatform::Technic => "Technic",
+            PackPlatform::ATLauncher => "ATLauncher",
+            PackPlatform::Other(name) => name,
+        }
+    }
+}
+
+/// Modpack metadata from the `ManagedPack*` keys in instance.cfg
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagedPack {
+    pub platform: PackPlatform,
+    pub id: String,
+    pub name: String,
+    pub version_id: String,
+    pub version_name: String,
+}
+
+impl ManagedPack {
+    fn from_config(config: &Ini) -> Option<Self> {
+        let managed = config
+            .get("General", "ManagedPack")
+            .is_some_and(|s| s == "true");
+        if !managed {
+            return None;
+        }
+
+        let get = |key: &str| config.get("General", key).unwrap_or_default();
+
+        Some(Self {
+            platform: PackPlatform::from_type(&get("ManagedPackType")),
+            id: get("ManagedPackID"),
+            name: get("ManagedPackName"),
+            version_id: get("ManagedPackVersionID"),
+            version_name: get("ManagedPackVersionName"),
+        })
+    }
+
+    /// Web page of the pack on its platform, if it has one
+    pub fn url(&self) -> Option<String> {
+        if self.id.is_empty() {
+            return None;
+        }
+        match self.platform {
+            PackPlatform::Modrinth => Some(format!("https://modrinth.com/modpack/{}", self.id)),
+            PackPlatform::CurseForge => {
+                Some(format!("https://www.curseforge.com/projects/{}", self.id))
+            }
+            PackPlatform::Ftb => Some(format!(
+                "https://www.feed-the-beast.com/modpacks/{}",
+                self.id
+            )),
+            _ => None,
+        }
+    }
+}
+
+/// Folder contents counts shown in the details screen.
+/// Computed on demand since they require reading the instance directories.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InstanceStats {
+    pub mods: usize,
+    pub saves: usize,
+    pub resource_packs: usize,
+}
+
+/// Problems with an instance's join-on-launch configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JoinConflict {
+    /// Both a singleplayer world and a server are set to join on launch
+    WorldAndServer,
+    /// The join server is no longer present in servers.dat
+    MissingServer,
+}
+
+impl JoinConflict {
+    pub fn label(self) -> &'static str {
+        match self {
+            JoinConflict::WorldAndServer => "World join also configured",
+            JoinConflict::MissingServer => "Server not in servers.dat",
+        }
+    }
+
+    pub fn fix_label(self) -> &'static str {
+        match self {
+            JoinConflict::WorldAndServer => "Clear world join",
+            JoinConflict::MissingServer => "Disable join",
+        }
+    }
+}
+
+/// Folders inside an instance's game directory that open straight from the
+/// details screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InstanceFolder {
+    Mods,
+    Config,
+    Saves,
+    Screenshots,
+    ResourcePacks,
+}
+
+impl InstanceFolder {
+    pub fn dir_name(self) -> &'static str {
+        match self {
+            InstanceFolder::Mods => "mods",
+            InstanceFolder::Config => "config",
+            InstanceFolder::Saves => "saves",
+            InstanceFolder::Screenshots => "screenshots",
+            InstanceFolder::ResourcePacks => "resourcepacks",
+        }
+    }
+}
+
 #[derive(Deserialize)]
 struct MmcPack {
     components: Vec<Component>,
@@ -68,54 +249,121 @@ impl Instance {
         let config_path = path.join("instance.cfg");
         let mut config = Ini::new();
 
-        let (name, total_time_played, last_launch, server_join) = if config_path.exists() {
+        // A missing instance.cfg leaves the config empty, so every key falls back
+        if config_path.exists() {
             config
                 .load(&config_path)
                 .map_err(|e| PrismError::Config(e.to_string()))?;
+        }
 
-            let name = config.get("General", "name").unwrap_or_else(|| id.clone());
+        let name = config.get("General", "name").unwrap_or_else(|| id.clone());
 
-            let total_time_played = config
-                .get("General", "totalTimePlayed")
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(0);
+        let total_time_played = config
+            .get("General", "totalTimePlayed")
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0);
 
-            let last_launch = config
-                .get("General", "lastLaunchTime")
-                .and_then(|s| s.parse().ok());
+        let last_launch = config
+            .get("General", "lastLaunchTime")
+            .and_then(|s| s.parse().ok());
 
-            let join_enabled = config
-                .get("General", "JoinServerOnLaunch")
-                .map(|s| s == "true")
-                .unwrap_or(false);
+        let join_enabled = config
+            .get("General", "JoinServerOnLaunch")
+            .map(|s| s == "true")
+            .unwrap_or(false);
 
-            let join_address = config.get("General", "JoinServerOnLaunchAddress");
+        let join_address = config.get("General", "JoinServerOnLaunchAddress");
 
-            let server_join = join_address.map(|address| ServerJoin {
-                enabled: join_enabled,
-                address,
-            });
+        let server_join = join_address.map(|address| ServerJoin {
+            enabled: join_enabled,
+            address,
+        });
 
-            (name, total_time_played, last_launch, server_join)
-        } else {
-            (id.clone(), 0, None, None)
-        };
+        let world_join = config
+            .get("General", "JoinWorldOnLaunch")
+            .filter(|w| !w.is_empty());
+
+        let managed_pack = ManagedPack::from_config(&config);
+
+        let icon_key = config
+            .get("General", "iconKey")
+            .filter(|k| !k.is_empty() && k != "default");
 
-        let (minecraft_version, mod_loader) = parse_mmc_pack(&path)?;
+        let overrides_commands = config
+            .get("General", "OverrideCommands")
+            .is_some_and(|s| s == "true");
+        let wrapper_command = config
+            .get("General", "WrapperCommand")
+            .filter(|w| overrides_commands && !w.is_empty());
+
+        let setting_overrides = instance_overrides(&config);
+        let cfg_values = general_values(&config);
+
+        let (minecraft_version, mod_loader, mod_loader_version) = parse_mmc_pack(&path)?;
 
         let group = groups.get(&id).cloned();
 
-        Ok(Self {
+        let mut instance = Self {
             id,
             name,
             path,
             group,
             minecraft_version,
             mod_loader,
+            mod_loader_version,
             total_time_played,
             last_launch,
             server_join,
-        })
+            world_join,
+            managed_pack,
+            icon_key,
+            wrapper_command,
+            setting_overrides,
+            cfg_values,
+            unloaded_mods: 0,
+            search_key: String::new(),
+        };
+        instance.refresh_search_key();
+        instance.refresh_unloaded_mods();
+        Ok(instance)
+    }
+
+    /// Count the jars in mods/ that nothing will load. PrismLauncher starts
+    /// an instance without a mod loader as vanilla, mods or not.
+    pub fn refresh_unloaded_mods(&mut self) {
+        self.unloaded_mods = if self.mod_loader.is_some() {
+            0
+        } else {
+            list_mods(&self.mods_dir())
+                .iter()
+                .filter(|m| m.enabled && m.name.ends_with(".jar"))
+                .count()
+        };
+    }
+
+    /// e.g. "3 mods but no mod loader", when mods/ has jars nothing loads
+    pub fn missing_loader_label(&self) -> Option<String> {
+        match self.unloaded_mods {
+            0 => None,
+            1 => Some("1 mod but no mod loader".to_string()),
+            n => Some(format!("{} mods but no mod loader", n)),
+        }
+    }
+
+    /// Rebuild `search_key` after a field it covers changed. Fields are kept
+    /// on separate lines so a query can't match across two of them.
+    pub fn refresh_search_key(&mut self) {
+        self.search_key = [
+            Some(self.name.as_str()),
+            Some(self.minecraft_version.as_str()),
+            self.mod_loader.as_deref(),
+            self.group.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n")
+        .to_lowercase();
     }
 
     pub fn servers_dat_path(&self) -> PathBuf {
@@ -130,13 +378,81 @@ impl Instance {
             .unwrap_or_else(|| self.path.join(".minecraft/logs"))
     }
 
+    pub fn mods_dir(&self) -> PathBuf {
+        self.game_folder(InstanceFolder::Mods)
+    }
+
+    pub fn game_folder(&self, folder: InstanceFolder) -> PathBuf {
+        self.minecraft_dir()
+            .map(|d| d.join(folder.dir_name()))
+            .unwrap_or_else(|| self.path.join(".minecraft").join(folder.dir_name()))
+    }
+
+    pub fn crash_reports_dir(&self) -> PathBuf {
+        self.minecraft_dir()
+            .map(|d| d.join("crash-reports"))
+            .unwrap_or_else(|| self.path.join(".minecraft/crash-reports"))
+    }
+
+    /// The game's options.txt, which may not exist before the first launch
+    pub fn options_file(&self) -> PathBuf {
+        self.minecraft_dir()
+            .map(|d| d.join("options.txt"))
+            .unwrap_or_else(|| self.path.join(".minecraft/options.txt"))
+    }
+
+    /// A launcher setting as it applies to this instance: its own override,
+    /// otherwise the global value
+    pub fn effective_setting(
+        &self,
+        global: &PrismSettings,
+        key: &str,
+    ) -> Option<(String, SettingSource)> {
+        if let Some(value) = self.setting_overrides.get(key) {
+            return Some((value.clone(), SettingSource::Instance));
+        }
+        global
+            .get(key)
+            .map(|v| (v.to_string(), SettingSource::Global))
+    }
+
+    /// Memory allocation as it applies to this instance, e.g. "512-4096 MB"
+    pub fn memory_setting(&self, global: &PrismSettings) -> Option<(String, SettingSource)> {
+        let setting = |key| self.effective_setting(global, key);
+        match (setting("MinMemAlloc"), setting("MaxMemAlloc")) {
+            (Some((min, source)), Some((max, _))) => Some((format!("{}-{} MB", min, max), source)),
+            (None, Some((max, source))) => Some((format!("up to {} MB", max), source)),
+            (Some((min, source)), None) => Some((format!("from {} MB", min), source)),
+            (None, None) => None,
+        }
+    }
+
+    /// Address of the enabled join-on-launch server
+    pub fn join_address(&self) -> Option<&str> {
+        self.server_join
+            .as_ref()
+            .filter(|sj| sj.enabled)
+            .map(|sj| sj.address.as_str())
+    }
+
+    /// Mod loader name with its component version, e.g. "Fabric 0.15.7"
+    pub fn mod_loader_display(&self) -> Option<String> {
+        let loader = self.mod_loader.as_deref()?;
+        Some(match &self.mod_loader_version {
+            Some(version) => format!("{} {}", loader, version),
+            None => loader.to_string(),
+        })
+    }
+
     pub fn formatted_playtime(&self) -> String {
-        let hours = self.total_time_played / 3600;
-        if hours > 0 {
-            format!("{}h played", hours)
-        } else {
-            let minutes = self.total_time_played / 60;
-            format!("{}m played", minutes)
+        format::playtime(self.total_time_played)
+    }
+
+    pub fn stats(&self) -> InstanceStats {
+        InstanceStats {
+            mods: self.mods_count(),
+            saves: self.saves_count(),
+            resource_packs: self.resource_packs_count(),
         }
     }
 
@@ -183,66 +499,152 @@ impl Instance {
 
     pub fn formatted_last_launch(&self) -> String {
         match self.last_launch {
-            Some(ts) if ts > 0 => {
-                use chrono::{DateTime, Local, Utc};
-                let dt = DateTime::<Utc>::from_timestamp(ts / 1000, 0);
-                match dt {
-                    Some(utc) => {
-                        let local: DateTime<Local> = utc.into();
-                        local.format("%Y-%m-%d %H:%M").to_string()
-                    }
-                    None => "Unknown".to_string(),
-                }
-            }
+            Some(ts) if ts > 0 => match chrono::DateTime::from_timestamp(ts / 1000, 0) {
+                Some(time) => format::timestamp(time),
+                None => "Unknown".to_string(),
+            },
+            _ => "Never".to_string(),
+        }
+    }
+
+    /// Date and age of the last launch, e.g. "2024-05-01 14:03 (3h ago)"
+    pub fn formatted_last_launch_full(&self) -> String {
+        match self.last_launch {
+            Some(ts) if ts > 0 => match chrono::DateTime::from_timestamp(ts / 1000, 0) {
+                Some(time) => format!("{} ({})", format::absolute(time), format::age(time)),
+                None => "Unknown".to_string(),
+            },
             _ => "Never".to_string(),
         }
     }
 
+    /// Age of the last launch for the instance list, e.g. "3h ago"
+    pub fn last_played(&self) -> String {
+        self.last_launch
+            .filter(|&ts| ts > 0)
+            .and_then(|ts| chrono::DateTime::from_timestamp(ts / 1000, 0))
+            .map_or_else(|| "-".to_string(), format::age)
+    }
+
     pub fn formatted_playtime_full(&self) -> String {
-        let total = self.total_time_played;
-        let hours = total / 3600;
-        let minutes = (total % 3600) / 60;
-        if hours > 0 {
-            format!("{}h {}m", hours, minutes)
-        } else {
-            format!("{}m", minutes)
+        format::playtime_full(self.total_time_played)
+    }
+
+    /// Whether something else changed the keys `edit` writes since we read
+    /// them. PrismLauncher rewrites instance.cfg on every launch and exit,
+    /// so the rest of the file is no sign of a conflict.
+    pub fn cfg_changed_on_disk(&self, edit: &InstanceCfgEdit) -> bool {
+        let on_disk = read_general_values(&self.path);
+        edit.keys()
+            .into_iter()
+            .map(|key| key.to_lowercase())
+            .any(|key| on_disk.get(&key) != self.cfg_values.get(&key))
+    }
+
+    /// Apply an instance.cfg change. With `write` unset only the loaded
+    /// values change, for edits held back until the game exits. Only the
+    /// edited keys are written, over whatever the file holds by then.
+    pub fn apply_cfg_edit(&mut self, edit: &InstanceCfgEdit, write: bool) -> Result<()> {
+        if write {
+            update_instance_cfg(&self.path, |config| edit.write(config))?;
+            let on_disk = read_general_values(&self.path);
+            for key in edit.keys() {
+                let key = key.to_lowercase();
+                match on_disk.get(&key) {
+                    Some(value) => self.cfg_values.insert(key, value.clone()),
+                    None => self.cfg_values.remove(&key),
+                };
+            }
+        }
+        match edit {
+            InstanceCfgEdit::ServerJoin { enabled, address } => {
+                self.server_join = address.clone().map(|address| ServerJoin {
+                    enabled: *enabled,
+                    address,
+                });
+            }
+            InstanceCfgEdit::ClearWorldJoin => self.world_join = None,
+            InstanceCfgEdit::WrapperCommand(wrapper) => self.wrapper_command = wrapper.clone(),
+            InstanceCfgEdit::Settings(values) => {
+                for (flag, keys) in OVERRIDE_GROUPS {
+                    let Some(enabled) = values.get(*flag) else {
+                        continue;
+                    };
+                    for key in *keys {
+                        match values
+                            .get(*key)
+                            .filter(|v| enabled == "true" && !v.is_empty())
+                        {
+                            Some(value) => self
+                                .setting_overrides
+                                .insert(key.to_string(), value.clone()),
+                            None => self.setting_overrides.remove(*key),
+                        };
+                    }
+                }
+            }
         }
+        Ok(())
     }
 
-    pub fn set_server_join(&mut self, enabled: bool, address: Option<String>) -> Result<()> {
+    /// The memory and Java settings in instance.cfg, for copying to other
+    /// instances. Override flags that aren't set count as off.
+    pub fn java_settings(&self) -> Result<BTreeMap<String, String>> {
         let config_path = self.path.join("instance.cfg");
-        let mut config = Ini::new();
-
+        let mut config = Ini::new_cs();
         if config_path.exists() {
             config
                 .load(&config_path)
                 .map_err(|e| PrismError::Config(e.to_string()))?;
         }
+        Ok(JAVA_SETTINGS
+            .iter()
+            .filter_map(|key| {
+                let value = config.get("General", key);
+                let value = match value {
+                    Some(value) => value,
+                    None if key.starts_with("Override") => "false".to_string(),
+                    None => return None,
+                };
+                Some((key.to_string(), value))
+            })
+            .collect())
+    }
 
-        config.set("General", "JoinServerOnLaunch", Some(enabled.to_string()));
+    /// Check the enabled join-on-launch server against the world join setting
+    /// and the instance's server list
+    pub fn join_conflict(&self, servers: &[Server]) -> Option<JoinConflict> {
+        let join = self.server_join.as_ref().filter(|sj| sj.enabled)?;
 
-        if let Some(addr) = &address {
-            config.set("General", "JoinServerOnLaunchAddress", Some(addr.clone()));
+        if self.world_join.is_some() {
+            Some(JoinConflict::WorldAndServer)
+        } else if !servers.iter().any(|s| s.ip == join.address) {
+            Some(JoinConflict::MissingServer)
+        } else {
+            None
         }
-
-        config
-            .write(&config_path)
-            .map_err(|e| PrismError::Config(e.to_string()))?;
-
-        self.server_join = address.map(|addr| ServerJoin {
-            enabled,
-            address: addr,
-        });
-
-        Ok(())
     }
 }
 
-fn parse_mmc_pack(instance_path: &Path) -> Result<(String, Option<String>)> {
+/// The components in mmc-pack.json as (uid, version)
+pub(crate) fn pack_components(instance_path: &Path) -> Result<Vec<(String, String)>> {
+    let content = fs::read_to_string(instance_path.join("mmc-pack.json"))?;
+    let pack: MmcPack = serde_json::from_str(&content)?;
+    Ok(pack
+        .components
+        .iter()
+        .filter_map(|c| Some((c.uid.clone(), c.get_version()?.to_string())))
+        .collect())
+}
+
+/// Parse mmc-pack.json into (minecraft version, mod loader, mod loader version)
+pub(crate) fn parse_mmc_pack(
+    instance_path: &Path,
+) -> Result<(String, Option<String>, Option<String>)> {
     let pack_path = instance_path.join("mmc-pack.json");
 
     if !pack_path.exists() {
-        return Ok(("Unknown".into(), None));
+        return Ok(("Unknown".into(), None, None));
     }
 
     let content = fs::read_to_string(&pack_path)?;
@@ -250,33 +652,171 @@ fn parse_mmc_pack(instance_path: &Path) -> Result<(String, Option<String>)> {
 
     let mut minecraft_version = "Unknown".to_string();
     let mut mod_loader = None;
+    let mut mod_loader_version = None;
 
     for component in pack.components {
-        match component.uid.as_str() {
+        let loader = match component.uid.as_str() {
             "net.minecraft" => {
                 if let Some(ver) = component.get_version() {
                     minecraft_version = ver.to_string();
                 }
+                continue;
+            }
+            "net.minecraftforge" => "Forge",
+            "net.fabricmc.fabric-loader" => "Fabric",
+            "org.quiltmc.quilt-loader" => "Quilt",
+            "net.neoforged" => "NeoForge",
+            _ => continue,
+        };
+        mod_loader = Some(loader.to_string());
+        mod_loader_version = component.get_version().map(|v| v.to_string());
+    }
+
+    Ok((minecraft_version, mod_loader, mod_loader_version))
+}
+
+/// A change to an instance's instance.cfg
+#[derive(Debug, Clone, PartialEq)]
+pub enum InstanceCfgEdit {
+    ServerJoin {
+        enabled: bool,
+        address: Option<String>,
+    },
+    ClearWorldJoin,
+    /// PrismLauncher's wrapper command. Enabling command overrides is
+    /// required for Prism to use it; clearing leaves other overrides alone.
+    WrapperCommand(Option<String>),
+    /// Raw keys, e.g. memory and Java settings copied from another instance
+    Settings(BTreeMap<String, String>),
+}
+
+impl InstanceCfgEdit {
+    /// The `[General]` keys `write` sets
+    fn keys(&self) -> Vec<&str> {
+        match self {
+            InstanceCfgEdit::ServerJoin { .. } => {
+                vec!["JoinServerOnLaunch", "JoinServerOnLaunchAddress"]
             }
-            "net.minecraftforge" => mod_loader = Some("Forge".to_string()),
-            "net.fabricmc.fabric-loader" => mod_loader = Some("Fabric".to_string()),
-            "org.quiltmc.quilt-loader" => mod_loader = Some("Quilt".to_string()),
-            "net.neoforged" => mod_loader = Some("NeoForge".to_string()),
-            _ => {}
+            InstanceCfgEdit::ClearWorldJoin => vec!["JoinWorldOnLaunch"],
+            InstanceCfgEdit::WrapperCommand(_) => vec!["OverrideCommands", "WrapperCommand"],
+            InstanceCfgEdit::Settings(values) => values.keys().map(String::as_str).collect(),
         }
     }
 
-    Ok((minecraft_version, mod_loader))
+    fn write(&self, config: &mut Ini) {
+        match self {
+            InstanceCfgEdit::ServerJoin { enabled, address } => {
+                config.set("General", "JoinServerOnLaunch", Some(enabled.to_string()));
+                if let Some(addr) = address {
+                    config.set("General", "JoinServerOnLaunchAddress", Some(addr.clone()));
+                }
+            }
+            InstanceCfgEdit::ClearWorldJoin => {
+                config.set("General", "JoinWorldOnLaunch", Some(String::new()));
+            }
+            InstanceCfgEdit::WrapperCommand(wrapper) => {
+                if wrapper.is_some() {
+                    config.set("General", "OverrideCommands", Some("true".to_string()));
+                }
+                config.set(
+                    "General",
+                    "WrapperCommand",
+                    Some(wrapper.clone().unwrap_or_default()),
+                );
+            }
+            InstanceCfgEdit::Settings(values) => {
+                for (key, value) in values {
+                    config.set("General", key, Some(value.clone()));
+                }
+            }
+        }
+    }
+}
+
+/// The `[General]` section of a case insensitively loaded instance.cfg
+fn general_values(config: &Ini) -> HashMap<String, String> {
+    config
+        .get_map_ref()
+        .get("general")
+        .into_iter()
+        .flatten()
+        .filter_map(|(key, value)| Some((key.clone(), value.clone()?)))
+        .collect()
+}
+
+/// Read instance.cfg's `[General]` values again; unreadable reads as empty
+fn read_general_values(instance_path: &Path) -> HashMap<String, String> {
+    let mut config = Ini::new();
+    match config.load(instance_path.join("instance.cfg")) {
+        Ok(_) => general_values(&config),
+        Err(_) => HashMap::new(),
+    }
+}
+
+/// Load instance.cfg, apply `edit` and write it back. Keys are read case
+/// sensitively here because PrismLauncher expects their original case.
+fn update_instance_cfg(instance_path: &Path, edit: impl FnOnce(&mut Ini)) -> Result<()> {
+    let config_path = instance_path.join("instance.cfg");
+    let mut config = Ini::new_cs();
+
+    if config_path.exists() {
+        config
+            .load(&config_path)
+            .map_err(|e| PrismError::Config(e.to_string()))?;
+    }
+
+    edit(&mut config);
+
+    write_atomic(&config_path, config.writes().as_bytes())?;
+
+    Ok(())
+}
+
+/// Resolve a user-supplied instance reference: an alias, then the folder id,
+/// then a case-insensitive name match
+pub fn find_instance<'a>(
+    instances: &'a [Instance],
+    aliases: &BTreeMap<String, String>,
+    query: &str,
+) -> Option<&'a Instance> {
+    let lower = query.to_lowercase();
+    if let Some(id) = aliases.get(&lower)
+        && let Some(instance) = instances.iter().find(|i| &i.id == id)
+    {
+        return Some(instance);
+    }
+    instances
+        .iter()
+        .find(|i| i.id == query)
+        .or_else(|| instances.iter().find(|i| i.name.to_lowercase() == lower))
 }
 
 pub fn load_instances(
-    instances_dir: &PathBuf,
+    instances_dir: &Path,
     groups: &HashMap<String, String>,
 ) -> Result<Vec<Instance>> {
-    let mut instances = Vec::new();
+    let dirs = instance_dirs(instances_dir)?;
+    let loaded = Mutex::new(Vec::with_capacity(dirs.len()));
+    load_instance_dirs(&dirs, groups, |instance| {
+        loaded
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(instance);
+    });
+    let mut instances = loaded.into_inner().unwrap_or_else(|e| e.into_inner());
+
+    // Sort by last launch time (most recent first)
+    instances.sort_by_key(|i| std::cmp::Reverse(i.last_launch));
+
+    Ok(instances)
+}
+
+/// Folders in `instances_dir` that hold an instance
+pub fn instance_dirs(instances_dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
 
     if !instances_dir.exists() {
-        return Ok(instances);
+        return Ok(dirs);
     }
 
     for entry in fs::read_dir(instances_dir)? {
@@ -298,14 +838,352 @@ pub fn load_instances(
             continue;
         }
 
-        match Instance::load(path, groups) {
-            Ok(instance) => instances.push(instance),
-            Err(e) => eprintln!("Warning: Failed to load instance: {}", e),
+        dirs.push(path);
+    }
+    Ok(dirs)
+}
+
+/// Read the instances in `dirs` on a few threads, handing each to `loaded`
+/// as soon as it is parsed. The folders are independent, so on slow disks
+/// this overlaps their reads instead of waiting on them one by one.
+pub fn load_instance_dirs(
+    dirs: &[PathBuf],
+    groups: &HashMap<String, String>,
+    loaded: impl Fn(Instance) + Sync,
+) {
+    let threads = std::thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .clamp(1, MAX_LOAD_THREADS)
+        .min(dirs.len());
+    let next = AtomicUsize::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                while let Some(path) = dirs.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    match Instance::load(path.clone(), groups) {
+                        Ok(instance) => loaded(instance),
+                        Err(e) => tracing::warn!("Failed to load instance: {}", e),
+                    }
+                }
+            });
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_instance(server_join: Option<ServerJoin>, world_join: Option<&str>) -> Instance {
+        Instance {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            path: PathBuf::from("/tmp/test"),
+            group: None,
+            minecraft_version: "1.20.1".to_string(),
+            mod_loader: None,
+            mod_loader_version: None,
+            total_time_played: 0,
+            last_launch: None,
+            server_join,
+            world_join: world_join.map(|s| s.to_string()),
+            managed_pack: None,
+            icon_key: None,
+            wrapper_command: None,
+            setting_overrides: HashMap::new(),
+            cfg_values: HashMap::new(),
+            unloaded_mods: 0,
+            search_key: String::new(),
         }
     }
 
-    // Sort by last launch time (most recent first)
-    instances.sort_by(|a, b| b.last_launch.cmp(&a.last_launch));
+    fn join(address: &str, enabled: bool) -> Option<ServerJoin> {
+        Some(ServerJoin {
+            enabled,
+            address: address.to_string(),
+        })
+    }
 
-    Ok(instances)
+    fn servers() -> Vec<Server> {
+        vec![Server::new(
+            "Example".to_string(),
+            "mc.example.com".to_string(),
+        )]
+    }
+
+    #[test]
+    fn test_mod_loader_display() {
+        let mut instance = create_test_instance(None, None);
+        assert_eq!(instance.mod_loader_display(), None);
+
+        instance.mod_loader = Some("Fabric".to_string());
+        assert_eq!(instance.mod_loader_display().as_deref(), Some("Fabric"));
+
+        instance.mod_loader_version = Some("0.15.7".to_string());
+        assert_eq!(
+            instance.mod_loader_display().as_deref(),
+            Some("Fabric 0.15.7")
+        );
+    }
+
+    #[test]
+    fn test_managed_pack_from_config() {
+        let mut config = Ini::new();
+        config
+            .read(
+                "[General]\nManagedPack=true\nManagedPackType=modrinth\nManagedPackID=abc123\n\
+                 ManagedPackName=Example Pack\nManagedPackVersionName=1.2.0\n"
+                    .to_string(),
+            )
+            .unwrap();
+
+        let pack = ManagedPack::from_config(&config).unwrap();
+        assert_eq!(pack.platform, PackPlatform::Modrinth);
+        assert_eq!(pack.name, "Example Pack");
+        assert_eq!(pack.version_name, "1.2.0");
+        assert_eq!(
+            pack.url().as_deref(),
+            Some("https://modrinth.com/modpack/abc123")
+        );
+    }
+
+    #[test]
+    fn test_managed_pack_absent() {
+        let mut config = Ini::new();
+        config
+            .read("[General]\nManagedPack=false\n".to_string())
+            .unwrap();
+        assert!(ManagedPack::from_config(&config).is_none());
+    }
+
+    #[test]
+    fn test_cfg_edit_keeps_key_case() {
+        let dir = crate::data::test_dir("instance");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("instance.cfg"),
+            "[General]\nJoinServerOnLaunch=false\n",
+        )
+        .unwrap();
+
+        let mut instance = create_test_instance(None, None);
+        instance.path = dir.clone();
+        instance
+            .apply_cfg_edit(
+                &InstanceCfgEdit::WrapperCommand(Some("gamemoderun".to_string())),
+                true,
+            )
+            .unwrap();
+
+        // Held back edits only change the loaded values
+        let join = InstanceCfgEdit::ServerJoin {
+            enabled: true,
+            address: Some("mc.example.com".to_string()),
+        };
+        instance.apply_cfg_edit(&join, false).unwrap();
+        assert!(instance.server_join.as_ref().is_some_and(|sj| sj.enabled));
+
+        let written = std::fs::read_to_string(dir.join("instance.cfg")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(written.contains("[General]"));
+        assert!(written.contains("JoinServerOnLaunch=false"));
+        assert!(!written.contains("mc.example.com"));
+        assert!(written.contains("OverrideCommands=true"));
+        assert!(written.contains("WrapperCommand=gamemoderun"));
+    }
+
+    #[test]
+    fn test_search_key_keeps_fields_apart() {
+        let mut instance = create_test_instance(None, None);
+        instance.mod_loader = Some("Fabric".to_string());
+        instance.group = Some("Modpacks".to_string());
+        instance.refresh_search_key();
+
+        assert!(instance.search_key.contains("fabric"));
+        assert!(instance.search_key.contains("modpacks"));
+        assert!(!instance.search_key.contains("fabricmod"));
+    }
+
+    #[test]
+    fn test_load_instances_skips_non_instances() {
+        let dir = crate::data::test_dir("load");
+        for (folder, last_launch) in [("a", 1), ("b", 3), ("c", 2), (".hidden", 4)] {
+            std::fs::create_dir_all(dir.join(folder)).unwrap();
+            std::fs::write(
+                dir.join(folder).join("instance.cfg"),
+                format!(
+                    "[General]\nname={}\nlastLaunchTime={}\n",
+                    folder, last_launch
+                ),
+            )
+            .unwrap();
+        }
+        std::fs::create_dir_all(dir.join("no-cfg")).unwrap();
+
+        let instances = load_instances(&dir, &HashMap::new()).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let ids: Vec<&str> = instances.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, ["b", "c", "a"]);
+    }
+
+    #[test]
+    fn test_unloaded_mods_only_without_a_loader() {
+        let dir = crate::data::test_dir("noloader");
+        let mods = dir.join(".minecraft").join("mods");
+        std::fs::create_dir_all(&mods).unwrap();
+        for file in [
+            "sodium-0.5.8.jar",
+            "lithium-0.11.jar",
+            "create-0.5.1.jar.disabled",
+        ] {
+            std::fs::write(mods.join(file), "").unwrap();
+        }
+        let mut instance = create_test_instance(None, None);
+        instance.path = dir.clone();
+
+        instance.refresh_unloaded_mods();
+        let without_loader = instance.missing_loader_label();
+        instance.mod_loader = Some("Fabric".to_string());
+        instance.refresh_unloaded_mods();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(without_loader.as_deref(), Some("2 mods but no mod loader"));
+        assert_eq!(instance.missing_loader_label(), None);
+    }
+
+    #[test]
+    fn test_java_settings_copy_to_another_instance() {
+        let dir = crate::data::test_dir("java");
+        std::fs::create_dir_all(dir.join("a")).unwrap();
+        std::fs::create_dir_all(dir.join("b")).unwrap();
+        std::fs::write(
+            dir.join("a/instance.cfg"),
+            "[General]\nOverrideMemory=true\nMaxMemAlloc=8192\nname=A\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join("b/instance.cfg"),
+            "[General]\nOverrideJavaArgs=true\nJvmArgs=-Xss4M\nname=B\n",
+        )
+        .unwrap();
+
+        let mut source = create_test_instance(None, None);
+        source.path = dir.join("a");
+        let settings = source.java_settings().unwrap();
+        let mut target = create_test_instance(None, None);
+        target.path = dir.join("b");
+        target
+            .setting_overrides
+            .insert("JvmArgs".to_string(), "-Xss4M".to_string());
+        target
+            .apply_cfg_edit(&InstanceCfgEdit::Settings(settings.clone()), true)
+            .unwrap();
+
+        let written = std::fs::read_to_string(dir.join("b/instance.cfg")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(
+            settings.get("OverrideJavaArgs").map(String::as_str),
+            Some("false")
+        );
+        assert!(!settings.contains_key("name") && !settings.contains_key("JvmArgs"));
+        assert!(written.contains("MaxMemAlloc=8192") && written.contains("name=B"));
+        assert!(written.contains("OverrideJavaArgs=false"));
+        assert_eq!(
+            target
+                .setting_overrides
+                .get("MaxMemAlloc")
+                .map(String::as_str),
+            Some("8192")
+        );
+        assert!(!target.setting_overrides.contains_key("JvmArgs"));
+    }
+
+    #[test]
+    fn test_cfg_writes_keep_mixed_case_keys() {
+        let dir = crate::data::test_dir("instance-case");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("instance.cfg"),
+            "[General]\nJoinServerOnLaunch=false\niconKey=flame\nJoinWorldOnLaunch=World\n",
+        )
+        .unwrap();
+
+        let mut instance = create_test_instance(None, Some("World"));
+        instance.path = dir.clone();
+        let join = InstanceCfgEdit::ServerJoin {
+            enabled: true,
+            address: Some("mc.example.com".to_string()),
+        };
+        instance.apply_cfg_edit(&join, true).unwrap();
+        instance
+            .apply_cfg_edit(&InstanceCfgEdit::ClearWorldJoin, true)
+            .unwrap();
+
+        let written = std::fs::read_to_string(dir.join("instance.cfg")).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let keys: Vec<&str> = written
+            .lines()
+            .filter_map(|l| l.split_once('=').map(|(k, _)| k))
+            .collect();
+        assert_eq!(keys.len(), 4);
+        assert!(keys.contains(&"iconKey"));
+        assert!(written.contains("JoinServerOnLaunch=true"));
+        assert!(written.contains("JoinServerOnLaunchAddress=mc.example.com"));
+        assert!(written.contains("JoinWorldOnLaunch="));
+        assert!(!written.to_lowercase().contains("joinserveronlaunch=false"));
+    }
+
+    #[test]
+    fn test_find_instance() {
+        let mut a = create_test_instance(None, None);
+        a.id = "All the Mods 9".to_string();
+        a.name = "All the Mods 9".to_string();
+        let mut b = create_test_instance(None, None);
+        b.id = "vanilla".to_string();
+        b.name = "Vanilla 1.21".to_string();
+        let instances = vec![a, b];
+
+        let mut aliases = BTreeMap::new();
+        aliases.insert("atm9".to_string(), "All the Mods 9".to_string());
+
+        let find = |q| find_instance(&instances, &aliases, q).map(|i| i.id.as_str());
+        assert_eq!(find("ATM9"), Some("All the Mods 9"));
+        assert_eq!(find("vanilla"), Some("vanilla"));
+        assert_eq!(find("vanilla 1.21"), Some("vanilla"));
+        assert_eq!(find("missing"), None);
+    }
+
+    #[test]
+    fn test_join_conflict_none_when_valid() {
+        let instance = create_test_instance(join("mc.example.com", true), None);
+        assert_eq!(instance.join_conflict(&servers()), None);
+    }
+
+    #[test]
+    fn test_join_conflict_ignores_disabled_join() {
+        let instance = create_test_instance(join("gone.example.com", false), Some("World"));
+        assert_eq!(instance.join_conflict(&servers()), None);
+    }
+
+    #[test]
+    fn test_join_conflict_world_and_server() {
+        let instance = create_test_instance(join("mc.example.com", true), Some("World"));
+        assert_eq!(
+            instance.join_conflict(&servers()),
+            Some(JoinConflict::WorldAndServer)
+        );
+    }
+
+    #[test]
+    fn test_join_conflict_missing_server() {
+        let instance = create_test_instance(join("gone.example.com", true), None);
+        assert_eq!(
+            instance.join_conflict(&servers()),
+            Some(JoinConflict::MissingServer)
+        );
+    }
 }
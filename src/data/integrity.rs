@@ -0,0 +1,406 @@
+//! Checking the libraries and assets an instance runs with against the
+//! SHA-1s in PrismLauncher's meta cache, e.g. after copying the data
+//! directory to another machine. Files the metadata has no hash for, like
+//! loader libraries fetched from a maven URL, only have to be there.
+
+use crate::data::instance::pack_components;
+use crate::error::Result;
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileProblem {
+    Missing,
+    /// Another size or hash than the metadata lists, or unreadable
+    Corrupt,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BadFile {
+    pub path: PathBuf,
+    pub problem: FileProblem,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntegrityReport {
+    /// Files compared with the metadata
+    pub checked: usize,
+    pub bad: Vec<BadFile>,
+    /// Components without metadata in the cache, as "uid version"
+    pub uncached: Vec<String>,
+}
+
+/// A component's file in meta/, or its override in the instance's patches/
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct ComponentMeta {
+    libraries: Vec<Library>,
+    main_jar: Option<Library>,
+    asset_index: Option<AssetIndexRef>,
+}
+
+#[derive(Deserialize)]
+struct Library {
+    name: String,
+    downloads: Option<Downloads>,
+    #[serde(default)]
+    natives: HashMap<String, String>,
+    #[serde(default)]
+    rules: Vec<Rule>,
+    /// "local" for jars kept in the instance's own libraries/
+    #[serde(rename = "MMC-hint")]
+    hint: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Downloads {
+    artifact: Option<Artifact>,
+    #[serde(default)]
+    classifiers: HashMap<String, Artifact>,
+}
+
+#[derive(Deserialize)]
+struct Artifact {
+    sha1: Option<String>,
+    size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct Rule {
+    action: String,
+    os: Option<OsRule>,
+}
+
+#[derive(Deserialize)]
+struct OsRule {
+    name: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct AssetIndexRef {
+    id: String,
+    sha1: Option<String>,
+    size: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct AssetIndex {
+    objects: HashMap<String, AssetObject>,
+}
+
+#[derive(Deserialize)]
+struct AssetObject {
+    hash: String,
+    size: u64,
+}
+
+/// A file the metadata asks for
+struct Expected {
+    path: PathBuf,
+    sha1: Option<String>,
+    size: Option<u64>,
+}
+
+impl Expected {
+    fn new(path: PathBuf, artifact: Option<&Artifact>) -> Self {
+        Self {
+            path,
+            sha1: artifact.and_then(|a| a.sha1.clone()),
+            size: artifact.and_then(|a| a.size),
+        }
+    }
+}
+
+/// Compare every library, native and asset the instance's components list
+/// with the files under `data_dir`
+pub fn verify_instance(instance_path: &Path, data_dir: &Path) -> Result<IntegrityReport> {
+    let os = os_name();
+    let mut report = IntegrityReport::default();
+    let mut expected = Vec::new();
+    for (uid, version) in pack_components(instance_path)? {
+        let patch = instance_path.join("patches").join(format!("{}.json", uid));
+        let meta_path = if patch.is_file() {
+            patch
+        } else {
+            data_dir
+                .join("meta")
+                .join(&uid)
+                .join(format!("{}.json", version))
+        };
+        let Ok(content) = fs::read_to_string(&meta_path) else {
+            report.uncached.push(format!("{} {}", uid, version));
+            continue;
+        };
+        let meta: ComponentMeta = serde_json::from_str(&content)?;
+        for library in meta.libraries.iter().chain(&meta.main_jar) {
+            expected.extend(library_files(library, &os, data_dir, instance_path));
+        }
+        if let Some(index) = &meta.asset_index {
+            expected.extend(asset_files(index, data_dir));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    for file in expected {
+        if !seen.insert(file.path.clone()) {
+            continue;
+        }
+        report.checked += 1;
+        if let Some(problem) = check_file(&file) {
+            report.bad.push(BadFile {
+                path: file.path,
+                problem,
+            });
+        }
+    }
+    Ok(report)
+}
+
+/// The jar and natives `library` needs on this system, if any
+fn library_files(
+    library: &Library,
+    os: &str,
+    data_dir: &Path,
+    instance_path: &Path,
+) -> Vec<Expected> {
+    if !rules_allow(&library.rules, os) {
+        return Vec::new();
+    }
+    let Some(path) = maven_path(&library.name) else {
+        return Vec::new();
+    };
+    let libraries = data_dir.join("libraries");
+    let mut files = Vec::new();
+
+    let artifact = library.downloads.as_ref().and_then(|d| d.artifact.as_ref());
+    if library.hint.as_deref() == Some("local") {
+        let local = instance_path
+            .join("libraries")
+            .join(path.file_name().unwrap_or_default());
+        files.push(Expected::new(local, artifact));
+    } else if artifact.is_some() || (library.downloads.is_none() && library.natives.is_empty()) {
+        files.push(Expected::new(libraries.join(&path), artifact));
+    }
+
+    if let Some(classifier) = library.natives.get(os) {
+        let classifier = classifier.replace("${arch}", "64");
+        if let Some(path) = maven_path(&format!("{}:{}", library.name, classifier)) {
+            let artifact = library
+                .downloads
+                .as_ref()
+                .and_then(|d| d.classifiers.get(&classifier));
+            files.push(Expected::new(libraries.join(path), artifact));
+        }
+    }
+    files
+}
+
+/// The asset index and, once it can be read, every object it lists
+fn asset_files(index: &AssetIndexRef, data_dir: &Path) -> Vec<Expected> {
+    let assets = data_dir.join("assets");
+    let index_path = assets.join("indexes").join(format!("{}.json", index.id));
+    let objects = fs::read_to_string(&index_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<AssetIndex>(&content).ok())
+        .map(|index| index.objects)
+        .unwrap_or_default();
+
+    let mut files = vec![Expected {
+        path: index_path,
+        sha1: index.sha1.clone(),
+        size: index.size,
+    }];
+    files.extend(objects.into_values().filter_map(|object| {
+        let prefix = object.hash.get(..2)?;
+        Some(Expected {
+            path: assets.join("objects").join(prefix).join(&object.hash),
+            sha1: Some(object.hash),
+            size: Some(object.size),
+        })
+    }));
+    files
+}
+
+fn check_file(file: &Expected) -> Option<FileProblem> {
+    let Ok(metadata) = fs::metadata(&file.path) else {
+        return Some(FileProblem::Missing);
+    };
+    // A wrong size settles it without reading the file
+    if file.size.is_some_and(|size| size != metadata.len()) {
+        return Some(FileProblem::Corrupt);
+    }
+    let sha1 = file.sha1.as_deref()?;
+    match file_sha1(&file.path) {
+        Ok(actual) if actual.eq_ignore_ascii_case(sha1) => None,
+        _ => Some(FileProblem::Corrupt),
+    }
+}
+
+fn file_sha1(path: &Path) -> io::Result<String> {
+    let mut hasher = Sha1::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect())
+}
+
+/// `group:artifact:version[:classifier][@extension]` as a path in a maven
+/// repository
+fn maven_path(name: &str) -> Option<PathBuf> {
+    let (coordinates, extension) = name.split_once('@').unwrap_or((name, "jar"));
+    let mut parts = coordinates.split(':');
+    let (group, artifact, version) = (parts.next()?, parts.next()?, parts.next()?);
+    let classifier = parts.next().map(|c| format!("-{}", c)).unwrap_or_default();
+    Some(
+        PathBuf::from(group.replace('.', "/"))
+            .join(artifact)
+            .join(version)
+            .join(format!(
+                "{}-{}{}.{}",
+                artifact, version, classifier, extension
+            )),
+    )
+}
+
+/// Whether a library's OS rules let it load here. Without rules it always
+/// does; otherwise the last rule that matches decides.
+fn rules_allow(rules: &[Rule], os: &str) -> bool {
+    if rules.is_empty() {
+        return true;
+    }
+    let mut allowed = false;
+    for rule in rules {
+        let name = rule.os.as_ref().and_then(|o| o.name.as_deref());
+        if name.is_none_or(|name| name == os) {
+            allowed = rule.action == "allow";
+        }
+    }
+    allowed
+}
+
+/// This system as PrismLauncher's metadata names it, e.g. "osx-arm64"
+fn os_name() -> String {
+    let os = match std::env::consts::OS {
+        "macos" => "osx",
+        other => other,
+    };
+    match std::env::consts::ARCH {
+        "aarch64" => format!("{}-arm64", os),
+        _ => os.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sha1_of(bytes: &[u8]) -> String {
+        Sha1::digest(bytes)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    #[test]
+    fn test_maven_path() {
+        assert_eq!(
+            maven_path("org.lwjgl:lwjgl:3.3.1:natives-linux"),
+            Some(PathBuf::from(
+                "org/lwjgl/lwjgl/3.3.1/lwjgl-3.3.1-natives-linux.jar"
+            ))
+        );
+        assert_eq!(
+            maven_path("net.minecraft:client:1.20.1@txt"),
+            Some(PathBuf::from(
+                "net/minecraft/client/1.20.1/client-1.20.1.txt"
+            ))
+        );
+        assert_eq!(maven_path("not-maven"), None);
+    }
+
+    #[test]
+    fn test_verify_finds_missing_and_corrupt_files() {
+        let root = crate::data::test_dir("verify");
+        let instance = root.join("instances/Test");
+        fs::create_dir_all(&instance).unwrap();
+        fs::write(
+            instance.join("mmc-pack.json"),
+            r#"{"components":[{"uid":"net.minecraft","version":"1.20.1"},
+                {"uid":"net.fabricmc.fabric-loader","version":"0.15.0"}]}"#,
+        )
+        .unwrap();
+
+        let good = b"good jar".as_slice();
+        let elsewhere = b"for another system".as_slice();
+        let sound = b"a sound".as_slice();
+        let index = format!(
+            r#"{{"objects":{{"a.ogg":{{"hash":"{}","size":7}},"b.ogg":{{"hash":"{}","size":7}}}}}}"#,
+            sha1_of(sound),
+            sha1_of(b"lost it")
+        );
+        let meta = format!(
+            r#"{{"libraries":[
+                {{"name":"com.mojang:logging:1.1.1","downloads":{{"artifact":{{"sha1":"{good}","size":8}}}}}},
+                {{"name":"com.mojang:brigadier:1.1.8","downloads":{{"artifact":{{"sha1":"{good}","size":8}}}}}},
+                {{"name":"ca.weblite:java-objc-bridge:1.1","downloads":{{"artifact":{{"sha1":"{elsewhere}"}}}},
+                  "rules":[{{"action":"allow","os":{{"name":"amiga"}}}}]}}],
+              "assetIndex":{{"id":"5","sha1":"{index_sha1}"}}}}"#,
+            good = sha1_of(good),
+            elsewhere = sha1_of(elsewhere),
+            index_sha1 = sha1_of(index.as_bytes()),
+        );
+        for (path, content) in [
+            ("meta/net.minecraft/1.20.1.json", meta.as_bytes()),
+            ("libraries/com/mojang/logging/1.1.1/logging-1.1.1.jar", good),
+            (
+                "libraries/com/mojang/brigadier/1.1.8/brigadier-1.1.8.jar",
+                b"bad jar!".as_slice(),
+            ),
+            ("assets/indexes/5.json", index.as_bytes()),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        let object = root.join("assets/objects").join(&sha1_of(sound)[..2]);
+        fs::create_dir_all(&object).unwrap();
+        fs::write(object.join(sha1_of(sound)), sound).unwrap();
+
+        let report = verify_instance(&instance, &root).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        let mut bad: Vec<(String, FileProblem)> = report
+            .bad
+            .iter()
+            .map(|f| {
+                let name = f.path.strip_prefix(&root).unwrap();
+                (name.display().to_string(), f.problem)
+            })
+            .collect();
+        bad.sort_by(|a, b| a.0.cmp(&b.0));
+        let lost = sha1_of(b"lost it");
+        assert_eq!(
+            bad,
+            [
+                (
+                    format!("assets/objects/{}/{}", &lost[..2], lost),
+                    FileProblem::Missing
+                ),
+                (
+                    "libraries/com/mojang/brigadier/1.1.8/brigadier-1.1.8.jar".to_string(),
+                    FileProblem::Corrupt
+                ),
+            ]
+        );
+        // Two libraries, the index and its two objects; the library for
+        // another system is left out
+        assert_eq!(report.checked, 5);
+        assert_eq!(report.uncached, ["net.fabricmc.fabric-loader 0.15.0"]);
+    }
+}
@@ -0,0 +1,129 @@
+//! Which Java a Minecraft version needs, and which one an instance is set
+//! to use, so a too-old runtime is caught before the game fails to start.
+
+use crate::data::{Instance, PrismSettings};
+use std::path::Path;
+
+/// A release version as (major, minor, patch), e.g. "1.20" is [1, 20, 0].
+/// Snapshots and pre-releases give `None`.
+pub fn release_version(version: &str) -> Option<[u32; 3]> {
+    let mut parts = [0; 3];
+    let mut split = version.split('.');
+    for part in parts.iter_mut() {
+        match split.next() {
+            Some(s) => *part = s.parse().ok()?,
+            None => break,
+        }
+    }
+    split.next().is_none().then_some(parts)
+}
+
+/// The oldest Java major version a Minecraft release runs on, as listed in
+/// Mojang's version manifests
+pub fn required_java(minecraft_version: &str) -> Option<u32> {
+    let version = release_version(minecraft_version)?;
+    Some(if version >= [1, 20, 5] {
+        21
+    } else if version >= [1, 18, 0] {
+        17
+    } else if version >= [1, 17, 0] {
+        16
+    } else {
+        8
+    })
+}
+
+/// The major version in a `java -version` string: "17.0.8" is 17 and the
+/// old "1.8.0_382" style is 8
+pub fn java_major(version: &str) -> Option<u32> {
+    let mut parts = version.trim().split(['.', '_', '-', '+']);
+    match parts.next()?.parse().ok()? {
+        1 => parts.next()?.parse().ok(),
+        major => Some(major),
+    }
+}
+
+/// Guess the major version from where a runtime is installed, e.g.
+/// /usr/lib/jvm/java-17-openjdk/bin/java or ~/.jdks/jdk-21.0.2/bin/java
+fn java_major_from_path(path: &str) -> Option<u32> {
+    Path::new(path).components().rev().find_map(|component| {
+        let name = component.as_os_str().to_str()?.to_lowercase();
+        ["java-", "jdk-", "jre-", "jdk", "jre", "java"]
+            .iter()
+            .find_map(|prefix| java_major(name.strip_prefix(prefix)?))
+    })
+}
+
+/// A Minecraft version paired with a Java too old for it
+#[derive(Debug, Clone, PartialEq)]
+pub struct JavaMismatch {
+    pub required: u32,
+    pub configured: u32,
+}
+
+impl JavaMismatch {
+    /// e.g. "needs Java 21, set to Java 17"
+    pub fn label(&self) -> String {
+        format!(
+            "needs Java {}, set to Java {}",
+            self.required, self.configured
+        )
+    }
+}
+
+impl Instance {
+    /// The Java major version the instance is set to use: the version
+    /// PrismLauncher recorded for it, or else one read off its path
+    pub fn java_major(&self, global: &PrismSettings) -> Option<u32> {
+        let setting = |key| self.effective_setting(global, key).map(|(value, _)| value);
+        setting("JavaVersion")
+            .and_then(|version| java_major(&version))
+            .or_else(|| setting("JavaPath").and_then(|path| java_major_from_path(&path)))
+    }
+
+    /// Whether the instance's Java is older than its Minecraft version needs.
+    /// Unknown versions and runtimes are not flagged.
+    pub fn java_mismatch(&self, global: &PrismSettings) -> Option<JavaMismatch> {
+        let required = required_java(&self.minecraft_version)?;
+        let configured = self.java_major(global)?;
+        (configured < required).then_some(JavaMismatch {
+            required,
+            configured,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_required_java() {
+        assert_eq!(required_java("1.12.2"), Some(8));
+        assert_eq!(required_java("1.17.1"), Some(16));
+        assert_eq!(required_java("1.20.4"), Some(17));
+        assert_eq!(required_java("1.20.5"), Some(21));
+        assert_eq!(required_java("1.21"), Some(21));
+        assert_eq!(required_java("24w14a"), None);
+    }
+
+    #[test]
+    fn test_java_major_from_version_and_path() {
+        assert_eq!(java_major("17.0.8"), Some(17));
+        assert_eq!(java_major("1.8.0_382"), Some(8));
+        assert_eq!(java_major("21"), Some(21));
+        assert_eq!(
+            java_major_from_path("/usr/lib/jvm/java-17-openjdk/bin/java"),
+            Some(17)
+        );
+        assert_eq!(
+            java_major_from_path("/home/me/.jdks/jdk-21.0.2/bin/java"),
+            Some(21)
+        );
+        assert_eq!(
+            java_major_from_path("/usr/lib/jvm/java-8-openjdk/jre/bin/java"),
+            Some(8)
+        );
+        assert_eq!(java_major_from_path("/usr/bin/java"), None);
+    }
+}
@@ -0,0 +1,158 @@
+//! Files instances pile up that nothing needs to keep: old crash reports,
+//! Fabric's cache of remapped jars and the natives PrismLauncher extracts on
+//! every launch. Removing them only gives disk space back.
+
+use crate::data::Instance;
+use crate::data::logs::{format_size, load_crash_reports};
+use crate::data::storage::dir_size;
+use crate::error::Result;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JunkKind {
+    /// A crash report older than the configured age
+    CrashReport,
+    /// `.fabric/`, rebuilt by Fabric on the next launch
+    FabricCache,
+    /// `natives/`, extracted again on the next launch
+    Natives,
+}
+
+/// A file or folder a cleanup would remove
+#[derive(Debug, Clone, PartialEq)]
+pub struct JunkItem {
+    pub kind: JunkKind,
+    pub path: PathBuf,
+    pub size: u64,
+}
+
+impl JunkItem {
+    /// e.g. "Delete .fabric/ (Fabric cache, 210.4 MB)"
+    pub fn describe(&self) -> String {
+        let name = self.path.file_name().unwrap_or_default().to_string_lossy();
+        match self.kind {
+            JunkKind::CrashReport => {
+                format!("Delete crash-reports/{} ({})", name, format_size(self.size))
+            }
+            JunkKind::FabricCache => format!(
+                "Delete {}/ (Fabric cache, {})",
+                name,
+                format_size(self.size)
+            ),
+            JunkKind::Natives => format!("Delete {}/ ({})", name, format_size(self.size)),
+        }
+    }
+}
+
+/// What a cleanup of `instance` would remove: crash reports older than
+/// `crash_report_age` (none without one), the Fabric cache and extracted
+/// natives. Empty folders are left out.
+pub fn plan_junk_cleanup(
+    instance: &Instance,
+    crash_report_age: Option<Duration>,
+    now: SystemTime,
+) -> Vec<JunkItem> {
+    let mut plan: Vec<JunkItem> = crash_report_age
+        .and_then(|max_age| {
+            let reports = load_crash_reports(&instance.crash_reports_dir()).ok()?;
+            Some(
+                reports
+                    .into_iter()
+                    .filter(|r| {
+                        r.modified
+                            .and_then(|m| now.duration_since(m).ok())
+                            .is_some_and(|age| age > max_age)
+                    })
+                    .map(|r| JunkItem {
+                        kind: JunkKind::CrashReport,
+                        path: r.path,
+                        size: r.size,
+                    })
+                    .collect(),
+            )
+        })
+        .unwrap_or_default();
+
+    let folders = [
+        (
+            JunkKind::FabricCache,
+            instance.minecraft_dir().map(|d| d.join(".fabric")),
+        ),
+        (JunkKind::Natives, Some(instance.path.join("natives"))),
+    ];
+    for (kind, path) in folders {
+        let Some(path) = path.filter(|p| p.is_dir()) else {
+            continue;
+        };
+        let size = dir_size(&path);
+        if size > 0 {
+            plan.push(JunkItem { kind, path, size });
+        }
+    }
+    plan
+}
+
+/// Remove everything in a cleanup plan, returning the bytes freed
+pub fn apply_junk_cleanup(plan: &[JunkItem]) -> Result<u64> {
+    for item in plan {
+        match item.kind {
+            JunkKind::CrashReport => fs::remove_file(&item.path)?,
+            JunkKind::FabricCache | JunkKind::Natives => fs::remove_dir_all(&item.path)?,
+        }
+    }
+    Ok(plan.iter().map(|item| item.size).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    #[test]
+    fn test_junk_cleanup_keeps_recent_crash_reports() {
+        let dir = crate::data::test_dir("junk");
+        let minecraft = dir.join(".minecraft");
+        fs::create_dir_all(minecraft.join("crash-reports")).unwrap();
+        fs::create_dir_all(minecraft.join(".fabric/remappedJars")).unwrap();
+        fs::create_dir_all(dir.join("natives")).unwrap();
+        fs::write(
+            minecraft.join(".fabric/remappedJars/sodium.jar"),
+            "remapped",
+        )
+        .unwrap();
+        let now = SystemTime::now();
+        let day = Duration::from_secs(24 * 60 * 60);
+        for (name, age) in [("crash-old-client.txt", 40), ("crash-new-client.txt", 2)] {
+            let path = minecraft.join("crash-reports").join(name);
+            fs::write(&path, "crash").unwrap();
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(now - day * age)
+                .unwrap();
+        }
+        let instance = Instance::load(dir.clone(), &Default::default()).unwrap();
+
+        let plan = plan_junk_cleanup(&instance, Some(day * 30), now);
+        let kept = plan_junk_cleanup(&instance, None, now).len();
+        let freed = apply_junk_cleanup(&plan).unwrap();
+        let old_left = minecraft
+            .join("crash-reports/crash-old-client.txt")
+            .exists();
+        let new_left = minecraft
+            .join("crash-reports/crash-new-client.txt")
+            .exists();
+        let fabric_left = minecraft.join(".fabric").exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let kinds: Vec<JunkKind> = plan.iter().map(|item| item.kind).collect();
+        // The natives folder is empty, so there is nothing to free there
+        assert_eq!(kinds, [JunkKind::CrashReport, JunkKind::FabricCache]);
+        assert_eq!(kept, 1);
+        assert_eq!(freed, 13);
+        assert!(!old_left && new_left && !fabric_left);
+    }
+}
@@ -1,31 +1,175 @@
+use crate::data::format;
 use crate::error::Result;
+use flate2::Compression;
 use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
 use std::fs::{self, File};
-use std::io::{BufRead, BufReader, Read};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
-use std::time::SystemTime;
+use std::time::{Duration, SystemTime};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct LogEntry {
     pub name: String,
     pub path: PathBuf,
     pub modified: Option<SystemTime>,
     pub size: u64,
+    /// Original size recorded in a .log.gz file's footer (modulo 4 GiB)
+    pub uncompressed_size: Option<u64>,
 }
 
 impl LogEntry {
+    /// e.g. "2.0 MB", or "2.0 MB (~80.0 MB)" for a gzipped log
     pub fn formatted_size(&self) -> String {
-        if self.size < 1024 {
-            format!("{} B", self.size)
-        } else if self.size < 1024 * 1024 {
-            format!("{:.1} KB", self.size as f64 / 1024.0)
-        } else {
-            format!("{:.1} MB", self.size as f64 / (1024.0 * 1024.0))
+        match self.uncompressed_size {
+            Some(original) => format!("{} (~{})", format_size(self.size), format_size(original)),
+            None => format_size(self.size),
         }
     }
+
+    pub fn formatted_modified(&self) -> Option<String> {
+        self.modified
+            .map(|time| format::timestamp(chrono::DateTime::<chrono::Local>::from(time)))
+    }
+}
+
+pub fn format_size(size: u64) -> String {
+    if size < 1024 {
+        format!("{} B", size)
+    } else if size < 1024 * 1024 {
+        format!("{:.1} KB", size as f64 / 1024.0)
+    } else if size < 1024 * 1024 * 1024 {
+        format!("{:.1} MB", size as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} GB", size as f64 / (1024.0 * 1024.0 * 1024.0))
+    }
+}
+
+/// Logs the game may still be writing to, never cleaned up
+const ACTIVE_LOGS: &[&str] = &["latest.log", "debug.log"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanupAction {
+    Delete,
+    Compress,
+}
+
+/// One file the retention policy would touch
+#[derive(Debug, Clone, PartialEq)]
+pub struct CleanupItem {
+    pub entry: LogEntry,
+    pub action: CleanupAction,
+}
+
+impl CleanupItem {
+    /// e.g. "Delete 2024-05-01-1.log.gz (12.0 KB)"
+    pub fn describe(&self) -> String {
+        let verb = match self.action {
+            CleanupAction::Delete => "Delete",
+            CleanupAction::Compress => "Compress",
+        };
+        format!(
+            "{} {} ({})",
+            verb,
+            self.entry.name,
+            self.entry.formatted_size()
+        )
+    }
+}
+
+/// Logs older than `max_age`, and what to do with each. When compressing,
+/// already gzipped logs are left alone.
+pub fn plan_log_cleanup(
+    entries: &[LogEntry],
+    max_age: Duration,
+    compress: bool,
+    now: SystemTime,
+) -> Vec<CleanupItem> {
+    entries
+        .iter()
+        // Crash reports are listed alongside logs but never cleaned up
+        .filter(|e| is_log_file(&e.name) && !ACTIVE_LOGS.contains(&e.name.as_str()))
+        .filter(|e| {
+            e.modified
+                .and_then(|m| now.duration_since(m).ok())
+                .is_some_and(|age| age > max_age)
+        })
+        .filter_map(|e| {
+            let action = if !compress {
+                CleanupAction::Delete
+            } else if e.name.ends_with(".log") {
+                CleanupAction::Compress
+            } else {
+                return None;
+            };
+            Some(CleanupItem {
+                entry: e.clone(),
+                action,
+            })
+        })
+        .collect()
+}
+
+/// Carry out a cleanup plan, returning how many files were handled
+pub fn apply_log_cleanup(plan: &[CleanupItem]) -> Result<usize> {
+    for item in plan {
+        match item.action {
+            CleanupAction::Delete => fs::remove_file(&item.entry.path)?,
+            CleanupAction::Compress => compress_log(&item.entry.path)?,
+        }
+    }
+    Ok(plan.len())
+}
+
+/// Gzip `name.log` into `name.log.gz` and remove the original
+fn compress_log(path: &Path) -> Result<()> {
+    let mut target = path.as_os_str().to_owned();
+    target.push(".gz");
+
+    let mut input = File::open(path)?;
+    let mut encoder = GzEncoder::new(File::create(&target)?, Compression::default());
+    io::copy(&mut input, &mut encoder)?;
+    encoder.finish()?;
+
+    // Keep the original timestamp so sorting and later cleanups still work
+    if let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) {
+        File::options()
+            .write(true)
+            .open(&target)?
+            .set_modified(modified)?;
+    }
+    fs::remove_file(path)?;
+    Ok(())
+}
+
+fn is_log_file(name: &str) -> bool {
+    name.ends_with(".log") || name.ends_with(".log.gz")
 }
 
 pub fn load_log_entries(dir: &Path) -> Result<Vec<LogEntry>> {
+    let mut entries = read_entries(dir, is_log_file)?;
+    sort_entries(&mut entries);
+    Ok(entries)
+}
+
+/// Log files plus crash reports from `crash_dir`, newest first
+pub fn load_log_entries_with_crashes(dir: &Path, crash_dir: &Path) -> Result<Vec<LogEntry>> {
+    let mut entries = read_entries(dir, is_log_file)?;
+    entries.extend(load_crash_reports(crash_dir)?);
+    sort_entries(&mut entries);
+    Ok(entries)
+}
+
+/// Crash reports in `crash_dir`, newest first
+pub fn load_crash_reports(crash_dir: &Path) -> Result<Vec<LogEntry>> {
+    let mut entries = read_entries(crash_dir, |name| {
+        name.starts_with("crash-") && name.ends_with(".txt")
+    })?;
+    sort_entries(&mut entries);
+    Ok(entries)
+}
+
+fn read_entries(dir: &Path, accept: fn(&str) -> bool) -> Result<Vec<LogEntry>> {
     let mut entries = Vec::new();
 
     if !dir.exists() {
@@ -46,23 +190,41 @@ pub fn load_log_entries(dir: &Path) -> Result<Vec<LogEntry>> {
             .unwrap_or("")
             .to_string();
 
-        // Only include .log and .log.gz files
-        if !name.ends_with(".log") && !name.ends_with(".log.gz") {
+        if !accept(&name) {
             continue;
         }
 
         let metadata = entry.metadata()?;
         let modified = metadata.modified().ok();
         let size = metadata.len();
+        let uncompressed_size = if name.ends_with(".gz") {
+            gzip_original_size(&path)
+        } else {
+            None
+        };
 
         entries.push(LogEntry {
             name,
             path,
             modified,
             size,
+            uncompressed_size,
         });
     }
 
+    Ok(entries)
+}
+
+/// The ISIZE field gzip writes as the last four bytes of a file
+fn gzip_original_size(path: &Path) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    file.seek(SeekFrom::End(-4)).ok()?;
+    let mut footer = [0; 4];
+    file.read_exact(&mut footer).ok()?;
+    Some(u32::from_le_bytes(footer) as u64)
+}
+
+fn sort_entries(entries: &mut [LogEntry]) {
     // Sort by modified time (most recent first), with latest.log always first
     entries.sort_by(|a, b| {
         // latest.log always comes first
@@ -81,8 +243,6 @@ pub fn load_log_entries(dir: &Path) -> Result<Vec<LogEntry>> {
             (None, None) => a.name.cmp(&b.name),
         }
     });
-
-    Ok(entries)
 }
 
 /// Maximum decompressed log file size (10 MB)
@@ -127,6 +287,7 @@ mod tests {
             path: PathBuf::from("/tmp/test.log"),
             modified: None,
             size: 512,
+            uncompressed_size: None,
         };
         assert_eq!(entry.formatted_size(), "512 B");
     }
@@ -138,6 +299,7 @@ mod tests {
             path: PathBuf::from("/tmp/test.log"),
             modified: None,
             size: 2048,
+            uncompressed_size: None,
         };
         assert_eq!(entry.formatted_size(), "2.0 KB");
     }
@@ -149,10 +311,77 @@ mod tests {
             path: PathBuf::from("/tmp/test.log"),
             modified: None,
             size: 5 * 1024 * 1024,
+            uncompressed_size: None,
         };
         assert_eq!(entry.formatted_size(), "5.0 MB");
     }
 
+    #[test]
+    fn test_log_entry_formatted_size_gzipped() {
+        let entry = LogEntry {
+            name: "test.log.gz".to_string(),
+            path: PathBuf::from("/tmp/test.log.gz"),
+            modified: None,
+            size: 2 * 1024 * 1024,
+            uncompressed_size: Some(80 * 1024 * 1024),
+        };
+        assert_eq!(entry.formatted_size(), "2.0 MB (~80.0 MB)");
+    }
+
+    #[test]
+    fn test_plan_log_cleanup() {
+        let day = Duration::from_secs(24 * 60 * 60);
+        let now = SystemTime::UNIX_EPOCH + day * 100;
+        let entry = |name: &str, age_days: u32| LogEntry {
+            name: name.to_string(),
+            path: PathBuf::from(format!("/tmp/{}", name)),
+            modified: Some(now - day * age_days),
+            size: 0,
+            uncompressed_size: None,
+        };
+        let entries = vec![
+            entry("latest.log", 30),
+            entry("2024-01-01-1.log.gz", 30),
+            entry("2024-01-02-1.log", 30),
+            entry("2024-03-01-1.log.gz", 2),
+            entry("crash-2024-01-03_10.00.00-client.txt", 30),
+        ];
+
+        let delete = plan_log_cleanup(&entries, day * 7, false, now);
+        let names: Vec<_> = delete.iter().map(|i| i.entry.name.as_str()).collect();
+        assert_eq!(names, ["2024-01-01-1.log.gz", "2024-01-02-1.log"]);
+
+        let compress = plan_log_cleanup(&entries, day * 7, true, now);
+        assert_eq!(compress.len(), 1);
+        assert_eq!(compress[0].entry.name, "2024-01-02-1.log");
+        assert_eq!(compress[0].action, CleanupAction::Compress);
+    }
+
+    #[test]
+    fn test_apply_log_cleanup_compresses() {
+        let dir = crate::data::test_dir("logs");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("2024-01-02-1.log");
+        fs::write(&path, "[INFO] hello\n").unwrap();
+
+        let entry = load_log_entries(&dir).unwrap().remove(0);
+        let plan = [CleanupItem {
+            entry,
+            action: CleanupAction::Compress,
+        }];
+        apply_log_cleanup(&plan).unwrap();
+
+        let content = load_log_content(&dir.join("2024-01-02-1.log.gz")).unwrap();
+        let compressed = load_log_entries(&dir).unwrap().remove(0);
+        let original_exists = path.exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!original_exists);
+        assert_eq!(content, ["[INFO] hello"]);
+        assert_eq!(compressed.uncompressed_size, Some(13));
+        assert!(compressed.formatted_size().ends_with("(~13 B)"));
+    }
+
     #[test]
     fn test_load_log_entries_empty_dir() {
         let result = load_log_entries(Path::new("/nonexistent/path"));
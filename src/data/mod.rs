@@ -1,15 +1,68 @@
 pub mod accounts;
 pub mod app_config;
+pub mod atomic;
+pub mod backups;
 pub mod config;
+pub mod crash;
+pub mod format;
 pub mod groups;
 pub mod instance;
+pub mod integrity;
+pub mod java;
+pub mod junk;
 pub mod logs;
+pub mod mods;
+pub mod new_instance;
+pub mod prism_settings;
+pub mod report;
+pub mod resourcepacks;
+pub mod screenshots;
+pub mod search;
 pub mod servers;
+pub mod storage;
+pub mod tasks;
+pub mod templates;
 
 pub use accounts::{Account, load_accounts};
-pub use app_config::AppConfig;
-pub use config::{PrismConfig, find_prism_data_dir};
+pub use app_config::{AppConfig, LaunchPreset, format_env_assignments, parse_env_assignments};
+pub use backups::{WorldBackup, backup_world, find_backups, list_worlds, restore_backup};
+pub use config::{PrismConfig, find_portable_data_dir, find_prism_data_dir};
+pub use crash::{CrashHint, analyze_log};
 pub use groups::load_groups;
-pub use instance::{Instance, load_instances};
-pub use logs::{LogEntry, load_log_content, load_log_entries};
-pub use servers::{Server, load_servers, save_servers};
+pub use instance::{
+    Instance, InstanceCfgEdit, InstanceFolder, InstanceStats, JoinConflict, ManagedPack,
+    PackPlatform, find_instance, instance_dirs, load_instance_dirs, load_instances,
+};
+pub use integrity::{FileProblem, IntegrityReport, verify_instance};
+pub use java::release_version;
+pub use junk::{JunkItem, JunkKind, apply_junk_cleanup, plan_junk_cleanup};
+pub use logs::{
+    CleanupAction, CleanupItem, LogEntry, apply_log_cleanup, format_size, load_crash_reports,
+    load_log_content, load_log_entries, load_log_entries_with_crashes, plan_log_cleanup,
+};
+pub use mods::{ModFile, disable_mod, find_mod_file, list_mods, set_mod_enabled};
+pub use new_instance::{create_instance, set_managed_pack};
+pub use prism_settings::{GLOBAL_SETTINGS, PrismSettings, SettingSource};
+pub use report::instance_report;
+pub use resourcepacks::{
+    ResourcePack, delete_resource_pack, load_resource_packs, resource_pack_format,
+    set_resource_pack_enabled,
+};
+pub use screenshots::{Screenshot, delete_screenshot, load_screenshots};
+pub use search::{
+    SearchFolders, SearchItem, SearchKind, SearchTarget, index_search_files, search_files_stamp,
+    search_items,
+};
+pub use servers::{
+    DEFAULT_PORT, ResourcePackPolicy, Server, find_duplicate, load_servers, merge_server_changes,
+    merge_servers, save_servers, split_address,
+};
+pub use storage::{STORAGE_USAGE_CACHE, StorageInfo, StorageUsage, storage_info, storage_usage};
+pub use tasks::{TASKS_FILE, Task, load_tasks, save_tasks};
+pub use templates::{Template, create_from_template, load_templates, save_template, templates_dir};
+
+/// A scratch path under the system temp folder, unique to this test run
+#[cfg(test)]
+pub(crate) fn test_dir(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("prism-tui-{}-{}", name, std::process::id()))
+}
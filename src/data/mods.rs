@@ -0,0 +1,199 @@
+use crate::error::{PrismError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Words that commonly follow the mod id in a jar name
+const NAME_SUFFIXES: &[&str] = &["fabric", "forge", "neoforge", "quilt", "mc"];
+
+/// Find the enabled jar for `mod_id` in a mods folder.
+///
+/// Jar names only loosely follow the id (`sodium-fabric-0.5.8.jar` for
+/// `sodium`), so the id must be followed by a version or loader name. This
+/// keeps `sodium` from matching `sodium-extra-0.5.1.jar`.
+pub fn find_mod_file(mods_dir: &Path, mod_id: &str) -> Option<PathBuf> {
+    let id = normalize(mod_id);
+    if id.is_empty() {
+        return None;
+    }
+    let mut matches: Vec<PathBuf> = fs::read_dir(mods_dir)
+        .ok()?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                return false;
+            };
+            let Some(stem) = name.strip_suffix(".jar") else {
+                return false;
+            };
+            jar_matches(&normalize(stem), &id)
+        })
+        .collect();
+    matches.sort();
+    matches.into_iter().next()
+}
+
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace('_', "-")
+}
+
+fn jar_matches(stem: &str, id: &str) -> bool {
+    let Some(rest) = stem.strip_prefix(id) else {
+        return false;
+    };
+    let Some(rest) = rest.strip_prefix(['-', '+', '.']) else {
+        return rest.is_empty();
+    };
+    rest.starts_with(|c: char| c.is_ascii_digit() || c == 'v')
+        || NAME_SUFFIXES.iter().any(|s| rest.starts_with(s))
+}
+
+/// A mod file in an instance's mods folder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModFile {
+    /// File name without the `.disabled` suffix
+    pub name: String,
+    pub enabled: bool,
+}
+
+impl ModFile {
+    /// The name on disk, with `.disabled` for a disabled mod
+    pub fn file_name(&self) -> String {
+        if self.enabled {
+            self.name.clone()
+        } else {
+            format!("{}.disabled", self.name)
+        }
+    }
+}
+
+/// All mod jars and zips in a mods folder, disabled ones included, by name
+pub fn list_mods(mods_dir: &Path) -> Vec<ModFile> {
+    let Ok(entries) = fs::read_dir(mods_dir) else {
+        return Vec::new();
+    };
+    let mut mods: Vec<ModFile> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter_map(|name| {
+            let (name, enabled) = match name.strip_suffix(".disabled") {
+                Some(stem) => (stem.to_string(), false),
+                None => (name, true),
+            };
+            (name.ends_with(".jar") || name.ends_with(".zip")).then_some(ModFile { name, enabled })
+        })
+        .collect();
+    mods.sort_by_key(|m| m.name.to_lowercase());
+    mods
+}
+
+/// Disable a mod the way PrismLauncher does, by appending `.disabled`
+pub fn disable_mod(path: &Path) -> Result<PathBuf> {
+    let mut target = path.as_os_str().to_owned();
+    target.push(".disabled");
+    let target = PathBuf::from(target);
+    fs::rename(path, &target)?;
+    Ok(target)
+}
+
+/// Enable or disable a mod in `mods_dir` by renaming it, the way
+/// PrismLauncher does. A file already using the new name is left alone.
+pub fn set_mod_enabled(mods_dir: &Path, file: &ModFile, enabled: bool) -> Result<()> {
+    let target = ModFile {
+        name: file.name.clone(),
+        enabled,
+    };
+    let from = mods_dir.join(file.file_name());
+    let to = mods_dir.join(target.file_name());
+    if from == to {
+        return Ok(());
+    }
+    if to.exists() {
+        return Err(PrismError::Other(format!(
+            "{} is in the mods folder both enabled and disabled",
+            file.name
+        )));
+    }
+    fs::rename(from, to)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jar_matches_id_followed_by_version_or_loader() {
+        assert!(jar_matches("sodium-fabric-0.5.8+mc1.20.1", "sodium"));
+        assert!(jar_matches("sodium-extra-0.5.1", "sodium-extra"));
+        assert!(jar_matches("alexsmobs-1.22.8", "alexsmobs"));
+        assert!(jar_matches("geckolib", "geckolib"));
+        assert!(!jar_matches("sodium-extra-0.5.1", "sodium"));
+        assert!(!jar_matches("sodiumextra-0.5.1", "sodium"));
+    }
+
+    #[test]
+    fn test_find_and_disable_mod() {
+        let dir = crate::data::test_dir("mods");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("sodium-extra-0.5.1.jar"), "").unwrap();
+        fs::write(dir.join("Sodium-Fabric-0.5.8.jar"), "").unwrap();
+
+        let found = find_mod_file(&dir, "sodium");
+        let disabled = found.as_deref().map(|p| disable_mod(p).unwrap());
+        let again = find_mod_file(&dir, "sodium");
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(found, Some(dir.join("Sodium-Fabric-0.5.8.jar")));
+        assert_eq!(disabled, Some(dir.join("Sodium-Fabric-0.5.8.jar.disabled")));
+        assert_eq!(again, None);
+    }
+
+    #[test]
+    fn test_list_mods_includes_disabled() {
+        let dir = crate::data::test_dir("modlist");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("sodium-0.5.8.jar"), "").unwrap();
+        fs::write(dir.join("Create-0.5.1.jar.disabled"), "").unwrap();
+        fs::write(dir.join("notes.txt"), "").unwrap();
+
+        let mods = list_mods(&dir);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<_> = mods.iter().map(|m| (m.name.as_str(), m.enabled)).collect();
+        assert_eq!(
+            names,
+            [("Create-0.5.1.jar", false), ("sodium-0.5.8.jar", true)]
+        );
+    }
+
+    #[test]
+    fn test_set_mod_enabled_renames_both_ways() {
+        let dir = crate::data::test_dir("modtoggle");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("sodium-0.5.8.jar"), "").unwrap();
+        fs::write(dir.join("lithium-0.11.jar"), "").unwrap();
+        fs::write(dir.join("lithium-0.11.jar.disabled"), "").unwrap();
+
+        let mods = list_mods(&dir);
+        let sodium = mods.iter().find(|m| m.name.starts_with("sodium")).unwrap();
+        set_mod_enabled(&dir, sodium, false).unwrap();
+        let disabled = list_mods(&dir);
+        let sodium = disabled
+            .iter()
+            .find(|m| m.name.starts_with("sodium"))
+            .unwrap();
+        set_mod_enabled(&dir, sodium, true).unwrap();
+        let lithium = ModFile {
+            name: "lithium-0.11.jar".to_string(),
+            enabled: true,
+        };
+        let clash = set_mod_enabled(&dir, &lithium, false);
+        let enabled = dir.join("sodium-0.5.8.jar").exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(!sodium.enabled);
+        assert!(enabled);
+        assert!(clash.is_err());
+    }
+}
@@ -0,0 +1,136 @@
+//! Fresh instances made from scratch: a Minecraft version and optionally a
+//! mod loader, written the way PrismLauncher lays out a new instance.
+
+use crate::data::ManagedPack;
+use crate::data::atomic::write_atomic;
+use crate::data::templates::{filled_or_removed, unused_folder_name};
+use crate::error::{PrismError, Result};
+use configparser::ini::Ini;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PackComponent<'a> {
+    uid: &'a str,
+    version: &'a str,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    important: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dependency_only: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PackFile<'a> {
+    components: Vec<PackComponent<'a>>,
+    format_version: u32,
+}
+
+/// Create an instance called `name` in `instances_dir` for
+/// `minecraft_version`, with any loader components as (uid, version),
+/// returning the new instance's folder name. PrismLauncher fills in the
+/// libraries the components need when it first loads the instance.
+pub fn create_instance(
+    instances_dir: &Path,
+    name: &str,
+    minecraft_version: &str,
+    loader_components: &[(&str, String)],
+) -> Result<String> {
+    let id = unused_folder_name(instances_dir, name)?;
+    let target = instances_dir.join(&id);
+
+    let mut components = vec![PackComponent {
+        uid: "net.minecraft",
+        version: minecraft_version,
+        important: true,
+        dependency_only: false,
+    }];
+    components.extend(
+        loader_components
+            .iter()
+            .map(|(uid, version)| PackComponent {
+                uid,
+                version,
+                important: false,
+                // Intermediary mappings come with the loader, not on their own
+                dependency_only: *uid == "net.fabricmc.intermediary",
+            }),
+    );
+    let pack = serde_json::to_string_pretty(&PackFile {
+        components,
+        format_version: 1,
+    })?;
+
+    filled_or_removed(&target, || {
+        fs::create_dir_all(target.join(".minecraft"))?;
+        fs::write(target.join("mmc-pack.json"), &pack)?;
+        let mut config = Ini::new_cs();
+        config.set("General", "ConfigVersion", Some("1.2".to_string()));
+        config.set("General", "InstanceType", Some("OneSix".to_string()));
+        config.set("General", "iconKey", Some("default".to_string()));
+        config.set("General", "name", Some(name.trim().to_string()));
+        fs::write(target.join("instance.cfg"), config.writes())?;
+        Ok(())
+    })?;
+    Ok(id)
+}
+
+/// Record in instance.cfg that the instance in `instance_dir` was installed
+/// from `pack`, as PrismLauncher does, so its update check finds it
+pub fn set_managed_pack(instance_dir: &Path, pack: &ManagedPack) -> Result<()> {
+    let path = instance_dir.join("instance.cfg");
+    let mut config = Ini::new_cs();
+    config
+        .load(&path)
+        .map_err(|e| PrismError::Config(e.to_string()))?;
+    let values = [
+        ("ManagedPack", "true"),
+        ("ManagedPackType", pack.platform.config_type()),
+        ("ManagedPackID", &pack.id),
+        ("ManagedPackName", &pack.name),
+        ("ManagedPackVersionID", &pack.version_id),
+        ("ManagedPackVersionName", &pack.version_name),
+    ];
+    for (key, value) in values {
+        config.set("General", key, Some(value.to_string()));
+    }
+    write_atomic(&path, config.writes().as_bytes())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Instance;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_created_instance_loads_back() {
+        let root = crate::data::test_dir("new-instance");
+        fs::create_dir_all(&root).unwrap();
+        let vanilla = create_instance(&root, "Plain", "1.21.4", &[]).unwrap();
+        let fabric = create_instance(
+            &root,
+            "Fabric: test",
+            "1.20.1",
+            &[
+                ("net.fabricmc.intermediary", "1.20.1".to_string()),
+                ("net.fabricmc.fabric-loader", "0.16.9".to_string()),
+            ],
+        )
+        .unwrap();
+
+        let vanilla = Instance::load(root.join(vanilla), &HashMap::new()).unwrap();
+        let fabric = Instance::load(root.join(fabric), &HashMap::new()).unwrap();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(vanilla.name, "Plain");
+        assert_eq!(vanilla.minecraft_version, "1.21.4");
+        assert_eq!(vanilla.mod_loader, None);
+        assert_eq!(fabric.id, "Fabric_ test");
+        assert_eq!(fabric.mod_loader.as_deref(), Some("Fabric"));
+        assert_eq!(fabric.mod_loader_version.as_deref(), Some("0.16.9"));
+    }
+}
@@ -0,0 +1,253 @@
+use crate::data::atomic::write_atomic;
+use crate::data::config::resolve_data_path;
+use crate::error::{PrismError, Result};
+use configparser::ini::Ini;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A prismlauncher.cfg key shown in the settings panel
+pub struct SettingDef {
+    pub key: &'static str,
+    pub label: &'static str,
+    /// Keys that decide where the launcher looks are shown but not written
+    pub editable: bool,
+}
+
+/// Folder settings and the default PrismLauncher uses for each
+pub const PATH_SETTINGS: &[(&str, &str)] = &[
+    ("InstanceDir", "instances"),
+    ("CentralModsDir", "mods"),
+    ("DownloadsDir", ""),
+];
+
+pub const GLOBAL_SETTINGS: &[SettingDef] = &[
+    SettingDef {
+        key: "MinMemAlloc",
+        label: "Min Memory (MB)",
+        editable: true,
+    },
+    SettingDef {
+        key: "MaxMemAlloc",
+        label: "Max Memory (MB)",
+        editable: true,
+    },
+    SettingDef {
+        key: "JavaPath",
+        label: "Java Path",
+        editable: true,
+    },
+    SettingDef {
+        key: "JvmArgs",
+        label: "JVM Arguments",
+        editable: true,
+    },
+    SettingDef {
+        key: "WrapperCommand",
+        label: "Wrapper Command",
+        editable: true,
+    },
+    SettingDef {
+        key: "DownloadsDir",
+        label: "Downloads Folder",
+        editable: true,
+    },
+    SettingDef {
+        key: "InstanceDir",
+        label: "Instances Folder",
+        editable: false,
+    },
+    SettingDef {
+        key: "CentralModsDir",
+        label: "Mods Folder",
+        editable: false,
+    },
+];
+
+/// Instance override flags and the keys each one unlocks in instance.cfg
+pub const OVERRIDE_GROUPS: &[(&str, &[&str])] = &[
+    ("OverrideMemory", &["MinMemAlloc", "MaxMemAlloc"]),
+    ("OverrideJavaLocation", &["JavaPath", "JavaVersion"]),
+    ("OverrideJavaArgs", &["JvmArgs"]),
+    ("OverrideCommands", &["WrapperCommand"]),
+];
+
+/// instance.cfg keys copied when syncing memory and Java settings between
+/// instances: the override flags, what they unlock, and PrismLauncher's
+/// notes on the chosen Java
+pub const JAVA_SETTINGS: &[&str] = &[
+    "OverrideMemory",
+    "MinMemAlloc",
+    "MaxMemAlloc",
+    "PermGen",
+    "OverrideJavaLocation",
+    "OverrideJava",
+    "AutomaticJava",
+    "IgnoreJavaCompatibility",
+    "JavaPath",
+    "JavaArchitecture",
+    "JavaRealArchitecture",
+    "JavaSignature",
+    "JavaVendor",
+    "JavaVersion",
+    "OverrideJavaArgs",
+    "JvmArgs",
+];
+
+/// Where an effective setting value comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingSource {
+    Instance,
+    Global,
+}
+
+impl SettingSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingSource::Instance => "instance",
+            SettingSource::Global => "global",
+        }
+    }
+}
+
+/// The launcher-wide settings from prismlauncher.cfg
+#[derive(Debug, Clone, Default)]
+pub struct PrismSettings {
+    values: HashMap<String, String>,
+}
+
+impl PrismSettings {
+    pub fn load(data_dir: &Path) -> Result<Self> {
+        let config_path = data_dir.join("prismlauncher.cfg");
+        let mut config = Ini::new_cs();
+
+        if config_path.exists() {
+            config
+                .load(&config_path)
+                .map_err(|e| PrismError::Config(e.to_string()))?;
+        }
+
+        let values = config
+            .get_map_ref()
+            .get("General")
+            .map(|section| {
+                section
+                    .iter()
+                    .filter_map(|(k, v)| Some((k.clone(), v.clone()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Self { values })
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values
+            .get(key)
+            .map(String::as_str)
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Where a folder setting points, relative paths resolved against the
+    /// data directory
+    pub fn resolved_path(&self, data_dir: &Path, key: &str) -> Option<PathBuf> {
+        let (_, default) = PATH_SETTINGS.iter().find(|(k, _)| *k == key)?;
+        if default.is_empty() && self.get(key).is_none() {
+            return None;
+        }
+        Some(resolve_data_path(data_dir, self.get(key), default))
+    }
+
+    /// Update one key in prismlauncher.cfg, leaving the rest untouched. An
+    /// empty value removes the key so the launcher falls back to its default
+    pub fn set(&mut self, data_dir: &Path, key: &str, value: String) -> Result<()> {
+        let config_path = data_dir.join("prismlauncher.cfg");
+        let mut config = Ini::new_cs();
+
+        if config_path.exists() {
+            config
+                .load(&config_path)
+                .map_err(|e| PrismError::Config(e.to_string()))?;
+        }
+
+        if value.is_empty() {
+            config.remove_key("General", key);
+        } else {
+            config.set("General", key, Some(value.clone()));
+        }
+        write_atomic(&config_path, config.writes().as_bytes())?;
+
+        if value.is_empty() {
+            self.values.remove(key);
+        } else {
+            self.values.insert(key.to_string(), value);
+        }
+        Ok(())
+    }
+}
+
+/// Keys an instance overrides, read from its instance.cfg
+pub fn instance_overrides(config: &Ini) -> HashMap<String, String> {
+    let mut overrides = HashMap::new();
+    for (flag, keys) in OVERRIDE_GROUPS {
+        if config.get("General", flag).as_deref() != Some("true") {
+            continue;
+        }
+        for key in *keys {
+            if let Some(value) = config.get("General", key).filter(|v| !v.is_empty()) {
+                overrides.insert(key.to_string(), value);
+            }
+        }
+    }
+    overrides
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instance_overrides_require_flag() {
+        let mut config = Ini::new();
+        config
+            .read(
+                "[General]\nOverrideMemory=true\nMaxMemAlloc=8192\nMinMemAlloc=1024\n\
+                 OverrideJavaLocation=false\nJavaPath=/usr/bin/java\n"
+                    .to_string(),
+            )
+            .unwrap();
+
+        let overrides = instance_overrides(&config);
+        assert_eq!(
+            overrides.get("MaxMemAlloc").map(String::as_str),
+            Some("8192")
+        );
+        assert_eq!(
+            overrides.get("MinMemAlloc").map(String::as_str),
+            Some("1024")
+        );
+        assert!(!overrides.contains_key("JavaPath"));
+    }
+
+    #[test]
+    fn test_set_preserves_other_keys() {
+        let dir = crate::data::test_dir("settings");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("prismlauncher.cfg"),
+            "[General]\nInstanceDir=instances\nMaxMemAlloc=4096\n",
+        )
+        .unwrap();
+
+        let mut settings = PrismSettings::load(&dir).unwrap();
+        settings
+            .set(&dir, "MaxMemAlloc", "8192".to_string())
+            .unwrap();
+        settings.set(&dir, "InstanceDir", String::new()).unwrap();
+
+        let reloaded = PrismSettings::load(&dir).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(reloaded.get("MaxMemAlloc"), Some("8192"));
+        assert_eq!(reloaded.get("InstanceDir"), None);
+    }
+}
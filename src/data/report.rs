@@ -0,0 +1,159 @@
+use crate::data::crash::analyze_log;
+use crate::data::logs::{load_crash_reports, load_log_content};
+use crate::data::mods::list_mods;
+use crate::data::{Instance, PrismSettings};
+
+/// A Markdown summary of an instance for pasting into support threads
+pub fn instance_report(instance: &Instance, settings: &PrismSettings) -> String {
+    let loader = instance
+        .mod_loader_display()
+        .unwrap_or_else(|| "None".to_string());
+    let mut lines = vec![
+        format!("# {}", instance.name),
+        String::new(),
+        format!("- Minecraft: {}", instance.minecraft_version),
+        format!("- Mod loader: {}", loader),
+    ];
+    if let Some(pack) = &instance.managed_pack {
+        let name = if pack.name.is_empty() {
+            &pack.id
+        } else {
+            &pack.name
+        };
+        lines.push(format!(
+            "- Pack: {} {} ({})",
+            name,
+            pack.version_name,
+            pack.platform.label()
+        ));
+    }
+    lines.push(format!(
+        "- Playtime: {}",
+        instance.formatted_playtime_full()
+    ));
+    lines.push(format!(
+        "- Last launch: {}",
+        instance.formatted_last_launch()
+    ));
+
+    lines.extend([String::new(), "## Java".to_string(), String::new()]);
+    for (label, value) in [
+        ("Memory", instance.memory_setting(settings)),
+        ("Java", instance.effective_setting(settings, "JavaPath")),
+        ("JVM args", instance.effective_setting(settings, "JvmArgs")),
+    ] {
+        lines.push(match value {
+            Some((value, source)) => format!("- {}: `{}` ({})", label, value, source.label()),
+            None => format!("- {}: default", label),
+        });
+    }
+
+    let mods = list_mods(&instance.mods_dir());
+    let enabled = mods.iter().filter(|m| m.enabled).count();
+    lines.push(String::new());
+    lines.push(format!(
+        "## Mods ({} enabled, {} total)",
+        enabled,
+        mods.len()
+    ));
+    lines.push(String::new());
+    if let Some(label) = instance.missing_loader_label() {
+        lines.push(format!(
+            "**Warning: {}, so the game starts without them**",
+            label
+        ));
+        lines.push(String::new());
+    }
+    if mods.is_empty() {
+        lines.push("No mods installed".to_string());
+    }
+    for m in &mods {
+        if m.enabled {
+            lines.push(format!("- {}", m.name));
+        } else {
+            lines.push(format!("- {} (disabled)", m.name));
+        }
+    }
+
+    lines.extend([String::new(), "## Recent crash".to_string(), String::new()]);
+    let latest = load_crash_reports(&instance.crash_reports_dir())
+        .ok()
+        .and_then(|reports| reports.into_iter().next());
+    match latest {
+        Some(report) => {
+            lines.push(format!("`{}`", report.name));
+            lines.push(String::new());
+            let hints = load_log_content(&report.path)
+                .map(|content| analyze_log(&content))
+                .unwrap_or_default();
+            if hints.is_empty() {
+                lines.push("No known cause recognized".to_string());
+            }
+            for hint in hints {
+                lines.push(format!("- {}: {}", hint.kind.label(), hint.message));
+            }
+        }
+        None => lines.push("No crash reports".to_string()),
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::path::PathBuf;
+
+    fn test_instance(path: PathBuf) -> Instance {
+        Instance {
+            id: "report".to_string(),
+            name: "Report Test".to_string(),
+            path,
+            group: None,
+            minecraft_version: "1.20.1".to_string(),
+            mod_loader: Some("Fabric".to_string()),
+            mod_loader_version: Some("0.15.7".to_string()),
+            total_time_played: 5400,
+            last_launch: None,
+            server_join: None,
+            world_join: None,
+            managed_pack: None,
+            icon_key: None,
+            wrapper_command: None,
+            setting_overrides: HashMap::from([("MaxMemAlloc".to_string(), "4096".to_string())]),
+            cfg_values: HashMap::new(),
+            unloaded_mods: 0,
+            search_key: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_instance_report() {
+        let dir = crate::data::test_dir("report");
+        let minecraft = dir.join(".minecraft");
+        fs::create_dir_all(minecraft.join("mods")).unwrap();
+        fs::create_dir_all(minecraft.join("crash-reports")).unwrap();
+        fs::write(minecraft.join("mods/sodium-0.5.8.jar"), "").unwrap();
+        fs::write(minecraft.join("mods/create-0.5.1.jar.disabled"), "").unwrap();
+        fs::write(
+            minecraft.join("crash-reports/crash-2024-05-01_10.00.00-client.txt"),
+            "java.lang.OutOfMemoryError: Java heap space\n",
+        )
+        .unwrap();
+
+        let report = instance_report(&test_instance(dir.clone()), &PrismSettings::default());
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(report.starts_with("# Report Test\n"));
+        assert!(report.contains("- Mod loader: Fabric 0.15.7"));
+        assert!(report.contains("- Playtime: 1h 30m"));
+        assert!(report.contains("- Memory: `up to 4096 MB` (instance)"));
+        assert!(report.contains("## Mods (1 enabled, 2 total)"));
+        assert!(report.contains("- create-0.5.1.jar (disabled)"));
+        assert!(report.contains("`crash-2024-05-01_10.00.00-client.txt`"));
+        assert!(report.contains("- Memory: Ran out of memory"));
+    }
+}
@@ -0,0 +1,312 @@
+//! The resource packs in an instance's `resourcepacks/` folder, and which of
+//! them the game loads, from the `resourcePacks` line of `options.txt`.
+
+use crate::data::atomic::write_atomic;
+use crate::data::release_version;
+use crate::error::Result;
+use serde_json::Value;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+/// The packs the game loads, lowest priority first
+const ENABLED_KEY: &str = "resourcePacks";
+/// Packs made for another game version that were turned on anyway; the game
+/// drops a pack for another version from `resourcePacks` unless it is here
+const INCOMPATIBLE_KEY: &str = "incompatibleResourcePacks";
+
+/// Resource pack format of each range of releases, newest first
+const PACK_FORMATS: &[(&str, &str, u32)] = &[
+    ("1.21.9", "1.21.10", 69),
+    ("1.21.7", "1.21.8", 64),
+    ("1.21.6", "1.21.6", 63),
+    ("1.21.5", "1.21.5", 55),
+    ("1.21.4", "1.21.4", 46),
+    ("1.21.2", "1.21.3", 42),
+    ("1.21", "1.21.1", 34),
+    ("1.20.5", "1.20.6", 32),
+    ("1.20.3", "1.20.4", 22),
+    ("1.20.2", "1.20.2", 18),
+    ("1.20", "1.20.1", 15),
+    ("1.19.4", "1.19.4", 13),
+    ("1.19.3", "1.19.3", 12),
+    ("1.19", "1.19.2", 9),
+    ("1.18", "1.18.2", 8),
+    ("1.17", "1.17.1", 7),
+    ("1.16.2", "1.16.5", 6),
+    ("1.15", "1.16.1", 5),
+    ("1.13", "1.14.4", 4),
+    ("1.11", "1.12.2", 3),
+    ("1.9", "1.10.2", 2),
+    ("1.6.1", "1.8.9", 1),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourcePack {
+    /// File or folder name in `resourcepacks/`
+    pub name: String,
+    pub path: PathBuf,
+    pub is_dir: bool,
+    /// Description from pack.mcmeta, without formatting codes
+    pub description: Option<String>,
+    pub pack_format: Option<u32>,
+    pub enabled: bool,
+}
+
+impl ResourcePack {
+    /// How options.txt refers to the pack, e.g. `file/Faithful 32x.zip`
+    pub fn option_id(&self) -> String {
+        format!("file/{}", self.name)
+    }
+}
+
+/// The pack format a Minecraft release expects. Snapshots and releases
+/// newer than the table give `None`.
+pub fn resource_pack_format(minecraft_version: &str) -> Option<u32> {
+    let parts = release_version(minecraft_version)?;
+    PACK_FORMATS
+        .iter()
+        .find(|(first, last, _)| {
+            release_version(first).is_some_and(|first| first <= parts)
+                && release_version(last).is_some_and(|last| parts <= last)
+        })
+        .map(|(_, _, format)| *format)
+}
+
+/// The zipped and unpacked packs in `dir` by name, marked enabled when
+/// `options` lists them
+pub fn load_resource_packs(dir: &Path, options: &Path) -> Vec<ResourcePack> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let enabled = read_pack_list(options, ENABLED_KEY);
+    let mut packs: Vec<ResourcePack> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            let path = e.path();
+            let is_dir = path.is_dir();
+            let mcmeta = if is_dir {
+                fs::read_to_string(path.join("pack.mcmeta")).ok()?
+            } else if name.to_lowercase().ends_with(".zip") {
+                read_zipped_mcmeta(&path).unwrap_or_default()
+            } else {
+                return None;
+            };
+            let (description, pack_format) = parse_mcmeta(&mcmeta);
+            let mut pack = ResourcePack {
+                name,
+                path,
+                is_dir,
+                description,
+                pack_format,
+                enabled: false,
+            };
+            pack.enabled = enabled.contains(&pack.option_id());
+            Some(pack)
+        })
+        .collect();
+    packs.sort_by_key(|p| p.name.to_lowercase());
+    packs
+}
+
+fn read_zipped_mcmeta(path: &Path) -> Option<String> {
+    let mut archive = ZipArchive::new(File::open(path).ok()?).ok()?;
+    let mut entry = archive.by_name("pack.mcmeta").ok()?;
+    let mut contents = String::new();
+    entry.read_to_string(&mut contents).ok()?;
+    Some(contents)
+}
+
+/// Description and pack_format of a pack.mcmeta, where readable
+pub fn parse_mcmeta(contents: &str) -> (Option<String>, Option<u32>) {
+    let Ok(json) = serde_json::from_str::<Value>(contents.trim_start_matches('\u{feff}')) else {
+        return (None, None);
+    };
+    let pack = &json["pack"];
+    let description = strip_formatting(&plain_text(&pack["description"]))
+        .trim()
+        .to_string();
+    let description = (!description.is_empty()).then_some(description);
+    let pack_format = pack["pack_format"]
+        .as_u64()
+        .and_then(|f| u32::try_from(f).ok());
+    (description, pack_format)
+}
+
+/// The text of a chat component, which is a string, a list of components or
+/// an object with `text` and `extra`
+fn plain_text(component: &Value) -> String {
+    match component {
+        Value::String(text) => text.clone(),
+        Value::Array(parts) => parts.iter().map(plain_text).collect(),
+        Value::Object(fields) => {
+            let mut text = fields
+                .get("text")
+                .or_else(|| fields.get("translate"))
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_string();
+            if let Some(extra) = fields.get("extra") {
+                text.push_str(&plain_text(extra));
+            }
+            text
+        }
+        _ => String::new(),
+    }
+}
+
+/// Drop `§` color and style codes
+fn strip_formatting(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            plain.push(c);
+        }
+    }
+    plain
+}
+
+/// Turn `pack` on or off in `options`, which is created if the game hasn't
+/// written it yet. A pack turned on goes on top, so it wins over the others,
+/// and is also marked incompatible unless its format is `game_format`.
+pub fn set_resource_pack_enabled(
+    options: &Path,
+    pack: &ResourcePack,
+    enabled: bool,
+    game_format: Option<u32>,
+) -> Result<()> {
+    let contents = match fs::read_to_string(options) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e.into()),
+    };
+    let id = pack.option_id();
+    let compatible = game_format.is_some() && pack.pack_format == game_format;
+    let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+    for key in [ENABLED_KEY, INCOMPATIBLE_KEY] {
+        let listed = parse_pack_list(&contents, key);
+        let add = enabled && (key == ENABLED_KEY || !compatible);
+        if listed.is_none() && key == INCOMPATIBLE_KEY && !add {
+            continue;
+        }
+        // A missing list means the defaults, which is only vanilla
+        let mut packs = listed.unwrap_or_else(|| {
+            if key == ENABLED_KEY {
+                vec!["vanilla".to_string()]
+            } else {
+                Vec::new()
+            }
+        });
+        packs.retain(|p| *p != id);
+        if add {
+            packs.push(id.clone());
+        }
+        let line = format!("{}:{}", key, serde_json::to_string(&packs)?);
+        let prefix = format!("{}:", key);
+        match lines.iter_mut().find(|l| l.starts_with(&prefix)) {
+            Some(existing) => *existing = line,
+            None => lines.push(line),
+        }
+    }
+
+    let newline = if contents.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut updated = lines.join(newline);
+    updated.push_str(newline);
+    write_atomic(options, updated.as_bytes())?;
+    Ok(())
+}
+
+fn read_pack_list(options: &Path, key: &str) -> Vec<String> {
+    fs::read_to_string(options)
+        .ok()
+        .and_then(|contents| parse_pack_list(&contents, key))
+        .unwrap_or_default()
+}
+
+/// A list like `resourcePacks:["vanilla","file/Faithful.zip"]` in options.txt
+fn parse_pack_list(contents: &str, key: &str) -> Option<Vec<String>> {
+    contents.lines().find_map(|line| {
+        let value = line.strip_prefix(key)?.strip_prefix(':')?;
+        serde_json::from_str(value.trim()).ok()
+    })
+}
+
+pub fn delete_resource_pack(pack: &ResourcePack) -> Result<()> {
+    if pack.is_dir {
+        fs::remove_dir_all(&pack.path)?;
+    } else {
+        fs::remove_file(&pack.path)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mcmeta_flattens_the_description() {
+        let (description, format) = parse_mcmeta(
+            r#"{"pack": {"pack_format": 15, "description": [{"text": "§6Faithful", "extra": [" 32x"]}, "!"]}}"#,
+        );
+        assert_eq!(description.as_deref(), Some("Faithful 32x!"));
+        assert_eq!(format, Some(15));
+        assert_eq!(parse_mcmeta("not json"), (None, None));
+    }
+
+    #[test]
+    fn test_resource_pack_format() {
+        assert_eq!(resource_pack_format("1.20.1"), Some(15));
+        assert_eq!(resource_pack_format("1.16.5"), Some(6));
+        assert_eq!(resource_pack_format("1.8"), Some(1));
+        assert_eq!(resource_pack_format("24w14a"), None);
+    }
+
+    #[test]
+    fn test_toggle_keeps_the_other_options() {
+        let dir = crate::data::test_dir("packs");
+        let packs_dir = dir.join("resourcepacks");
+        fs::create_dir_all(packs_dir.join("Unpacked")).unwrap();
+        fs::write(
+            packs_dir.join("Unpacked/pack.mcmeta"),
+            r#"{"pack": {"pack_format": 8, "description": "Old"}}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(packs_dir.join("no-mcmeta")).unwrap();
+        fs::write(packs_dir.join("Faithful.zip"), "not really a zip").unwrap();
+        let options = dir.join("options.txt");
+        fs::write(
+            &options,
+            "version:3465\nresourcePacks:[\"vanilla\",\"file/Faithful.zip\"]\nfov:0.5\n",
+        )
+        .unwrap();
+
+        let packs = load_resource_packs(&packs_dir, &options);
+        set_resource_pack_enabled(&options, &packs[0], false, Some(8)).unwrap();
+        set_resource_pack_enabled(&options, &packs[1], true, Some(8)).unwrap();
+        let written = fs::read_to_string(&options).unwrap();
+        set_resource_pack_enabled(&options, &packs[1], true, Some(15)).unwrap();
+        let outdated = fs::read_to_string(&options).unwrap();
+        let reloaded = load_resource_packs(&packs_dir, &options);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let listed: Vec<_> = packs.iter().map(|p| (p.name.as_str(), p.enabled)).collect();
+        assert_eq!(listed, [("Faithful.zip", true), ("Unpacked", false)]);
+        assert_eq!(packs[1].pack_format, Some(8));
+        assert_eq!(
+            written,
+            "version:3465\nresourcePacks:[\"vanilla\",\"file/Unpacked\"]\nfov:0.5\n"
+        );
+        assert!(outdated.ends_with("incompatibleResourcePacks:[\"file/Unpacked\"]\n"));
+        assert!(!reloaded[0].enabled && reloaded[1].enabled);
+    }
+}
@@ -0,0 +1,104 @@
+//! The screenshots in an instance's `screenshots/` folder.
+
+use crate::data::format;
+use crate::data::logs::format_size;
+use crate::error::Result;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Screenshot {
+    pub name: String,
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    /// Width and height from the PNG header
+    pub dimensions: Option<(u32, u32)>,
+}
+
+impl Screenshot {
+    /// e.g. "1920x1080, 2.1 MB, 2024-05-01 10:00"
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some((width, height)) = self.dimensions {
+            parts.push(format!("{}x{}", width, height));
+        }
+        parts.push(format_size(self.size));
+        if let Some(time) = self.modified {
+            parts.push(format::timestamp(chrono::DateTime::<chrono::Local>::from(
+                time,
+            )));
+        }
+        parts.join(", ")
+    }
+}
+
+/// The PNG screenshots in `dir`, newest first
+pub fn load_screenshots(dir: &Path) -> Vec<Screenshot> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut screenshots: Vec<Screenshot> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let name = e.file_name().into_string().ok()?;
+            if !name.to_lowercase().ends_with(".png") {
+                return None;
+            }
+            let metadata = e.metadata().ok().filter(|m| m.is_file())?;
+            let path = e.path();
+            Some(Screenshot {
+                dimensions: read_png_dimensions(&path),
+                name,
+                size: metadata.len(),
+                modified: metadata.modified().ok(),
+                path,
+            })
+        })
+        .collect();
+    screenshots.sort_by(|a, b| {
+        b.modified
+            .cmp(&a.modified)
+            .then_with(|| a.name.cmp(&b.name))
+    });
+    screenshots
+}
+
+fn read_png_dimensions(path: &Path) -> Option<(u32, u32)> {
+    let mut header = [0; 24];
+    File::open(path).ok()?.read_exact(&mut header).ok()?;
+    png_dimensions(&header)
+}
+
+/// Width and height in a PNG's IHDR chunk, which always comes first
+pub fn png_dimensions(header: &[u8]) -> Option<(u32, u32)> {
+    const SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+    if header.get(..8)? != SIGNATURE || header.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let width = u32::from_be_bytes(header.get(16..20)?.try_into().ok()?);
+    let height = u32::from_be_bytes(header.get(20..24)?.try_into().ok()?);
+    Some((width, height))
+}
+
+pub fn delete_screenshot(screenshot: &Screenshot) -> Result<()> {
+    fs::remove_file(&screenshot.path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_png_dimensions_from_the_header() {
+        let mut header = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR".to_vec();
+        header.extend(1920u32.to_be_bytes());
+        header.extend(1080u32.to_be_bytes());
+        assert_eq!(png_dimensions(&header), Some((1920, 1080)));
+        assert_eq!(png_dimensions(&header[..20]), None);
+        assert_eq!(png_dimensions(b"GIF89a not a png at all!"), None);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::data::logs::{load_log_entries, load_log_entries_with_crashes};
+use crate::data::servers::load_servers;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// What a global search result refers to, in the order groups are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SearchKind {
+    Instance,
+    Account,
+    Server,
+    Log,
+    /// The Logs screen's instance picker
+    LogInstance,
+}
+
+impl SearchKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            SearchKind::Instance => "Instances",
+            SearchKind::Account => "Accounts",
+            SearchKind::Server => "Servers",
+            SearchKind::Log => "Logs",
+            SearchKind::LogInstance => "Open logs of",
+        }
+    }
+}
+
+/// Where selecting a result jumps to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SearchTarget {
+    Instance(String),
+    Account(usize),
+    Server {
+        instance_id: String,
+        index: usize,
+    },
+    /// A log file; launcher logs have no instance
+    Log {
+        instance_id: Option<String>,
+        name: String,
+    },
+    /// Another instance's logs, keeping the Logs screen's source
+    InstanceLogs(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchItem {
+    pub kind: SearchKind,
+    pub label: String,
+    /// Secondary text, also searched (owning instance, address, alias...)
+    pub detail: String,
+    pub target: SearchTarget,
+}
+
+/// The files of one instance the global search lists
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchFolders {
+    pub instance_id: String,
+    pub instance_name: String,
+    pub servers_dat: PathBuf,
+    pub logs_dir: PathBuf,
+    pub crash_reports_dir: PathBuf,
+}
+
+impl SearchFolders {
+    fn paths(&self) -> [&Path; 3] {
+        [&self.servers_dat, &self.logs_dir, &self.crash_reports_dir]
+    }
+}
+
+/// The servers and log files of these instances, plus the launcher's own
+/// logs in `launcher_logs`: the part of the global search read from disk
+pub fn index_search_files(instances: &[SearchFolders], launcher_logs: &Path) -> Vec<SearchItem> {
+    let mut items = Vec::new();
+    for folders in instances {
+        let servers = load_servers(&folders.servers_dat).unwrap_or_default();
+        for (idx, server) in servers.into_iter().enumerate() {
+            items.push(SearchItem {
+                kind: SearchKind::Server,
+                label: server.name,
+                detail: format!("{} ({})", server.ip, folders.instance_name),
+                target: SearchTarget::Server {
+                    instance_id: folders.instance_id.clone(),
+                    index: idx,
+                },
+            });
+        }
+    }
+    for folders in instances {
+        let entries = load_log_entries_with_crashes(&folders.logs_dir, &folders.crash_reports_dir)
+            .unwrap_or_default();
+        items.extend(entries.into_iter().map(|entry| SearchItem {
+            kind: SearchKind::Log,
+            label: entry.name.clone(),
+            detail: folders.instance_name.clone(),
+            target: SearchTarget::Log {
+                instance_id: Some(folders.instance_id.clone()),
+                name: entry.name,
+            },
+        }));
+    }
+    let launcher = load_log_entries(launcher_logs).unwrap_or_default();
+    items.extend(launcher.into_iter().map(|entry| SearchItem {
+        kind: SearchKind::Log,
+        label: entry.name.clone(),
+        detail: "Launcher".to_string(),
+        target: SearchTarget::Log {
+            instance_id: None,
+            name: entry.name,
+        },
+    }));
+    items
+}
+
+/// Changes whenever what `index_search_files` would find does: an instance
+/// comes, goes or is renamed, a servers.dat is written or a log is added or
+/// removed (which touches its folder). Only the files' times are read.
+pub fn search_files_stamp(instances: &[SearchFolders], launcher_logs: &Path) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for folders in instances {
+        (&folders.instance_id, &folders.instance_name).hash(&mut hasher);
+        for path in folders.paths() {
+            hash_modified(path, &mut hasher);
+        }
+    }
+    hash_modified(launcher_logs, &mut hasher);
+    hasher.finish()
+}
+
+fn hash_modified(path: &Path, hasher: &mut DefaultHasher) {
+    let modified = path.metadata().and_then(|m| m.modified()).ok();
+    (path, modified).hash(hasher);
+}
+
+/// Results shown per group, so one noisy group can't push out the others
+const MAX_PER_KIND: usize = 8;
+
+/// Indices of items matching `query`, grouped by kind in display order
+pub fn search_items(items: &[SearchItem], query: &str) -> Vec<usize> {
+    let query = query.to_lowercase();
+    let mut results: Vec<usize> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            item.label.to_lowercase().contains(&query)
+                || item.detail.to_lowercase().contains(&query)
+        })
+        .map(|(idx, _)| idx)
+        .collect();
+    // Stable, so each group keeps the order items were collected in
+    results.sort_by_key(|&idx| items[idx].kind);
+
+    let mut group = None;
+    let mut count = 0;
+    results.retain(|&idx| {
+        let kind = items[idx].kind;
+        if group != Some(kind) {
+            group = Some(kind);
+            count = 0;
+        }
+        count += 1;
+        count <= MAX_PER_KIND
+    });
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(kind: SearchKind, label: &str, detail: &str) -> SearchItem {
+        SearchItem {
+            kind,
+            label: label.to_string(),
+            detail: detail.to_string(),
+            target: SearchTarget::Account(0),
+        }
+    }
+
+    #[test]
+    fn test_search_groups_by_kind_and_matches_detail() {
+        let items = vec![
+            item(SearchKind::Log, "latest.log", "Vanilla"),
+            item(SearchKind::Server, "Hypixel", "mc.hypixel.net"),
+            item(SearchKind::Instance, "Vanilla", "1.21"),
+            item(SearchKind::Account, "Steve", ""),
+        ];
+        assert_eq!(search_items(&items, "vanilla"), [2, 0]);
+        assert_eq!(search_items(&items, "HYPIXEL.NET"), [1]);
+        assert_eq!(search_items(&items, "").len(), 4);
+    }
+
+    #[test]
+    fn test_search_limits_each_group() {
+        let mut items: Vec<_> = (0..20)
+            .map(|i| item(SearchKind::Log, &format!("{}.log", i), ""))
+            .collect();
+        items.push(item(SearchKind::Account, "log-alt", ""));
+        let results = search_items(&items, "log");
+        assert_eq!(results.len(), MAX_PER_KIND + 1);
+        assert_eq!(results[0], 20);
+    }
+}
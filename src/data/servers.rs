@@ -1,13 +1,151 @@
+use crate::data::atomic::write_atomic;
 use crate::error::Result;
 use hematite_nbt::{Blob, Value};
+use std::collections::HashMap;
 use std::fs::File;
-use std::io::{BufReader, BufWriter};
-use std::path::PathBuf;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone)]
+/// How the client handles a server's resource pack (`acceptTextures`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResourcePackPolicy {
+    #[default]
+    Prompt,
+    Always,
+    Never,
+}
+
+impl ResourcePackPolicy {
+    /// A missing tag means the client prompts
+    fn from_nbt(value: Option<&Value>) -> Self {
+        match value {
+            Some(Value::Byte(0)) => ResourcePackPolicy::Never,
+            Some(Value::Byte(_)) => ResourcePackPolicy::Always,
+            _ => ResourcePackPolicy::Prompt,
+        }
+    }
+
+    fn to_nbt(self) -> Option<Value> {
+        match self {
+            ResourcePackPolicy::Prompt => None,
+            ResourcePackPolicy::Always => Some(Value::Byte(1)),
+            ResourcePackPolicy::Never => Some(Value::Byte(0)),
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ResourcePackPolicy::Prompt => "Prompt",
+            ResourcePackPolicy::Always => "Always",
+            ResourcePackPolicy::Never => "Never",
+        }
+    }
+
+    pub fn next(&self) -> Self {
+        match self {
+            ResourcePackPolicy::Prompt => ResourcePackPolicy::Always,
+            ResourcePackPolicy::Always => ResourcePackPolicy::Never,
+            ResourcePackPolicy::Never => ResourcePackPolicy::Prompt,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 pub struct Server {
     pub name: String,
     pub ip: String,
+    pub accept_textures: ResourcePackPolicy,
+    /// Hidden entries are kept out of the in-game list
+    pub hidden: bool,
+    /// Tags we don't edit (icon, hidden, ...), written back unchanged
+    pub extra: HashMap<String, Value>,
+}
+
+impl Server {
+    pub fn new(name: String, ip: String) -> Self {
+        Self {
+            name,
+            ip,
+            accept_textures: ResourcePackPolicy::default(),
+            hidden: false,
+            extra: HashMap::new(),
+        }
+    }
+}
+
+/// Port the client uses when an address doesn't name one
+pub const DEFAULT_PORT: u16 = 25565;
+
+/// Split an address into a lowercased host and port
+pub fn split_address(address: &str) -> (String, u16) {
+    match address.rsplit_once(':') {
+        Some((host, port)) if !host.contains(':') => match port.parse() {
+            Ok(port) => (host.to_lowercase(), port),
+            Err(_) => (address.to_lowercase(), DEFAULT_PORT),
+        },
+        _ => (address.to_lowercase(), DEFAULT_PORT),
+    }
+}
+
+/// A server other than `skip` pointing at the same host and port as `address`
+pub fn find_duplicate<'a>(
+    servers: &'a [Server],
+    address: &str,
+    skip: Option<usize>,
+) -> Option<&'a Server> {
+    let target = split_address(address);
+    servers
+        .iter()
+        .enumerate()
+        .find(|(idx, s)| Some(*idx) != skip && split_address(&s.ip) == target)
+        .map(|(_, s)| s)
+}
+
+/// Append the `imported` servers whose address isn't in `servers` yet.
+/// Returns how many were added.
+pub fn merge_servers(servers: &mut Vec<Server>, imported: Vec<Server>) -> usize {
+    let before = servers.len();
+    for server in imported {
+        if find_duplicate(servers, &server.ip, None).is_none() {
+            servers.push(server);
+        }
+    }
+    servers.len() - before
+}
+
+/// Three-way merge of a server list changed both here (`ours`) and on disk
+/// (`theirs`) since `base` was read, matching entries by address. The file's
+/// order and changes stay; our edits, additions and removals go on top. An
+/// entry one side removed stays removed unless the other side changed it.
+pub fn merge_server_changes(
+    base: &[Server],
+    ours: Vec<Server>,
+    theirs: Vec<Server>,
+) -> Vec<Server> {
+    let mut ours: Vec<Option<Server>> = ours.into_iter().map(Some).collect();
+    let mut merged = Vec::new();
+    for their in theirs {
+        let address = split_address(&their.ip);
+        let our = ours
+            .iter_mut()
+            .find(|s| s.as_ref().is_some_and(|s| split_address(&s.ip) == address))
+            .and_then(Option::take);
+        match (our, find_duplicate(base, &their.ip, None)) {
+            // Changed or added here
+            (Some(our), base) if base != Some(&our) => merged.push(our),
+            // Removed here and left alone there
+            (None, Some(base)) if *base == their => {}
+            _ => merged.push(their),
+        }
+    }
+    for our in ours.into_iter().flatten() {
+        match find_duplicate(base, &our.ip, None) {
+            // Removed there and left alone here
+            Some(base) if *base == our => {}
+            _ => merged.push(our),
+        }
+    }
+    merged
 }
 
 pub fn load_servers(servers_dat_path: &PathBuf) -> Result<Vec<Server>> {
@@ -24,15 +162,25 @@ pub fn load_servers(servers_dat_path: &PathBuf) -> Result<Vec<Server>> {
     if let Some(Value::List(server_list)) = blob.get("servers") {
         for server_value in server_list {
             if let Value::Compound(server_map) = server_value {
-                let name = match server_map.get("name") {
-                    Some(Value::String(s)) => s.clone(),
+                let mut extra = server_map.clone();
+                let name = match extra.remove("name") {
+                    Some(Value::String(s)) => s,
                     _ => "Unknown".to_string(),
                 };
-                let ip = match server_map.get("ip") {
-                    Some(Value::String(s)) => s.clone(),
+                let ip = match extra.remove("ip") {
+                    Some(Value::String(s)) => s,
                     _ => continue,
                 };
-                servers.push(Server { name, ip });
+                let accept_textures =
+                    ResourcePackPolicy::from_nbt(extra.remove("acceptTextures").as_ref());
+                let hidden = matches!(extra.remove("hidden"), Some(Value::Byte(b)) if b != 0);
+                servers.push(Server {
+                    name,
+                    ip,
+                    accept_textures,
+                    hidden,
+                    extra,
+                });
             }
         }
     }
@@ -40,15 +188,21 @@ pub fn load_servers(servers_dat_path: &PathBuf) -> Result<Vec<Server>> {
     Ok(servers)
 }
 
-pub fn save_servers(servers_dat_path: &PathBuf, servers: &[Server]) -> Result<()> {
+pub fn save_servers(servers_dat_path: &Path, servers: &[Server]) -> Result<()> {
     let mut blob = Blob::new();
 
     let server_list: Vec<Value> = servers
         .iter()
         .map(|server| {
-            let mut map = std::collections::HashMap::new();
+            let mut map = server.extra.clone();
             map.insert("name".to_string(), Value::String(server.name.clone()));
             map.insert("ip".to_string(), Value::String(server.ip.clone()));
+            if let Some(value) = server.accept_textures.to_nbt() {
+                map.insert("acceptTextures".to_string(), value);
+            }
+            if server.hidden {
+                map.insert("hidden".to_string(), Value::Byte(1));
+            }
             Value::Compound(map)
         })
         .collect();
@@ -60,9 +214,114 @@ pub fn save_servers(servers_dat_path: &PathBuf, servers: &[Server]) -> Result<()
         std::fs::create_dir_all(parent)?;
     }
 
-    let file = File::create(servers_dat_path)?;
-    let mut writer = BufWriter::new(file);
-    blob.to_writer(&mut writer)?;
+    let mut contents = Vec::new();
+    blob.to_writer(&mut contents)?;
+    write_atomic(servers_dat_path, &contents)?;
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_preserves_unknown_tags() {
+        let path = crate::data::test_dir("servers.dat");
+
+        let mut server = Server::new("Example".to_string(), "mc.example.com".to_string());
+        server.accept_textures = ResourcePackPolicy::Never;
+        server.hidden = true;
+        server
+            .extra
+            .insert("icon".to_string(), Value::String("aWNvbg==".to_string()));
+        save_servers(&path, &[server]).unwrap();
+
+        let loaded = load_servers(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].name, "Example");
+        assert_eq!(loaded[0].accept_textures, ResourcePackPolicy::Never);
+        assert!(loaded[0].hidden);
+        assert_eq!(
+            loaded[0].extra.get("icon"),
+            Some(&Value::String("aWNvbg==".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_find_duplicate_normalizes_address() {
+        let servers = vec![
+            Server::new("A".to_string(), "Play.Example.com".to_string()),
+            Server::new("B".to_string(), "other.example.com:25566".to_string()),
+        ];
+        assert_eq!(
+            find_duplicate(&servers, "play.example.com:25565", None).map(|s| s.name.as_str()),
+            Some("A")
+        );
+        assert!(find_duplicate(&servers, "play.example.com", Some(0)).is_none());
+        assert!(find_duplicate(&servers, "other.example.com", None).is_none());
+    }
+
+    #[test]
+    fn test_merge_servers_skips_known_addresses() {
+        let mut servers = vec![Server::new(
+            "Hub".to_string(),
+            "hub.example.com".to_string(),
+        )];
+        let imported = vec![
+            Server::new("Hub copy".to_string(), "HUB.example.com:25565".to_string()),
+            Server::new("Survival".to_string(), "smp.example.com".to_string()),
+            Server::new("Survival again".to_string(), "smp.example.com".to_string()),
+        ];
+        assert_eq!(merge_servers(&mut servers, imported), 1);
+        let names: Vec<&str> = servers.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Hub", "Survival"]);
+    }
+
+    #[test]
+    fn test_merge_server_changes_keeps_both_sides() {
+        let server = |name: &str, ip: &str| Server::new(name.to_string(), ip.to_string());
+        let base = vec![
+            server("Hub", "hub.example.com"),
+            server("Old", "old.example.com"),
+            server("Gone", "gone.example.com"),
+            server("Survival", "smp.example.com"),
+        ];
+        // Here: Hub renamed, Gone removed, Creative added
+        let ours = vec![
+            server("Lobby", "hub.example.com"),
+            server("Old", "old.example.com"),
+            server("Survival", "smp.example.com"),
+            server("Creative", "creative.example.com"),
+        ];
+        // On disk: Old removed, Survival renamed, Minigames added
+        let theirs = vec![
+            server("Hub", "hub.example.com"),
+            server("Gone", "gone.example.com"),
+            server("SMP", "smp.example.com"),
+            server("Minigames", "mini.example.com"),
+        ];
+
+        let merged = merge_server_changes(&base, ours, theirs);
+        let names: Vec<&str> = merged.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Lobby", "SMP", "Minigames", "Creative"]);
+    }
+
+    #[test]
+    fn test_resource_pack_policy_from_nbt() {
+        assert_eq!(
+            ResourcePackPolicy::from_nbt(None),
+            ResourcePackPolicy::Prompt
+        );
+        assert_eq!(
+            ResourcePackPolicy::from_nbt(Some(&Value::Byte(1))),
+            ResourcePackPolicy::Always
+        );
+        assert_eq!(
+            ResourcePackPolicy::from_nbt(Some(&Value::Byte(0))),
+            ResourcePackPolicy::Never
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Where instance folders really live: symlinks to other drives, and how
+//! much room is left there before copying into them. Also what takes up the
+//! room in the launcher's data directory.
+
+use crate::data::format_size;
+use crate::error::{PrismError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use sysinfo::Disks;
+
+/// Name of the last scan in prism-tui's cache folder
+pub const STORAGE_USAGE_CACHE: &str = "storage-usage";
+
+/// Top-level folders of the data directory counted on their own
+const SHARED_FOLDERS: [&str; 3] = ["libraries", "assets", "icons"];
+
+#[derive(Debug, Clone, Default)]
+pub struct StorageInfo {
+    /// The folder with symlinks resolved, when that isn't where it's listed
+    pub real_path: Option<PathBuf>,
+    /// Mount point of the drive holding the folder
+    pub mount_point: Option<PathBuf>,
+    /// Free space on that drive, in bytes
+    pub available: Option<u64>,
+    /// On a different drive than the launcher's data directory
+    pub external: bool,
+}
+
+/// What the launcher's data directory holds, from one scan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StorageUsage {
+    pub data_dir: PathBuf,
+    /// Each instance by name, largest first
+    pub instances: Vec<(String, u64)>,
+    pub libraries: u64,
+    pub assets: u64,
+    pub icons: u64,
+    /// Everything else: metadata, caches, Java runtimes, logs
+    pub other: u64,
+    pub scanned_at: SystemTime,
+}
+
+impl StorageUsage {
+    pub fn instances_total(&self) -> u64 {
+        self.instances.iter().map(|(_, size)| size).sum()
+    }
+
+    pub fn total(&self) -> u64 {
+        self.instances_total() + self.libraries + self.assets + self.icons + self.other
+    }
+}
+
+/// Add up `data_dir` by what uses it. Instance folders are sized through
+/// their symlinks, so instances moved to another drive still count.
+pub fn storage_usage(
+    data_dir: &Path,
+    instances_dir: &Path,
+    instances: &[(String, PathBuf)],
+) -> StorageUsage {
+    let mut sized: Vec<(String, u64)> = instances
+        .iter()
+        .map(|(name, path)| (name.clone(), dir_size(path)))
+        .collect();
+    sized.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    let other = fs::read_dir(data_dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            !SHARED_FOLDERS.iter().any(|name| e.file_name() == *name) && e.path() != instances_dir
+        })
+        .filter_map(|e| Some(entry_size(&e.path(), e.file_type().ok()?)))
+        .sum();
+
+    StorageUsage {
+        data_dir: data_dir.to_path_buf(),
+        instances: sized,
+        libraries: dir_size(&data_dir.join("libraries")),
+        assets: dir_size(&data_dir.join("assets")),
+        icons: dir_size(&data_dir.join("icons")),
+        other,
+        scanned_at: SystemTime::now(),
+    }
+}
+
+/// Resolve `path` and find its drive, comparing it with `data_dir`'s
+pub fn storage_info(path: &Path, data_dir: &Path) -> StorageInfo {
+    let real = fs::canonicalize(path).ok();
+    let listed = path
+        .parent()
+        .and_then(|parent| fs::canonicalize(parent).ok())
+        .zip(path.file_name())
+        .map(|(parent, name)| parent.join(name));
+    let real_path = real.clone().filter(|real| Some(real) != listed.as_ref());
+
+    let disks = Disks::new_with_refreshed_list();
+    let disk = real.as_deref().and_then(|p| disk_for(&disks, p));
+    let data_disk = fs::canonicalize(data_dir)
+        .ok()
+        .and_then(|p| disk_for(&disks, &p).map(|d| d.mount_point().to_path_buf()));
+    let mount_point = disk.map(|d| d.mount_point().to_path_buf());
+
+    StorageInfo {
+        real_path,
+        external: mount_point.is_some() && data_disk.is_some() && mount_point != data_disk,
+        available: disk.map(|d| d.available_space()),
+        mount_point,
+    }
+}
+
+/// Free space on the drive `path` is on, or would be created on
+pub fn free_space(path: &Path) -> Option<u64> {
+    let existing = path.ancestors().find(|p| p.exists())?;
+    let real = fs::canonicalize(existing).ok()?;
+    let disks = Disks::new_with_refreshed_list();
+    disk_for(&disks, &real).map(|d| d.available_space())
+}
+
+/// Refuse a copy of `size` bytes into `target` that wouldn't fit. Unknown
+/// free space lets it go ahead.
+pub fn ensure_free_space(size: u64, target: &Path) -> Result<()> {
+    match free_space(target) {
+        Some(available) if available < size => Err(PrismError::Other(format!(
+            "Not enough free space for {}: needs {}, {} free",
+            target.display(),
+            format_size(size),
+            format_size(available)
+        ))),
+        _ => Ok(()),
+    }
+}
+
+/// Total size of the files under `path`, not following symlinks
+pub fn dir_size(path: &Path) -> u64 {
+    let Ok(entries) = fs::read_dir(path) else {
+        return 0;
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| Some(entry_size(&e.path(), e.file_type().ok()?)))
+        .sum()
+}
+
+fn entry_size(path: &Path, kind: fs::FileType) -> u64 {
+    if kind.is_dir() {
+        dir_size(path)
+    } else if kind.is_file() {
+        fs::metadata(path).map(|m| m.len()).unwrap_or(0)
+    } else {
+        0
+    }
+}
+
+/// The disk mounted closest to `path`
+fn disk_for<'a>(disks: &'a Disks, path: &Path) -> Option<&'a sysinfo::Disk> {
+    disks
+        .iter()
+        .filter(|d| path.starts_with(d.mount_point()))
+        .max_by_key(|d| d.mount_point().components().count())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_storage_info_follows_symlinks() {
+        let root = crate::data::test_dir("storage");
+        let elsewhere = root.join("elsewhere");
+        let instances = root.join("instances");
+        fs::create_dir_all(elsewhere.join("mods")).unwrap();
+        fs::create_dir_all(instances.join("Local")).unwrap();
+        fs::write(elsewhere.join("mods/a.jar"), [0; 100]).unwrap();
+        fs::write(elsewhere.join("instance.cfg"), [0; 20]).unwrap();
+        std::os::unix::fs::symlink(&elsewhere, instances.join("Linked")).unwrap();
+
+        let linked = storage_info(&instances.join("Linked"), &root);
+        let local = storage_info(&instances.join("Local"), &root);
+        let size = dir_size(&elsewhere);
+        let through_link = dir_size(&instances);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert!(linked.real_path.is_some_and(|p| p.ends_with("elsewhere")));
+        assert_eq!(local.real_path, None);
+        assert!(!linked.external);
+        assert_eq!(size, 120);
+        assert_eq!(through_link, 0);
+    }
+
+    #[test]
+    fn test_storage_usage_splits_the_data_dir() {
+        let root = crate::data::test_dir("usage");
+        let instances = root.join("instances");
+        for (path, size) in [
+            ("instances/Big/.minecraft/mods/a.jar", 300),
+            ("instances/Small/instance.cfg", 10),
+            ("libraries/lwjgl.jar", 200),
+            ("assets/objects/ab/abcd", 50),
+            ("meta/index.json", 7),
+            ("prismlauncher.cfg", 3),
+        ] {
+            let path = root.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, vec![0; size]).unwrap();
+        }
+
+        let usage = storage_usage(
+            &root,
+            &instances,
+            &[
+                ("Small".to_string(), instances.join("Small")),
+                ("Big".to_string(), instances.join("Big")),
+            ],
+        );
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(
+            usage.instances,
+            [("Big".to_string(), 300), ("Small".to_string(), 10)]
+        );
+        assert_eq!(
+            (usage.libraries, usage.assets, usage.icons, usage.other),
+            (200, 50, 0, 10)
+        );
+        assert_eq!(usage.total(), 570);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Sidecar file in the instance folder holding its task list
+pub const TASKS_FILE: &str = "prism-tui-tasks.json";
+
+/// A pack maintenance note, e.g. "backup world before 1.21"
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Task {
+    pub text: String,
+    #[serde(default)]
+    pub done: bool,
+}
+
+impl Task {
+    pub fn new(text: String) -> Self {
+        Self { text, done: false }
+    }
+}
+
+pub fn load_tasks(instance_path: &Path) -> Result<Vec<Task>> {
+    let path = instance_path.join(TASKS_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&content)?)
+}
+
+/// Write the task list, removing the sidecar once it is empty
+pub fn save_tasks(instance_path: &Path, tasks: &[Task]) -> Result<()> {
+    let path = instance_path.join(TASKS_FILE);
+    if tasks.is_empty() {
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        return Ok(());
+    }
+    fs::write(path, serde_json::to_string_pretty(tasks)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tasks_round_trip_and_cleanup() {
+        let dir = crate::data::test_dir("tasks");
+        fs::create_dir_all(&dir).unwrap();
+
+        let missing = load_tasks(&dir).unwrap();
+        let mut tasks = vec![Task::new("update Create".to_string())];
+        tasks.push(Task {
+            text: "backup world before 1.21".to_string(),
+            done: true,
+        });
+        save_tasks(&dir, &tasks).unwrap();
+        let loaded = load_tasks(&dir).unwrap();
+        save_tasks(&dir, &[]).unwrap();
+        let sidecar_removed = !dir.join(TASKS_FILE).exists();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert!(missing.is_empty());
+        assert_eq!(loaded, tasks);
+        assert!(sidecar_removed);
+    }
+
+    #[test]
+    fn test_done_defaults_to_false() {
+        let tasks: Vec<Task> = serde_json::from_str(r#"[{"text": "update Create"}]"#).unwrap();
+        assert!(!tasks[0].done);
+    }
+}
@@ -0,0 +1,274 @@
+//! Instance templates: an instance's components, settings and enabled mods
+//! saved under `<config dir>/prism-tui/templates/<name>/`, to create fresh
+//! instances from.
+
+use crate::data::AppConfig;
+use crate::data::instance::parse_mmc_pack;
+use crate::data::mods::list_mods;
+use crate::data::storage::{dir_size, ensure_free_space};
+use crate::error::{PrismError, Result};
+use configparser::ini::Ini;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// instance.cfg keys that describe one instance's history rather than its
+/// setup, left out of templates
+const INSTANCE_ONLY_KEYS: &[&str] = &[
+    "name",
+    "totalTimePlayed",
+    "lastTimePlayed",
+    "lastLaunchTime",
+    "JoinServerOnLaunch",
+    "JoinServerOnLaunchAddress",
+    "JoinWorldOnLaunch",
+    "ManagedPack",
+    "ManagedPackID",
+    "ManagedPackName",
+    "ManagedPackType",
+    "ManagedPackVersionID",
+    "ManagedPackVersionName",
+];
+
+/// Characters PrismLauncher doesn't allow in instance folder names
+const UNSAFE_CHARS: &[char] = &['/', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    pub name: String,
+    pub path: PathBuf,
+    pub minecraft_version: String,
+    pub mod_loader: Option<String>,
+    pub mod_count: usize,
+}
+
+impl Template {
+    /// e.g. "1.20.1 Fabric, 42 mods"
+    pub fn summary(&self) -> String {
+        let loader = self
+            .mod_loader
+            .as_deref()
+            .map(|l| format!(" {}", l))
+            .unwrap_or_default();
+        let mods = match self.mod_count {
+            1 => "1 mod".to_string(),
+            n => format!("{} mods", n),
+        };
+        format!("{}{}, {}", self.minecraft_version, loader, mods)
+    }
+}
+
+pub fn templates_dir() -> PathBuf {
+    AppConfig::config_path().with_file_name("templates")
+}
+
+/// Saved templates in `dir`, by name
+pub fn load_templates(dir: &Path) -> Vec<Template> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut templates: Vec<Template> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|path| path.join("mmc-pack.json").is_file())
+        .filter_map(|path| {
+            let (minecraft_version, mod_loader, _) = parse_mmc_pack(&path).ok()?;
+            Some(Template {
+                name: path.file_name()?.to_string_lossy().into_owned(),
+                mod_count: list_mods(&path.join("mods")).len(),
+                path,
+                minecraft_version,
+                mod_loader,
+            })
+        })
+        .collect();
+    templates.sort_by_key(|t| t.name.to_lowercase());
+    templates
+}
+
+/// Save the instance in `instance_path` as the template `name` in `dir`:
+/// its components, its instance.cfg without play history, and copies of the
+/// enabled mods in `mods_dir`
+pub fn save_template(
+    instance_path: &Path,
+    mods_dir: &Path,
+    dir: &Path,
+    name: &str,
+) -> Result<PathBuf> {
+    let target = dir.join(folder_name(name)?);
+    if target.exists() {
+        return Err(PrismError::Other(format!(
+            "A template named {} already exists",
+            name
+        )));
+    }
+    if !instance_path.join("mmc-pack.json").is_file() {
+        return Err(PrismError::Other(
+            "The instance has no mmc-pack.json to take its components from".to_string(),
+        ));
+    }
+    let mods = list_mods(mods_dir);
+    let size = mods
+        .iter()
+        .filter(|m| m.enabled)
+        .filter_map(|m| fs::metadata(mods_dir.join(&m.name)).ok())
+        .map(|m| m.len())
+        .sum();
+    ensure_free_space(size, dir)?;
+
+    filled_or_removed(&target, || {
+        fs::create_dir_all(target.join("mods"))?;
+        fs::copy(
+            instance_path.join("mmc-pack.json"),
+            target.join("mmc-pack.json"),
+        )?;
+        let mut config = read_cfg(&instance_path.join("instance.cfg"))?;
+        for key in INSTANCE_ONLY_KEYS {
+            config.remove_key("General", key);
+        }
+        fs::write(target.join("instance.cfg"), config.writes())?;
+
+        for file in mods.iter().filter(|m| m.enabled) {
+            fs::copy(
+                mods_dir.join(&file.name),
+                target.join("mods").join(&file.name),
+            )?;
+        }
+        Ok(())
+    })?;
+    Ok(target)
+}
+
+/// Create an instance called `name` in `instances_dir` from a template,
+/// returning the new instance's folder name
+pub fn create_from_template(
+    template: &Template,
+    instances_dir: &Path,
+    name: &str,
+) -> Result<String> {
+    let id = unused_folder_name(instances_dir, name)?;
+    let target = instances_dir.join(&id);
+    ensure_free_space(dir_size(&template.path), instances_dir)?;
+
+    filled_or_removed(&target, || {
+        let mods = target.join(".minecraft").join("mods");
+        fs::create_dir_all(&mods)?;
+        fs::copy(
+            template.path.join("mmc-pack.json"),
+            target.join("mmc-pack.json"),
+        )?;
+        let mut config = read_cfg(&template.path.join("instance.cfg"))?;
+        config.set("General", "name", Some(name.trim().to_string()));
+        fs::write(target.join("instance.cfg"), config.writes())?;
+
+        for file in list_mods(&template.path.join("mods")) {
+            fs::copy(
+                template.path.join("mods").join(&file.name),
+                mods.join(&file.name),
+            )?;
+        }
+        Ok(())
+    })?;
+    Ok(id)
+}
+
+/// A folder name for a new instance or world called `name` that isn't taken
+/// in `dir`, numbered if it has to be
+pub(super) fn unused_folder_name(dir: &Path, name: &str) -> Result<String> {
+    let base = folder_name(name)?;
+    let mut id = base.clone();
+    let mut n = 2;
+    while dir.join(&id).exists() {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    Ok(id)
+}
+
+/// Run `fill` to populate the new folder `target`, removing whatever it left
+/// behind if it fails so a retry starts clean
+pub(super) fn filled_or_removed(target: &Path, fill: impl FnOnce() -> Result<()>) -> Result<()> {
+    let result = fill();
+    if result.is_err() {
+        let _ = fs::remove_dir_all(target);
+    }
+    result
+}
+
+/// instance.cfg with its keys' case kept, as PrismLauncher expects
+fn read_cfg(path: &Path) -> Result<Ini> {
+    let mut config = Ini::new_cs();
+    if path.exists() {
+        config
+            .load(path)
+            .map_err(|e| PrismError::Config(e.to_string()))?;
+    }
+    Ok(config)
+}
+
+/// A folder name for `name`, with characters that aren't allowed replaced
+fn folder_name(name: &str) -> Result<String> {
+    let folder: String = name
+        .trim()
+        .chars()
+        .map(|c| if UNSAFE_CHARS.contains(&c) { '_' } else { c })
+        .collect();
+    if folder.is_empty() || folder.starts_with('.') {
+        return Err(PrismError::Other(format!(
+            "'{}' is not a usable name",
+            name
+        )));
+    }
+    Ok(folder)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Instance;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_template_round_trip() {
+        let root = crate::data::test_dir("templates");
+        let source = root.join("instances").join("Dev");
+        fs::create_dir_all(source.join(".minecraft/mods")).unwrap();
+        fs::write(
+            source.join("instance.cfg"),
+            "[General]\nname=Dev\ntotalTimePlayed=500\nMaxMemAlloc=8192\n",
+        )
+        .unwrap();
+        fs::write(
+            source.join("mmc-pack.json"),
+            r#"{"components":[{"uid":"net.minecraft","version":"1.20.1"}]}"#,
+        )
+        .unwrap();
+        fs::write(source.join(".minecraft/mods/sodium.jar"), "jar").unwrap();
+        fs::write(source.join(".minecraft/mods/old.jar.disabled"), "jar").unwrap();
+
+        let instance = Instance::load(source, &HashMap::new()).unwrap();
+        save_template(
+            &instance.path,
+            &instance.mods_dir(),
+            &root.join("templates"),
+            "Dev: base",
+        )
+        .unwrap();
+        let templates = load_templates(&root.join("templates"));
+        let id = create_from_template(&templates[0], &root.join("instances"), "Test 2").unwrap();
+        let again = create_from_template(&templates[0], &root.join("instances"), "Test 2").unwrap();
+
+        let created = root.join("instances").join(&id);
+        let cfg = fs::read_to_string(created.join("instance.cfg")).unwrap();
+        let mods = fs::read_dir(created.join(".minecraft/mods"))
+            .unwrap()
+            .count();
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(templates[0].name, "Dev_ base");
+        assert_eq!(templates[0].summary(), "1.20.1, 1 mod");
+        assert_eq!((id.as_str(), again.as_str()), ("Test 2", "Test 2-2"));
+        assert!(cfg.contains("name=Test 2") && cfg.contains("MaxMemAlloc=8192"));
+        assert!(!cfg.contains("totalTimePlayed"));
+        assert_eq!(mods, 1);
+    }
+}
@@ -11,12 +11,21 @@ pub enum PrismError {
     #[error("NBT parse error: {0}")]
     Nbt(#[from] hematite_nbt::Error),
 
+    #[error("HTTP error: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
     #[error("Config parse error: {0}")]
     Config(String),
 
     #[error("PrismLauncher data directory not found")]
     DataDirNotFound,
 
+    #[error("{} was changed by another program", .0.display())]
+    ChangedOnDisk(std::path::PathBuf),
+
     #[error("Launch failed: {0}")]
     LaunchFailed(String),
 
@@ -25,3 +34,77 @@ pub enum PrismError {
 }
 
 pub type Result<T> = std::result::Result<T, PrismError>;
+
+/// The layers of a "Failed to save servers: IO error: ..." message, outermost
+/// first
+pub fn error_chain(message: &str) -> Vec<String> {
+    message
+        .split(": ")
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// What usually fixes an error, going by its message
+pub fn remedy(message: &str) -> Option<&'static str> {
+    let message = message.to_lowercase();
+    let has = |needle: &str| message.contains(needle);
+    Some(if has("missing or corrupt") {
+        "Delete the corrupt files, then launch the instance in PrismLauncher to download them again."
+    } else if has("no metadata cached") {
+        "Launch the instance once in PrismLauncher so it caches its metadata, then verify again."
+    } else if has("read-only mode") {
+        "Start prism-tui without --read-only and read_only in config.toml to make changes."
+    } else if has("permission denied") {
+        "Check that the file belongs to you and is writable, or run prism-tui as the user that runs PrismLauncher."
+    } else if has("no space left") {
+        "Free up disk space and try again; the original file was left as it was."
+    } else if has("changed by another program") {
+        "Reload to pick up the other program's changes, then make your edit again."
+    } else if has("json parse") || has("nbt parse") || has("config parse") {
+        "The file is damaged. Restore the .bak copy next to it or let PrismLauncher rewrite it, then press Ctrl+R."
+    } else if has("not found in path") {
+        "Install PrismLauncher or put prismlauncher on your PATH."
+    } else if has("no such file") || has("not found") {
+        "Check that the PrismLauncher data directory is right (--data-dir or data_dir in config.toml)."
+    } else if has("http error") || has("dns") || has("timed out") || has("connection") {
+        "Check your network connection and try again."
+    } else {
+        return None;
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_chain_splits_layers() {
+        let chain =
+            error_chain("Failed to save servers: IO error: Permission denied (os error 13)");
+        assert_eq!(
+            chain,
+            [
+                "Failed to save servers",
+                "IO error",
+                "Permission denied (os error 13)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_remedy_matches_cause() {
+        assert!(
+            remedy("Failed to load servers: NBT parse error: bad tag")
+                .unwrap()
+                .contains(".bak")
+        );
+        assert!(
+            remedy("Launch failed: prismlauncher not found in PATH")
+                .unwrap()
+                .contains("PATH")
+        );
+        assert_eq!(remedy("Server name cannot be empty"), None);
+    }
+}
@@ -0,0 +1,125 @@
+//! Translations for the interface text.
+//!
+//! Strings are looked up by their English text, so English needs no bundle
+//! and anything missing from a translation falls back to it. A bundle is a
+//! flat TOML table of `"English" = "Translation"` pairs. Bundles shipped with
+//! prism-tui live in `locales/`; a file in `<config dir>/prism-tui/locales/`
+//! overrides or extends the shipped one for the same language.
+
+use crate::data::app_config::AppConfig;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::OnceLock;
+
+/// Bundles compiled into the binary, by language code
+const BUILTIN: &[(&str, &str)] = &[("de", include_str!("../locales/de.toml"))];
+
+static CATALOG: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Pick the language from `language`, or the environment when unset, and
+/// load its bundle. Only the first call has any effect. Returns why the
+/// user's bundle was skipped, if it was.
+pub fn init(language: Option<&str>) -> Option<String> {
+    let language = language.map(normalize).or_else(detect_language)?;
+    if language == "en" {
+        return None;
+    }
+
+    let mut catalog = HashMap::new();
+    if let Some((_, bundle)) = BUILTIN.iter().find(|(code, _)| *code == language) {
+        // Shipped bundles are covered by the tests below
+        catalog.extend(parse_bundle(bundle).unwrap_or_default());
+    }
+    let user_bundle = AppConfig::config_path()
+        .with_file_name("locales")
+        .join(format!("{}.toml", language));
+    let warning = match fs::read_to_string(&user_bundle).map(|c| parse_bundle(&c)) {
+        Ok(Ok(entries)) => {
+            catalog.extend(entries);
+            None
+        }
+        Ok(Err(e)) => Some(format!("{}: {}", user_bundle.display(), e)),
+        Err(_) => None,
+    };
+    let _ = CATALOG.set(catalog);
+    warning
+}
+
+/// The translation of `text`, or `text` itself when there is none
+pub fn tr(text: &str) -> &str {
+    CATALOG
+        .get()
+        .and_then(|catalog| catalog.get(text))
+        .map_or(text, String::as_str)
+}
+
+/// Language code from the usual locale variables, e.g. "de" for "de_DE.UTF-8"
+fn detect_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .map(|value| normalize(&value))
+        .filter(|language| language != "c" && language != "posix")
+}
+
+fn normalize(locale: &str) -> String {
+    locale
+        .split(['_', '.', '@', '-'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase()
+}
+
+fn parse_bundle(content: &str) -> Result<HashMap<String, String>, String> {
+    let table: toml::Table = toml::from_str(content).map_err(|e| e.message().to_string())?;
+    table
+        .into_iter()
+        .map(|(key, value)| match value {
+            toml::Value::String(translation) => Ok((key, translation)),
+            _ => Err(format!("`{}` is not a string", key)),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_locale() {
+        assert_eq!(normalize("de_DE.UTF-8"), "de");
+        assert_eq!(normalize("pt-BR"), "pt");
+        assert_eq!(normalize("EN"), "en");
+        assert_eq!(normalize("sr@latin"), "sr");
+    }
+
+    #[test]
+    fn test_builtin_bundles_parse() {
+        for (code, bundle) in BUILTIN {
+            let entries = parse_bundle(bundle).unwrap_or_else(|e| panic!("{}: {}", code, e));
+            assert!(
+                entries.contains_key("Instances"),
+                "{} lacks tab titles",
+                code
+            );
+            assert!(
+                entries.values().all(|t| !t.is_empty()),
+                "{} has blanks",
+                code
+            );
+        }
+    }
+
+    #[test]
+    fn test_bundle_rejects_non_strings() {
+        assert!(parse_bundle("\"Quit\" = \"Beenden\"").is_ok());
+        assert!(parse_bundle("Quit = 1").is_err());
+        assert!(parse_bundle("Quit = ").is_err());
+    }
+
+    #[test]
+    fn test_untranslated_text_passes_through() {
+        assert_eq!(tr("no such string"), "no such string");
+    }
+}
@@ -0,0 +1,54 @@
+//! prism-tui's own diagnostics, written to one file per day under
+//! `<config dir>/prism-tui/logs/` and kept for a week. Attach these when
+//! reporting a bug; they can also be read on the Logs screen (`gd`).
+
+use crate::data::app_config::AppConfig;
+use std::path::PathBuf;
+use tracing_appender::non_blocking::WorkerGuard;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable that overrides the configured level
+const LEVEL_VAR: &str = "PRISM_TUI_LOG";
+
+/// Days of logs to keep
+const KEEP_FILES: usize = 7;
+
+pub fn log_dir() -> PathBuf {
+    AppConfig::config_path().with_file_name("logs")
+}
+
+/// Start writing the log. The level is taken from `PRISM_TUI_LOG`, then
+/// `level`, then "info", in tracing's filter syntax, e.g. "debug" or
+/// "prism_tui::net=trace". Lines are written in the background, so the
+/// guard must be kept until exit to flush them.
+pub fn init(level: Option<&str>) -> Result<WorkerGuard, String> {
+    let filter = match std::env::var(LEVEL_VAR) {
+        Ok(value) => EnvFilter::try_new(&value).map_err(|e| format!("{}: {}", LEVEL_VAR, e))?,
+        Err(_) => {
+            EnvFilter::try_new(level.unwrap_or("info")).map_err(|e| format!("log_level: {}", e))?
+        }
+    };
+    let appender = RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("prism-tui")
+        .filename_suffix("log")
+        .max_log_files(KEEP_FILES)
+        .build(log_dir())
+        .map_err(|e| e.to_string())?;
+    let (writer, guard) = tracing_appender::non_blocking(appender);
+    tracing_subscriber::fmt()
+        .with_writer(writer)
+        .with_ansi(false)
+        .with_env_filter(filter)
+        .try_init()
+        .map_err(|e| e.to_string())?;
+
+    // Keep color-eyre's report on screen, and a copy in the log
+    let report = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        tracing::error!("{}", info);
+        report(info);
+    }));
+    Ok(guard)
+}
@@ -1,41 +1,364 @@
 mod actions;
 mod app;
+mod cli;
+mod command;
+mod dashboard;
 mod data;
 mod error;
+mod i18n;
+mod logging;
 mod message;
+mod net;
+mod schedule;
 mod theme;
 mod tui;
 mod update;
 mod view;
 
 use app::App;
+use clap::Parser;
+use cli::{Cli, Command, ConfigAction, StartScreen};
 use color_eyre::Result;
-use data::{PrismConfig, find_prism_data_dir};
+use color_eyre::eyre::eyre;
+use command::Runner;
+use data::{AppConfig, PrismConfig, find_portable_data_dir, find_prism_data_dir};
 use message::Message;
-use std::time::Duration;
+use net::control::ControlCommand;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 use tui::{Event, EventStream, Terminal};
+use view::Announcer;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     color_eyre::install()?;
+    let cli = Cli::parse();
 
-    let data_dir = find_prism_data_dir()?;
+    // The control client talks to a running TUI and needs no data directory
+    if let Some(Command::Ctl { command }) = &cli.command {
+        return send_control(&command.join(" "));
+    }
+    // Settings are prism-tui's own, so no PrismLauncher install is needed
+    if let Some(Command::Config { action }) = cli.command {
+        return run_config_command(action);
+    }
+
+    let saved = AppConfig::load_checked().0;
+    let mut startup_warnings: Vec<String> = i18n::init(saved.language.as_deref())
+        .map(|warning| format!("Translation not loaded: {}", warning))
+        .into_iter()
+        .collect();
+    // Kept until exit so the last lines reach the file
+    let _log_guard = logging::init(saved.log_level.as_deref())
+        .map_err(|e| startup_warnings.push(format!("Logging disabled: {}", e)))
+        .ok();
+    startup_warnings.extend(theme::init(saved.theme.as_deref(), &saved.log_colors));
+    startup_warnings.extend(data::format::init(
+        saved.timestamp_format.as_deref(),
+        saved.playtime_format.as_deref(),
+    ));
+    theme::set_plain(cli.plain || saved.plain_mode);
+    let saved_dir = saved.data_dir;
+    let data_dir = match cli.data_dir {
+        Some(dir) if dir.is_dir() => dir,
+        Some(dir) => return Err(eyre!("Data directory {} does not exist", dir.display())),
+        // A portable install we were started from beats the folder saved
+        // for the usual one
+        None => match find_portable_data_dir().or(saved_dir.filter(|dir| dir.is_dir())) {
+            Some(dir) => dir,
+            None => find_prism_data_dir()?,
+        },
+    };
     let config = PrismConfig::load(&data_dir)?;
-    let mut app = App::new(config)?;
-    let mut terminal = Terminal::new()?;
-    let mut events = EventStream::new(Duration::from_millis(250));
+    tracing::info!(
+        "prism-tui {} using {}",
+        env!("CARGO_PKG_VERSION"),
+        data_dir.display()
+    );
+    for warning in &startup_warnings {
+        tracing::warn!("{}", warning);
+    }
+
+    if let Some(command) = cli.command {
+        return run_command(command, &config, cli.read_only).await;
+    }
+    if cli.once {
+        print!("{}", dashboard::snapshot(&config)?);
+        return Ok(());
+    }
+    if cli.dashboard {
+        return dashboard::run(&config, Duration::from_secs(cli.interval.max(1))).await;
+    }
+    let (message_tx, mut message_rx) = mpsc::unbounded_channel();
+    let mut app = App::new(config, message_tx)?;
+    app.read_only |= cli.read_only;
+    if let Some(warning) = startup_warnings.first() {
+        app.set_error(warning.clone());
+    }
+    // Instances load in the background; these wait until all are in
+    if cli.instance.is_some() || cli.screen.is_some() {
+        app.after_instances_load
+            .push(Message::Control(ControlCommand::Focus {
+                screen: cli.screen.unwrap_or(StartScreen::Instances),
+                instance: cli.instance.clone(),
+            }));
+    }
+    if app.app_config.check_pack_updates_on_start {
+        app.after_instances_load.push(Message::CheckPackUpdates);
+    }
+    if app.app_config.clean_logs_on_start && !app.read_only {
+        app.after_instances_load.push(Message::CleanAllLogs);
+    }
+    // Best effort: a second TUI simply runs without the socket
+    let control = net::control::listen(app.message_tx.clone()).ok();
+    update::start_metrics_server(&mut app);
+    let screen_reader = cli.screen_reader || app.app_config.screen_reader;
+    let mut terminal = if screen_reader {
+        // The tour is visual; the help screen lists every key instead
+        app.tour_step = None;
+        Terminal::linear()?
+    } else {
+        Terminal::new()?
+    };
+    app.image_protocol = terminal.image_protocol();
+    let mut announcer = screen_reader.then(Announcer::default);
+    let mut events = EventStream::new(app.tick_rate());
+    let runner = Runner::new(&app);
 
     while app.running {
-        terminal.draw(|frame| view::render(&mut app, frame))?;
-
-        if let Some(event) = events.next().await {
-            let msg = match event {
-                Event::Key(key) => Message::Key(key),
-                Event::Mouse(mouse) => Message::Mouse(mouse),
-                Event::Tick => Message::Tick,
-                Event::Resize(_, _) => Message::Tick, // Trigger redraw
+        match &mut announcer {
+            Some(announcer) => {
+                for line in announcer.changes(&app) {
+                    terminal.announce(&line)?;
+                }
+            }
+            None => {
+                let started = Instant::now();
+                terminal.draw(|frame| view::render(&mut app, frame))?;
+                if terminal.show_image(app.image_preview.as_ref())? {
+                    terminal.draw(|frame| view::render(&mut app, frame))?;
+                    terminal.show_image(app.image_preview.as_ref())?;
+                }
+                app.debug_stats.record_frame(started.elapsed());
+            }
+        }
+        app.debug_stats.queued = message_rx.len();
+        events.set_tick_rate(app.tick_rate());
+
+        tokio::select! {
+            Some(event) = events.next() => {
+                let msg = match event {
+                    Event::Key(key) => Message::Key(key),
+                    Event::Mouse(mouse) => Message::Mouse(mouse),
+                    Event::Tick => Message::Tick,
+                    Event::Resize(_, _) => Message::Tick, // Trigger redraw
+                };
+                for command in update::update(&mut app, msg) {
+                    runner.run(command);
+                }
+            }
+            // Results posted back by background tasks
+            Some(msg) = message_rx.recv() => {
+                for command in update::update(&mut app, msg) {
+                    runner.run(command);
+                }
+            }
+        }
+    }
+
+    update::write_pending_cfg_edits(&mut app);
+    // A game still running would write its own settings over the changes
+    // when it closes, and nothing will be watching for that any more
+    drop(terminal);
+    for id in app.pending_cfg_edits.keys() {
+        let name = app
+            .instance_by_id(id)
+            .map_or(id.as_str(), |i| i.name.as_str());
+        let warning = format!(
+            "{} is still running, so its held back changes were not saved",
+            name
+        );
+        tracing::warn!("{}", warning);
+        eprintln!("Warning: {}", warning);
+    }
+
+    if let Some(control) = control {
+        net::control::close(control);
+    }
+
+    Ok(())
+}
+
+fn send_control(command: &str) -> Result<()> {
+    let reply = net::control::send(command)?;
+    match reply.strip_prefix("error: ") {
+        Some(error) => Err(eyre!("{}", error)),
+        None => Ok(()),
+    }
+}
+
+fn run_config_command(action: ConfigAction) -> Result<()> {
+    match action {
+        ConfigAction::Export { output } => {
+            let content = AppConfig::load().export()?;
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, content)?;
+                    println!("Wrote {}", path.display());
+                }
+                None => print!("{}", content),
+            }
+        }
+        ConfigAction::Import { file, replace } => {
+            let content = std::fs::read_to_string(&file)?;
+            let base = if replace {
+                AppConfig::default()
+            } else {
+                AppConfig::load()
+            };
+            base.import(&content)?.try_save()?;
+            println!(
+                "Imported {} into {}",
+                file.display(),
+                AppConfig::config_path().display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Run a CLI subcommand without starting the TUI
+async fn run_command(command: Command, config: &PrismConfig, read_only: bool) -> Result<()> {
+    use data::{
+        CleanupAction, PrismSettings, apply_log_cleanup, find_instance, instance_report,
+        load_accounts, load_groups, load_instances, load_log_entries, plan_log_cleanup,
+    };
+    use std::time::SystemTime;
+
+    match command {
+        // Handled before the data directory is looked up
+        Command::Ctl { .. } | Command::Config { .. } => {}
+        Command::Launch {
+            instance,
+            server,
+            ping,
+        } => {
+            let instances_dir = config.instances_dir();
+            let groups = load_groups(&instances_dir)?;
+            let instances = load_instances(&instances_dir, &groups)?;
+            let app_config = AppConfig::load();
+
+            let target = find_instance(&instances, &app_config.aliases, &instance)
+                .ok_or_else(|| eyre!("No instance matching '{}'", instance))?;
+
+            let account = load_accounts(&config.accounts_path())?
+                .into_iter()
+                .find(|a| a.is_active)
+                .map(|a| a.username);
+            let settings = PrismSettings::load(&config.data_dir).unwrap_or_default();
+            if let Some(mismatch) = target.java_mismatch(&settings) {
+                eprintln!(
+                    "Warning: Minecraft {} {}",
+                    target.minecraft_version,
+                    mismatch.label()
+                );
+            }
+            if let (true, Some(address)) = (ping, &server) {
+                let protocol = net::ping::protocol_version(&target.minecraft_version);
+                let result = net::ping::ping(address, protocol)
+                    .await
+                    .map_err(|e| e.to_string());
+                if let Some(warning) =
+                    net::ping::join_warning(address, &target.minecraft_version, &result)
+                {
+                    return Err(eyre!("{} (run without --ping to launch anyway)", warning));
+                }
+            }
+            let server = server.or_else(|| target.join_address().map(str::to_string));
+
+            let options = actions::LaunchOptions {
+                account,
+                server,
+                env: app_config.env_for(&target.id),
+                wrapper: app_config.wrapper_for(&target.id),
             };
-            update::update(&mut app, msg);
+            actions::launch_instance(&target.id, &options)?;
+            println!("Launched {}", target.name);
+        }
+        Command::Report { instance, output } => {
+            let instances_dir = config.instances_dir();
+            let groups = load_groups(&instances_dir)?;
+            let instances = load_instances(&instances_dir, &groups)?;
+            let app_config = AppConfig::load();
+
+            let target = find_instance(&instances, &app_config.aliases, &instance)
+                .ok_or_else(|| eyre!("No instance matching '{}'", instance))?;
+            let settings = PrismSettings::load(&config.data_dir).unwrap_or_default();
+            let report = instance_report(target, &settings);
+            match output {
+                Some(path) => {
+                    std::fs::write(&path, report)?;
+                    println!("Wrote {}", path.display());
+                }
+                None => print!("{}", report),
+            }
+        }
+        Command::CleanLogs { instance, dry_run } => {
+            let instances_dir = config.instances_dir();
+            let groups = load_groups(&instances_dir)?;
+            let instances = load_instances(&instances_dir, &groups)?;
+            let app_config = AppConfig::load();
+            // Read-only never deletes, so the cleanup is only listed
+            let read_only = read_only || app_config.read_only;
+            if read_only && !dry_run {
+                println!("Read-only: listing the cleanup without changing anything");
+            }
+            let dry_run = dry_run || read_only;
+
+            let targets = match &instance {
+                Some(query) => vec![
+                    find_instance(&instances, &app_config.aliases, query)
+                        .ok_or_else(|| eyre!("No instance matching '{}'", query))?,
+                ],
+                None => instances.iter().collect(),
+            };
+
+            let mut total = 0;
+            for target in targets {
+                let Some(max_age) = app_config.log_retention_for(&target.id) else {
+                    continue;
+                };
+                let entries = load_log_entries(&target.logs_dir())?;
+                let plan = plan_log_cleanup(
+                    &entries,
+                    max_age,
+                    app_config.log_retention_compress,
+                    SystemTime::now(),
+                );
+                for item in &plan {
+                    let verb = match (item.action, dry_run) {
+                        (CleanupAction::Delete, true) => "would delete",
+                        (CleanupAction::Delete, false) => "deleting",
+                        (CleanupAction::Compress, true) => "would compress",
+                        (CleanupAction::Compress, false) => "compressing",
+                    };
+                    println!(
+                        "{} {}/{} ({})",
+                        verb,
+                        target.name,
+                        item.entry.name,
+                        item.entry.formatted_size()
+                    );
+                }
+                if !dry_run {
+                    apply_log_cleanup(&plan)?;
+                }
+                total += plan.len();
+            }
+
+            if total == 0 {
+                println!("No logs to clean up");
+            }
         }
     }
 
@@ -1,5 +1,18 @@
-use crate::app::{LogLevel, Screen};
+use crate::app::{LogLevel, LogSource, Screen};
+use crate::data::{
+    Instance, InstanceFolder, IntegrityReport, JunkItem, SearchItem, StorageInfo, StorageUsage,
+    WorldBackup,
+};
+use crate::net::control::ControlCommand;
+use crate::net::lan::LanGame;
+use crate::net::loaders::{Loader, LoaderCatalog};
+use crate::net::mod_source::{ModHit, ModUpdate};
+use crate::net::modpack::PackInstall;
+use crate::net::modrinth::PackUpdate;
+use crate::net::mojang::VersionManifest;
+use crate::net::ping::ServerStatus;
 use crossterm::event::{KeyEvent, MouseEvent};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -9,16 +22,229 @@ pub enum Message {
     Tick,
     SwitchToScreen(Screen),
 
+    // Background task results
+    /// An instance read by the startup or reload load tagged `load`
+    InstanceLoaded {
+        load: u64,
+        instance: Box<Instance>,
+    },
+    InstancesLoaded {
+        load: u64,
+    },
+    ProcessScanComplete(HashMap<String, sysinfo::Pid>),
+    PackUpdateChecked {
+        instance_id: String,
+        result: Result<Option<PackUpdate>, String>,
+    },
+    VersionManifestLoaded(Result<VersionManifest, String>),
+    ServerCheckComplete {
+        address: String,
+        warnings: Vec<String>,
+        report_ok: bool,
+    },
+    LanGameDiscovered(LanGame),
+    LanDiscoveryFailed(String),
+    /// Command received on the control socket
+    Control(ControlCommand),
+    LogCleanupFinished(Result<usize, String>),
+    LoaderVersionsLoaded {
+        loader: Loader,
+        minecraft_version: String,
+        result: Result<LoaderCatalog, String>,
+    },
+    /// Answer to a server ping; `for_launch` ones hold back a launch
+    ServerPinged {
+        address: String,
+        result: Result<ServerStatus, String>,
+        for_launch: bool,
+    },
+    ModSearchDone(Result<Vec<ModHit>, String>),
+    /// A mod downloaded into the mods folder, under the file name returned
+    ModInstalled {
+        name: String,
+        result: Result<String, String>,
+    },
+    /// The Modrinth page of a mod, or `None` when Modrinth doesn't host it
+    ModPageFound {
+        name: String,
+        result: Result<Option<String>, String>,
+    },
+    /// Mods with a newer file on Modrinth
+    ModUpdatesChecked(Result<Vec<ModUpdate>, String>),
+    /// Mods swapped for their newer files, by their old names, and the
+    /// ones that failed
+    ModsUpdated {
+        updated: Vec<String>,
+        errors: Vec<String>,
+    },
+    /// A world backup unpacked into saves/, under the folder name returned
+    BackupRestored {
+        world: String,
+        result: Result<String, String>,
+    },
+    /// The world backups found in an instance's game folder, newest first
+    BackupsFound {
+        instance_id: String,
+        backups: Vec<WorldBackup>,
+    },
+    /// A world zipped into backups/, under the file name returned
+    WorldBackedUp {
+        world: String,
+        result: Result<String, String>,
+    },
+    /// The data directory sized by what uses it
+    StorageScanned(StorageUsage),
+    /// Where an instance's folder really is and how full its drive is
+    StorageInfoRead {
+        id: String,
+        info: StorageInfo,
+    },
+    /// What a cleanup of the instance would remove, possibly nothing
+    JunkCleanupPlanned {
+        instance_id: String,
+        plan: Vec<JunkItem>,
+    },
+    /// An instance cleaned up, freeing the bytes returned
+    JunkCleaned {
+        name: String,
+        result: Result<u64, String>,
+    },
+    /// The servers and log files for the global search, read at `stamp`
+    SearchFilesIndexed {
+        stamp: u64,
+        items: Vec<SearchItem>,
+    },
+    /// An instance's libraries and assets compared with the meta cache
+    InstanceVerified {
+        name: String,
+        result: Result<IntegrityReport, String>,
+    },
+    /// An instance saved as the template `name`
+    TemplateSaved {
+        name: String,
+        result: Result<(), String>,
+    },
+    /// An instance created from a template, under the folder name returned
+    InstanceCreated {
+        name: String,
+        result: Result<String, String>,
+    },
+    PackSearchDone(Result<Vec<ModHit>, String>),
+    /// A modpack installed as a new instance
+    PackInstalled {
+        name: String,
+        result: Result<PackInstall, String>,
+    },
+
     // Instance actions
     SelectInstance(usize),
     LaunchInstance,
+    LaunchInstanceAt(usize),
+    /// Go ahead with a launch held back for a too-old Java
+    ConfirmJavaLaunch,
+    /// Go ahead with a launch held back for low disk space
+    ConfirmLowSpaceLaunch,
+    /// Go ahead with a launch into a server that did not answer as expected
+    ConfirmServerLaunch,
     KillInstance,
     OpenInstanceFolder,
+    /// Ask which folder inside the instance to open
+    PickInstanceFolder,
+    OpenGameFolder(InstanceFolder),
+    /// Remove the instance's old crash reports, Fabric cache and natives,
+    /// after showing what that frees
+    PlanJunkCleanup,
+    ConfirmJunkCleanup,
+    /// Check the instance's libraries and assets against PrismLauncher's
+    /// metadata for missing or damaged files
+    VerifyInstance,
     OpenInstanceDetails,
+    /// List the selected instance's mods to turn them on or off
+    OpenModsScreen,
+    SelectMod(usize),
+    ToggleModMark,
+    ClearModMarks,
+    /// Enable or disable the marked mods, or the selected one
+    ToggleMods,
+    /// Ask what to search Modrinth and CurseForge for
+    StartModSearch,
+    /// Download the numbered search result into the mods folder
+    InstallModHit(usize),
+    /// Look up the instance's mods on Modrinth for newer files
+    CheckModUpdates,
+    /// Swap every mod with an update for its newer file, keeping the old one
+    UpdateAllMods,
+    /// Open the selected mod's Modrinth page, found by its file's hash
+    OpenModPage,
+    /// Browse the selected instance's screenshots
+    OpenScreenshots,
+    SelectScreenshot(usize),
+    /// Open the selected screenshot in the system's image viewer
+    OpenScreenshot,
+    DeleteScreenshot,
+    ConfirmDeleteScreenshot,
+    /// Manage the selected instance's resource packs
+    OpenResourcePacks,
+    SelectResourcePack(usize),
+    /// Turn the selected pack on or off in options.txt
+    ToggleResourcePack,
+    DeleteResourcePack,
+    ConfirmDeleteResourcePack,
+    /// Browse the selected instance's worlds and the backups of them
+    OpenWorlds,
+    SelectWorld(usize),
+    SelectBackup(usize),
+    /// Move between the worlds and the backups
+    SwitchWorldsPane,
+    /// Zip the selected world into the instance's backups/
+    BackupWorld,
+    /// Unpack the selected backup next to the instance's worlds
+    RestoreBackup,
+    CopyLaunchCommand,
+    CreateShortcut,
+    EditAlias,
+    ToggleInstanceMark,
+    ClearInstanceMarks,
+    /// Copy the selected instance's memory and Java settings to the marked ones
+    SyncInstanceSettings,
+    ConfirmSyncSettings,
+    /// Save the selected instance as a template
+    SaveTemplate,
+    /// Create an instance: pick a saved template, or a Minecraft version, a
+    /// loader and its version, then name it
+    OpenCreateInstance,
+    SelectDraftItem(usize),
+    ConfirmDraftItem,
+    ToggleAllVersions,
+    DraftStepBack,
+    /// Ask what to search Modrinth and CurseForge for modpacks
+    StartPackSearch,
+    /// Install the numbered modpack as a new instance
+    InstallPackHit(usize),
+    EditEnv,
+    EditWrapper,
+    EditPrismWrapper,
+    RefreshInstanceStats,
+    OpenPackPage,
+    CheckPackUpdates,
+    CopyInstanceReport,
+
+    // Instance tasks
+    SelectTask(usize),
+    AddTask,
+    EditTask,
+    ToggleTask,
+    DeleteTask,
+
+    // Launcher settings
+    OpenSettings,
+    SelectSetting(usize),
+    EditSetting,
 
     // Account actions
     SelectAccount(usize),
     ConfirmAccountSelection,
+    CycleAccount,
 
     // Server actions
     SelectServer(usize),
@@ -26,8 +252,35 @@ pub enum Message {
     EditServer,
     DeleteServer,
     ConfirmDeleteServer,
+    /// Answers to a save that found its file changed on disk
+    ReloadChangedFile,
+    MergeChangedFile,
+    OverwriteChangedFile,
     SetJoinOnLaunch,
+    /// Turn join-on-launch on or off for the server it is set to
+    ToggleJoinOnLaunch,
+    /// Make this server the one joined on launch
+    SetJoinServer(usize),
+    FixJoinConflict,
+    OpenServerWebsite,
+    CopyServerAddress(usize),
+    CycleResourcePackPolicy,
+    ToggleHiddenServers,
+    CheckServer,
+    /// Show the selected server's version and player count
+    PingSelectedServer,
+    /// Write the server list to a standalone servers.dat, or add the
+    /// servers of one to it
+    ExportServers,
+    ImportServers,
+    NextLanGame,
+    JoinLanGame,
     LaunchWithServer,
+    QuickLaunch(u8),
+    LowPriorityLaunch,
+    /// Choose one of the selected instance's launch presets
+    OpenLaunchPresets,
+    LaunchWithPreset(String),
 
     // Input handling for dialogs
     InputChar(char),
@@ -40,6 +293,14 @@ pub enum Message {
     OpenServerScreen,
     OpenInstanceLogs,
     OpenLauncherLogs,
+    OpenAppLogs,
+    /// The Logs tab: back to the last source, or a choice of sources
+    OpenLogsTab,
+    PickLogSource(LogSource),
+    /// Switch to the next source without leaving the Logs screen
+    CycleLogSource,
+    /// Pick another instance whose logs to show
+    OpenLogInstancePicker,
     OpenHelp,
     Back,
 
@@ -63,6 +324,34 @@ pub enum Message {
     // Log level filtering
     ToggleLogLevel(LogLevel),
     ShowAllLogLevels,
+    ToggleLogFilterPanel,
+    ToggleLogLineNumbers,
+    ToggleLogWrap,
+    ToggleLogFollow,
+    JumpToLogLevel {
+        level: LogLevel,
+        forward: bool,
+    },
+    PlanLogCleanup,
+    ConfirmLogCleanup,
+    DisableSuspectedMod,
+    ConfirmDisableMod,
+    CleanAllLogs,
+    ResizeLogList(i16),
+
+    // Dry run
+    ToggleDryRun,
+    ScrollDryRunPreview(isize),
+    ConfirmDryRun,
+
+    // Error details
+    ShowErrorDetails,
+    ScrollErrorDetails(isize),
+    CopyErrorDetails,
+    /// Break the data directory down by what takes up the space
+    OpenStorageOverview,
+    /// Size the data directory again instead of showing the last scan
+    RescanStorage,
 
     // Search
     StartSearch,
@@ -71,11 +360,24 @@ pub enum Message {
     SearchConfirm,
     SearchCancel,
 
+    // Global search across instances, accounts, servers and logs
+    OpenGlobalSearch,
+    GlobalSearchChar(char),
+    GlobalSearchBackspace,
+    GlobalSearchMove(isize),
+    GlobalSearchConfirm,
+    GlobalSearchCancel,
+    SelectSearchResult(usize),
+
     // Sorting
     CycleSortMode,
     ToggleSortDirection,
 
     // Collapsible groups
+    /// Put the cursor on a group's header
+    SelectGroup(usize),
+    /// Launch the first instance of the group under the cursor
+    LaunchGroup,
     ToggleGroupCollapse,
     NextGroup,
     PrevGroup,
@@ -83,7 +385,105 @@ pub enum Message {
     // Help
     ScrollHelpUp,
     ScrollHelpDown,
+    StartTour,
+    /// Move through the onboarding tour; past the last step ends it
+    TourStep(isize),
+    EndTour,
+
+    // Empty-state actions
+    OpenLauncher,
+    ReloadData,
+    /// Read PrismLauncher's files again after one failed to parse
+    RetryDataFiles,
+    EditDataDir,
 
     // App control
+    ToggleDebugOverlay,
     Quit,
 }
+
+impl Message {
+    /// Actions that change PrismLauncher's files, prism-tui's own data or
+    /// running games, refused in read-only mode
+    pub fn is_mutating(&self) -> bool {
+        matches!(
+            self,
+            Message::KillInstance
+                | Message::CreateShortcut
+                | Message::EditAlias
+                | Message::SaveTemplate
+                | Message::SyncInstanceSettings
+                | Message::OpenCreateInstance
+                | Message::BackupWorld
+                | Message::RestoreBackup
+                | Message::PlanJunkCleanup
+                | Message::ToggleMods
+                | Message::StartModSearch
+                | Message::InstallModHit(_)
+                | Message::StartPackSearch
+                | Message::InstallPackHit(_)
+                | Message::UpdateAllMods
+                | Message::DeleteScreenshot
+                | Message::ToggleResourcePack
+                | Message::DeleteResourcePack
+                | Message::EditEnv
+                | Message::EditWrapper
+                | Message::EditPrismWrapper
+                | Message::AddTask
+                | Message::EditTask
+                | Message::ToggleTask
+                | Message::DeleteTask
+                | Message::EditSetting
+                | Message::AddServer
+                | Message::ImportServers
+                | Message::EditServer
+                | Message::DeleteServer
+                | Message::SetJoinOnLaunch
+                | Message::ToggleJoinOnLaunch
+                | Message::SetJoinServer(_)
+                | Message::FixJoinConflict
+                | Message::CycleResourcePackPolicy
+                | Message::PlanLogCleanup
+                | Message::DisableSuspectedMod
+                | Message::CleanAllLogs
+        )
+    }
+
+    /// Messages that don't originate from user input
+    pub fn is_background(&self) -> bool {
+        matches!(
+            self,
+            Message::Tick
+                | Message::InstanceLoaded { .. }
+                | Message::InstancesLoaded { .. }
+                | Message::ProcessScanComplete(_)
+                | Message::PackUpdateChecked { .. }
+                | Message::VersionManifestLoaded(_)
+                | Message::LoaderVersionsLoaded { .. }
+                | Message::ServerCheckComplete { .. }
+                | Message::LanGameDiscovered(_)
+                | Message::LanDiscoveryFailed(_)
+                | Message::LogCleanupFinished(_)
+                | Message::BackupRestored { .. }
+                | Message::BackupsFound { .. }
+                | Message::WorldBackedUp { .. }
+                | Message::StorageScanned(_)
+                | Message::StorageInfoRead { .. }
+                | Message::JunkCleanupPlanned { .. }
+                | Message::JunkCleaned { .. }
+                | Message::SearchFilesIndexed { .. }
+                | Message::InstanceVerified { .. }
+                | Message::TemplateSaved { .. }
+                | Message::InstanceCreated { .. }
+                | Message::PackSearchDone(_)
+                | Message::PackInstalled { .. }
+                | Message::ServerPinged { .. }
+                | Message::ModSearchDone(_)
+                | Message::ModInstalled { .. }
+                | Message::ModPageFound { .. }
+                | Message::ModUpdatesChecked(_)
+                | Message::ModsUpdated { .. }
+                | Message::Control(_)
+        )
+    }
+}
@@ -0,0 +1,94 @@
+use crate::error::Result;
+use crate::net::cache;
+use sha1::{Digest, Sha1};
+use std::collections::HashSet;
+use std::time::Duration;
+
+const BLOCKLIST_URL: &str = "https://sessionserver.mojang.com/blockedservers";
+const CACHE_NAME: &str = "blocked_servers";
+const CACHE_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Fetch Mojang's list of SHA-1 hashes of blocked server patterns
+pub async fn blocked_hashes(client: &reqwest::Client) -> Result<HashSet<String>> {
+    let hashes: Vec<String> = cache::fetch_cached(CACHE_NAME, CACHE_MAX_AGE, async {
+        let text = client
+            .get(BLOCKLIST_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .text()
+            .await?;
+        Ok(text
+            .lines()
+            .map(|l| l.trim().to_lowercase())
+            .filter(|l| !l.is_empty())
+            .collect())
+    })
+    .await?;
+    Ok(hashes.into_iter().collect())
+}
+
+/// The pattern that blocks `host`, if any. Mojang hashes the host itself
+/// and wildcard forms: `*.example.com` for names, `1.2.3.*` for IPs.
+pub fn blocked_pattern(hashes: &HashSet<String>, host: &str) -> Option<String> {
+    patterns(&host.to_lowercase())
+        .into_iter()
+        .find(|p| hashes.contains(&sha1_hex(p)))
+}
+
+fn patterns(host: &str) -> Vec<String> {
+    let mut patterns = vec![host.to_string()];
+    let parts: Vec<&str> = host.split('.').collect();
+    let is_ipv4 = parts.len() == 4 && parts.iter().all(|p| p.parse::<u8>().is_ok());
+
+    if is_ipv4 {
+        for keep in (1..4).rev() {
+            let mut pattern: Vec<&str> = parts[..keep].to_vec();
+            pattern.resize(4, "*");
+            patterns.push(pattern.join("."));
+        }
+    } else {
+        for skip in 1..parts.len() {
+            patterns.push(format!("*.{}", parts[skip..].join(".")));
+        }
+    }
+    patterns
+}
+
+fn sha1_hex(text: &str) -> String {
+    Sha1::digest(text.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_patterns_for_hostname() {
+        assert_eq!(
+            patterns("play.example.com"),
+            ["play.example.com", "*.example.com", "*.com"]
+        );
+    }
+
+    #[test]
+    fn test_patterns_for_ip() {
+        assert_eq!(
+            patterns("10.1.2.3"),
+            ["10.1.2.3", "10.1.2.*", "10.1.*.*", "10.*.*.*"]
+        );
+    }
+
+    #[test]
+    fn test_blocked_pattern_matches_wildcard() {
+        let hashes: HashSet<String> = [sha1_hex("*.example.com")].into_iter().collect();
+        assert_eq!(
+            blocked_pattern(&hashes, "Play.Example.com"),
+            Some("*.example.com".to_string())
+        );
+        assert_eq!(blocked_pattern(&hashes, "example.org"), None);
+    }
+}
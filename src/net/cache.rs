@@ -0,0 +1,64 @@
+use crate::error::Result;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+/// Directory for cached API responses
+pub fn cache_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("prism-tui")
+}
+
+fn cache_path(name: &str) -> PathBuf {
+    cache_dir().join(format!("{}.json", name))
+}
+
+/// Read a cached value. With `max_age`, entries older than that are ignored.
+pub fn load<T: DeserializeOwned>(name: &str, max_age: Option<Duration>) -> Option<T> {
+    let path = cache_path(name);
+    if let Some(max_age) = max_age {
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        let age = SystemTime::now()
+            .duration_since(modified)
+            .unwrap_or_default();
+        if age > max_age {
+            return None;
+        }
+    }
+    let content = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Write a value to the cache. Failures are ignored; the cache is best effort.
+pub fn store<T: Serialize>(name: &str, value: &T) {
+    let path = cache_path(name);
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    if let Ok(content) = serde_json::to_string(value) {
+        let _ = fs::write(path, content);
+    }
+}
+
+/// Serve `name` from the cache while it is younger than `max_age`, otherwise
+/// run `fetch` and cache its result. A stale entry is used if `fetch` fails.
+pub async fn fetch_cached<T, F>(name: &str, max_age: Duration, fetch: F) -> Result<T>
+where
+    T: Serialize + DeserializeOwned,
+    F: Future<Output = Result<T>>,
+{
+    if let Some(value) = load(name, Some(max_age)) {
+        return Ok(value);
+    }
+
+    match fetch.await {
+        Ok(value) => {
+            store(name, &value);
+            Ok(value)
+        }
+        Err(e) => load(name, None).ok_or(e),
+    }
+}
@@ -0,0 +1,310 @@
+//! Commands from other shells to a running TUI, over a Unix socket or, on
+//! Windows, a named pipe.
+
+use crate::cli::StartScreen;
+use crate::error::{self, PrismError};
+use crate::message::Message;
+use clap::ValueEnum;
+use std::io::{BufRead, BufReader, Read, Write};
+use tokio::io::{
+    AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader as AsyncBufReader,
+};
+use tokio::sync::mpsc::UnboundedSender;
+
+/// A command sent to a running TUI over the control socket
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ControlCommand {
+    /// `launch <instance> [--server <address>]`
+    Launch {
+        instance: String,
+        server: Option<String>,
+    },
+    /// `focus <screen> [instance]`
+    Focus {
+        screen: StartScreen,
+        instance: Option<String>,
+    },
+    /// `quit`
+    Quit,
+}
+
+/// Parse one command line. Instance names may contain spaces, which are
+/// kept as they are.
+pub fn parse_command(line: &str) -> Result<ControlCommand, String> {
+    let line = line.trim();
+    let (verb, rest) = line
+        .split_once(char::is_whitespace)
+        .map_or((line, ""), |(verb, rest)| (verb, rest.trim()));
+    match verb {
+        "launch" => {
+            let usage = || "usage: launch <instance> [--server <address>]".to_string();
+            let (instance, server) = match split_server_flag(rest) {
+                Some((_, "")) => return Err(usage()),
+                Some((_, server)) if server.contains(char::is_whitespace) => {
+                    return Err(usage());
+                }
+                Some((instance, server)) => (instance, Some(server.to_string())),
+                None => (rest, None),
+            };
+            if instance.is_empty() {
+                return Err(usage());
+            }
+            Ok(ControlCommand::Launch {
+                instance: instance.to_string(),
+                server,
+            })
+        }
+        "focus" => {
+            let (screen, instance) = rest
+                .split_once(char::is_whitespace)
+                .map_or((rest, ""), |(screen, instance)| (screen, instance.trim()));
+            if screen.is_empty() {
+                return Err("usage: focus <screen> [instance]".to_string());
+            }
+            let screen = StartScreen::from_str(screen, true)
+                .map_err(|_| format!("unknown screen '{}'", screen))?;
+            let instance = Some(instance.to_string()).filter(|i| !i.is_empty());
+            Ok(ControlCommand::Focus { screen, instance })
+        }
+        "quit" => Ok(ControlCommand::Quit),
+        "" => Err("empty command".to_string()),
+        other => Err(format!("unknown command '{}'", other)),
+    }
+}
+
+/// Split `rest` around a `--server` that stands as its own word, into the
+/// trimmed text before and after it
+fn split_server_flag(rest: &str) -> Option<(&str, &str)> {
+    rest.match_indices("--server").find_map(|(at, flag)| {
+        let (before, after) = (&rest[..at], &rest[at + flag.len()..]);
+        let own_word = (before.is_empty() || before.ends_with(char::is_whitespace))
+            && (after.is_empty() || after.starts_with(char::is_whitespace));
+        own_word.then(|| (before.trim(), after.trim()))
+    })
+}
+
+/// Read one command per line and answer "ok" or "error: ..."
+async fn handle_client<S>(stream: S, tx: UnboundedSender<Message>)
+where
+    S: AsyncRead + AsyncWrite,
+{
+    let (reader, mut writer) = tokio::io::split(stream);
+    let mut lines = AsyncBufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let reply = match parse_command(&line) {
+            Ok(command) => match tx.send(Message::Control(command)) {
+                Ok(()) => "ok".to_string(),
+                Err(_) => "error: prism-tui is shutting down".to_string(),
+            },
+            Err(e) => format!("error: {}", e),
+        };
+        if writer
+            .write_all(format!("{}\n", reply).as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// Write `command` to a connected TUI and read back its reply
+fn exchange(mut stream: impl Read + Write, command: &str) -> error::Result<String> {
+    writeln!(stream, "{}", command)?;
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply)?;
+    Ok(reply.trim_end().to_string())
+}
+
+fn not_running() -> PrismError {
+    PrismError::Other("prism-tui is not running".to_string())
+}
+
+#[cfg(unix)]
+pub use unix::{close, listen, send};
+
+#[cfg(unix)]
+mod unix {
+    use super::{exchange, handle_client, not_running};
+    use crate::error::{PrismError, Result};
+    use crate::message::Message;
+    use std::fs::{self, DirBuilder};
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+    use std::path::PathBuf;
+    use tokio::net::UnixListener;
+    use tokio::sync::mpsc::UnboundedSender;
+    use tokio::task::JoinHandle;
+
+    /// Socket the TUI listens on, in the user's runtime directory
+    fn socket_path() -> Option<PathBuf> {
+        Some(
+            dirs::runtime_dir()
+                .or_else(private_dir)?
+                .join("prism-tui.sock"),
+        )
+    }
+
+    /// Where the socket goes without a runtime directory, e.g. on macOS
+    fn private_dir() -> Option<PathBuf> {
+        Some(dirs::cache_dir()?.join("prism-tui").join("control"))
+    }
+
+    /// Bind the control socket, replacing a stale one left by a crashed run.
+    /// Fails when another TUI is already listening.
+    pub fn listen(tx: UnboundedSender<Message>) -> Result<JoinHandle<()>> {
+        let path = socket_path()
+            .ok_or_else(|| PrismError::Other("No private folder for the socket".to_string()))?;
+        if dirs::runtime_dir().is_none()
+            && let Some(dir) = private_dir()
+        {
+            // Other users must not reach the socket, whatever the folder
+            // was created with before
+            DirBuilder::new().recursive(true).mode(0o700).create(&dir)?;
+            fs::set_permissions(&dir, fs::Permissions::from_mode(0o700))?;
+        }
+        if std::os::unix::net::UnixStream::connect(&path).is_ok() {
+            return Err(PrismError::Other(
+                "Another prism-tui is already listening".to_string(),
+            ));
+        }
+        let _ = fs::remove_file(&path);
+        let listener = UnixListener::bind(&path)?;
+
+        Ok(tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_client(stream, tx.clone()));
+            }
+        }))
+    }
+
+    /// Stop listening and remove the socket
+    pub fn close(listener: JoinHandle<()>) {
+        listener.abort();
+        if let Some(path) = socket_path() {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Send a command to the running TUI and return its reply
+    pub fn send(command: &str) -> Result<String> {
+        let path = socket_path().ok_or_else(not_running)?;
+        let stream = std::os::unix::net::UnixStream::connect(path).map_err(|_| not_running())?;
+        exchange(&stream, command)
+    }
+}
+
+#[cfg(windows)]
+pub use windows::{close, listen, send};
+
+#[cfg(windows)]
+mod windows {
+    use super::{exchange, handle_client, not_running};
+    use crate::error::{PrismError, Result};
+    use crate::message::Message;
+    use std::fs::OpenOptions;
+    use tokio::net::windows::named_pipe::ServerOptions;
+    use tokio::sync::mpsc::UnboundedSender;
+    use tokio::task::JoinHandle;
+
+    /// Named pipe the TUI listens on, one per user
+    fn pipe_name() -> String {
+        let user = std::env::var("USERNAME").unwrap_or_default();
+        format!(r"\\.\pipe\prism-tui-{}", user)
+    }
+
+    /// Create the control pipe. Fails when another TUI already has it.
+    pub fn listen(tx: UnboundedSender<Message>) -> Result<JoinHandle<()>> {
+        let name = pipe_name();
+        let mut server = ServerOptions::new()
+            .first_pipe_instance(true)
+            .create(&name)
+            .map_err(|_| PrismError::Other("Another prism-tui is already listening".to_string()))?;
+
+        Ok(tokio::spawn(async move {
+            // Each client takes the pipe it connected to; a fresh one
+            // waits for the next
+            while server.connect().await.is_ok() {
+                let Ok(next) = ServerOptions::new().create(&name) else {
+                    return;
+                };
+                let connected = std::mem::replace(&mut server, next);
+                tokio::spawn(handle_client(connected, tx.clone()));
+            }
+        }))
+    }
+
+    /// Stop listening; the pipe goes away with its last handle
+    pub fn close(listener: JoinHandle<()>) {
+        listener.abort();
+    }
+
+    /// Send a command to the running TUI and return its reply
+    pub fn send(command: &str) -> Result<String> {
+        let pipe = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(pipe_name())
+            .map_err(|_| not_running())?;
+        exchange(&pipe, command)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_launch_with_spaces_and_server() {
+        assert_eq!(
+            parse_command("launch All the Mods 9 --server mc.example.com"),
+            Ok(ControlCommand::Launch {
+                instance: "All the Mods 9".to_string(),
+                server: Some("mc.example.com".to_string()),
+            })
+        );
+        assert!(parse_command("launch").is_err());
+        assert!(parse_command("launch atm9 --server").is_err());
+        assert_eq!(
+            parse_command("launch Base--server  Pack"),
+            Ok(ControlCommand::Launch {
+                instance: "Base--server  Pack".to_string(),
+                server: None,
+            })
+        );
+        assert_eq!(
+            parse_command("  launch My  Pack \t--server   mc.example.com "),
+            Ok(ControlCommand::Launch {
+                instance: "My  Pack".to_string(),
+                server: Some("mc.example.com".to_string()),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_focus() {
+        assert_eq!(
+            parse_command("focus launcher-logs"),
+            Ok(ControlCommand::Focus {
+                screen: StartScreen::LauncherLogs,
+                instance: None,
+            })
+        );
+        assert_eq!(
+            parse_command("focus logs atm9"),
+            Ok(ControlCommand::Focus {
+                screen: StartScreen::Logs,
+                instance: Some("atm9".to_string()),
+            })
+        );
+        assert_eq!(
+            parse_command("focus logs My  Pack"),
+            Ok(ControlCommand::Focus {
+                screen: StartScreen::Logs,
+                instance: Some("My  Pack".to_string()),
+            })
+        );
+        assert!(parse_command("focus").is_err());
+        assert!(parse_command("focus nowhere").is_err());
+        assert!(parse_command("reboot").is_err());
+    }
+}
@@ -0,0 +1,277 @@
+//! CurseForge's API, used for mods and packs hosted only there. Every
+//! request needs the API key from `curseforge_api_key` in config.toml.
+
+use crate::error::{PrismError, Result};
+use crate::net::loaders::Loader;
+use crate::net::modrinth::{PackUpdate, ProjectVersion, newer_versions};
+use serde::Deserialize;
+
+const API_BASE: &str = "https://api.curseforge.com/v1";
+/// CurseForge's id for Minecraft
+const GAME_ID: &str = "432";
+/// Class of mods, as opposed to modpacks, resource packs and worlds
+pub const CLASS_MODS: u64 = 6;
+pub const CLASS_MODPACKS: u64 = 4471;
+pub const CLASS_RESOURCE_PACKS: u64 = 12;
+pub const CLASS_SHADERS: u64 = 6552;
+/// `hashes[].algo` of a SHA-1
+const ALGO_SHA1: u8 = 1;
+
+#[derive(Deserialize)]
+struct Data<T> {
+    data: T,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Project {
+    pub id: u64,
+    pub name: String,
+    #[serde(default)]
+    pub summary: String,
+    #[serde(default)]
+    pub download_count: f64,
+    #[serde(default)]
+    pub class_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct File {
+    pub id: u64,
+    #[serde(default)]
+    pub mod_id: u64,
+    pub display_name: String,
+    pub file_name: String,
+    pub file_date: String,
+    /// Missing when the author only allows downloads from the website
+    #[serde(default)]
+    pub download_url: Option<String>,
+    #[serde(default)]
+    pub is_server_pack: bool,
+    #[serde(default)]
+    hashes: Vec<FileHash>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct FileHash {
+    value: String,
+    algo: u8,
+}
+
+impl File {
+    pub fn sha1(&self) -> Option<&str> {
+        self.hashes
+            .iter()
+            .find(|h| h.algo == ALGO_SHA1)
+            .map(|h| h.value.as_str())
+    }
+}
+
+/// CurseForge's `modLoaderType` number
+fn loader_type(loader: Loader) -> &'static str {
+    match loader {
+        Loader::Forge => "1",
+        Loader::Fabric => "4",
+        Loader::Quilt => "5",
+        Loader::NeoForge => "6",
+    }
+}
+
+async fn get<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    api_key: &str,
+    path: &str,
+    query: &[(&str, &str)],
+) -> Result<T> {
+    let request = client.get(format!("{}{}", API_BASE, path)).query(query);
+    send(request, api_key).await
+}
+
+async fn post<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    api_key: &str,
+    path: &str,
+    body: &serde_json::Value,
+) -> Result<T> {
+    let request = client.post(format!("{}{}", API_BASE, path)).json(body);
+    send(request, api_key).await
+}
+
+async fn send<T: serde::de::DeserializeOwned>(
+    request: reqwest::RequestBuilder,
+    api_key: &str,
+) -> Result<T> {
+    if api_key.is_empty() {
+        return Err(PrismError::Other(
+            "set curseforge_api_key in config.toml to use CurseForge".to_string(),
+        ));
+    }
+    let response: Data<T> = request
+        .header("x-api-key", api_key)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(response.data)
+}
+
+/// Mods matching `query` that have files for this Minecraft version and
+/// loader, most downloaded first
+pub async fn search_mods(
+    client: &reqwest::Client,
+    api_key: &str,
+    query: &str,
+    minecraft_version: &str,
+    loader: Loader,
+    limit: usize,
+) -> Result<Vec<Project>> {
+    let (limit, class) = (limit.to_string(), CLASS_MODS.to_string());
+    get(
+        client,
+        api_key,
+        "/mods/search",
+        &[
+            ("gameId", GAME_ID),
+            ("classId", &class),
+            ("searchFilter", query),
+            ("gameVersion", minecraft_version),
+            ("modLoaderType", loader_type(loader)),
+            ("sortField", "6"), // total downloads
+            ("sortOrder", "desc"),
+            ("pageSize", &limit),
+        ],
+    )
+    .await
+}
+
+/// The newest file of a mod for this Minecraft version and loader
+pub async fn latest_file(
+    client: &reqwest::Client,
+    api_key: &str,
+    mod_id: u64,
+    minecraft_version: &str,
+    loader: Loader,
+) -> Result<Option<File>> {
+    let files: Vec<File> = get(
+        client,
+        api_key,
+        &format!("/mods/{}/files", mod_id),
+        &[
+            ("gameVersion", minecraft_version),
+            ("modLoaderType", loader_type(loader)),
+        ],
+    )
+    .await?;
+    // ISO 8601 timestamps sort lexicographically
+    Ok(files
+        .into_iter()
+        .max_by(|a, b| a.file_date.cmp(&b.file_date)))
+}
+
+/// Modpacks matching `query`, most downloaded first
+pub async fn search_packs(
+    client: &reqwest::Client,
+    api_key: &str,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<Project>> {
+    let (limit, class) = (limit.to_string(), CLASS_MODPACKS.to_string());
+    get(
+        client,
+        api_key,
+        "/mods/search",
+        &[
+            ("gameId", GAME_ID),
+            ("classId", &class),
+            ("searchFilter", query),
+            ("sortField", "6"), // total downloads
+            ("sortOrder", "desc"),
+            ("pageSize", &limit),
+        ],
+    )
+    .await
+}
+
+/// The newest client file of a modpack
+pub async fn latest_pack_file(
+    client: &reqwest::Client,
+    api_key: &str,
+    pack_id: u64,
+) -> Result<Option<File>> {
+    let files: Vec<File> = get(
+        client,
+        api_key,
+        &format!("/mods/{}/files", pack_id),
+        &[("pageSize", "50")],
+    )
+    .await?;
+    Ok(files
+        .into_iter()
+        .filter(|f| !f.is_server_pack)
+        .max_by(|a, b| a.file_date.cmp(&b.file_date)))
+}
+
+/// Files by id, as a pack's manifest lists them
+pub async fn files(client: &reqwest::Client, api_key: &str, file_ids: &[u64]) -> Result<Vec<File>> {
+    let body = serde_json::json!({ "fileIds": file_ids });
+    post(client, api_key, "/mods/files", &body).await
+}
+
+/// Projects by id, for the class that decides which folder their files
+/// go in
+pub async fn projects(
+    client: &reqwest::Client,
+    api_key: &str,
+    mod_ids: &[u64],
+) -> Result<Vec<Project>> {
+    let body = serde_json::json!({ "modIds": mod_ids });
+    post(client, api_key, "/mods", &body).await
+}
+
+/// Check a CurseForge pack for files newer than the installed one, the way
+/// `modrinth::check_pack_update` does for Modrinth packs
+pub async fn check_pack_update(
+    client: &reqwest::Client,
+    api_key: &str,
+    project_id: &str,
+    installed_file_id: &str,
+) -> Result<Option<PackUpdate>> {
+    let files: Vec<File> = get(
+        client,
+        api_key,
+        &format!("/mods/{}/files", project_id),
+        &[("pageSize", "50")],
+    )
+    .await?;
+    let mut versions: Vec<ProjectVersion> = files.into_iter().map(pack_version).collect();
+    versions.sort_by(|a, b| b.date_published.cmp(&a.date_published));
+    Ok(newer_versions(versions, installed_file_id))
+}
+
+fn pack_version(file: File) -> ProjectVersion {
+    ProjectVersion {
+        id: file.id.to_string(),
+        version_number: file.display_name,
+        changelog: None,
+        date_published: file.file_date,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_without_download_url() {
+        let json = r#"{"data":[{"id":42,"displayName":"Example 1.2","fileName":"example-1.2.jar",
+            "fileDate":"2024-02-01T00:00:00Z","downloadUrl":null,
+            "hashes":[{"value":"abc","algo":2},{"value":"def","algo":1}]}]}"#;
+        let files: Data<Vec<File>> = serde_json::from_str(json).unwrap();
+        let file = &files.data[0];
+        assert_eq!(file.download_url, None);
+        assert_eq!(file.sha1(), Some("def"));
+        assert_eq!(pack_version(file.clone()).id, "42");
+    }
+}
@@ -0,0 +1,84 @@
+use crate::error::Result;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use tokio::net::UdpSocket;
+
+/// Group and port Minecraft clients announce opened LAN worlds on
+const LAN_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 2, 60);
+const LAN_PORT: u16 = 4445;
+
+/// A world opened to LAN by a client on the local network
+#[derive(Debug, Clone, PartialEq)]
+pub struct LanGame {
+    pub motd: String,
+    pub address: String,
+}
+
+/// Join the LAN announcement group. The address is shared so a running
+/// Minecraft client can listen on the same port.
+pub fn bind() -> Result<UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::UNSPECIFIED, LAN_PORT)).into())?;
+    socket.join_multicast_v4(&LAN_GROUP, &Ipv4Addr::UNSPECIFIED)?;
+    Ok(UdpSocket::from_std(socket.into())?)
+}
+
+/// Wait for the next valid announcement, skipping malformed packets
+pub async fn next_game(socket: &UdpSocket) -> Result<LanGame> {
+    let mut buf = [0u8; 1024];
+    loop {
+        let (len, from) = socket.recv_from(&mut buf).await?;
+        let text = String::from_utf8_lossy(&buf[..len]);
+        if let Some(game) = parse_announcement(&text, &from.ip().to_string()) {
+            return Ok(game);
+        }
+    }
+}
+
+/// Parse "[MOTD]name[/MOTD][AD]port[/AD]". The host is the sender's address.
+fn parse_announcement(text: &str, host: &str) -> Option<LanGame> {
+    let motd = between(text, "[MOTD]", "[/MOTD]")?;
+    let port: u16 = between(text, "[AD]", "[/AD]")?.trim().parse().ok()?;
+    Some(LanGame {
+        motd: motd.to_string(),
+        address: format!("{}:{}", host, port),
+    })
+}
+
+fn between<'a>(text: &'a str, open: &str, close: &str) -> Option<&'a str> {
+    let start = text.find(open)? + open.len();
+    let end = start + text[start..].find(close)?;
+    Some(&text[start..end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_announcement() {
+        let game = parse_announcement(
+            "[MOTD]Steve - New World[/MOTD][AD]41827[/AD]",
+            "192.168.1.20",
+        );
+        assert_eq!(
+            game,
+            Some(LanGame {
+                motd: "Steve - New World".to_string(),
+                address: "192.168.1.20:41827".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_announcement_rejects_malformed() {
+        assert_eq!(parse_announcement("[MOTD]World[/MOTD]", "10.0.0.2"), None);
+        assert_eq!(
+            parse_announcement("[MOTD]World[/MOTD][AD]abc[/AD]", "10.0.0.2"),
+            None
+        );
+        assert_eq!(parse_announcement("hello", "10.0.0.2"), None);
+    }
+}
@@ -0,0 +1,288 @@
+use crate::error::Result;
+use crate::net::cache;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+const FABRIC_META: &str = "https://meta.fabricmc.net/v2/versions/loader";
+const QUILT_META: &str = "https://meta.quiltmc.org/v3/versions/loader";
+const FORGE_METADATA: &str =
+    "https://files.minecraftforge.net/net/minecraftforge/forge/maven-metadata.json";
+const NEOFORGE_VERSIONS: &str =
+    "https://maven.neoforged.net/api/maven/versions/releases/net/neoforged/neoforge";
+const CACHE_MAX_AGE: Duration = Duration::from_secs(12 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Loader {
+    Fabric,
+    Forge,
+    NeoForge,
+    Quilt,
+}
+
+impl Loader {
+    /// Loaders a new instance can be created with, in the order offered
+    pub const ALL: [Loader; 4] = [
+        Loader::Fabric,
+        Loader::Quilt,
+        Loader::Forge,
+        Loader::NeoForge,
+    ];
+
+    /// The name stored on `Instance::mod_loader`
+    pub fn name(self) -> &'static str {
+        match self {
+            Loader::Fabric => "Fabric",
+            Loader::Forge => "Forge",
+            Loader::NeoForge => "NeoForge",
+            Loader::Quilt => "Quilt",
+        }
+    }
+
+    /// mmc-pack.json components (uid, version) that add this loader to a
+    /// `minecraft_version` instance
+    pub fn pack_components(
+        self,
+        minecraft_version: &str,
+        version: &str,
+    ) -> Vec<(&'static str, String)> {
+        let intermediary = ("net.fabricmc.intermediary", minecraft_version.to_string());
+        match self {
+            Loader::Fabric => vec![
+                intermediary,
+                ("net.fabricmc.fabric-loader", version.to_string()),
+            ],
+            Loader::Quilt => vec![
+                intermediary,
+                ("org.quiltmc.quilt-loader", version.to_string()),
+            ],
+            Loader::Forge => vec![("net.minecraftforge", version.to_string())],
+            Loader::NeoForge => vec![("net.neoforged", version.to_string())],
+        }
+    }
+
+    /// Parse the loader name stored on `Instance::mod_loader`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "Fabric" => Some(Loader::Fabric),
+            "Forge" => Some(Loader::Forge),
+            "NeoForge" => Some(Loader::NeoForge),
+            "Quilt" => Some(Loader::Quilt),
+            _ => None,
+        }
+    }
+
+    /// Lowercase name, as used in cache files and by Modrinth
+    pub fn slug(self) -> &'static str {
+        match self {
+            Loader::Fabric => "fabric",
+            Loader::Forge => "forge",
+            Loader::NeoForge => "neoforge",
+            Loader::Quilt => "quilt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoaderVersion {
+    pub version: String,
+    pub stable: bool,
+}
+
+/// Loader versions available for a Minecraft version, newest first
+pub type LoaderCatalog = Vec<LoaderVersion>;
+
+#[derive(Deserialize)]
+struct MetaEntry {
+    loader: MetaLoader,
+}
+
+#[derive(Deserialize)]
+struct MetaLoader {
+    version: String,
+    #[serde(default)]
+    stable: Option<bool>,
+}
+
+#[derive(Deserialize)]
+struct NeoForgeVersions {
+    versions: Vec<String>,
+}
+
+/// Fetch the loader versions for `minecraft_version`, cached on disk
+pub async fn loader_versions(
+    client: &reqwest::Client,
+    loader: Loader,
+    minecraft_version: &str,
+) -> Result<LoaderCatalog> {
+    let name = format!("loader_{}_{}", loader.slug(), minecraft_version);
+    cache::fetch_cached(&name, CACHE_MAX_AGE, async {
+        match loader {
+            Loader::Fabric | Loader::Quilt => {
+                let base = if loader == Loader::Fabric {
+                    FABRIC_META
+                } else {
+                    QUILT_META
+                };
+                let url = format!("{}/{}", base, minecraft_version);
+                let entries: Vec<MetaEntry> = get_json(client, &url).await?;
+                Ok(from_meta(entries))
+            }
+            Loader::Forge => {
+                let metadata: HashMap<String, Vec<String>> =
+                    get_json(client, FORGE_METADATA).await?;
+                Ok(forge_versions(&metadata, minecraft_version))
+            }
+            Loader::NeoForge => {
+                let list: NeoForgeVersions = get_json(client, NEOFORGE_VERSIONS).await?;
+                Ok(neoforge_versions(&list.versions, minecraft_version))
+            }
+        }
+    })
+    .await
+}
+
+async fn get_json<T: serde::de::DeserializeOwned>(
+    client: &reqwest::Client,
+    url: &str,
+) -> Result<T> {
+    let value = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(value)
+}
+
+/// The newest stable version, if `installed` is older than it.
+/// Versions missing from the catalog are not compared.
+pub fn newer_stable<'a>(
+    catalog: &'a [LoaderVersion],
+    installed: &str,
+) -> Option<&'a LoaderVersion> {
+    let installed_pos = catalog.iter().position(|v| v.version == installed)?;
+    let (latest_pos, latest) = catalog.iter().enumerate().find(|(_, v)| v.stable)?;
+    (latest_pos < installed_pos).then_some(latest)
+}
+
+/// Fabric and Quilt meta lists are already newest first. Quilt has no
+/// stable flag, so pre-release suffixes mark unstable versions.
+fn from_meta(entries: Vec<MetaEntry>) -> LoaderCatalog {
+    entries
+        .into_iter()
+        .map(|e| {
+            let stable = e.loader.stable.unwrap_or(!e.loader.version.contains('-'));
+            LoaderVersion {
+                version: e.loader.version,
+                stable,
+            }
+        })
+        .collect()
+}
+
+/// Forge metadata maps a Minecraft version to "mc-forge[-mc]" strings, oldest
+/// first. Prism stores only the forge part.
+fn forge_versions(
+    metadata: &HashMap<String, Vec<String>>,
+    minecraft_version: &str,
+) -> LoaderCatalog {
+    let Some(versions) = metadata.get(minecraft_version) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}-", minecraft_version);
+    let suffix = format!("-{}", minecraft_version);
+    versions
+        .iter()
+        .rev()
+        .map(|v| {
+            let v = v.strip_prefix(&prefix).unwrap_or(v);
+            let v = v.strip_suffix(&suffix).unwrap_or(v);
+            LoaderVersion {
+                version: v.to_string(),
+                stable: true,
+            }
+        })
+        .collect()
+}
+
+/// NeoForge versions encode the Minecraft version: 1.20.4 -> 20.4.x and
+/// 1.21 -> 21.0.x. The list is oldest first.
+fn neoforge_versions(versions: &[String], minecraft_version: &str) -> LoaderCatalog {
+    let Some(rest) = minecraft_version.strip_prefix("1.") else {
+        return Vec::new();
+    };
+    let prefix = if rest.contains('.') {
+        format!("{}.", rest)
+    } else {
+        format!("{}.0.", rest)
+    };
+    versions
+        .iter()
+        .rev()
+        .filter(|v| v.starts_with(&prefix))
+        .map(|v| LoaderVersion {
+            version: v.clone(),
+            stable: !v.contains("beta"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(version: &str, stable: bool) -> LoaderVersion {
+        LoaderVersion {
+            version: version.to_string(),
+            stable,
+        }
+    }
+
+    #[test]
+    fn test_newer_stable() {
+        let catalog = vec![
+            version("0.16.10", false),
+            version("0.16.9", true),
+            version("0.15.11", true),
+        ];
+        assert_eq!(newer_stable(&catalog, "0.15.11"), Some(&catalog[1]));
+        assert_eq!(newer_stable(&catalog, "0.16.9"), None);
+        assert_eq!(newer_stable(&catalog, "0.16.10"), None);
+        assert_eq!(newer_stable(&catalog, "0.1.0"), None);
+    }
+
+    #[test]
+    fn test_forge_versions_strip_minecraft_version() {
+        let mut metadata = HashMap::new();
+        metadata.insert(
+            "1.7.10".to_string(),
+            vec![
+                "1.7.10-10.13.4.1558-1.7.10".to_string(),
+                "1.7.10-10.13.4.1614-1.7.10".to_string(),
+            ],
+        );
+        let catalog = forge_versions(&metadata, "1.7.10");
+        assert_eq!(catalog[0].version, "10.13.4.1614");
+        assert_eq!(catalog[1].version, "10.13.4.1558");
+        assert!(forge_versions(&metadata, "1.20.1").is_empty());
+    }
+
+    #[test]
+    fn test_neoforge_versions_match_minecraft_version() {
+        let versions: Vec<String> = ["20.4.237", "20.6.119", "21.0.1-beta", "21.0.167"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let catalog = neoforge_versions(&versions, "1.21");
+        assert_eq!(
+            catalog,
+            vec![version("21.0.167", true), version("21.0.1-beta", false)]
+        );
+        assert_eq!(
+            neoforge_versions(&versions, "1.20.4"),
+            vec![version("20.4.237", true)]
+        );
+    }
+}
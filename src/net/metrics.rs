@@ -0,0 +1,245 @@
+use crate::error::Result;
+use std::fmt::Write;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use sysinfo::{Pid, ProcessRefreshKind, ProcessesToUpdate, System};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// An instance as last published by the TUI
+#[derive(Debug, Clone)]
+pub struct InstanceState {
+    pub id: String,
+    pub name: String,
+    /// Set while the game is running; the PID once its Java process is found
+    pub session: Option<(Instant, Option<Pid>)>,
+}
+
+/// Instance list shared between the TUI and the metrics server
+pub type SharedState = Arc<Mutex<Vec<InstanceState>>>;
+
+/// One instance's numbers at scrape time
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub id: String,
+    pub name: String,
+    pub running: bool,
+    /// Percent of one core since the previous scrape
+    pub cpu: f32,
+    pub rss_bytes: u64,
+    pub session_seconds: u64,
+}
+
+/// Serve `/metrics` (Prometheus text) and `/metrics.json` on `address`
+pub fn serve(address: &str, state: SharedState) -> Result<()> {
+    let listener = std::net::TcpListener::bind(address)?;
+    listener.set_nonblocking(true)?;
+    let listener = TcpListener::from_std(listener)?;
+    let system = Arc::new(Mutex::new(System::new()));
+
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(handle_request(
+                stream,
+                Arc::clone(&state),
+                Arc::clone(&system),
+            ));
+        }
+    });
+    Ok(())
+}
+
+/// Content type of an endpoint and how it formats the samples
+type Endpoint = (&'static str, fn(&[Sample]) -> String);
+
+async fn handle_request(
+    mut stream: TcpStream,
+    state: SharedState,
+    system: Arc<Mutex<System>>,
+) -> Result<()> {
+    // Only the request line matters; headers and bodies are ignored
+    let mut buf = [0u8; 1024];
+    let len = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..len]);
+    let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+    let render: Option<Endpoint> = match path {
+        "/metrics" => Some(("text/plain; version=0.0.4", prometheus)),
+        "/metrics.json" => Some(("application/json", json)),
+        _ => None,
+    };
+    let (status, content_type, body) = match render {
+        Some((content_type, render)) => {
+            // Refreshing processes reads /proc, which would hold up the runtime
+            let samples = tokio::task::spawn_blocking(move || sample(&state, &system))
+                .await
+                .unwrap_or_default();
+            ("200 OK", content_type, render(&samples))
+        }
+        None => ("404 Not Found", "text/plain", "Not found\n".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    Ok(())
+}
+
+/// Read CPU and memory for the running games
+fn sample(state: &SharedState, system: &Mutex<System>) -> Vec<Sample> {
+    let instances = state.lock().map(|s| s.clone()).unwrap_or_default();
+    let Ok(mut system) = system.lock() else {
+        return Vec::new();
+    };
+    let pids: Vec<Pid> = instances
+        .iter()
+        .filter_map(|i| i.session.and_then(|(_, pid)| pid))
+        .collect();
+    system.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&pids),
+        true,
+        ProcessRefreshKind::nothing().with_cpu().with_memory(),
+    );
+
+    instances
+        .into_iter()
+        .map(|instance| {
+            let process = instance
+                .session
+                .and_then(|(_, pid)| pid)
+                .and_then(|pid| system.process(pid));
+            Sample {
+                running: instance.session.is_some(),
+                cpu: process.map_or(0.0, |p| p.cpu_usage()),
+                rss_bytes: process.map_or(0, |p| p.memory()),
+                session_seconds: instance
+                    .session
+                    .map_or(0, |(started, _)| started.elapsed().as_secs()),
+                id: instance.id,
+                name: instance.name,
+            }
+        })
+        .collect()
+}
+
+/// Metric name, help text and value; every metric is a gauge
+type Gauge = (&'static str, &'static str, fn(&Sample) -> String);
+
+/// Prometheus text exposition format
+pub fn prometheus(samples: &[Sample]) -> String {
+    let metrics: [Gauge; 4] = [
+        (
+            "prism_instance_running",
+            "Whether the instance is running",
+            |s| u8::from(s.running).to_string(),
+        ),
+        (
+            "prism_instance_cpu_percent",
+            "CPU usage of the game in percent of one core",
+            |s| format!("{:.1}", s.cpu),
+        ),
+        (
+            "prism_instance_resident_memory_bytes",
+            "Resident memory of the game process",
+            |s| s.rss_bytes.to_string(),
+        ),
+        (
+            "prism_instance_session_seconds",
+            "Time since the running game was launched",
+            |s| s.session_seconds.to_string(),
+        ),
+    ];
+
+    let mut out = String::new();
+    for (name, help, value) in metrics {
+        let _ = writeln!(out, "# HELP {} {}", name, help);
+        let _ = writeln!(out, "# TYPE {} gauge", name);
+        for s in samples {
+            let _ = writeln!(
+                out,
+                "{}{{instance=\"{}\",name=\"{}\"}} {}",
+                name,
+                escape_label(&s.id),
+                escape_label(&s.name),
+                value(s)
+            );
+        }
+    }
+    out
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+pub fn json(samples: &[Sample]) -> String {
+    let instances: Vec<_> = samples
+        .iter()
+        .map(|s| {
+            serde_json::json!({
+                "id": s.id,
+                "name": s.name,
+                "running": s.running,
+                "cpu_percent": s.cpu,
+                "rss_bytes": s.rss_bytes,
+                "session_seconds": s.session_seconds,
+            })
+        })
+        .collect();
+    serde_json::json!({ "instances": instances }).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn samples() -> Vec<Sample> {
+        vec![
+            Sample {
+                id: "atm9".to_string(),
+                name: "All the \"Mods\" 9".to_string(),
+                running: true,
+                cpu: 112.25,
+                rss_bytes: 4_294_967_296,
+                session_seconds: 3600,
+            },
+            Sample {
+                id: "vanilla".to_string(),
+                name: "Vanilla".to_string(),
+                running: false,
+                cpu: 0.0,
+                rss_bytes: 0,
+                session_seconds: 0,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_prometheus_output() {
+        let text = prometheus(&samples());
+        assert!(text.contains("# TYPE prism_instance_running gauge\n"));
+        assert!(text.contains(
+            "prism_instance_running{instance=\"atm9\",name=\"All the \\\"Mods\\\" 9\"} 1\n"
+        ));
+        assert!(text.contains("prism_instance_running{instance=\"vanilla\",name=\"Vanilla\"} 0\n"));
+        assert!(text.contains(
+            "prism_instance_cpu_percent{instance=\"atm9\",name=\"All the \\\"Mods\\\" 9\"} 112.2\n"
+        ));
+        assert!(text.contains("} 4294967296\n"));
+    }
+
+    #[test]
+    fn test_json_output() {
+        let value: serde_json::Value = serde_json::from_str(&json(&samples())).unwrap();
+        assert_eq!(value["instances"][0]["id"], "atm9");
+        assert_eq!(value["instances"][0]["session_seconds"], 3600);
+        assert_eq!(value["instances"][1]["running"], false);
+    }
+}
@@ -0,0 +1,42 @@
+pub mod blocklist;
+pub mod cache;
+pub mod control;
+pub mod curseforge;
+pub mod lan;
+pub mod loaders;
+pub mod metrics;
+pub mod mod_source;
+pub mod modpack;
+pub mod modrinth;
+pub mod mojang;
+pub mod ping;
+
+use crate::error::Result;
+use std::time::Duration;
+
+const USER_AGENT: &str = concat!(
+    "OneNoted/prism-tui/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/OneNoted/prism-tui)"
+);
+
+/// Longest an API call may take as a whole, and longest any request may go
+/// without connecting or receiving data
+const API_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Longest a file download may take as a whole; a stalled one still fails
+/// after `API_TIMEOUT` without data
+pub const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+
+/// Build the shared HTTP client. Modrinth requires an identifying user agent.
+/// Downloads set `DOWNLOAD_TIMEOUT` on their request in place of the total
+/// timeout meant for API calls.
+pub fn build_client() -> Result<reqwest::Client> {
+    let client = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(API_TIMEOUT)
+        .connect_timeout(API_TIMEOUT)
+        .read_timeout(API_TIMEOUT)
+        .build()?;
+    Ok(client)
+}
@@ -0,0 +1,515 @@
+//! Finding mods and modpacks on Modrinth and CurseForge and installing mods
+//! into an instance. Both sites go through the same search and download
+//! steps, so results can be listed together.
+
+use crate::data::atomic::write_atomic;
+use crate::data::storage::ensure_free_space;
+use crate::data::{ModFile, list_mods};
+use crate::error::{PrismError, Result};
+use crate::net::DOWNLOAD_TIMEOUT;
+use crate::net::curseforge;
+use crate::net::loaders::Loader;
+use crate::net::modrinth;
+use sha1::{Digest, Sha1};
+use std::path::{Path, PathBuf};
+
+/// Most results a search lists, one per picker number
+pub const SEARCH_LIMIT: usize = 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModSource {
+    Modrinth,
+    CurseForge,
+}
+
+impl ModSource {
+    pub fn name(self) -> &'static str {
+        match self {
+            ModSource::Modrinth => "Modrinth",
+            ModSource::CurseForge => "CurseForge",
+        }
+    }
+}
+
+/// A mod or modpack found by a search
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModHit {
+    pub source: ModSource,
+    /// Project id on its site
+    pub id: String,
+    pub name: String,
+    pub summary: String,
+    pub downloads: u64,
+}
+
+impl ModHit {
+    /// e.g. "Sodium (Modrinth, 12.3M)"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} ({}, {})",
+            self.name,
+            self.source.name(),
+            format_count(self.downloads)
+        )
+    }
+}
+
+/// Downloads in the short form the sites use, e.g. 12.3M or 450k
+fn format_count(count: u64) -> String {
+    match count {
+        0..1_000 => count.to_string(),
+        1_000..1_000_000 => format!("{}k", count / 1_000),
+        _ => format!("{:.1}M", count as f64 / 1_000_000.0),
+    }
+}
+
+/// A file to put in the mods folder
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct ModDownload {
+    pub(super) file_name: String,
+    pub(super) url: String,
+    pub(super) sha1: Option<String>,
+}
+
+/// Search both sites for mods for this Minecraft version and loader, most
+/// downloaded first. CurseForge is skipped without an API key; a site that
+/// fails is left out unless both do.
+pub async fn search_mods(
+    client: &reqwest::Client,
+    curseforge_key: Option<&str>,
+    query: &str,
+    minecraft_version: &str,
+    loader: Loader,
+) -> Result<Vec<ModHit>> {
+    let modrinth = modrinth_hits(client, query, minecraft_version, loader);
+    let curseforge = async {
+        match curseforge_key {
+            Some(key) => Some(curseforge_hits(client, key, query, minecraft_version, loader).await),
+            None => None,
+        }
+    };
+    let (modrinth, curseforge) = futures::join!(modrinth, curseforge);
+    merge_hits(modrinth, curseforge)
+}
+
+/// Search both sites for modpacks, most downloaded first, the way
+/// `search_mods` does for mods
+pub async fn search_packs(
+    client: &reqwest::Client,
+    curseforge_key: Option<&str>,
+    query: &str,
+) -> Result<Vec<ModHit>> {
+    let modrinth = async {
+        let hits = modrinth::search_packs(client, query, SEARCH_LIMIT).await?;
+        Ok(hits.into_iter().map(modrinth_hit).collect())
+    };
+    let curseforge = async {
+        let key = curseforge_key?;
+        let projects = curseforge::search_packs(client, key, query, SEARCH_LIMIT).await;
+        Some(projects.map(|projects| projects.into_iter().map(curseforge_hit).collect()))
+    };
+    let (modrinth, curseforge) = futures::join!(modrinth, curseforge);
+    merge_hits(modrinth, curseforge)
+}
+
+/// One list from both sites' results, leaving out a site that failed unless
+/// both did
+fn merge_hits(
+    modrinth: Result<Vec<ModHit>>,
+    curseforge: Option<Result<Vec<ModHit>>>,
+) -> Result<Vec<ModHit>> {
+    let mut hits = match (modrinth, curseforge) {
+        (Err(e), None | Some(Err(_))) => return Err(e),
+        (modrinth, curseforge) => {
+            if let Err(e) = &modrinth {
+                tracing::warn!("Modrinth search failed: {}", e);
+            }
+            if let Some(Err(e)) = &curseforge {
+                tracing::warn!("CurseForge search failed: {}", e);
+            }
+            let mut hits = modrinth.unwrap_or_default();
+            hits.extend(curseforge.and_then(Result::ok).unwrap_or_default());
+            hits
+        }
+    };
+    hits.sort_by_key(|hit| std::cmp::Reverse(hit.downloads));
+    hits.truncate(SEARCH_LIMIT);
+    Ok(hits)
+}
+
+async fn modrinth_hits(
+    client: &reqwest::Client,
+    query: &str,
+    minecraft_version: &str,
+    loader: Loader,
+) -> Result<Vec<ModHit>> {
+    let hits =
+        modrinth::search_mods(client, query, minecraft_version, loader, SEARCH_LIMIT).await?;
+    Ok(hits.into_iter().map(modrinth_hit).collect())
+}
+
+fn modrinth_hit(hit: modrinth::SearchHit) -> ModHit {
+    ModHit {
+        source: ModSource::Modrinth,
+        id: hit.project_id,
+        name: hit.title,
+        summary: hit.description,
+        downloads: hit.downloads,
+    }
+}
+
+async fn curseforge_hits(
+    client: &reqwest::Client,
+    api_key: &str,
+    query: &str,
+    minecraft_version: &str,
+    loader: Loader,
+) -> Result<Vec<ModHit>> {
+    let projects = curseforge::search_mods(
+        client,
+        api_key,
+        query,
+        minecraft_version,
+        loader,
+        SEARCH_LIMIT,
+    )
+    .await?;
+    Ok(projects.into_iter().map(curseforge_hit).collect())
+}
+
+fn curseforge_hit(project: curseforge::Project) -> ModHit {
+    ModHit {
+        source: ModSource::CurseForge,
+        id: project.id.to_string(),
+        name: project.name,
+        summary: project.summary,
+        downloads: project.download_count as u64,
+    }
+}
+
+/// Download the newest file of `hit` for this Minecraft version and loader
+/// into `mods_dir`. Returns the file name.
+pub async fn install_mod(
+    client: &reqwest::Client,
+    curseforge_key: Option<&str>,
+    hit: &ModHit,
+    minecraft_version: &str,
+    loader: Loader,
+    mods_dir: &Path,
+) -> Result<String> {
+    let download = find_download(client, curseforge_key, hit, minecraft_version, loader).await?;
+    let target = mods_dir.join(&download.file_name);
+    let disabled = mods_dir.join(format!("{}.disabled", download.file_name));
+    if target.exists() || disabled.exists() {
+        return Err(PrismError::Other(format!(
+            "{} is already in the mods folder",
+            download.file_name
+        )));
+    }
+
+    let bytes = fetch_checked(client, &download).await?;
+    std::fs::create_dir_all(mods_dir)?;
+    ensure_free_space(bytes.len() as u64, mods_dir)?;
+    write_atomic(&target, &bytes)?;
+    Ok(download.file_name)
+}
+
+/// Download a file, refusing it if it doesn't match the site's SHA-1. Mod
+/// installs, mod updates and modpacks all come through here.
+pub(super) async fn fetch_checked(
+    client: &reqwest::Client,
+    download: &ModDownload,
+) -> Result<Vec<u8>> {
+    let bytes = client
+        .get(&download.url)
+        .timeout(DOWNLOAD_TIMEOUT)
+        .send()
+        .await?
+        .error_for_status()?
+        .bytes()
+        .await?;
+    if let Some(expected) = &download.sha1
+        && !sha1_hex(&bytes).eq_ignore_ascii_case(expected)
+    {
+        return Err(PrismError::Other(format!(
+            "{} did not match its checksum",
+            download.file_name
+        )));
+    }
+    Ok(bytes.to_vec())
+}
+
+fn sha1_hex(bytes: &[u8]) -> String {
+    Sha1::digest(bytes)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// A newer file on Modrinth for an installed mod
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModUpdate {
+    /// The installed file it replaces
+    pub installed: ModFile,
+    pub version: String,
+    download: ModDownload,
+}
+
+/// Look up the mods in `mods_dir` on Modrinth by their SHA-1 and list those
+/// with a newer file for this Minecraft version and loader. Mods Modrinth
+/// doesn't host are skipped.
+pub async fn check_mod_updates(
+    client: &reqwest::Client,
+    mods_dir: &Path,
+    minecraft_version: &str,
+    loader: Loader,
+) -> Result<Vec<ModUpdate>> {
+    let dir = mods_dir.to_path_buf();
+    let hashed = tokio::task::spawn_blocking(move || hash_mods(&dir))
+        .await
+        .map_err(|e| PrismError::Other(e.to_string()))??;
+    if hashed.is_empty() {
+        return Ok(Vec::new());
+    }
+    let hashes: Vec<String> = hashed.iter().map(|(_, hash)| hash.clone()).collect();
+    let mut latest =
+        modrinth::latest_versions_by_hash(client, &hashes, minecraft_version, loader).await?;
+    Ok(hashed
+        .into_iter()
+        .filter_map(|(installed, hash)| {
+            let version = latest.remove(&hash)?;
+            newer_file(installed, &hash, &version)
+        })
+        .collect())
+}
+
+/// The Modrinth page of the mod file at `path`, when Modrinth hosts it
+pub async fn mod_page(client: &reqwest::Client, path: &Path) -> Result<Option<String>> {
+    let bytes = tokio::fs::read(path).await?;
+    let project = modrinth::project_for_hash(client, &sha1_hex(&bytes)).await?;
+    Ok(project.map(|id| format!("https://modrinth.com/mod/{}", id)))
+}
+
+/// The installed mods with the SHA-1 of each file
+fn hash_mods(mods_dir: &Path) -> Result<Vec<(ModFile, String)>> {
+    list_mods(mods_dir)
+        .into_iter()
+        .map(|file| {
+            let bytes = std::fs::read(mods_dir.join(file.file_name()))?;
+            Ok((file, sha1_hex(&bytes)))
+        })
+        .collect()
+}
+
+/// An update when the version's file isn't the installed one
+fn newer_file(
+    installed: ModFile,
+    installed_hash: &str,
+    version: &modrinth::VersionFiles,
+) -> Option<ModUpdate> {
+    let file = version.primary_file()?;
+    if file
+        .hashes
+        .sha1
+        .as_deref()
+        .is_some_and(|sha1| sha1.eq_ignore_ascii_case(installed_hash))
+    {
+        return None;
+    }
+    // The name comes from the site; keep it inside the mods folder
+    if Path::new(&file.filename).file_name() != Some(file.filename.as_ref()) {
+        return None;
+    }
+    Some(ModUpdate {
+        installed,
+        version: version.version_number.clone(),
+        download: ModDownload {
+            file_name: file.filename.clone(),
+            url: file.url.clone(),
+            sha1: file.hashes.sha1.clone(),
+        },
+    })
+}
+
+/// Swap an installed mod for its newer file. The old file stays next to it
+/// as `<name>.bak`, which no loader reads, and a disabled mod stays
+/// disabled. Either both files end up in place or the folder is left as it
+/// was. Returns the new file name.
+pub async fn apply_mod_update(
+    client: &reqwest::Client,
+    mods_dir: &Path,
+    update: &ModUpdate,
+) -> Result<String> {
+    let bytes = fetch_checked(client, &update.download).await?;
+    let new_file = ModFile {
+        name: update.download.file_name.clone(),
+        enabled: update.installed.enabled,
+    };
+    let old = mods_dir.join(update.installed.file_name());
+    let target = mods_dir.join(new_file.file_name());
+    if target != old && target.exists() {
+        return Err(PrismError::Other(format!(
+            "{} is already in the mods folder",
+            new_file.file_name()
+        )));
+    }
+    ensure_free_space(bytes.len() as u64, mods_dir)?;
+    let backup = free_backup_path(&old);
+    if target == old {
+        // Same name, new contents: move the old file aside first
+        std::fs::rename(&old, &backup)?;
+        if let Err(e) = write_atomic(&target, &bytes) {
+            let _ = std::fs::rename(&backup, &old);
+            return Err(e.into());
+        }
+    } else {
+        write_atomic(&target, &bytes)?;
+        if let Err(e) = std::fs::rename(&old, &backup) {
+            let _ = std::fs::remove_file(&target);
+            return Err(e.into());
+        }
+    }
+    Ok(new_file.name)
+}
+
+/// `<file>.bak`, or `<file>.2.bak` and so on when earlier updates left
+/// backups already
+fn free_backup_path(file: &Path) -> PathBuf {
+    let name = file.file_name().unwrap_or_default().to_string_lossy();
+    let mut backup = file.with_file_name(format!("{}.bak", name));
+    let mut n = 2;
+    while backup.exists() {
+        backup = file.with_file_name(format!("{}.{}.bak", name, n));
+        n += 1;
+    }
+    backup
+}
+
+async fn find_download(
+    client: &reqwest::Client,
+    curseforge_key: Option<&str>,
+    hit: &ModHit,
+    minecraft_version: &str,
+    loader: Loader,
+) -> Result<ModDownload> {
+    let no_file = || {
+        PrismError::Other(format!(
+            "{} has no file for {} {}",
+            hit.name,
+            loader.name(),
+            minecraft_version
+        ))
+    };
+    let download = match hit.source {
+        ModSource::Modrinth => {
+            let version = modrinth::latest_mod_version(client, &hit.id, minecraft_version, loader)
+                .await?
+                .ok_or_else(no_file)?;
+            let file = version.primary_file().ok_or_else(no_file)?;
+            ModDownload {
+                file_name: file.filename.clone(),
+                url: file.url.clone(),
+                sha1: file.hashes.sha1.clone(),
+            }
+        }
+        ModSource::CurseForge => {
+            let id = hit
+                .id
+                .parse()
+                .map_err(|_| PrismError::Other(format!("bad CurseForge id {}", hit.id)))?;
+            let file = curseforge::latest_file(
+                client,
+                curseforge_key.unwrap_or_default(),
+                id,
+                minecraft_version,
+                loader,
+            )
+            .await?
+            .ok_or_else(no_file)?;
+            let sha1 = file.sha1().map(str::to_string);
+            ModDownload {
+                url: file.download_url.ok_or_else(|| {
+                    PrismError::Other(format!(
+                        "{} can only be downloaded from curseforge.com",
+                        hit.name
+                    ))
+                })?,
+                file_name: file.file_name,
+                sha1,
+            }
+        }
+    };
+    // The name comes from the site; keep it inside the mods folder
+    if Path::new(&download.file_name).file_name() != Some(download.file_name.as_ref()) {
+        return Err(PrismError::Other(format!(
+            "refusing to install {:?}",
+            download.file_name
+        )));
+    }
+    Ok(download)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_summary_shortens_downloads() {
+        let hit = |downloads| ModHit {
+            source: ModSource::CurseForge,
+            id: "1".to_string(),
+            name: "JEI".to_string(),
+            summary: String::new(),
+            downloads,
+        };
+        assert_eq!(hit(999).summary(), "JEI (CurseForge, 999)");
+        assert_eq!(hit(450_123).summary(), "JEI (CurseForge, 450k)");
+        assert_eq!(hit(312_400_000).summary(), "JEI (CurseForge, 312.4M)");
+    }
+
+    #[test]
+    fn test_newer_file_skips_the_installed_one() {
+        let version = |filename: &str, sha1: &str| modrinth::VersionFiles {
+            id: "OihdIimA".to_string(),
+            version_number: "mc1.20.1-0.5.11".to_string(),
+            date_published: String::new(),
+            files: vec![modrinth::VersionFile {
+                url: format!("https://cdn.modrinth.com/{}", filename),
+                filename: filename.to_string(),
+                primary: true,
+                hashes: modrinth::FileHashes {
+                    sha1: Some(sha1.to_string()),
+                },
+            }],
+        };
+        let installed = ModFile {
+            name: "sodium-fabric-0.5.8.jar".to_string(),
+            enabled: false,
+        };
+
+        let same = version("sodium-fabric-0.5.8.jar", "ABC123");
+        assert_eq!(newer_file(installed.clone(), "abc123", &same), None);
+        let escaping = version("../sodium-fabric-0.5.11.jar", "def456");
+        assert_eq!(newer_file(installed.clone(), "abc123", &escaping), None);
+
+        let newer = version("sodium-fabric-0.5.11.jar", "def456");
+        let update = newer_file(installed.clone(), "abc123", &newer).unwrap();
+        assert_eq!(update.installed, installed);
+        assert_eq!(update.version, "mc1.20.1-0.5.11");
+        assert_eq!(update.download.file_name, "sodium-fabric-0.5.11.jar");
+    }
+
+    #[test]
+    fn test_backups_never_replace_an_earlier_one() {
+        let dir = crate::data::test_dir("mod-bak");
+        std::fs::create_dir_all(&dir).unwrap();
+        let jar = dir.join("sodium.jar");
+        let first = free_backup_path(&jar);
+        std::fs::write(&first, "old").unwrap();
+        let second = free_backup_path(&jar);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(first, dir.join("sodium.jar.bak"));
+        assert_eq!(second, dir.join("sodium.jar.2.bak"));
+    }
+}
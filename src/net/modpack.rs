@@ -0,0 +1,535 @@
+//! Modpacks from Modrinth (.mrpack) and CurseForge (a zip with
+//! manifest.json) installed as new instances. Both archives are read into
+//! the same plan: a Minecraft version, a loader, files to download and
+//! folders to copy over `.minecraft`. The plan is then carried out with the
+//! mod installer's checked downloads.
+
+use crate::data::atomic::write_atomic;
+use crate::data::storage::ensure_free_space;
+use crate::data::{ManagedPack, PackPlatform, create_instance, set_managed_pack};
+use crate::error::{PrismError, Result};
+use crate::net::curseforge;
+use crate::net::loaders::Loader;
+use crate::net::mod_source::{ModDownload, ModHit, ModSource, fetch_checked};
+use crate::net::modrinth;
+use futures::StreamExt;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use std::path::{Component, Path, PathBuf};
+use zip::ZipArchive;
+
+/// Files downloaded at once
+const PARALLEL_DOWNLOADS: usize = 8;
+
+/// A pack installed by `install_pack`
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackInstall {
+    /// Folder name of the new instance
+    pub id: String,
+    /// Files the site only hands out on its website, left for the player
+    pub manual: Vec<String>,
+}
+
+/// What an archive asks for, whichever site it came from
+#[derive(Debug, Default, PartialEq)]
+struct PackPlan {
+    minecraft_version: String,
+    loader: Option<(Loader, String)>,
+    /// Files for `.minecraft`, by their path inside it
+    files: Vec<(PathBuf, ModDownload)>,
+    manual: Vec<String>,
+    /// Folders in the archive copied over `.minecraft`, later ones winning
+    overrides: Vec<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MrpackIndex {
+    files: Vec<MrpackFile>,
+    dependencies: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct MrpackFile {
+    path: String,
+    #[serde(default)]
+    hashes: modrinth::FileHashes,
+    #[serde(default)]
+    env: Option<MrpackEnv>,
+    downloads: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct MrpackEnv {
+    client: String,
+}
+
+#[derive(Deserialize)]
+struct CurseManifest {
+    minecraft: CurseMinecraft,
+    #[serde(default)]
+    files: Vec<CurseFileRef>,
+    #[serde(default)]
+    overrides: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CurseMinecraft {
+    version: String,
+    #[serde(default)]
+    mod_loaders: Vec<CurseLoader>,
+}
+
+#[derive(Deserialize)]
+struct CurseLoader {
+    id: String,
+    #[serde(default)]
+    primary: bool,
+}
+
+#[derive(Deserialize)]
+struct CurseFileRef {
+    #[serde(rename = "projectID")]
+    project_id: u64,
+    #[serde(rename = "fileID")]
+    file_id: u64,
+    #[serde(default = "required_default")]
+    required: bool,
+}
+
+fn required_default() -> bool {
+    true
+}
+
+/// Install the newest version of the modpack `hit` as a new instance in
+/// `instances_dir`, named after the pack. A failed install leaves no
+/// instance behind.
+pub async fn install_pack(
+    client: &reqwest::Client,
+    curseforge_key: Option<&str>,
+    hit: &ModHit,
+    instances_dir: &Path,
+) -> Result<PackInstall> {
+    let key = curseforge_key.unwrap_or_default();
+    let (download, version_id, version_name) = find_pack_file(client, key, hit).await?;
+    let archive = fetch_checked(client, &download).await?;
+    let plan = match hit.source {
+        ModSource::Modrinth => read_mrpack(&archive)?,
+        ModSource::CurseForge => {
+            let (mut plan, refs) = read_curseforge_manifest(&archive)?;
+            resolve_curseforge_files(client, key, &mut plan, &refs).await?;
+            plan
+        }
+    };
+
+    let components = plan
+        .loader
+        .as_ref()
+        .map(|(loader, version)| loader.pack_components(&plan.minecraft_version, version))
+        .unwrap_or_default();
+    let id = create_instance(
+        instances_dir,
+        &hit.name,
+        &plan.minecraft_version,
+        &components,
+    )?;
+    let target = instances_dir.join(&id);
+    let pack = ManagedPack {
+        platform: match hit.source {
+            ModSource::Modrinth => PackPlatform::Modrinth,
+            ModSource::CurseForge => PackPlatform::CurseForge,
+        },
+        id: hit.id.clone(),
+        name: hit.name.clone(),
+        version_id,
+        version_name,
+    };
+    // Removed again on failure, so a retry starts clean
+    if let Err(e) = fill_instance(client, &target, &plan, &archive, &pack).await {
+        let _ = std::fs::remove_dir_all(&target);
+        return Err(e);
+    }
+    Ok(PackInstall {
+        id,
+        manual: plan.manual,
+    })
+}
+
+/// The archive of the pack's newest version, with that version's id and name
+async fn find_pack_file(
+    client: &reqwest::Client,
+    curseforge_key: &str,
+    hit: &ModHit,
+) -> Result<(ModDownload, String, String)> {
+    let no_file = || PrismError::Other(format!("{} has no files to install", hit.name));
+    match hit.source {
+        ModSource::Modrinth => {
+            let version = modrinth::latest_pack_version(client, &hit.id)
+                .await?
+                .ok_or_else(no_file)?;
+            let file = version.primary_file().ok_or_else(no_file)?;
+            let download = ModDownload {
+                file_name: file.filename.clone(),
+                url: file.url.clone(),
+                sha1: file.hashes.sha1.clone(),
+            };
+            Ok((download, version.id, version.version_number))
+        }
+        ModSource::CurseForge => {
+            let id = hit
+                .id
+                .parse()
+                .map_err(|_| PrismError::Other(format!("bad CurseForge id {}", hit.id)))?;
+            let file = curseforge::latest_pack_file(client, curseforge_key, id)
+                .await?
+                .ok_or_else(no_file)?;
+            let download = ModDownload {
+                url: file.download_url.clone().ok_or_else(|| {
+                    PrismError::Other(format!(
+                        "{} can only be downloaded from curseforge.com",
+                        hit.name
+                    ))
+                })?,
+                sha1: file.sha1().map(str::to_string),
+                file_name: file.file_name,
+            };
+            Ok((download, file.id.to_string(), file.display_name))
+        }
+    }
+}
+
+/// Download the plan's files into the new instance, copy the archive's
+/// override folders over them and record where the pack came from
+async fn fill_instance(
+    client: &reqwest::Client,
+    target: &Path,
+    plan: &PackPlan,
+    archive: &[u8],
+    pack: &ManagedPack,
+) -> Result<()> {
+    let game_dir = target.join(".minecraft");
+    // Owned items and client, so the task running this stays `Send`
+    let mut downloads = futures::stream::iter(plan.files.clone())
+        .map(|(path, download)| {
+            let client = client.clone();
+            async move {
+                fetch_checked(&client, &download)
+                    .await
+                    .map(|bytes| (path, bytes))
+            }
+        })
+        .buffer_unordered(PARALLEL_DOWNLOADS);
+    while let Some(downloaded) = downloads.next().await {
+        let (path, bytes) = downloaded?;
+        let file = game_dir.join(path);
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        ensure_free_space(bytes.len() as u64, &game_dir)?;
+        write_atomic(&file, &bytes)?;
+    }
+    copy_overrides(archive, &plan.overrides, &game_dir)?;
+    set_managed_pack(target, pack)
+}
+
+/// Read a Modrinth pack's modrinth.index.json. Files the client doesn't use
+/// are left out.
+fn read_mrpack(archive: &[u8]) -> Result<PackPlan> {
+    let index: MrpackIndex = serde_json::from_slice(&read_entry(archive, "modrinth.index.json")?)?;
+    let dependencies = index.dependencies;
+    let minecraft_version = dependencies
+        .get("minecraft")
+        .cloned()
+        .ok_or_else(|| PrismError::Other("the pack names no Minecraft version".to_string()))?;
+    let loader = [
+        ("fabric-loader", Loader::Fabric),
+        ("quilt-loader", Loader::Quilt),
+        ("forge", Loader::Forge),
+        ("neoforge", Loader::NeoForge),
+    ]
+    .into_iter()
+    .find_map(|(key, loader)| Some((loader, dependencies.get(key)?.clone())));
+
+    let mut files = Vec::new();
+    for file in index.files {
+        if file.env.is_some_and(|env| env.client == "unsupported") {
+            continue;
+        }
+        let path = relative_path(&file.path)?;
+        let url = file.downloads.into_iter().next().ok_or_else(|| {
+            PrismError::Other(format!("the pack gives no download for {}", file.path))
+        })?;
+        let download = ModDownload {
+            file_name: file.path,
+            url,
+            sha1: file.hashes.sha1,
+        };
+        files.push((path, download));
+    }
+    Ok(PackPlan {
+        minecraft_version,
+        loader,
+        files,
+        manual: Vec::new(),
+        overrides: vec!["overrides".to_string(), "client-overrides".to_string()],
+    })
+}
+
+/// Read a CurseForge pack's manifest.json. Its files are only ids, looked
+/// up by `resolve_curseforge_files`.
+fn read_curseforge_manifest(archive: &[u8]) -> Result<(PackPlan, Vec<CurseFileRef>)> {
+    let manifest: CurseManifest = serde_json::from_slice(&read_entry(archive, "manifest.json")?)?;
+    let loaders = &manifest.minecraft.mod_loaders;
+    let loader = loaders
+        .iter()
+        .find(|l| l.primary)
+        .or_else(|| loaders.first())
+        .map(|l| {
+            let (name, version) = l.id.split_once('-').unwrap_or((&l.id, ""));
+            let loader = Loader::from_name(match name {
+                "forge" => "Forge",
+                "neoforge" => "NeoForge",
+                "fabric" => "Fabric",
+                "quilt" => "Quilt",
+                _ => "",
+            })
+            .ok_or_else(|| PrismError::Other(format!("unknown mod loader {}", l.id)))?;
+            Ok::<_, PrismError>((loader, version.to_string()))
+        })
+        .transpose()?;
+    let plan = PackPlan {
+        minecraft_version: manifest.minecraft.version,
+        loader,
+        overrides: vec![
+            manifest
+                .overrides
+                .unwrap_or_else(|| "overrides".to_string()),
+        ],
+        ..PackPlan::default()
+    };
+    let refs = manifest.files.into_iter().filter(|f| f.required).collect();
+    Ok((plan, refs))
+}
+
+/// Look up the files a CurseForge manifest lists and add them to `plan`,
+/// each in the folder its project's class belongs in
+async fn resolve_curseforge_files(
+    client: &reqwest::Client,
+    api_key: &str,
+    plan: &mut PackPlan,
+    refs: &[CurseFileRef],
+) -> Result<()> {
+    if refs.is_empty() {
+        return Ok(());
+    }
+    let file_ids: Vec<u64> = refs.iter().map(|f| f.file_id).collect();
+    let project_ids: Vec<u64> = refs.iter().map(|f| f.project_id).collect();
+    let (files, projects) = futures::join!(
+        curseforge::files(client, api_key, &file_ids),
+        curseforge::projects(client, api_key, &project_ids)
+    );
+    let classes = projects?
+        .into_iter()
+        .filter_map(|p| Some((p.id, p.class_id?)))
+        .collect();
+    add_curseforge_files(plan, files?, &classes)
+}
+
+/// Add `files` to `plan` by the class of their project, or to its manual
+/// list when CurseForge gives no download link
+fn add_curseforge_files(
+    plan: &mut PackPlan,
+    files: Vec<curseforge::File>,
+    classes: &HashMap<u64, u64>,
+) -> Result<()> {
+    for file in files {
+        let folder = match classes.get(&file.mod_id).copied() {
+            Some(curseforge::CLASS_RESOURCE_PACKS) => "resourcepacks",
+            Some(curseforge::CLASS_SHADERS) => "shaderpacks",
+            _ => "mods",
+        };
+        // The name comes from the site; keep it inside the folder
+        if Path::new(&file.file_name).file_name() != Some(file.file_name.as_ref()) {
+            return Err(PrismError::Other(format!(
+                "refusing to install {:?}",
+                file.file_name
+            )));
+        }
+        let sha1 = file.sha1().map(str::to_string);
+        let Some(url) = file.download_url else {
+            plan.manual.push(file.file_name);
+            continue;
+        };
+        let path = Path::new(folder).join(&file.file_name);
+        let download = ModDownload {
+            file_name: file.file_name,
+            url,
+            sha1,
+        };
+        plan.files.push((path, download));
+    }
+    Ok(())
+}
+
+/// The contents of the file `name` in `archive`
+fn read_entry(archive: &[u8], name: &str) -> Result<Vec<u8>> {
+    let mut zip = ZipArchive::new(Cursor::new(archive))?;
+    let mut entry = zip
+        .by_name(name)
+        .map_err(|_| PrismError::Other(format!("the pack has no {}", name)))?;
+    let mut content = Vec::new();
+    entry.read_to_end(&mut content)?;
+    Ok(content)
+}
+
+/// `path` from a pack, refused when it would leave the folder it's for
+fn relative_path(path: &str) -> Result<PathBuf> {
+    let relative = PathBuf::from(path);
+    let inside = relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)));
+    if !inside || relative.as_os_str().is_empty() {
+        return Err(PrismError::Other(format!("refusing to install {:?}", path)));
+    }
+    Ok(relative)
+}
+
+/// Unpack the archive's `folders` over `game_dir`, in order
+fn copy_overrides(archive: &[u8], folders: &[String], game_dir: &Path) -> Result<()> {
+    let mut zip = ZipArchive::new(Cursor::new(archive))?;
+    let unpacked_size = (0..zip.len())
+        .filter_map(|i| zip.by_index_raw(i).ok().map(|e| e.size()))
+        .sum();
+    ensure_free_space(unpacked_size, game_dir)?;
+    for folder in folders {
+        for i in 0..zip.len() {
+            let mut entry = zip.by_index(i)?;
+            // Entries outside the folder, or escaping it, are skipped
+            let Some(relative) = entry
+                .enclosed_name()
+                .and_then(|p| p.strip_prefix(folder).ok().map(Path::to_path_buf))
+            else {
+                continue;
+            };
+            let dest = game_dir.join(relative);
+            if entry.is_dir() {
+                std::fs::create_dir_all(&dest)?;
+            } else {
+                if let Some(parent) = dest.parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::io::copy(&mut entry, &mut std::fs::File::create(&dest)?)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::ZipWriter;
+    use zip::write::SimpleFileOptions;
+
+    fn zip(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, content) in files {
+            zip.start_file(*name, SimpleFileOptions::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    #[test]
+    fn test_mrpack_lists_client_files_and_its_loader() {
+        let index = r#"{"formatVersion":1,"game":"minecraft","versionId":"1.0","name":"Pack",
+            "files":[
+              {"path":"mods/sodium.jar","hashes":{"sha1":"abc"},
+               "downloads":["https://cdn.modrinth.com/sodium.jar"]},
+              {"path":"mods/server-only.jar","hashes":{"sha1":"def"},
+               "env":{"client":"unsupported","server":"required"},
+               "downloads":["https://cdn.modrinth.com/server-only.jar"]}],
+            "dependencies":{"minecraft":"1.20.1","fabric-loader":"0.15.7"}}"#;
+        let plan = read_mrpack(&zip(&[("modrinth.index.json", index)])).unwrap();
+
+        assert_eq!(plan.minecraft_version, "1.20.1");
+        assert_eq!(plan.loader, Some((Loader::Fabric, "0.15.7".to_string())));
+        assert_eq!(plan.files.len(), 1);
+        assert_eq!(plan.files[0].0, Path::new("mods/sodium.jar"));
+        assert_eq!(plan.files[0].1.sha1.as_deref(), Some("abc"));
+
+        let escaping = index.replace("mods/sodium.jar\"", "../sodium.jar\"");
+        assert!(read_mrpack(&zip(&[("modrinth.index.json", &escaping)])).is_err());
+    }
+
+    #[test]
+    fn test_curseforge_files_go_by_class_and_blocked_ones_are_listed() {
+        let manifest = r#"{"minecraft":{"version":"1.20.1",
+            "modLoaders":[{"id":"forge-47.2.0","primary":true}]},
+            "manifestType":"minecraftModpack","name":"Pack","version":"1.0",
+            "files":[{"projectID":1,"fileID":10,"required":true},
+                     {"projectID":2,"fileID":20,"required":false}],
+            "overrides":"overrides"}"#;
+        let (mut plan, refs) =
+            read_curseforge_manifest(&zip(&[("manifest.json", manifest)])).unwrap();
+        let file = |id: u64, mod_id: u64, name: &str, url: Option<&str>| {
+            let json = serde_json::json!({
+                "id": id, "modId": mod_id, "displayName": name, "fileName": name,
+                "fileDate": "2024-01-01T00:00:00Z", "downloadUrl": url,
+            });
+            serde_json::from_value::<curseforge::File>(json).unwrap()
+        };
+        let files = vec![
+            file(10, 1, "jei.jar", Some("https://edge.forgecdn.net/jei.jar")),
+            file(
+                30,
+                3,
+                "faithful.zip",
+                Some("https://edge.forgecdn.net/faithful.zip"),
+            ),
+            file(40, 4, "optifine.jar", None),
+        ];
+        let classes = HashMap::from([
+            (1, curseforge::CLASS_MODS),
+            (3, curseforge::CLASS_RESOURCE_PACKS),
+        ]);
+        add_curseforge_files(&mut plan, files, &classes).unwrap();
+
+        assert_eq!(plan.loader, Some((Loader::Forge, "47.2.0".to_string())));
+        assert_eq!(refs.len(), 1);
+        let paths: Vec<_> = plan.files.iter().map(|(p, _)| p.clone()).collect();
+        assert_eq!(
+            paths,
+            [
+                PathBuf::from("mods/jei.jar"),
+                PathBuf::from("resourcepacks/faithful.zip")
+            ]
+        );
+        assert_eq!(plan.manual, ["optifine.jar"]);
+    }
+
+    #[test]
+    fn test_overrides_are_copied_with_client_ones_last() {
+        let dir = crate::data::test_dir("pack-overrides");
+        let archive = zip(&[
+            ("overrides/config/a.toml", "shared"),
+            ("overrides/options.txt", "fov:70"),
+            ("client-overrides/options.txt", "fov:90"),
+            ("modrinth.index.json", "{}"),
+        ]);
+        std::fs::create_dir_all(&dir).unwrap();
+        let folders = ["overrides".to_string(), "client-overrides".to_string()];
+        copy_overrides(&archive, &folders, &dir).unwrap();
+        let config = std::fs::read_to_string(dir.join("config/a.toml")).unwrap();
+        let options = std::fs::read_to_string(dir.join("options.txt")).unwrap();
+        let index = dir.join("modrinth.index.json").exists();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(config, "shared");
+        assert_eq!(options, "fov:90");
+        assert!(!index);
+    }
+}
@@ -0,0 +1,302 @@
+use crate::error::Result;
+use crate::net::loaders::Loader;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+const API_BASE: &str = "https://api.modrinth.com/v2";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ProjectVersion {
+    pub id: String,
+    pub version_number: String,
+    #[serde(default)]
+    pub changelog: Option<String>,
+    pub date_published: String,
+}
+
+/// Newer versions of a managed pack, newest first
+#[derive(Debug, Clone)]
+pub struct PackUpdate {
+    pub newer_versions: Vec<ProjectVersion>,
+}
+
+impl PackUpdate {
+    pub fn latest(&self) -> Option<&ProjectVersion> {
+        self.newer_versions.first()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SearchHit {
+    pub project_id: String,
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub downloads: u64,
+}
+
+#[derive(Deserialize)]
+struct SearchResults {
+    hits: Vec<SearchHit>,
+}
+
+/// A version with its files, for downloading
+#[derive(Debug, Clone, Deserialize)]
+pub struct VersionFiles {
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub version_number: String,
+    pub date_published: String,
+    pub files: Vec<VersionFile>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct VersionFile {
+    pub url: String,
+    pub filename: String,
+    #[serde(default)]
+    pub primary: bool,
+    #[serde(default)]
+    pub hashes: FileHashes,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct FileHashes {
+    pub sha1: Option<String>,
+}
+
+impl VersionFiles {
+    /// The file marked primary, or the first when none is
+    pub fn primary_file(&self) -> Option<&VersionFile> {
+        self.files
+            .iter()
+            .find(|f| f.primary)
+            .or_else(|| self.files.first())
+    }
+}
+
+/// Mods matching `query` that have versions for this Minecraft version and
+/// loader, most downloaded first
+pub async fn search_mods(
+    client: &reqwest::Client,
+    query: &str,
+    minecraft_version: &str,
+    loader: Loader,
+    limit: usize,
+) -> Result<Vec<SearchHit>> {
+    let facets = format!(
+        r#"[["project_type:mod"],["categories:{}"],["versions:{}"]]"#,
+        loader.slug(),
+        minecraft_version
+    );
+    search(client, query, &facets, limit).await
+}
+
+/// Modpacks matching `query`, most downloaded first
+pub async fn search_packs(
+    client: &reqwest::Client,
+    query: &str,
+    limit: usize,
+) -> Result<Vec<SearchHit>> {
+    search(client, query, r#"[["project_type:modpack"]]"#, limit).await
+}
+
+async fn search(
+    client: &reqwest::Client,
+    query: &str,
+    facets: &str,
+    limit: usize,
+) -> Result<Vec<SearchHit>> {
+    let results: SearchResults = client
+        .get(format!("{}/search", API_BASE))
+        .query(&[
+            ("query", query),
+            ("facets", facets),
+            ("index", "downloads"),
+            ("limit", &limit.to_string()),
+        ])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(results.hits)
+}
+
+/// The newest version of a mod for this Minecraft version and loader
+pub async fn latest_mod_version(
+    client: &reqwest::Client,
+    project_id: &str,
+    minecraft_version: &str,
+    loader: Loader,
+) -> Result<Option<VersionFiles>> {
+    let loaders = format!(r#"["{}"]"#, loader.slug());
+    let game_versions = format!(r#"["{}"]"#, minecraft_version);
+    let versions: Vec<VersionFiles> = client
+        .get(format!("{}/project/{}/version", API_BASE, project_id))
+        .query(&[("loaders", &loaders), ("game_versions", &game_versions)])
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(versions
+        .into_iter()
+        .max_by(|a, b| a.date_published.cmp(&b.date_published)))
+}
+
+/// The newest version of a modpack, whose primary file is its .mrpack
+pub async fn latest_pack_version(
+    client: &reqwest::Client,
+    project_id: &str,
+) -> Result<Option<VersionFiles>> {
+    let versions: Vec<VersionFiles> = client
+        .get(format!("{}/project/{}/version", API_BASE, project_id))
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    Ok(versions
+        .into_iter()
+        .max_by(|a, b| a.date_published.cmp(&b.date_published)))
+}
+
+/// The newest version for this Minecraft version and loader of each file,
+/// by the file's SHA-1. Files Modrinth doesn't host are left out.
+pub async fn latest_versions_by_hash(
+    client: &reqwest::Client,
+    hashes: &[String],
+    minecraft_version: &str,
+    loader: Loader,
+) -> Result<HashMap<String, VersionFiles>> {
+    let body = serde_json::json!({
+        "hashes": hashes,
+        "algorithm": "sha1",
+        "loaders": [loader.slug()],
+        "game_versions": [minecraft_version],
+    });
+    Ok(client
+        .post(format!("{}/version_files/update", API_BASE))
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+/// The project a file belongs to, by the file's SHA-1. Files Modrinth
+/// doesn't host give `None`.
+pub async fn project_for_hash(client: &reqwest::Client, sha1: &str) -> Result<Option<String>> {
+    #[derive(Deserialize)]
+    struct Version {
+        project_id: String,
+    }
+    let response = client
+        .get(format!("{}/version_file/{}", API_BASE, sha1))
+        .query(&[("algorithm", "sha1")])
+        .send()
+        .await?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    let version: Version = response.error_for_status()?.json().await?;
+    Ok(Some(version.project_id))
+}
+
+/// Fetch all versions of a project, newest first
+pub async fn project_versions(
+    client: &reqwest::Client,
+    project_id: &str,
+) -> Result<Vec<ProjectVersion>> {
+    let url = format!("{}/project/{}/version", API_BASE, project_id);
+    let mut versions: Vec<ProjectVersion> = client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+    // RFC 3339 timestamps sort lexicographically
+    versions.sort_by(|a, b| b.date_published.cmp(&a.date_published));
+    Ok(versions)
+}
+
+/// Check a pack for versions newer than the installed one.
+/// Returns `None` when the installed version is the latest.
+pub async fn check_pack_update(
+    client: &reqwest::Client,
+    project_id: &str,
+    installed_version_id: &str,
+) -> Result<Option<PackUpdate>> {
+    let versions = project_versions(client, project_id).await?;
+    Ok(newer_versions(versions, installed_version_id))
+}
+
+/// Versions published after the installed one (input sorted newest first).
+/// If the installed version isn't listed, only the latest is reported.
+pub(super) fn newer_versions(
+    versions: Vec<ProjectVersion>,
+    installed_id: &str,
+) -> Option<PackUpdate> {
+    let newer: Vec<ProjectVersion> = match versions.iter().position(|v| v.id == installed_id) {
+        Some(pos) => versions.into_iter().take(pos).collect(),
+        None => versions.into_iter().take(1).collect(),
+    };
+
+    if newer.is_empty() {
+        None
+    } else {
+        Some(PackUpdate {
+            newer_versions: newer,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(id: &str, date: &str) -> ProjectVersion {
+        ProjectVersion {
+            id: id.to_string(),
+            version_number: id.to_string(),
+            changelog: None,
+            date_published: date.to_string(),
+        }
+    }
+
+    fn versions() -> Vec<ProjectVersion> {
+        vec![
+            version("c", "2024-03-01T00:00:00Z"),
+            version("b", "2024-02-01T00:00:00Z"),
+            version("a", "2024-01-01T00:00:00Z"),
+        ]
+    }
+
+    #[test]
+    fn test_newer_versions_up_to_date() {
+        assert!(newer_versions(versions(), "c").is_none());
+    }
+
+    #[test]
+    fn test_newer_versions_lists_newer() {
+        let update = newer_versions(versions(), "a").unwrap();
+        let ids: Vec<&str> = update
+            .newer_versions
+            .iter()
+            .map(|v| v.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["c", "b"]);
+        assert_eq!(update.latest().unwrap().id, "c");
+    }
+
+    #[test]
+    fn test_newer_versions_unknown_installed() {
+        let update = newer_versions(versions(), "zzz").unwrap();
+        assert_eq!(update.newer_versions.len(), 1);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::error::Result;
+use crate::net::cache;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const MANIFEST_URL: &str = "https://piston-meta.mojang.com/mc/game/version_manifest_v2.json";
+const CACHE_NAME: &str = "version_manifest";
+const CACHE_MAX_AGE: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VersionType {
+    Release,
+    Snapshot,
+    OldBeta,
+    OldAlpha,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestVersion {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: VersionType,
+    pub url: String,
+    pub release_time: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatestVersions {
+    pub release: String,
+    pub snapshot: String,
+}
+
+/// Mojang's list of every Minecraft version, newest first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionManifest {
+    pub latest: LatestVersions,
+    pub versions: Vec<ManifestVersion>,
+}
+
+impl VersionManifest {
+    pub fn get(&self, id: &str) -> Option<&ManifestVersion> {
+        self.versions.iter().find(|v| v.id == id)
+    }
+
+    /// The latest release, if `version` is an older release.
+    /// Snapshots and unknown versions are not compared.
+    pub fn newer_release(&self, version: &str) -> Option<&str> {
+        let installed = self.get(version)?;
+        if installed.kind != VersionType::Release || installed.id == self.latest.release {
+            return None;
+        }
+        let latest = self.get(&self.latest.release)?;
+        // RFC 3339 timestamps sort lexicographically
+        (latest.release_time > installed.release_time).then_some(latest.id.as_str())
+    }
+}
+
+/// Fetch the version manifest, served from the on-disk cache while it is
+/// fresh. Falls back to a stale cache when the request fails.
+pub async fn version_manifest(client: &reqwest::Client) -> Result<VersionManifest> {
+    cache::fetch_cached(CACHE_NAME, CACHE_MAX_AGE, async {
+        let manifest: VersionManifest = client
+            .get(MANIFEST_URL)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        Ok(manifest)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest() -> VersionManifest {
+        let json = r#"{
+            "latest": {"release": "1.21.4", "snapshot": "25w02a"},
+            "versions": [
+                {"id": "25w02a", "type": "snapshot", "url": "", "releaseTime": "2025-01-08T12:00:00+00:00"},
+                {"id": "1.21.4", "type": "release", "url": "", "releaseTime": "2024-12-03T10:12:57+00:00"},
+                {"id": "1.20.1", "type": "release", "url": "", "releaseTime": "2023-06-12T13:25:51+00:00"},
+                {"id": "b1.7.3", "type": "old_beta", "url": "", "releaseTime": "2011-07-07T22:00:00+00:00"}
+            ]
+        }"#;
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_newer_release() {
+        let manifest = manifest();
+        assert_eq!(manifest.newer_release("1.20.1"), Some("1.21.4"));
+        assert_eq!(manifest.newer_release("1.21.4"), None);
+        assert_eq!(manifest.newer_release("25w02a"), None);
+        assert_eq!(manifest.newer_release("b1.7.3"), None);
+        assert_eq!(manifest.newer_release("unknown"), None);
+    }
+}
@@ -0,0 +1,340 @@
+//! Server List Ping: ask a Minecraft server for its version and player
+//! count the way the multiplayer screen does, without logging in.
+
+use crate::data::{DEFAULT_PORT, release_version, split_address};
+use crate::error::{PrismError, Result};
+use hickory_resolver::TokioAsyncResolver;
+use serde::Deserialize;
+use std::net::IpAddr;
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// How long a server gets to answer before it counts as offline
+pub const PING_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest status response accepted; real ones are a few KB plus the icon
+const MAX_RESPONSE: usize = 1 << 21;
+
+/// The releases each protocol number was used by, as (first, last,
+/// protocol), newest first
+const PROTOCOLS: &[(&str, &str, i32)] = &[
+    ("1.21.9", "1.21.10", 773),
+    ("1.21.7", "1.21.8", 772),
+    ("1.21.6", "1.21.6", 771),
+    ("1.21.5", "1.21.5", 770),
+    ("1.21.4", "1.21.4", 769),
+    ("1.21.2", "1.21.3", 768),
+    ("1.21", "1.21.1", 767),
+    ("1.20.5", "1.20.6", 766),
+    ("1.20.3", "1.20.4", 765),
+    ("1.20.2", "1.20.2", 764),
+    ("1.20", "1.20.1", 763),
+    ("1.19.4", "1.19.4", 762),
+    ("1.19.3", "1.19.3", 761),
+    ("1.19.1", "1.19.2", 760),
+    ("1.19", "1.19", 759),
+    ("1.18.2", "1.18.2", 758),
+    ("1.18", "1.18.1", 757),
+    ("1.17.1", "1.17.1", 756),
+    ("1.17", "1.17", 755),
+    ("1.16.4", "1.16.5", 754),
+    ("1.16.3", "1.16.3", 753),
+    ("1.16.2", "1.16.2", 751),
+    ("1.16.1", "1.16.1", 736),
+    ("1.16", "1.16", 735),
+    ("1.15.2", "1.15.2", 578),
+    ("1.15.1", "1.15.1", 575),
+    ("1.15", "1.15", 573),
+    ("1.14.4", "1.14.4", 498),
+    ("1.14.3", "1.14.3", 490),
+    ("1.14.2", "1.14.2", 485),
+    ("1.14.1", "1.14.1", 480),
+    ("1.14", "1.14", 477),
+    ("1.13.2", "1.13.2", 404),
+    ("1.13.1", "1.13.1", 401),
+    ("1.13", "1.13", 393),
+    ("1.12.2", "1.12.2", 340),
+    ("1.12.1", "1.12.1", 338),
+    ("1.12", "1.12", 335),
+    ("1.11.1", "1.11.2", 316),
+    ("1.11", "1.11", 315),
+    ("1.10", "1.10.2", 210),
+    ("1.9.3", "1.9.4", 110),
+    ("1.9.2", "1.9.2", 109),
+    ("1.9.1", "1.9.1", 108),
+    ("1.9", "1.9", 107),
+    ("1.8", "1.8.9", 47),
+    ("1.7.6", "1.7.10", 5),
+    ("1.7.2", "1.7.5", 4),
+];
+
+/// What a server reports about itself
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServerStatus {
+    /// e.g. "1.20.1" or "Paper 1.20.1"
+    pub version_name: String,
+    pub protocol: i32,
+    pub players_online: u32,
+    pub players_max: u32,
+}
+
+#[derive(Deserialize)]
+struct StatusResponse {
+    version: StatusVersion,
+    players: Option<StatusPlayers>,
+}
+
+#[derive(Deserialize)]
+struct StatusVersion {
+    name: String,
+    protocol: i32,
+}
+
+#[derive(Deserialize)]
+struct StatusPlayers {
+    online: u32,
+    max: u32,
+}
+
+/// The network protocol of a Minecraft release, if it is a known one.
+/// Snapshots and unknown versions give `None`.
+pub fn protocol_version(minecraft_version: &str) -> Option<i32> {
+    let parts = release_version(minecraft_version)?;
+    PROTOCOLS
+        .iter()
+        .find(|(first, last, _)| {
+            release_version(first).is_some_and(|first| first <= parts)
+                && release_version(last).is_some_and(|last| parts <= last)
+        })
+        .map(|(_, _, protocol)| *protocol)
+}
+
+/// The releases that speak `protocol`, e.g. "1.20.3-1.20.4"
+pub fn minecraft_versions(protocol: i32) -> Option<String> {
+    let (first, last, _) = PROTOCOLS.iter().find(|(_, _, p)| *p == protocol)?;
+    Some(if first == last {
+        first.to_string()
+    } else {
+        format!("{}-{}", first, last)
+    })
+}
+
+/// Ask the server at `address` for its status. `protocol` is the client
+/// version sent along, which proxies use to pick the version they report.
+pub async fn ping(address: &str, protocol: Option<i32>) -> Result<ServerStatus> {
+    tokio::time::timeout(PING_TIMEOUT, ping_inner(address, protocol))
+        .await
+        .map_err(|_| PrismError::Other("no answer (timed out)".to_string()))?
+}
+
+/// The host and port the game connects to for `address`. Like the client,
+/// an address on the default port is first looked up as a
+/// `_minecraft._tcp` SRV record, which servers use to sit behind a plain
+/// domain name while running elsewhere.
+pub async fn server_target(address: &str) -> (String, u16) {
+    let (host, port) = split_address(address);
+    if port != DEFAULT_PORT || host.parse::<IpAddr>().is_ok() {
+        return (host, port);
+    }
+    srv_target(&host).await.unwrap_or((host, port))
+}
+
+/// The target of the host's SRV record with the lowest priority
+async fn srv_target(host: &str) -> Option<(String, u16)> {
+    let resolver = TokioAsyncResolver::tokio_from_system_conf().ok()?;
+    let lookup = resolver
+        .srv_lookup(format!("_minecraft._tcp.{}.", host))
+        .await
+        .ok()?;
+    let record = lookup.iter().min_by_key(|r| r.priority())?;
+    let target = record.target().to_utf8();
+    Some((target.trim_end_matches('.').to_lowercase(), record.port()))
+}
+
+async fn ping_inner(address: &str, protocol: Option<i32>) -> Result<ServerStatus> {
+    let (host, port) = server_target(address).await;
+    let mut stream = TcpStream::connect((host.as_str(), port)).await?;
+
+    let mut handshake = Vec::new();
+    write_varint(&mut handshake, 0x00);
+    write_varint(&mut handshake, protocol.unwrap_or(-1));
+    write_varint(&mut handshake, host.len() as i32);
+    handshake.extend_from_slice(host.as_bytes());
+    handshake.extend_from_slice(&port.to_be_bytes());
+    write_varint(&mut handshake, 1); // next state: status
+    let mut request = frame(&handshake);
+    request.extend(frame(&[0x00]));
+    stream.write_all(&request).await?;
+
+    let length = read_varint(&mut stream).await? as usize;
+    if length > MAX_RESPONSE {
+        return Err(PrismError::Other("status response too large".to_string()));
+    }
+    let mut packet = vec![0; length];
+    stream.read_exact(&mut packet).await?;
+    let mut body = packet.as_slice();
+    if read_varint(&mut body).await? != 0x00 {
+        return Err(PrismError::Other("unexpected status packet".to_string()));
+    }
+    let json_len = read_varint(&mut body).await? as usize;
+    let json = body
+        .get(..json_len)
+        .ok_or_else(|| PrismError::Other("truncated status response".to_string()))?;
+    let response: StatusResponse = serde_json::from_slice(json)?;
+    let players = response
+        .players
+        .unwrap_or(StatusPlayers { online: 0, max: 0 });
+    Ok(ServerStatus {
+        version_name: response.version.name,
+        protocol: response.version.protocol,
+        players_online: players.online,
+        players_max: players.max,
+    })
+}
+
+impl ServerStatus {
+    /// The versions the server speaks, going by its protocol rather than the
+    /// name it reports, which proxies and server software fill in freely
+    pub fn versions(&self) -> String {
+        minecraft_versions(self.protocol).unwrap_or_else(|| self.version_name.clone())
+    }
+
+    /// e.g. "1.20.3-1.20.4 (protocol 765), 3/20 players"
+    pub fn summary(&self) -> String {
+        format!(
+            "{} (protocol {}), {}/{} players",
+            self.versions(),
+            self.protocol,
+            self.players_online,
+            self.players_max
+        )
+    }
+
+    /// Whether a `minecraft_version` client can't join; `None` when the
+    /// version's protocol isn't known
+    pub fn mismatches(&self, minecraft_version: &str) -> Option<bool> {
+        protocol_version(minecraft_version).map(|ours| ours != self.protocol)
+    }
+}
+
+/// Why joining `address` with `minecraft_version` will likely fail, going by
+/// the ping `result`
+pub fn join_warning(
+    address: &str,
+    minecraft_version: &str,
+    result: &std::result::Result<ServerStatus, String>,
+) -> Option<String> {
+    match result {
+        Err(e) => Some(format!("{} did not answer: {}", address, e)),
+        Ok(status) => status.mismatches(minecraft_version)?.then(|| {
+            format!(
+                "{} runs {}, not {}",
+                address,
+                status.versions(),
+                minecraft_version
+            )
+        }),
+    }
+}
+
+/// Prefix a packet with its length
+fn frame(packet: &[u8]) -> Vec<u8> {
+    let mut framed = Vec::with_capacity(packet.len() + 5);
+    write_varint(&mut framed, packet.len() as i32);
+    framed.extend_from_slice(packet);
+    framed
+}
+
+fn write_varint(buf: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7F == 0 {
+            buf.push(value as u8);
+            return;
+        }
+        buf.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+}
+
+async fn read_varint<R: AsyncRead + Unpin>(reader: &mut R) -> Result<i32> {
+    let mut value: u32 = 0;
+    for shift in (0..35).step_by(7) {
+        let byte = reader.read_u8().await?;
+        value |= ((byte & 0x7F) as u32) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err(PrismError::Other("malformed varint".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[test]
+    fn test_protocol_version_of_releases() {
+        assert_eq!(protocol_version("1.20.1"), Some(763));
+        assert_eq!(protocol_version("1.20"), Some(763));
+        assert_eq!(protocol_version("1.21.8"), Some(772));
+        assert_eq!(protocol_version("1.8.9"), Some(47));
+        assert_eq!(protocol_version("1.7.10"), Some(5));
+        assert_eq!(protocol_version("1.6.4"), None);
+        assert_eq!(protocol_version("1.21.11"), None);
+        assert_eq!(protocol_version("24w14a"), None);
+    }
+
+    #[test]
+    fn test_minecraft_versions_of_protocols() {
+        assert_eq!(minecraft_versions(765).as_deref(), Some("1.20.3-1.20.4"));
+        assert_eq!(minecraft_versions(764).as_deref(), Some("1.20.2"));
+        assert_eq!(minecraft_versions(1), None);
+        // Every release in the table maps back to its own protocol
+        for (first, last, protocol) in PROTOCOLS {
+            assert_eq!(protocol_version(first), Some(*protocol));
+            assert_eq!(protocol_version(last), Some(*protocol));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_srv_records_are_only_looked_up_for_the_default_port() {
+        // Neither needs a lookup, so no DNS is involved
+        assert_eq!(
+            server_target("Play.Example.com:25570").await,
+            ("play.example.com".to_string(), 25570)
+        );
+        assert_eq!(
+            server_target("127.0.0.1").await,
+            ("127.0.0.1".to_string(), DEFAULT_PORT)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ping_reads_the_status() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = format!("127.0.0.1:{}", listener.local_addr().unwrap().port());
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = [0u8; 64];
+            let _ = socket.read(&mut request).await.unwrap();
+            let json = r#"{"version":{"name":"Paper 1.20.1","protocol":763},"players":{"online":3,"max":20}}"#;
+            let mut packet = vec![0x00];
+            write_varint(&mut packet, json.len() as i32);
+            packet.extend_from_slice(json.as_bytes());
+            socket.write_all(&frame(&packet)).await.unwrap();
+        });
+
+        let status = ping(&address, protocol_version("1.20.1")).await.unwrap();
+        assert_eq!(status.version_name, "Paper 1.20.1");
+        assert_eq!(status.players_online, 3);
+        assert_eq!(status.summary(), "1.20-1.20.1 (protocol 763), 3/20 players");
+        assert_eq!(join_warning(&address, "1.20.1", &Ok(status.clone())), None);
+        assert_eq!(
+            join_warning(&address, "1.21", &Ok(status)),
+            Some(format!("{} runs 1.20-1.20.1, not 1.21", address))
+        );
+    }
+}
@@ -0,0 +1,94 @@
+//! Periodic work done from the tick handler. Each task keeps its own
+//! interval, stretched by a little jitter so tasks that share an interval
+//! don't all land on the same tick.
+
+use std::collections::HashMap;
+use std::hash::{BuildHasher, RandomState};
+use std::time::{Duration, Instant};
+
+/// Share of a task's interval added at random to each run
+const JITTER_PERCENT: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Periodic {
+    /// Look for the Java processes of launched games
+    ProcessScan,
+    /// Read the free space on the data directory's drive
+    FreeSpace,
+    /// Check the followed log file for new lines
+    FollowLog,
+}
+
+impl Periodic {
+    pub fn label(self) -> &'static str {
+        match self {
+            Periodic::ProcessScan => "process scan",
+            Periodic::FreeSpace => "free space",
+            Periodic::FollowLog => "log follow",
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    next_run: HashMap<Periodic, Instant>,
+    random: RandomState,
+}
+
+impl Scheduler {
+    /// Whether `task` should run now. A due task is booked again `interval`
+    /// (plus jitter) from `now`; one never run before is due straight away.
+    pub fn due(&mut self, task: Periodic, interval: Duration, now: Instant) -> bool {
+        if self.next_run.get(&task).is_some_and(|next| now < *next) {
+            return false;
+        }
+        let jitter = interval.mul_f64(self.jitter_fraction(task, now));
+        self.next_run.insert(task, now + interval + jitter);
+        true
+    }
+
+    /// Run `task` at the next chance, e.g. after the data it reads changed
+    pub fn run_soon(&mut self, task: Periodic) {
+        self.next_run.remove(&task);
+    }
+
+    /// Time until each booked task runs next, for the debug overlay
+    pub fn upcoming(&self, now: Instant) -> Vec<(Periodic, Duration)> {
+        let mut upcoming: Vec<_> = self
+            .next_run
+            .iter()
+            .map(|(task, next)| (*task, next.saturating_duration_since(now)))
+            .collect();
+        upcoming.sort_by_key(|(_, wait)| *wait);
+        upcoming
+    }
+
+    fn jitter_fraction(&self, task: Periodic, now: Instant) -> f64 {
+        let hash = self.random.hash_one((task, now));
+        (hash % 1000) as f64 / 1000.0 * JITTER_PERCENT as f64 / 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scheduler_keeps_task_intervals_apart() {
+        let mut scheduler = Scheduler::default();
+        let start = Instant::now();
+        let second = Duration::from_secs(1);
+
+        assert!(scheduler.due(Periodic::ProcessScan, second, start));
+        assert!(scheduler.due(Periodic::FreeSpace, 30 * second, start));
+        assert!(!scheduler.due(Periodic::ProcessScan, second, start + second / 2));
+        assert!(scheduler.due(Periodic::ProcessScan, second, start + 2 * second));
+        assert!(!scheduler.due(Periodic::FreeSpace, 30 * second, start + 2 * second));
+
+        scheduler.run_soon(Periodic::FreeSpace);
+        assert!(scheduler.due(Periodic::FreeSpace, 30 * second, start + 3 * second));
+        // Jitter only ever delays, by at most a tenth of the interval
+        assert!(!scheduler.due(Periodic::FreeSpace, 30 * second, start + 32 * second));
+        assert!(scheduler.due(Periodic::FreeSpace, 30 * second, start + 37 * second));
+    }
+}
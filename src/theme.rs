@@ -1,4 +1,8 @@
+use ratatui::buffer::Buffer;
 use ratatui::style::Color;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// Catppuccin Mocha color palette
 #[allow(dead_code)]
@@ -42,45 +46,377 @@ pub mod colors {
     pub const LAVENDER: Color = Color::Rgb(180, 190, 254);
 }
 
-/// Semantic color aliases for UI elements
-#[allow(dead_code)]
+/// Colors for each UI role; log levels are separate from the UI accents
+/// so a theme can keep them apart
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Theme {
+    pub primary: Color,
+    pub active: Color,
+    pub highlight: Color,
+    pub error: Color,
+    pub warning: Color,
+    pub muted: Color,
+    pub text: Color,
+    pub dialog_border: Color,
+    pub help_border: Color,
+    pub log: LogColors,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LogColors {
+    pub error: Color,
+    pub warn: Color,
+    pub info: Color,
+    pub debug: Color,
+}
+
+/// Catppuccin Mocha, the default
+pub const CATPPUCCIN: Theme = Theme {
+    primary: colors::MAUVE,
+    active: colors::GREEN,
+    highlight: colors::YELLOW,
+    error: colors::RED,
+    warning: colors::YELLOW,
+    muted: colors::OVERLAY0,
+    text: colors::TEXT,
+    dialog_border: colors::YELLOW,
+    help_border: colors::MAUVE,
+    log: LogColors {
+        error: colors::RED,
+        warn: colors::YELLOW,
+        info: colors::BLUE,
+        debug: colors::OVERLAY0,
+    },
+};
+
+/// Bright colors on the terminal background, for low vision or glare
+pub const HIGH_CONTRAST: Theme = Theme {
+    primary: Color::Rgb(0, 255, 255),
+    active: Color::Rgb(0, 255, 0),
+    highlight: Color::Rgb(255, 255, 0),
+    error: Color::Rgb(255, 80, 80),
+    warning: Color::Rgb(255, 255, 0),
+    muted: Color::Rgb(200, 200, 200),
+    text: Color::Rgb(255, 255, 255),
+    dialog_border: Color::Rgb(255, 255, 0),
+    help_border: Color::Rgb(0, 255, 255),
+    log: LogColors {
+        error: Color::Rgb(255, 80, 80),
+        warn: Color::Rgb(255, 255, 0),
+        info: Color::Rgb(0, 255, 255),
+        debug: Color::Rgb(200, 200, 200),
+    },
+};
+
+/// Blue and orange instead of green and red, for red-green color blindness
+/// with weak green (Okabe-Ito colors)
+pub const DEUTERANOPIA: Theme = Theme {
+    primary: Color::Rgb(204, 121, 167),
+    active: Color::Rgb(86, 180, 233),
+    highlight: Color::Rgb(240, 228, 66),
+    error: Color::Rgb(213, 94, 0),
+    warning: Color::Rgb(240, 228, 66),
+    muted: colors::OVERLAY1,
+    text: colors::TEXT,
+    dialog_border: Color::Rgb(240, 228, 66),
+    help_border: Color::Rgb(204, 121, 167),
+    log: LogColors {
+        error: Color::Rgb(230, 159, 0),
+        warn: Color::Rgb(240, 228, 66),
+        info: Color::Rgb(86, 180, 233),
+        debug: colors::OVERLAY1,
+    },
+};
+
+/// Like deuteranopia, with lighter warm colors since reds look dark
+/// with weak red
+pub const PROTANOPIA: Theme = Theme {
+    primary: Color::Rgb(150, 140, 255),
+    active: Color::Rgb(86, 180, 233),
+    highlight: Color::Rgb(255, 236, 120),
+    error: Color::Rgb(255, 150, 40),
+    warning: Color::Rgb(255, 236, 120),
+    muted: colors::OVERLAY1,
+    text: colors::TEXT,
+    dialog_border: Color::Rgb(255, 236, 120),
+    help_border: Color::Rgb(150, 140, 255),
+    log: LogColors {
+        error: Color::Rgb(255, 150, 40),
+        warn: Color::Rgb(255, 236, 120),
+        info: Color::Rgb(86, 180, 233),
+        debug: colors::OVERLAY1,
+    },
+};
+
+/// Built-in themes by config name
+pub const THEMES: &[(&str, Theme)] = &[
+    ("catppuccin", CATPPUCCIN),
+    ("high-contrast", HIGH_CONTRAST),
+    ("deuteranopia", DEUTERANOPIA),
+    ("protanopia", PROTANOPIA),
+];
+
+static THEME: OnceLock<Theme> = OnceLock::new();
+
+/// Select the theme for this run. Only the first call has any effect.
+pub fn init(name: Option<&str>, log_colors: &BTreeMap<String, String>) -> Vec<String> {
+    let (theme, warnings) = resolve(name, log_colors);
+    let _ = THEME.set(theme);
+    warnings
+}
+
+/// Look up a theme by name and apply per-level log color overrides such as
+/// `error = "#ff8800"`. Problems are returned and the defaults kept.
+fn resolve(name: Option<&str>, log_colors: &BTreeMap<String, String>) -> (Theme, Vec<String>) {
+    let mut warnings = Vec::new();
+    let mut theme = match name {
+        None => CATPPUCCIN,
+        Some(name) => match THEMES.iter().find(|(n, _)| *n == name) {
+            Some((_, theme)) => *theme,
+            None => {
+                let names: Vec<&str> = THEMES.iter().map(|(n, _)| *n).collect();
+                warnings.push(format!(
+                    "Unknown theme '{}', expected one of {}",
+                    name,
+                    names.join(", ")
+                ));
+                CATPPUCCIN
+            }
+        },
+    };
+
+    for (level, value) in log_colors {
+        let slot = match level.as_str() {
+            "error" => &mut theme.log.error,
+            "warn" => &mut theme.log.warn,
+            "info" => &mut theme.log.info,
+            "debug" => &mut theme.log.debug,
+            _ => {
+                warnings.push(format!("Unknown log level '{}' in log_colors", level));
+                continue;
+            }
+        };
+        match value.parse() {
+            Ok(color) => *slot = color,
+            Err(_) => warnings.push(format!("Invalid color '{}' for {} logs", value, level)),
+        }
+    }
+
+    (theme, warnings)
+}
+
+pub fn current() -> &'static Theme {
+    THEME.get().unwrap_or(&CATPPUCCIN)
+}
+
+/// Semantic color aliases for UI elements, from the current theme
 pub mod ui {
-    use super::Color;
-    use super::colors;
+    use super::{Color, current};
 
     /// Primary accent color for titles and selected items
-    pub const PRIMARY: Color = colors::MAUVE;
+    pub fn primary() -> Color {
+        current().primary
+    }
 
     /// Secondary accent for active/enabled states
-    pub const ACTIVE: Color = colors::GREEN;
+    pub fn active() -> Color {
+        current().active
+    }
 
     /// Search and highlight color
-    pub const HIGHLIGHT: Color = colors::YELLOW;
+    pub fn highlight() -> Color {
+        current().highlight
+    }
 
     /// Error states and messages
-    pub const ERROR: Color = colors::RED;
+    pub fn error() -> Color {
+        current().error
+    }
+
+    /// Warnings outside the log viewer
+    pub fn warning() -> Color {
+        current().warning
+    }
+
+    /// Muted/secondary text
+    pub fn muted() -> Color {
+        current().muted
+    }
+
+    /// Normal text color
+    pub fn text() -> Color {
+        current().text
+    }
+
+    /// Dialog borders
+    pub fn dialog_border() -> Color {
+        current().dialog_border
+    }
+
+    /// Help dialog border
+    pub fn help_border() -> Color {
+        current().help_border
+    }
+
+    /// Error log level
+    pub fn log_error() -> Color {
+        current().log.error
+    }
 
     /// Warning log level
-    pub const WARNING: Color = colors::YELLOW;
+    pub fn log_warn() -> Color {
+        current().log.warn
+    }
 
     /// Info log level
-    pub const INFO: Color = colors::BLUE;
+    pub fn log_info() -> Color {
+        current().log.info
+    }
 
-    /// Mode indicator (vim/arrows)
-    pub const MODE: Color = colors::MAUVE;
+    /// Debug log level
+    pub fn log_debug() -> Color {
+        current().log.debug
+    }
+}
 
-    /// Muted/secondary text
-    pub const MUTED: Color = colors::OVERLAY0;
+static PLAIN: AtomicBool = AtomicBool::new(false);
 
-    /// Normal text color
-    pub const TEXT: Color = colors::TEXT;
+/// Draw with ANSI 16 colors and ASCII only, for limited terminals and
+/// accessibility tooling
+pub fn set_plain(plain: bool) {
+    PLAIN.store(plain, Ordering::Relaxed);
+}
 
-    /// Debug log level
-    pub const DEBUG: Color = colors::OVERLAY0;
+pub fn is_plain() -> bool {
+    PLAIN.load(Ordering::Relaxed)
+}
 
-    /// Dialog borders
-    pub const DIALOG_BORDER: Color = colors::YELLOW;
+/// Rewrite a rendered frame for plain mode. Palette colors become their ANSI
+/// counterparts and borders, arrows and dots become ASCII; text is left alone.
+pub fn downgrade(buf: &mut Buffer) {
+    for cell in buf.content.iter_mut() {
+        cell.fg = ansi_color(cell.fg);
+        cell.bg = ansi_color(cell.bg);
+        if let Some(symbol) = ascii_symbol(cell.symbol()) {
+            cell.set_symbol(symbol);
+        }
+    }
+}
 
-    /// Help dialog border
-    pub const HELP_BORDER: Color = colors::MAUVE;
+fn ansi_color(color: Color) -> Color {
+    use colors::*;
+    match color {
+        BASE | MANTLE | CRUST => Color::Black,
+        SURFACE0 | SURFACE1 | SURFACE2 | OVERLAY0 | OVERLAY1 | OVERLAY2 => Color::DarkGray,
+        TEXT | SUBTEXT0 | SUBTEXT1 => Color::Reset,
+        ROSEWATER | FLAMINGO | PINK => Color::LightMagenta,
+        MAUVE => Color::Magenta,
+        RED | MAROON => Color::Red,
+        PEACH => Color::LightRed,
+        YELLOW => Color::Yellow,
+        GREEN => Color::Green,
+        TEAL => Color::Cyan,
+        SKY | SAPPHIRE => Color::LightCyan,
+        BLUE => Color::Blue,
+        LAVENDER => Color::LightBlue,
+        Color::Rgb(r, g, b) => nearest_ansi(r, g, b),
+        Color::Indexed(_) => Color::Reset,
+        other => other,
+    }
+}
+
+/// Closest of the 16 ANSI colors, going by xterm's default values
+fn nearest_ansi(r: u8, g: u8, b: u8) -> Color {
+    const ANSI: [(Color, (i32, i32, i32)); 16] = [
+        (Color::Black, (0, 0, 0)),
+        (Color::Red, (205, 0, 0)),
+        (Color::Green, (0, 205, 0)),
+        (Color::Yellow, (205, 205, 0)),
+        (Color::Blue, (0, 0, 238)),
+        (Color::Magenta, (205, 0, 205)),
+        (Color::Cyan, (0, 205, 205)),
+        (Color::Gray, (229, 229, 229)),
+        (Color::DarkGray, (127, 127, 127)),
+        (Color::LightRed, (255, 0, 0)),
+        (Color::LightGreen, (0, 255, 0)),
+        (Color::LightYellow, (255, 255, 0)),
+        (Color::LightBlue, (92, 92, 255)),
+        (Color::LightMagenta, (255, 0, 255)),
+        (Color::LightCyan, (0, 255, 255)),
+        (Color::White, (255, 255, 255)),
+    ];
+    let (r, g, b) = (i32::from(r), i32::from(g), i32::from(b));
+    ANSI.iter()
+        .min_by_key(|(_, (ar, ag, ab))| (r - ar).pow(2) + (g - ag).pow(2) + (b - ab).pow(2))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+fn ascii_symbol(symbol: &str) -> Option<&'static str> {
+    let mut chars = symbol.chars();
+    let (Some(c), None) = (chars.next(), chars.next()) else {
+        return None;
+    };
+    Some(match c {
+        '▲' | '↑' => "^",
+        '▼' | '↓' => "v",
+        '←' => "<",
+        '→' => ">",
+        '●' | '•' => "*",
+        '○' => "o",
+        '…' | '·' => ".",
+        '▌' | '▐' => "|",
+        '─' | '━' | '═' | '┄' | '┅' | '┈' | '┉' | '╌' | '╍' => "-",
+        '│' | '┃' | '║' | '┆' | '┇' | '┊' | '┋' | '╎' | '╏' => "|",
+        // Corners and junctions
+        '\u{2500}'..='\u{257f}' => "+",
+        // Block elements: scrollbar thumbs, gauges
+        '\u{2580}'..='\u{259f}' => "#",
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::layout::Rect;
+    use ratatui::style::Style;
+
+    #[test]
+    fn test_downgrade_to_ascii_and_ansi() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 1));
+        buf.set_string(
+            0,
+            0,
+            "┌─●▲ é",
+            Style::default().fg(ui::primary()).bg(colors::SURFACE0),
+        );
+        downgrade(&mut buf);
+
+        let text: String = buf.content.iter().map(|c| c.symbol()).collect();
+        assert_eq!(text, "+-*^ é  ");
+        assert_eq!(buf[(0, 0)].fg, Color::Magenta);
+        assert_eq!(buf[(0, 0)].bg, Color::DarkGray);
+        assert_eq!(ansi_color(Color::Rgb(1, 2, 3)), Color::Black);
+        assert_eq!(ansi_color(Color::Rgb(240, 228, 66)), Color::LightYellow);
+        assert_eq!(ansi_color(Color::Green), Color::Green);
+    }
+
+    #[test]
+    fn test_resolve_theme_with_log_colors() {
+        let overrides = BTreeMap::from([
+            ("error".to_string(), "#ff8800".to_string()),
+            ("info".to_string(), "lightblue".to_string()),
+            ("debug".to_string(), "not a color".to_string()),
+        ]);
+        let (theme, warnings) = resolve(Some("deuteranopia"), &overrides);
+        assert_eq!(theme.active, DEUTERANOPIA.active);
+        assert_eq!(theme.log.error, Color::Rgb(255, 136, 0));
+        assert_eq!(theme.log.info, Color::LightBlue);
+        assert_eq!(theme.log.debug, DEUTERANOPIA.log.debug);
+        assert_eq!(warnings.len(), 1);
+
+        let (theme, warnings) = resolve(Some("solarized"), &BTreeMap::new());
+        assert_eq!(theme, CATPPUCCIN);
+        assert!(warnings[0].contains("high-contrast"));
+    }
 }
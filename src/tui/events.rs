@@ -1,7 +1,7 @@
 use crossterm::event::{Event as CrosstermEvent, KeyEvent, KeyEventKind, MouseEvent};
 use futures::{FutureExt, StreamExt};
 use std::time::Duration;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, watch};
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -15,12 +15,14 @@ pub enum Event {
 pub struct EventStream {
     rx: mpsc::UnboundedReceiver<Event>,
     _tx: mpsc::UnboundedSender<Event>,
+    tick_rate_tx: watch::Sender<Duration>,
 }
 
 impl EventStream {
     pub fn new(tick_rate: Duration) -> Self {
         let (tx, rx) = mpsc::unbounded_channel();
         let event_tx = tx.clone();
+        let (tick_rate_tx, mut tick_rate_rx) = watch::channel(tick_rate);
 
         tokio::spawn(async move {
             let mut reader = crossterm::event::EventStream::new();
@@ -31,6 +33,13 @@ impl EventStream {
                 let crossterm_event = reader.next().fuse();
 
                 tokio::select! {
+                    changed = tick_rate_rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                        let rate = *tick_rate_rx.borrow_and_update();
+                        tick_interval = tokio::time::interval(rate);
+                    }
                     _ = tick_delay => {
                         if event_tx.send(Event::Tick).is_err() {
                             break;
@@ -61,7 +70,23 @@ impl EventStream {
             }
         });
 
-        Self { rx, _tx: tx }
+        Self {
+            rx,
+            _tx: tx,
+            tick_rate_tx,
+        }
+    }
+
+    /// Change the tick interval; no-op if it is unchanged
+    pub fn set_tick_rate(&self, tick_rate: Duration) {
+        self.tick_rate_tx.send_if_modified(|rate| {
+            if *rate == tick_rate {
+                false
+            } else {
+                *rate = tick_rate;
+                true
+            }
+        });
     }
 
     pub async fn next(&mut self) -> Option<Event> {
@@ -0,0 +1,190 @@
+//! Images drawn straight into the terminal, with the kitty graphics protocol
+//! (kitty, Ghostty), iTerm2's inline images (iTerm2, WezTerm) or sixel (foot,
+//! Windows Terminal, Konsole, mlterm). The first two take PNG files as they
+//! are; for sixel the file is decoded and scaled first. Other terminals, and
+//! multiplexers that would need the escapes passed through, get no image.
+
+use super::sixel;
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use ratatui::layout::Rect;
+use std::path::PathBuf;
+
+/// Most base64 bytes kitty accepts in one escape
+const KITTY_CHUNK: usize = 4096;
+
+/// Environment variable naming the protocol to use ("kitty", "iterm",
+/// "sixel" or "none"), for terminals not recognized by their environment,
+/// such as xterm started with `-ti vt340`
+const PROTOCOL_VAR: &str = "PRISM_TUI_IMAGES";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageProtocol {
+    Kitty,
+    Iterm,
+    Sixel,
+}
+
+/// A PNG to draw over part of the screen once the frame is drawn
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImagePreview {
+    pub path: PathBuf,
+    pub dimensions: (u32, u32),
+    pub area: Rect,
+}
+
+impl ImageProtocol {
+    /// The protocol the terminal speaks, going by its environment
+    pub fn detect() -> Option<Self> {
+        Self::from_env(|key| std::env::var(key).ok())
+    }
+
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        match var(PROTOCOL_VAR).as_deref() {
+            Some("kitty") => return Some(ImageProtocol::Kitty),
+            Some("iterm") => return Some(ImageProtocol::Iterm),
+            Some("sixel") => return Some(ImageProtocol::Sixel),
+            Some("none") => return None,
+            _ => {}
+        }
+        let term = var("TERM").unwrap_or_default();
+        let program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("TMUX").is_some() || term.starts_with("screen") || term.starts_with("tmux") {
+            return None;
+        }
+        if var("KITTY_WINDOW_ID").is_some()
+            || term.contains("kitty")
+            || term.contains("ghostty")
+            || program == "ghostty"
+        {
+            Some(ImageProtocol::Kitty)
+        } else if program == "iTerm.app" || program == "WezTerm" {
+            Some(ImageProtocol::Iterm)
+        } else if term.starts_with("foot")
+            || term.starts_with("mlterm")
+            || term.starts_with("contour")
+            || var("WT_SESSION").is_some()
+            || var("KONSOLE_VERSION").is_some()
+        {
+            Some(ImageProtocol::Sixel)
+        } else {
+            None
+        }
+    }
+
+    /// Escapes drawing `png` at the top left of `area`, as large as fits
+    /// without stretching it, with cells of `cell` pixels. The cursor is left
+    /// where it was. Sixel draws nothing for a PNG it can't decode.
+    pub fn draw(self, png: &[u8], dimensions: (u32, u32), area: Rect, cell: (u16, u16)) -> String {
+        let (cols, rows) = fit(dimensions, area);
+        let data = STANDARD.encode(png);
+        let mut out = format!("\x1b7\x1b[{};{}H", area.y + 1, area.x + 1);
+        match self {
+            ImageProtocol::Kitty => {
+                let chunks: Vec<&[u8]> = data.as_bytes().chunks(KITTY_CHUNK).collect();
+                for (i, chunk) in chunks.iter().enumerate() {
+                    let more = u8::from(i + 1 < chunks.len());
+                    // q=2 keeps the terminal from answering on stdin
+                    let keys = if i == 0 {
+                        format!("a=T,f=100,c={},r={},C=1,q=2,m={}", cols, rows, more)
+                    } else {
+                        format!("m={}", more)
+                    };
+                    out.push_str(&format!(
+                        "\x1b_G{};{}\x1b\\",
+                        keys,
+                        String::from_utf8_lossy(chunk)
+                    ));
+                }
+            }
+            ImageProtocol::Sixel => {
+                let Some(image) = sixel::decode_png(png) else {
+                    return String::new();
+                };
+                let width = usize::from(area.width) * usize::from(cell.0);
+                let height = usize::from(area.height) * usize::from(cell.1);
+                out.push_str(&sixel::encode(&sixel::fit(&image, width, height)));
+            }
+            ImageProtocol::Iterm => out.push_str(&format!(
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+                png.len(),
+                cols,
+                rows,
+                data
+            )),
+        }
+        out.push_str("\x1b8");
+        out
+    }
+
+    /// Escape removing kitty's images. iTerm2's and sixel's are text cells,
+    /// gone once the screen is drawn over.
+    pub fn clear(self) -> Option<&'static str> {
+        match self {
+            ImageProtocol::Kitty => Some("\x1b_Ga=d,d=A,q=2\x1b\\"),
+            ImageProtocol::Iterm | ImageProtocol::Sixel => None,
+        }
+    }
+}
+
+/// The columns and rows an image takes inside `area` at its own shape,
+/// taking cells to be about twice as tall as they are wide
+pub fn fit((width, height): (u32, u32), area: Rect) -> (u16, u16) {
+    if width == 0 || height == 0 || area.width == 0 || area.height == 0 {
+        return (0, 0);
+    }
+    let (width, height) = (u64::from(width), u64::from(height));
+    let rows_at_full_width = u64::from(area.width) * height / (width * 2);
+    if rows_at_full_width <= u64::from(area.height) {
+        (area.width, rows_at_full_width.max(1) as u16)
+    } else {
+        let cols = u64::from(area.height) * 2 * width / height;
+        (cols.max(1) as u16, area.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protocol_from_environment() {
+        let detect = |vars: &[(&str, &str)]| {
+            ImageProtocol::from_env(|key| {
+                vars.iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            })
+        };
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty")]),
+            Some(ImageProtocol::Kitty)
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("TERM_PROGRAM", "WezTerm")]),
+            Some(ImageProtocol::Iterm)
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty"), ("TMUX", "/tmp/tmux-0/default")]),
+            None
+        );
+        assert_eq!(detect(&[("TERM", "foot")]), Some(ImageProtocol::Sixel));
+        assert_eq!(
+            detect(&[("TERM", "xterm-256color"), ("PRISM_TUI_IMAGES", "sixel")]),
+            Some(ImageProtocol::Sixel)
+        );
+        assert_eq!(
+            detect(&[("TERM", "xterm-kitty"), ("PRISM_TUI_IMAGES", "none")]),
+            None
+        );
+        assert_eq!(detect(&[("TERM", "xterm-256color")]), None);
+    }
+
+    #[test]
+    fn test_fit_keeps_the_shape() {
+        let area = |width, height| Rect::new(0, 0, width, height);
+        assert_eq!(fit((1920, 1080), area(80, 40)), (80, 22));
+        assert_eq!(fit((1920, 1080), area(80, 10)), (35, 10));
+        assert_eq!(fit((0, 1080), area(80, 10)), (0, 0));
+    }
+}
@@ -1,5 +1,8 @@
 pub mod events;
+pub mod image;
+mod sixel;
 pub mod terminal;
 
 pub use events::{Event, EventStream};
+pub use image::{ImagePreview, ImageProtocol};
 pub use terminal::Terminal;
@@ -0,0 +1,392 @@
+//! Sixel output for terminals without a protocol that takes PNG files: the
+//! PNG is decoded here, scaled to the cells it covers, mapped onto a fixed
+//! palette and written out six pixel rows at a time.
+
+use flate2::read::ZlibDecoder;
+use std::io::Read;
+
+/// Largest image decoded, in pixels, so a huge file can't take all memory
+const MAX_PIXELS: u64 = 64 * 1024 * 1024;
+
+/// Levels per channel of the palette; 6 * 6 * 6 colors fit sixel's 256
+/// color registers
+const LEVELS: usize = 6;
+
+/// An image as 8-bit RGBA pixels, row by row
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rgba {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<[u8; 4]>,
+}
+
+/// Decode a PNG. Any color type and bit depth is read; interlaced files are
+/// not, and neither is anything damaged.
+pub fn decode_png(png: &[u8]) -> Option<Rgba> {
+    let mut rest = png.strip_prefix(b"\x89PNG\r\n\x1a\n")?;
+    let mut header = None;
+    let mut palette: Vec<[u8; 4]> = Vec::new();
+    let mut data = Vec::new();
+    while rest.len() >= 12 {
+        let len = u32::from_be_bytes(rest[..4].try_into().ok()?) as usize;
+        let kind = &rest[4..8];
+        let body = rest.get(8..8 + len)?;
+        match kind {
+            b"IHDR" if body.len() >= 13 => header = Some(body.to_vec()),
+            b"PLTE" => {
+                palette = body
+                    .chunks_exact(3)
+                    .map(|c| [c[0], c[1], c[2], 255])
+                    .collect()
+            }
+            b"tRNS" => {
+                for (entry, &alpha) in palette.iter_mut().zip(body) {
+                    entry[3] = alpha;
+                }
+            }
+            b"IDAT" => data.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+        rest = rest.get(12 + len..)?;
+    }
+    let header = header?;
+    let width = u32::from_be_bytes(header[..4].try_into().ok()?) as usize;
+    let height = u32::from_be_bytes(header[4..8].try_into().ok()?) as usize;
+    let (depth, color_type, interlace) = (header[8], header[9], header[12]);
+    let channels = match color_type {
+        0 | 3 => 1,
+        4 => 2,
+        2 => 3,
+        6 => 4,
+        _ => return None,
+    };
+    let pixel_count = width as u64 * height as u64;
+    if interlace != 0 || pixel_count == 0 || pixel_count > MAX_PIXELS {
+        return None;
+    }
+    if !matches!(depth, 1 | 2 | 4 | 8 | 16) {
+        return None;
+    }
+    if color_type == 3 && palette.is_empty() {
+        return None;
+    }
+
+    let bits = channels * depth as usize;
+    let stride = (width * bits).div_ceil(8);
+    let mut raw = Vec::with_capacity((stride + 1) * height);
+    ZlibDecoder::new(&data[..]).read_to_end(&mut raw).ok()?;
+    let rows = unfilter(&raw, stride, bits.div_ceil(8), height)?;
+
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in rows.chunks_exact(stride) {
+        for x in 0..width {
+            let sample = |channel: usize| read_sample(row, x * channels + channel, depth);
+            let pixel = match color_type {
+                0 => {
+                    let v = scale_sample(sample(0), depth);
+                    [v, v, v, 255]
+                }
+                3 => *palette.get(sample(0) as usize)?,
+                4 => {
+                    let v = scale_sample(sample(0), depth);
+                    [v, v, v, scale_sample(sample(1), depth)]
+                }
+                2 => [
+                    scale_sample(sample(0), depth),
+                    scale_sample(sample(1), depth),
+                    scale_sample(sample(2), depth),
+                    255,
+                ],
+                _ => [
+                    scale_sample(sample(0), depth),
+                    scale_sample(sample(1), depth),
+                    scale_sample(sample(2), depth),
+                    scale_sample(sample(3), depth),
+                ],
+            };
+            pixels.push(pixel);
+        }
+    }
+    Some(Rgba {
+        width,
+        height,
+        pixels,
+    })
+}
+
+/// Undo the per-row filters, returning the rows without their filter bytes
+fn unfilter(raw: &[u8], stride: usize, bpp: usize, height: usize) -> Option<Vec<u8>> {
+    let mut out = vec![0u8; stride * height];
+    for y in 0..height {
+        let line = raw.get(y * (stride + 1)..(y + 1) * (stride + 1))?;
+        let (filter, line) = (line[0], &line[1..]);
+        let (done, current) = out.split_at_mut(y * stride);
+        let previous = y.checked_sub(1).map(|_| &done[done.len() - stride..]);
+        let current = &mut current[..stride];
+        for x in 0..stride {
+            let a = if x >= bpp { current[x - bpp] } else { 0 };
+            let b = previous.map_or(0, |p| p[x]);
+            let c = if x >= bpp {
+                previous.map_or(0, |p| p[x - bpp])
+            } else {
+                0
+            };
+            let predicted = match filter {
+                0 => 0,
+                1 => a,
+                2 => b,
+                3 => ((u16::from(a) + u16::from(b)) / 2) as u8,
+                4 => paeth(a, b, c),
+                _ => return None,
+            };
+            current[x] = line[x].wrapping_add(predicted);
+        }
+    }
+    Some(out)
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = i16::from(a) + i16::from(b) - i16::from(c);
+    let (pa, pb, pc) = (
+        (p - i16::from(a)).abs(),
+        (p - i16::from(b)).abs(),
+        (p - i16::from(c)).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+/// The `index`th sample of a row, of `depth` bits each
+fn read_sample(row: &[u8], index: usize, depth: u8) -> u16 {
+    match depth {
+        16 => u16::from_be_bytes([row[index * 2], row[index * 2 + 1]]),
+        8 => u16::from(row[index]),
+        _ => {
+            let depth = depth as usize;
+            let bit = index * depth;
+            let shift = 8 - depth - bit % 8;
+            u16::from((row[bit / 8] >> shift) & ((1 << depth) - 1) as u8)
+        }
+    }
+}
+
+/// A sample of `depth` bits stretched or cut to 8 bits
+fn scale_sample(value: u16, depth: u8) -> u8 {
+    match depth {
+        16 => (value >> 8) as u8,
+        8 => value as u8,
+        _ => (u32::from(value) * 255 / ((1 << depth) - 1)) as u8,
+    }
+}
+
+/// `image` shrunk to fit `width` by `height` pixels at its own shape, each
+/// pixel the average of those it covers. Smaller images are kept as they are.
+pub fn fit(image: &Rgba, width: usize, height: usize) -> Rgba {
+    let scale = (width as f64 / image.width as f64)
+        .min(height as f64 / image.height as f64)
+        .min(1.0);
+    let target_width = ((image.width as f64 * scale) as usize).max(1);
+    let target_height = ((image.height as f64 * scale) as usize).max(1);
+    let mut pixels = Vec::with_capacity(target_width * target_height);
+    for y in 0..target_height {
+        let (top, bottom) = span(y, target_height, image.height);
+        for x in 0..target_width {
+            let (left, right) = span(x, target_width, image.width);
+            let mut sum = [0u32; 4];
+            for row in top..bottom {
+                for pixel in &image.pixels[row * image.width + left..row * image.width + right] {
+                    for (total, &channel) in sum.iter_mut().zip(pixel) {
+                        *total += u32::from(channel);
+                    }
+                }
+            }
+            let count = ((bottom - top) * (right - left)) as u32;
+            pixels.push(sum.map(|total| (total / count) as u8));
+        }
+    }
+    Rgba {
+        width: target_width,
+        height: target_height,
+        pixels,
+    }
+}
+
+/// The source pixels target pixel `index` of `target` covers out of `source`
+fn span(index: usize, target: usize, source: usize) -> (usize, usize) {
+    let start = index * source / target;
+    let end = ((index + 1) * source / target).max(start + 1);
+    (start, end.min(source))
+}
+
+/// The palette register closest to an 8-bit color
+fn register([r, g, b, _]: [u8; 4]) -> usize {
+    let level = |v: u8| (usize::from(v) * (LEVELS - 1) + 127) / 255;
+    (level(r) * LEVELS + level(g)) * LEVELS + level(b)
+}
+
+/// A register's color in sixel's percentages
+fn register_color(register: usize) -> (usize, usize, usize) {
+    let percent = |level: usize| level * 100 / (LEVELS - 1);
+    (
+        percent(register / (LEVELS * LEVELS)),
+        percent(register / LEVELS % LEVELS),
+        percent(register % LEVELS),
+    )
+}
+
+/// The sixel escape drawing `image` at the cursor. Mostly transparent pixels
+/// are left out, so the text under them shows through.
+pub fn encode(image: &Rgba) -> String {
+    let registers = LEVELS * LEVELS * LEVELS;
+    let mut used = vec![false; registers];
+    for &pixel in &image.pixels {
+        if pixel[3] >= 128 {
+            used[register(pixel)] = true;
+        }
+    }
+    // Transparent background, square pixels
+    let mut out = format!("\x1bP0;1;0q\"1;1;{};{}", image.width, image.height);
+    for (idx, _) in used.iter().enumerate().filter(|(_, used)| **used) {
+        let (r, g, b) = register_color(idx);
+        out.push_str(&format!("#{};2;{};{};{}", idx, r, g, b));
+    }
+
+    let mut bands: Vec<Option<Vec<u8>>> = vec![None; registers];
+    for top in (0..image.height).step_by(6) {
+        for dy in 0..6.min(image.height - top) {
+            let row = &image.pixels[(top + dy) * image.width..(top + dy + 1) * image.width];
+            for (x, &pixel) in row.iter().enumerate() {
+                if pixel[3] < 128 {
+                    continue;
+                }
+                let bits = bands[register(pixel)].get_or_insert_with(|| vec![0; image.width]);
+                bits[x] |= 1 << dy;
+            }
+        }
+        let mut first = true;
+        for (idx, bits) in bands.iter_mut().enumerate() {
+            let Some(bits) = bits.take() else {
+                continue;
+            };
+            if !first {
+                out.push('$');
+            }
+            first = false;
+            out.push_str(&format!("#{}", idx));
+            push_run_length(&mut out, &bits);
+        }
+        out.push('-');
+    }
+    out.push_str("\x1b\\");
+    out
+}
+
+/// Sixel characters for `bits`, runs of the same one written as `!<n><c>`
+fn push_run_length(out: &mut String, bits: &[u8]) {
+    let mut rest = bits;
+    while let Some(&first) = rest.first() {
+        let run = rest.iter().take_while(|&&b| b == first).count();
+        let c = char::from(b'?' + first);
+        if run > 3 {
+            out.push_str(&format!("!{}{}", run, c));
+        } else {
+            (0..run).for_each(|_| out.push(c));
+        }
+        rest = &rest[run..];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::Compression;
+    use flate2::write::ZlibEncoder;
+    use std::io::Write;
+
+    /// A PNG of `rows`, each starting with its filter byte
+    fn png(width: u32, height: u32, depth: u8, color_type: u8, rows: &[u8]) -> Vec<u8> {
+        let chunk = |kind: &[u8], body: &[u8]| {
+            let mut out = (body.len() as u32).to_be_bytes().to_vec();
+            out.extend_from_slice(kind);
+            out.extend_from_slice(body);
+            // The CRC isn't checked
+            out.extend_from_slice(&[0; 4]);
+            out
+        };
+        let mut header = width.to_be_bytes().to_vec();
+        header.extend_from_slice(&height.to_be_bytes());
+        header.extend_from_slice(&[depth, color_type, 0, 0, 0]);
+        let mut zlib = ZlibEncoder::new(Vec::new(), Compression::default());
+        zlib.write_all(rows).unwrap();
+
+        let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+        out.extend(chunk(b"IHDR", &header));
+        out.extend(chunk(b"IDAT", &zlib.finish().unwrap()));
+        out.extend(chunk(b"IEND", &[]));
+        out
+    }
+
+    #[test]
+    fn test_decode_undoes_the_row_filters() {
+        // Red, green; then the same again through the Up and Sub filters
+        let rows = [
+            0, 255, 0, 0, 0, 255, 0, //
+            2, 0, 0, 0, 0, 0, 0, //
+            1, 255, 0, 0, 1, 255, 0,
+        ];
+        let image = decode_png(&png(2, 3, 8, 2, &rows)).unwrap();
+        let red = [255, 0, 0, 255];
+        let green = [0, 255, 0, 255];
+        assert_eq!(image.pixels, [red, green, red, green, red, green]);
+
+        // Two pixels of 1-bit gray
+        let gray = decode_png(&png(2, 1, 1, 0, &[0, 0b0100_0000])).unwrap();
+        assert_eq!(gray.pixels, [[0, 0, 0, 255], [255, 255, 255, 255]]);
+        assert_eq!(decode_png(b"GIF89a"), None);
+    }
+
+    #[test]
+    fn test_fit_averages_the_pixels_it_covers() {
+        let image = Rgba {
+            width: 4,
+            height: 2,
+            pixels: vec![
+                [0, 0, 0, 255],
+                [255, 255, 255, 255],
+                [0, 0, 0, 255],
+                [0, 0, 0, 255],
+                [0, 0, 0, 255],
+                [255, 255, 255, 255],
+                [0, 0, 0, 255],
+                [0, 0, 0, 255],
+            ],
+        };
+        let small = fit(&image, 2, 10);
+        assert_eq!((small.width, small.height), (2, 1));
+        assert_eq!(small.pixels, [[127, 127, 127, 255], [0, 0, 0, 255]]);
+        assert_eq!(fit(&image, 100, 100), image);
+    }
+
+    #[test]
+    fn test_encode_writes_bands_with_run_lengths() {
+        // 8 red pixels over 2 rows and a transparent third
+        let red = [255, 0, 0, 255];
+        let clear = [0, 0, 0, 0];
+        let image = Rgba {
+            width: 8,
+            height: 3,
+            pixels: [vec![red; 16], vec![clear; 8]].concat(),
+        };
+        let red = register([255, 0, 0, 255]);
+        assert_eq!(
+            encode(&image),
+            format!("\x1bP0;1;0q\"1;1;8;3#{red};2;100;0;0#{red}!8B-\x1b\\")
+        );
+    }
+}
@@ -1,36 +1,132 @@
+use super::{ImagePreview, ImageProtocol};
 use crate::error::Result;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
-    terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+    terminal::{
+        EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode, window_size,
+    },
 };
 use ratatui::prelude::*;
-use std::io::{self, Stdout, stdout};
+use std::fs;
+use std::io::{self, Stdout, Write, stdout};
 
 pub type CrosstermTerminal = ratatui::Terminal<CrosstermBackend<Stdout>>;
 
 pub struct Terminal {
-    terminal: CrosstermTerminal,
+    /// None in linear mode, where nothing is drawn
+    terminal: Option<CrosstermTerminal>,
+    image_protocol: Option<ImageProtocol>,
+    /// The image drawn over the screen at the moment
+    shown: Option<ImagePreview>,
 }
 
 impl Terminal {
     pub fn new() -> Result<Self> {
         let terminal = setup_terminal()?;
-        Ok(Self { terminal })
+        Ok(Self {
+            terminal: Some(terminal),
+            image_protocol: ImageProtocol::detect(),
+            shown: None,
+        })
+    }
+
+    /// Read keys without taking over the screen; output goes through
+    /// `announce` as ordinary lines
+    pub fn linear() -> Result<Self> {
+        enable_raw_mode()?;
+        Ok(Self {
+            terminal: None,
+            image_protocol: None,
+            shown: None,
+        })
     }
 
     pub fn draw<F>(&mut self, f: F) -> Result<()>
     where
         F: FnOnce(&mut Frame),
     {
-        self.terminal.draw(f)?;
+        if let Some(terminal) = &mut self.terminal {
+            terminal.draw(f)?;
+        }
+        Ok(())
+    }
+
+    /// How images can be drawn, if at all
+    pub fn image_protocol(&self) -> Option<ImageProtocol> {
+        self.image_protocol
+    }
+
+    /// Draw `image` over the last frame unless it is already there. Returns
+    /// true when the screen had to be wiped to take the old image away, and
+    /// the frame has to be drawn again before calling this once more.
+    pub fn show_image(&mut self, image: Option<&ImagePreview>) -> Result<bool> {
+        let Some(protocol) = self.image_protocol else {
+            return Ok(false);
+        };
+        if self.shown.as_ref() == image {
+            return Ok(false);
+        }
+        let mut stdout = stdout();
+        if self.shown.take().is_some() {
+            match protocol.clear() {
+                Some(escape) => write!(stdout, "{}", escape)?,
+                None => {
+                    if let Some(terminal) = &mut self.terminal {
+                        terminal.clear()?;
+                    }
+                    return Ok(true);
+                }
+            }
+        }
+        if let Some(image) = image {
+            // A file that went away just shows no picture
+            if let Ok(png) = fs::read(&image.path) {
+                write!(
+                    stdout,
+                    "{}",
+                    protocol.draw(&png, image.dimensions, image.area, cell_size())
+                )?;
+            }
+            self.shown = Some(image.clone());
+        }
+        stdout.flush()?;
+        Ok(false)
+    }
+
+    /// Print a line after the previous ones
+    pub fn announce(&mut self, line: &str) -> Result<()> {
+        let mut stdout = stdout();
+        // Raw mode needs the carriage return spelled out
+        write!(stdout, "{}\r\n", line)?;
+        stdout.flush()?;
         Ok(())
     }
 }
 
 impl Drop for Terminal {
     fn drop(&mut self) {
-        let _ = restore_terminal();
+        if let (Some(_), Some(escape)) = (
+            &self.shown,
+            self.image_protocol.and_then(ImageProtocol::clear),
+        ) {
+            let _ = write!(stdout(), "{}", escape);
+        }
+        let _ = match self.terminal {
+            Some(_) => restore_terminal(),
+            None => disable_raw_mode(),
+        };
+    }
+}
+
+/// Pixels per cell, for images drawn pixel by pixel. Terminals that don't
+/// report their size in pixels are taken to use a common 10 by 20.
+fn cell_size() -> (u16, u16) {
+    match window_size() {
+        Ok(size) if size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0 => {
+            (size.width / size.columns, size.height / size.rows)
+        }
+        _ => (10, 20),
     }
 }
 
@@ -0,0 +1,66 @@
+//! Picking the account instances launch with.
+
+use crate::app::{App, Screen};
+use crate::message::Message;
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::SelectAccount(idx) => {
+            if idx < app.accounts.len() {
+                app.selected_account_index = idx;
+            }
+        }
+        Message::ConfirmAccountSelection => {
+            if let Some(account) = app.selected_account().cloned() {
+                app.active_account = Some(account);
+                app.screen = Screen::Instances;
+            }
+        }
+        Message::CycleAccount => {
+            if !app.accounts.is_empty() {
+                let current = app.active_account.as_ref().and_then(|active| {
+                    app.accounts
+                        .iter()
+                        .position(|a| a.profile_id == active.profile_id)
+                });
+                let next = current.map_or(0, |idx| (idx + 1) % app.accounts.len());
+                let account = app.accounts[next].clone();
+                app.selected_account_index = next;
+                app.set_status(format!("Account: {}", account.username));
+                app.active_account = Some(account);
+            }
+        }
+        Message::OpenAccountScreen => {
+            app.previous_screen = Some(app.screen);
+            app.screen = Screen::Accounts;
+        }
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Account;
+    use crate::update::tests::test_app;
+
+    #[test]
+    fn test_cycle_account_wraps_around() {
+        let mut app = test_app("accounts", &[]);
+        app.accounts = ["Steve", "Alex"]
+            .map(|name| Account {
+                profile_id: name.to_lowercase(),
+                username: name.to_string(),
+                is_active: false,
+            })
+            .to_vec();
+
+        reduce(&mut app, Message::SelectAccount(5)).unwrap();
+        assert_eq!(app.selected_account_index, 0);
+        for expected in ["Steve", "Alex", "Steve"] {
+            reduce(&mut app, Message::CycleAccount).unwrap();
+            assert_eq!(app.active_account.as_ref().unwrap().username, expected);
+        }
+    }
+}
@@ -0,0 +1,450 @@
+//! The new-instance screen: a saved template, or a Minecraft version, a
+//! loader and its version picked from lists, then a name. A modpack from
+//! Modrinth or CurseForge can be installed from here instead.
+
+use crate::app::{App, CreateStep, InputMode, InstanceDraft, Screen};
+use crate::command::Command;
+use crate::data::{Instance, PrismConfig, create_instance, load_templates, templates_dir};
+use crate::message::Message;
+use std::collections::HashMap;
+
+use super::dispatch;
+use super::instances::{queue_loader_catalog_fetch, queue_version_manifest_fetch};
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::OpenCreateInstance => {
+            app.draft = InstanceDraft::default();
+            app.templates = load_templates(&templates_dir());
+            if !app.templates.is_empty() {
+                app.draft.step = CreateStep::Template;
+            }
+            if app.screen != Screen::CreateInstance {
+                app.previous_screen = Some(app.screen);
+            }
+            app.screen = Screen::CreateInstance;
+            queue_version_manifest_fetch(app);
+        }
+        Message::SelectDraftItem(idx) => {
+            if idx < app.draft_len() {
+                app.draft.selected = idx;
+            }
+        }
+        Message::ToggleAllVersions => {
+            if app.draft.step == CreateStep::MinecraftVersion {
+                // Stay on the same version when the list changes around it
+                let selected = app
+                    .draft_versions()
+                    .get(app.draft.selected)
+                    .map(|v| v.id.clone());
+                app.draft.all_versions = !app.draft.all_versions;
+                app.draft.selected = app
+                    .draft_versions()
+                    .iter()
+                    .position(|v| Some(&v.id) == selected.as_ref())
+                    .unwrap_or(0);
+            }
+        }
+        Message::ConfirmDraftItem => match app.draft.step {
+            CreateStep::Template => match app.draft.selected.checked_sub(1) {
+                None => {
+                    app.draft.step = CreateStep::MinecraftVersion;
+                    app.draft.selected = 0;
+                }
+                Some(idx) => {
+                    let Some(template) = app.templates.get(idx) else {
+                        return Ok(());
+                    };
+                    app.input_buffer = template.name.clone();
+                    app.chosen_template = Some(idx);
+                    app.input_mode = InputMode::NewFromTemplate;
+                }
+            },
+            CreateStep::MinecraftVersion => {
+                let Some(version) = app
+                    .draft_versions()
+                    .get(app.draft.selected)
+                    .map(|v| v.id.clone())
+                else {
+                    return Ok(());
+                };
+                app.draft.minecraft_version = Some(version);
+                app.draft.step = CreateStep::Loader;
+                app.draft.selected = 0;
+            }
+            CreateStep::Loader => {
+                let Some(&loader) = app.draft_loaders().get(app.draft.selected) else {
+                    return Ok(());
+                };
+                app.draft.loader = loader;
+                app.draft.loader_version = None;
+                match (loader, app.draft.minecraft_version.clone()) {
+                    (Some(loader), Some(minecraft_version)) => {
+                        app.draft.step = CreateStep::LoaderVersion;
+                        app.draft.selected = 0;
+                        queue_loader_catalog_fetch(app, loader, minecraft_version);
+                    }
+                    _ => ask_name(app),
+                }
+            }
+            CreateStep::LoaderVersion => {
+                let Some(version) = app
+                    .draft_loader_versions()
+                    .and_then(|c| c.get(app.draft.selected))
+                    .map(|v| v.version.clone())
+                else {
+                    return Ok(());
+                };
+                app.draft.loader_version = Some(version);
+                ask_name(app);
+            }
+        },
+        Message::DraftStepBack => match app.draft.step {
+            CreateStep::MinecraftVersion if !app.templates.is_empty() => {
+                app.draft.step = CreateStep::Template;
+                app.draft.selected = 0;
+            }
+            CreateStep::Template | CreateStep::MinecraftVersion => dispatch(app, Message::Back),
+            CreateStep::Loader => {
+                app.draft.step = CreateStep::MinecraftVersion;
+                let version = app.draft.minecraft_version.take();
+                app.draft.selected = app
+                    .draft_versions()
+                    .iter()
+                    .position(|v| Some(&v.id) == version.as_ref())
+                    .unwrap_or(0);
+            }
+            CreateStep::LoaderVersion => {
+                app.draft.step = CreateStep::Loader;
+                let loader = app.draft.loader.take();
+                app.draft.selected = app
+                    .draft_loaders()
+                    .iter()
+                    .position(|l| *l == loader)
+                    .unwrap_or(0);
+            }
+        },
+        Message::InstanceCreated { name, result } => show_created_instance(app, &name, result),
+        Message::StartPackSearch => {
+            app.input_buffer.clear();
+            app.input_mode = InputMode::SearchPacks;
+        }
+        Message::PackSearchDone(result) => {
+            // Leaving the screen drops the search
+            if app.screen != Screen::CreateInstance || app.input_mode != InputMode::Normal {
+                return Ok(());
+            }
+            app.status_message = None;
+            match result {
+                Ok(hits) if hits.is_empty() => app.set_error("No modpacks found".to_string()),
+                Ok(hits) => {
+                    app.mod_hits = hits;
+                    app.input_mode = InputMode::PickPackHit;
+                }
+                Err(e) => app.set_error(format!("Modpack search failed: {}", e)),
+            }
+        }
+        Message::InstallPackHit(idx) => {
+            let Some(hit) = app.mod_hits.get(idx).cloned() else {
+                return Ok(());
+            };
+            app.mod_hits.clear();
+            match PrismConfig::load(&app.data_dir) {
+                Ok(config) => {
+                    let status = format!("Installing {}…", hit.name);
+                    app.commands.push(Command::InstallPack {
+                        hit,
+                        instances_dir: config.instances_dir(),
+                        curseforge_key: app.curseforge_key(),
+                    });
+                    close_wizard(app);
+                    app.set_status(status);
+                }
+                Err(e) => {
+                    app.input_mode = InputMode::Normal;
+                    app.set_error(format!("Failed to install {}: {}", hit.name, e));
+                }
+            }
+        }
+        Message::PackInstalled { name, result } => match result {
+            Ok(install) => match add_created_instance(app, &install.id) {
+                Ok(()) if install.manual.is_empty() => {
+                    app.set_status(format!("Installed {}", name));
+                }
+                Ok(()) => app.set_error(format!(
+                    "Installed {}, but CurseForge only hands out {} on its website; \
+                     put them in the instance's mods folder",
+                    name,
+                    install.manual.join(", ")
+                )),
+                Err(e) => app.set_error(format!("Installed {} but couldn't read it: {}", name, e)),
+            },
+            Err(e) => app.set_error(format!("Failed to install {}: {}", name, e)),
+        },
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+/// Search both sites for modpacks matching `query`, in the background
+pub(super) fn search_packs(app: &mut App, query: &str) {
+    if query.is_empty() {
+        app.set_error("Type what to search for".to_string());
+        return;
+    }
+    app.commands.push(Command::SearchPacks {
+        query: query.to_string(),
+        curseforge_key: app.curseforge_key(),
+    });
+    app.input_buffer.clear();
+    app.input_mode = InputMode::Normal;
+    app.set_status(format!("Searching for {}…", query));
+}
+
+/// Suggest a name from the choices, e.g. "1.20.1 Fabric"
+fn ask_name(app: &mut App) {
+    let version = app.draft.minecraft_version.clone().unwrap_or_default();
+    app.input_buffer = match app.draft.loader {
+        Some(loader) => format!("{} {}", version, loader.name()),
+        None => version,
+    };
+    app.input_mode = InputMode::NameNewInstance;
+}
+
+/// Write the drafted instance as `name` and load it into the list
+pub(super) fn create_drafted_instance(app: &mut App, name: &str) {
+    let Some(minecraft_version) = app.draft.minecraft_version.clone() else {
+        return;
+    };
+    let loader_components = match (app.draft.loader, &app.draft.loader_version) {
+        (Some(loader), Some(version)) => loader.pack_components(&minecraft_version, version),
+        _ => Vec::new(),
+    };
+    let created = PrismConfig::load(&app.data_dir).and_then(|config| {
+        create_instance(
+            &config.instances_dir(),
+            name,
+            &minecraft_version,
+            &loader_components,
+        )
+    });
+    if created.is_ok() {
+        close_wizard(app);
+    }
+    show_created_instance(app, name, created.map_err(|e| e.to_string()));
+}
+
+/// Copy the chosen template into a new instance called `name`, in the
+/// background
+pub(super) fn create_from_chosen_template(app: &mut App, name: &str) {
+    let Some(template) = app.chosen_template.and_then(|i| app.templates.get(i)) else {
+        return;
+    };
+    let template = template.clone();
+    match PrismConfig::load(&app.data_dir) {
+        Ok(config) => {
+            app.commands.push(Command::CreateFromTemplate {
+                template,
+                instances_dir: config.instances_dir(),
+                name: name.to_string(),
+            });
+            close_wizard(app);
+            app.set_status(format!("Creating {}…", name));
+        }
+        Err(e) => app.set_error(format!("Failed to create instance: {}", e)),
+    }
+}
+
+/// Back to the instance list once the new instance is on its way
+fn close_wizard(app: &mut App) {
+    app.input_buffer.clear();
+    app.input_mode = InputMode::Normal;
+    app.screen = Screen::Instances;
+    app.previous_screen = None;
+}
+
+/// Add an instance created in the folder `id` to the list and select it, or
+/// say why it wasn't created
+fn show_created_instance(app: &mut App, name: &str, result: Result<String, String>) {
+    match result {
+        Ok(id) => match add_created_instance(app, &id) {
+            Ok(()) => app.set_status(format!("Created {}", name)),
+            Err(e) => app.set_error(format!("Created {} but couldn't read it: {}", name, e)),
+        },
+        Err(e) => app.set_error(format!("Failed to create instance: {}", e)),
+    }
+}
+
+/// Read the instance just created in folder `id` into the list and select it
+fn add_created_instance(app: &mut App, id: &str) -> crate::error::Result<()> {
+    let config = PrismConfig::load(&app.data_dir)?;
+    let instance = Instance::load(config.instances_dir().join(id), &HashMap::new())?;
+    app.add_loaded_instance(instance);
+    app.place_loaded_instances();
+    app.select_instance_by_id(id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Template;
+    use crate::net::loaders::Loader;
+    use crate::net::mod_source::{ModHit, ModSource};
+    use crate::net::modpack::PackInstall;
+    use crate::net::mojang::{LatestVersions, ManifestVersion, VersionManifest, VersionType};
+    use crate::update::tests::test_app;
+
+    fn version(id: &str, kind: VersionType) -> ManifestVersion {
+        ManifestVersion {
+            id: id.to_string(),
+            kind,
+            url: String::new(),
+            release_time: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_steps_forward_and_back_keep_the_choices() {
+        let mut app = test_app("create", &[]);
+        reduce(&mut app, Message::OpenCreateInstance).unwrap();
+        app.version_manifest = Some(VersionManifest {
+            latest: LatestVersions {
+                release: "1.21.4".to_string(),
+                snapshot: "25w02a".to_string(),
+            },
+            versions: vec![
+                version("25w02a", VersionType::Snapshot),
+                version("1.21.4", VersionType::Release),
+                version("1.20.1", VersionType::Release),
+            ],
+        });
+
+        reduce(&mut app, Message::SelectDraftItem(1)).unwrap();
+        reduce(&mut app, Message::ToggleAllVersions).unwrap();
+        assert_eq!(app.draft.selected, 2);
+        reduce(&mut app, Message::ConfirmDraftItem).unwrap();
+        assert_eq!(app.draft.minecraft_version.as_deref(), Some("1.20.1"));
+
+        reduce(&mut app, Message::SelectDraftItem(1)).unwrap();
+        reduce(&mut app, Message::ConfirmDraftItem).unwrap();
+        assert_eq!(app.draft.step, CreateStep::LoaderVersion);
+        assert_eq!(app.draft.loader, Some(Loader::Fabric));
+
+        reduce(&mut app, Message::DraftStepBack).unwrap();
+        reduce(&mut app, Message::DraftStepBack).unwrap();
+        assert_eq!(app.draft.step, CreateStep::MinecraftVersion);
+        assert_eq!(app.draft.selected, 2);
+
+        reduce(&mut app, Message::ConfirmDraftItem).unwrap();
+        reduce(&mut app, Message::ConfirmDraftItem).unwrap();
+        assert_eq!(app.input_mode, InputMode::NameNewInstance);
+        assert_eq!(app.input_buffer, "1.20.1");
+    }
+
+    #[test]
+    fn test_templates_are_offered_first_and_copied_in_the_background() {
+        let mut app = test_app("create-template", &["Alpha"]);
+        reduce(&mut app, Message::OpenCreateInstance).unwrap();
+        app.templates = vec![Template {
+            name: "Modded".to_string(),
+            path: app.data_dir.join("Modded"),
+            minecraft_version: "1.20.1".to_string(),
+            mod_loader: Some("Fabric".to_string()),
+            mod_count: 3,
+        }];
+
+        reduce(&mut app, Message::DraftStepBack).unwrap();
+        assert_eq!(app.draft.step, CreateStep::Template);
+        assert_eq!(app.draft_len(), 2);
+        reduce(&mut app, Message::SelectDraftItem(1)).unwrap();
+        reduce(&mut app, Message::ConfirmDraftItem).unwrap();
+        assert_eq!(app.input_mode, InputMode::NewFromTemplate);
+        assert_eq!(app.input_buffer, "Modded");
+
+        app.commands.clear();
+        create_from_chosen_template(&mut app, "Modded 2");
+        let commands = std::mem::take(&mut app.commands);
+        assert!(matches!(
+            &commands[..],
+            [Command::CreateFromTemplate { template, name, .. }]
+                if template.name == "Modded" && name == "Modded 2"
+        ));
+        assert_eq!(app.screen, Screen::Instances);
+
+        let dir = app.data_dir.join("instances").join("Modded 2");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("instance.cfg"), "[General]\nname=Modded 2\n").unwrap();
+        let created = Message::InstanceCreated {
+            name: "Modded 2".to_string(),
+            result: Ok("Modded 2".to_string()),
+        };
+        reduce(&mut app, created).unwrap();
+        std::fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert_eq!(app.instances.len(), 2);
+        assert_eq!(
+            app.selected_instance().map(|i| i.name.as_str()),
+            Some("Modded 2")
+        );
+        assert_eq!(app.status_message.as_deref(), Some("Created Modded 2"));
+    }
+
+    #[test]
+    fn test_modpacks_are_searched_and_installed_in_the_background() {
+        let mut app = test_app("create-pack", &["Alpha"]);
+        reduce(&mut app, Message::OpenCreateInstance).unwrap();
+        reduce(&mut app, Message::StartPackSearch).unwrap();
+        assert_eq!(app.input_mode, InputMode::SearchPacks);
+        app.commands.clear();
+        search_packs(&mut app, "all the mods");
+        assert_eq!(
+            std::mem::take(&mut app.commands),
+            vec![Command::SearchPacks {
+                query: "all the mods".to_string(),
+                curseforge_key: None,
+            }]
+        );
+
+        let hit = ModHit {
+            source: ModSource::CurseForge,
+            id: "715572".to_string(),
+            name: "All the Mods 9".to_string(),
+            summary: String::new(),
+            downloads: 9_000_000,
+        };
+        reduce(&mut app, Message::PackSearchDone(Ok(vec![hit.clone()]))).unwrap();
+        assert_eq!(app.input_mode, InputMode::PickPackHit);
+        reduce(&mut app, Message::InstallPackHit(0)).unwrap();
+        let commands = std::mem::take(&mut app.commands);
+        assert!(matches!(
+            &commands[..],
+            [Command::InstallPack { hit: installed, instances_dir, .. }]
+                if *installed == hit && *instances_dir == app.data_dir.join("instances")
+        ));
+        assert_eq!(app.screen, Screen::Instances);
+
+        let dir = app.data_dir.join("instances").join("All the Mods 9");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("instance.cfg"), "[General]\nname=All the Mods 9\n").unwrap();
+        let installed = Message::PackInstalled {
+            name: hit.name.clone(),
+            result: Ok(PackInstall {
+                id: "All the Mods 9".to_string(),
+                manual: vec!["optifine.jar".to_string()],
+            }),
+        };
+        reduce(&mut app, installed).unwrap();
+        std::fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert_eq!(
+            app.selected_instance().map(|i| i.name.as_str()),
+            Some("All the Mods 9")
+        );
+        assert!(
+            app.error_message
+                .as_deref()
+                .is_some_and(|e| e.contains("optifine.jar"))
+        );
+    }
+}
@@ -0,0 +1,665 @@
+//! Everything not tied to one screen: ticks, navigation, help and the tour,
+//! settings, global search, error details and outside file changes.
+
+use crate::actions::{copy_to_clipboard, open_launcher};
+use crate::app::{
+    App, ErrorDetails, ExternalChange, FREE_SPACE_CHECK_INTERVAL, InputMode, LOG_FOLLOW_INTERVAL,
+    LogSource, STORAGE_USAGE_MAX_AGE, Screen,
+};
+use crate::command::Command;
+use crate::data::{
+    GLOBAL_SETTINGS, PrismConfig, PrismSettings, STORAGE_USAGE_CACHE, SearchKind, SearchTarget,
+    StorageUsage, find_instance, merge_server_changes, search_items,
+};
+use crate::message::Message;
+use crate::net::cache;
+use crate::net::control::ControlCommand;
+use crate::schedule::Periodic;
+use crate::view::TOUR_STEP_COUNT;
+use std::time::Instant;
+
+use super::instances::{queue_process_scan, reload_selected_instance, start_instance};
+use super::logs::{open_logs, refresh_followed_log, reopen_logs};
+use super::servers::{save_server_list, set_servers_error, stop_lan_discovery};
+use super::{dispatch, open_screen, publish_metrics, reload_data};
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::Tick => {
+            app.place_loaded_instances();
+            publish_metrics(app);
+            let now = Instant::now();
+            let scan_interval = app.app_config.process_scan_interval();
+            if !app.running_instances.is_empty()
+                && !app.process_scan_pending
+                && app.scheduler.due(Periodic::ProcessScan, scan_interval, now)
+            {
+                queue_process_scan(app);
+            }
+            if app
+                .scheduler
+                .due(Periodic::FreeSpace, FREE_SPACE_CHECK_INTERVAL, now)
+            {
+                app.refresh_free_space();
+            }
+            if app.screen == Screen::Logs
+                && app.log_follow
+                && app
+                    .scheduler
+                    .due(Periodic::FollowLog, LOG_FOLLOW_INTERVAL, now)
+            {
+                refresh_followed_log(app);
+            }
+            if app.screen == Screen::Servers {
+                app.prune_lan_games();
+            } else {
+                stop_lan_discovery(app);
+            }
+        }
+        Message::SwitchToScreen(screen) => match screen {
+            Screen::Instances => {
+                app.screen = Screen::Instances;
+            }
+            Screen::Accounts => {
+                dispatch(app, Message::OpenAccountScreen);
+            }
+            Screen::Servers => {
+                dispatch(app, Message::OpenServerScreen);
+            }
+            Screen::Logs => {
+                dispatch(app, Message::OpenLogsTab);
+            }
+            _ => {}
+        },
+        Message::OpenSettings => match PrismSettings::load(&app.data_dir) {
+            Ok(settings) => {
+                app.prism_settings = settings;
+                app.previous_screen = Some(app.screen);
+                app.screen = Screen::Settings;
+            }
+            Err(e) => app.set_error_at(
+                format!("Failed to load prismlauncher.cfg: {}", e),
+                &app.data_dir.join("prismlauncher.cfg"),
+            ),
+        },
+        Message::SelectSetting(idx) => {
+            if idx < GLOBAL_SETTINGS.len() {
+                app.selected_setting_index = idx;
+            }
+        }
+        Message::EditSetting => {
+            if let Some(def) = GLOBAL_SETTINGS.get(app.selected_setting_index) {
+                if def.editable {
+                    app.input_buffer = app.prism_settings.get(def.key).unwrap_or("").to_string();
+                    app.input_mode = InputMode::EditPrismSetting;
+                } else {
+                    app.set_error(format!("{} is read-only here", def.label));
+                }
+            }
+        }
+        Message::OpenLauncher => match open_launcher() {
+            Ok(()) => {
+                app.set_status("Opened PrismLauncher; press r here when you're done".to_string())
+            }
+            Err(e) => app.set_error(e.to_string()),
+        },
+        Message::ReloadData => {
+            let dir = app.data_dir.clone();
+            match reload_data(app, &dir) {
+                Ok(()) => app.set_status("Reloaded".to_string()),
+                Err(e) => app.set_error(format!("Failed to reload: {}", e)),
+            }
+        }
+        Message::RetryDataFiles => {
+            let dir = app.data_dir.clone();
+            if let Err(e) = reload_data(app, &dir) {
+                app.set_error(format!("Failed to reload: {}", e));
+                return Ok(());
+            }
+            if app.error_message.is_none() {
+                app.set_status("Reloaded".to_string());
+            }
+        }
+        Message::EditDataDir => {
+            app.input_buffer = app.data_dir.display().to_string();
+            app.input_mode = InputMode::SetDataDir;
+        }
+        Message::Control(command) => match command {
+            ControlCommand::Launch { instance, server } => {
+                match find_instance(&app.instances, &app.app_config.aliases, &instance) {
+                    Some(target) => {
+                        let id = target.id.clone();
+                        let name = target.name.clone();
+                        let server = server.or_else(|| target.join_address().map(str::to_string));
+                        if start_instance(app, id, server, false) {
+                            app.set_status(format!("Launched {}", name));
+                        }
+                    }
+                    None => app.set_error(format!("No instance matching '{}'", instance)),
+                }
+            }
+            ControlCommand::Focus { screen, instance } => {
+                if let Some(query) = instance {
+                    let id = find_instance(&app.instances, &app.app_config.aliases, &query)
+                        .map(|i| i.id.clone());
+                    match id {
+                        Some(id) => {
+                            app.select_instance_by_id(&id);
+                        }
+                        None => {
+                            app.set_error(format!("No instance matching '{}'", query));
+                            return Ok(());
+                        }
+                    }
+                }
+                // Leave any prompt or overlay so the screen is usable
+                dispatch(app, Message::InputCancel);
+                open_screen(app, screen);
+            }
+            ControlCommand::Quit => dispatch(app, Message::Quit),
+        },
+        Message::OpenHelp => {
+            app.previous_screen = Some(app.screen);
+            app.help_scroll_offset = 0;
+            app.screen = Screen::Help;
+        }
+        Message::StartTour => {
+            if app.screen == Screen::Help {
+                dispatch(app, Message::Back);
+            }
+            app.tour_step = Some(0);
+        }
+        Message::TourStep(delta) => {
+            if let Some(step) = app.tour_step {
+                let next = step.saturating_add_signed(delta);
+                if next >= TOUR_STEP_COUNT {
+                    dispatch(app, Message::EndTour);
+                } else {
+                    app.tour_step = Some(next);
+                    if !app.app_config.tour_seen {
+                        app.app_config.tour_step = next;
+                        app.save_config();
+                    }
+                }
+            }
+        }
+        Message::EndTour => {
+            app.tour_step = None;
+            if !app.app_config.tour_seen {
+                app.app_config.tour_seen = true;
+                app.app_config.tour_step = 0;
+                app.save_config();
+            }
+        }
+        Message::Back => {
+            if let Some(prev) = app.previous_screen.take() {
+                app.screen = prev;
+            } else {
+                app.screen = Screen::Instances;
+            }
+        }
+        Message::ToggleDebugOverlay => {
+            app.show_debug_overlay = !app.show_debug_overlay;
+        }
+        Message::ToggleDryRun => {
+            app.dry_run = !app.dry_run;
+            app.set_status(
+                if app.dry_run {
+                    "Dry run on: deletes and cleanups are previewed first"
+                } else {
+                    "Dry run off"
+                }
+                .to_string(),
+            );
+        }
+        Message::ScrollDryRunPreview(delta) => {
+            if let Some(preview) = &mut app.dry_run_preview {
+                let last = preview.lines.len().saturating_sub(1);
+                preview.scroll = preview.scroll.saturating_add_signed(delta).min(last);
+            }
+        }
+        Message::ConfirmDryRun => {
+            app.input_mode = InputMode::Normal;
+            if let Some(preview) = app.dry_run_preview.take() {
+                // Run the real thing this once
+                app.dry_run = false;
+                dispatch(app, preview.confirm);
+                app.dry_run = true;
+            }
+        }
+        Message::ShowErrorDetails => {
+            if let Some(message) = app.error_message.take() {
+                let path = app.error_path.take();
+                app.error_details = Some(ErrorDetails::new(&message, path));
+                app.input_mode = InputMode::ErrorDetails;
+            }
+        }
+        Message::ScrollErrorDetails(delta) => {
+            if let Some(details) = &mut app.error_details {
+                let last = details.to_text().lines().count().saturating_sub(1);
+                details.scroll = details.scroll.saturating_add_signed(delta).min(last);
+            }
+        }
+        Message::CopyErrorDetails => {
+            if let Some(text) = app.error_details.as_ref().map(ErrorDetails::to_text) {
+                match copy_to_clipboard(&text) {
+                    Ok(_) => app.set_status("Copied error details".to_string()),
+                    Err(e) => app.set_error(e.to_string()),
+                }
+            }
+        }
+        Message::OpenStorageOverview => {
+            // A scan from an earlier run shows right away while a new one runs
+            if app
+                .storage_usage
+                .as_ref()
+                .is_none_or(|usage| usage.data_dir != app.data_dir)
+            {
+                app.storage_usage = cache::load::<StorageUsage>(STORAGE_USAGE_CACHE, None)
+                    .filter(|usage| usage.data_dir == app.data_dir);
+            }
+            app.input_mode = InputMode::StorageOverview;
+            let fresh = app.storage_usage.as_ref().is_some_and(|usage| {
+                usage
+                    .scanned_at
+                    .elapsed()
+                    .is_ok_and(|age| age < STORAGE_USAGE_MAX_AGE)
+            });
+            if !fresh {
+                scan_storage(app);
+            }
+        }
+        Message::RescanStorage => scan_storage(app),
+        Message::StorageScanned(usage) => {
+            app.storage_scanning = false;
+            if usage.data_dir == app.data_dir {
+                app.storage_usage = Some(usage);
+            }
+        }
+        Message::StorageInfoRead { id, info } => {
+            app.instance_storage.insert(id, info);
+        }
+        Message::ReloadChangedFile => {
+            app.input_mode = InputMode::Normal;
+            let result = match app.external_change.take() {
+                Some(ExternalChange::Servers) => app.load_servers_for_instance(),
+                Some(ExternalChange::InstanceCfg(_)) => reload_selected_instance(app),
+                None => return Ok(()),
+            };
+            match result {
+                Ok(()) => app.set_status("Reloaded from disk; your change was dropped".to_string()),
+                Err(e) => app.set_error(format!("Failed to reload: {}", e)),
+            }
+        }
+        Message::MergeChangedFile => {
+            app.input_mode = InputMode::Normal;
+            match app.external_change.take() {
+                Some(ExternalChange::Servers) => {
+                    // Apply our changes since the last read to the file's list
+                    let ours = std::mem::take(&mut app.servers);
+                    let base = std::mem::take(&mut app.servers_base);
+                    if let Err(e) = app.load_servers_for_instance() {
+                        app.servers = ours;
+                        app.servers_base = base;
+                        set_servers_error(app, format!("Failed to reload servers: {}", e));
+                        return Ok(());
+                    }
+                    let theirs = std::mem::take(&mut app.servers);
+                    app.servers = merge_server_changes(&base, ours, theirs);
+                    app.clamp_server_selection();
+                    if save_server_list(app) {
+                        app.set_status("Merged with the servers saved elsewhere".to_string());
+                    }
+                }
+                Some(ExternalChange::InstanceCfg(edit)) => {
+                    // Only the edited keys are written, so the rest survives
+                    let result = match app.selected_instance_mut() {
+                        Some(instance) => instance.apply_cfg_edit(&edit, true),
+                        None => return Ok(()),
+                    };
+                    match result.and_then(|()| reload_selected_instance(app)) {
+                        Ok(()) => {
+                            app.set_status("Applied your change to the newer file".to_string())
+                        }
+                        Err(e) => app.set_error(format!("Failed to update config: {}", e)),
+                    }
+                }
+                None => {}
+            }
+        }
+        Message::OverwriteChangedFile => match app.external_change.take() {
+            Some(ExternalChange::Servers) => {
+                app.input_mode = InputMode::Normal;
+                if let Err(e) = app.save_servers_for_instance(true) {
+                    set_servers_error(app, format!("Failed to save servers: {}", e));
+                }
+            }
+            // instance.cfg is only ever merged key by key
+            other => app.external_change = other,
+        },
+        Message::OpenGlobalSearch => {
+            app.refresh_search_files();
+            app.search_index = app.build_search_index();
+            app.input_buffer.clear();
+            app.search_results = search_items(&app.search_index, "");
+            app.selected_search_result = 0;
+            app.input_mode = InputMode::GlobalSearch;
+        }
+        Message::SearchFilesIndexed { stamp, items } => {
+            app.search_indexing = false;
+            app.search_files = items;
+            app.search_files_stamp = Some(stamp);
+            // Show what was found in a search opened meanwhile, but not in
+            // the Logs screen's instance picker
+            let picking_logs = app
+                .search_index
+                .iter()
+                .any(|item| item.kind == SearchKind::LogInstance);
+            if app.input_mode == InputMode::GlobalSearch && !picking_logs {
+                app.search_index = app.build_search_index();
+                app.search_results = search_items(&app.search_index, &app.input_buffer);
+                app.selected_search_result = app
+                    .selected_search_result
+                    .min(app.search_results.len().saturating_sub(1));
+            }
+        }
+        Message::GlobalSearchChar(c) => {
+            app.input_buffer.push(c);
+            app.search_results = search_items(&app.search_index, &app.input_buffer);
+            app.selected_search_result = 0;
+        }
+        Message::GlobalSearchBackspace => {
+            app.input_buffer.pop();
+            app.search_results = search_items(&app.search_index, &app.input_buffer);
+            app.selected_search_result = 0;
+        }
+        Message::GlobalSearchMove(offset) => {
+            if let Some(idx) = app
+                .selected_search_result
+                .checked_add_signed(offset)
+                .filter(|&i| i < app.search_results.len())
+            {
+                app.selected_search_result = idx;
+            }
+        }
+        Message::SelectSearchResult(idx) => {
+            if idx < app.search_results.len() {
+                app.selected_search_result = idx;
+                dispatch(app, Message::GlobalSearchConfirm);
+            }
+        }
+        Message::GlobalSearchConfirm => {
+            let target = app
+                .search_results
+                .get(app.selected_search_result)
+                .and_then(|&idx| app.search_index.get(idx))
+                .map(|item| item.target.clone());
+            dispatch(app, Message::GlobalSearchCancel);
+            if let Some(target) = target {
+                jump_to_search_target(app, target);
+            }
+        }
+        Message::GlobalSearchCancel => {
+            app.input_buffer.clear();
+            app.search_index.clear();
+            app.search_results.clear();
+            app.input_mode = InputMode::Normal;
+        }
+        // Help scrolling
+        Message::ScrollHelpUp => {
+            app.help_scroll_offset = app.help_scroll_offset.saturating_sub(1);
+        }
+        Message::ScrollHelpDown => {
+            app.help_scroll_offset += 1;
+        }
+        Message::Quit => {
+            app.running = false;
+        }
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+/// Size the data directory in the background, unless that is under way
+fn scan_storage(app: &mut App) {
+    if app.storage_scanning {
+        return;
+    }
+    let instances_dir = PrismConfig::load(&app.data_dir)
+        .map(|config| config.instances_dir())
+        .unwrap_or_else(|_| app.data_dir.join("instances"));
+    app.storage_scanning = true;
+    app.commands.push(Command::ScanStorage {
+        data_dir: app.data_dir.clone(),
+        instances_dir,
+        instances: app
+            .instances
+            .iter()
+            .map(|i| (i.name.clone(), i.path.clone()))
+            .collect(),
+    });
+}
+
+/// Open the screen a global search result belongs to, with it selected
+fn jump_to_search_target(app: &mut App, target: SearchTarget) {
+    match target {
+        SearchTarget::Instance(id) => {
+            if app.select_instance_by_id(&id) {
+                app.screen = Screen::Instances;
+            }
+        }
+        SearchTarget::Account(idx) => {
+            dispatch(app, Message::SelectAccount(idx));
+            dispatch(app, Message::OpenAccountScreen);
+        }
+        SearchTarget::Server { instance_id, index } => {
+            if !app.select_instance_by_id(&instance_id) {
+                return;
+            }
+            dispatch(app, Message::OpenServerScreen);
+            if app.screen == Screen::Servers && index < app.servers.len() {
+                app.show_hidden_servers |= app.servers[index].hidden;
+                app.selected_server_index = index;
+            }
+        }
+        SearchTarget::Log { instance_id, name } => {
+            match instance_id {
+                Some(id) if app.select_instance_by_id(&id) => open_logs(app, LogSource::Instance),
+                Some(_) => return,
+                None => open_logs(app, LogSource::Launcher),
+            }
+            if app.screen != Screen::Logs {
+                return;
+            }
+            if let Some(idx) = app.log_entries.iter().position(|e| e.name == name) {
+                dispatch(app, Message::SelectLog(idx));
+                dispatch(app, Message::LoadLogContent);
+            }
+        }
+        SearchTarget::InstanceLogs(id) => {
+            let current = app.selected_instance().map(|i| i.id.clone());
+            if current.as_ref() == Some(&id) || !app.select_instance_by_id(&id) {
+                return;
+            }
+            app.previous_log_instance = current;
+            let source = if app.log_source.is_per_instance() {
+                app.log_source
+            } else {
+                LogSource::Instance
+            };
+            reopen_logs(app, source);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::{InstanceCfgEdit, ResourcePackPolicy, Server, load_servers, save_servers};
+    use crate::update::instances::edit_instance_cfg;
+    use crate::update::tests::test_app;
+    use crate::update::update;
+    use std::fs;
+
+    #[test]
+    fn test_back_returns_to_the_screen_help_was_opened_from() {
+        let mut app = test_app("help", &["Alpha"]);
+        app.screen = Screen::Accounts;
+
+        reduce(&mut app, Message::OpenHelp).unwrap();
+        assert_eq!(app.screen, Screen::Help);
+        reduce(&mut app, Message::Back).unwrap();
+        assert_eq!(app.screen, Screen::Accounts);
+        reduce(&mut app, Message::Back).unwrap();
+        assert_eq!(app.screen, Screen::Instances);
+    }
+
+    #[test]
+    fn test_storage_scans_run_one_at_a_time() {
+        let mut app = test_app("storage", &["Alpha"]);
+
+        reduce(&mut app, Message::OpenStorageOverview).unwrap();
+        reduce(&mut app, Message::RescanStorage).unwrap();
+        let commands = std::mem::take(&mut app.commands);
+        assert_eq!(commands.len(), 1);
+        assert!(matches!(
+            &commands[0],
+            Command::ScanStorage { instances, .. } if instances[0].0 == "Alpha"
+        ));
+
+        let usage = StorageUsage {
+            data_dir: app.data_dir.clone(),
+            instances: vec![("Alpha".to_string(), 100)],
+            libraries: 0,
+            assets: 0,
+            icons: 0,
+            other: 0,
+            scanned_at: std::time::SystemTime::now(),
+        };
+        let elsewhere = StorageUsage {
+            data_dir: app.data_dir.join("other"),
+            ..usage.clone()
+        };
+        reduce(&mut app, Message::StorageScanned(elsewhere)).unwrap();
+        assert_eq!(app.storage_usage, None);
+        reduce(&mut app, Message::StorageScanned(usage.clone())).unwrap();
+        assert_eq!(app.storage_usage, Some(usage));
+        assert!(!app.storage_scanning);
+        assert_eq!(app.input_mode, InputMode::StorageOverview);
+    }
+
+    #[test]
+    fn test_search_files_are_indexed_again_only_after_a_change() {
+        let mut app = test_app("search-index", &["Alpha"]);
+        let logs = app.instances[0].logs_dir();
+        fs::create_dir_all(&logs).unwrap();
+        fs::write(logs.join("latest.log"), "log").unwrap();
+        // The first index started when the instances loaded
+        let started = app.search_indexing;
+        reduce(&mut app, Message::OpenGlobalSearch).unwrap();
+        let while_running = app.commands.len();
+        let stale = Message::SearchFilesIndexed {
+            stamp: 0,
+            items: Vec::new(),
+        };
+        reduce(&mut app, stale).unwrap();
+        reduce(&mut app, Message::GlobalSearchCancel).unwrap();
+
+        reduce(&mut app, Message::OpenGlobalSearch).unwrap();
+        let Some(Command::IndexSearchFiles {
+            instances,
+            launcher_logs,
+            stamp,
+        }) = app.commands.pop()
+        else {
+            panic!("the changed logs weren't indexed");
+        };
+        let items = crate::data::index_search_files(&instances, &launcher_logs);
+        reduce(&mut app, Message::SearchFilesIndexed { stamp, items }).unwrap();
+        let found = app.search_results.len();
+        reduce(&mut app, Message::GlobalSearchCancel).unwrap();
+        reduce(&mut app, Message::OpenGlobalSearch).unwrap();
+        let unchanged = app.commands.len();
+        fs::write(logs.join("debug.log"), "log").unwrap();
+        reduce(&mut app, Message::GlobalSearchCancel).unwrap();
+        reduce(&mut app, Message::OpenGlobalSearch).unwrap();
+        let changed = app.commands.len();
+        fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert!(started);
+        assert_eq!(while_running, 0);
+        // Alpha and its latest.log, shown in the search already open
+        assert_eq!(found, 2);
+        assert_eq!(unchanged, 0);
+        assert_eq!(changed, 1);
+    }
+
+    #[test]
+    fn test_merging_servers_keeps_changes_from_both_sides() {
+        let mut app = test_app("merge-servers", &["Alpha"]);
+        let path = app.instances[0].servers_dat_path();
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        let server = |name: &str, ip: &str| Server::new(name.to_string(), ip.to_string());
+        let base = [
+            server("Hub", "hub.example.com"),
+            server("Survival", "smp.example.com"),
+        ];
+        save_servers(&path, &base).unwrap();
+        app.load_servers_for_instance().unwrap();
+        let changed = [
+            server("Hub", "hub.example.com"),
+            server("SMP", "smp.example.com"),
+            server("Minigames", "mini.example.com"),
+        ];
+        save_servers(&path, &changed).unwrap();
+
+        update(&mut app, Message::CycleResourcePackPolicy);
+        let mode = app.input_mode;
+        reduce(&mut app, Message::MergeChangedFile).unwrap();
+        let saved = load_servers(&path).unwrap();
+        fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert_eq!(mode, InputMode::ConfirmExternalChange);
+        let names: Vec<&str> = saved.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, ["Hub", "SMP", "Minigames"]);
+        assert_eq!(saved[0].accept_textures, ResourcePackPolicy::Always);
+        assert_eq!(app.servers, saved);
+    }
+
+    #[test]
+    fn test_cfg_edits_only_conflict_over_the_keys_they_write() {
+        let mut app = test_app("cfg-conflict", &["Alpha"]);
+        let cfg = app.instances[0].path.join("instance.cfg");
+        fs::create_dir_all(&app.instances[0].path).unwrap();
+        fs::write(&cfg, "[General]\nname=Alpha\nJoinWorldOnLaunch=Old\n").unwrap();
+        reload_selected_instance(&mut app).unwrap();
+        // The game exiting rewrites the file with its playtime
+        fs::write(
+            &cfg,
+            "[General]\nname=Alpha\nJoinWorldOnLaunch=Old\ntotalTimePlayed=60\n",
+        )
+        .unwrap();
+
+        let server_join = InstanceCfgEdit::ServerJoin {
+            enabled: true,
+            address: Some("mc.example.com".to_string()),
+        };
+        edit_instance_cfg(&mut app, server_join);
+        let first_mode = app.input_mode;
+        let joined = fs::read_to_string(&cfg).unwrap();
+        // Then another world is picked in PrismLauncher
+        fs::write(&cfg, joined.replace("=Old", "=New")).unwrap();
+        edit_instance_cfg(&mut app, InstanceCfgEdit::ClearWorldJoin);
+        let second_mode = app.input_mode;
+        reduce(&mut app, Message::MergeChangedFile).unwrap();
+        let merged = fs::read_to_string(&cfg).unwrap();
+        fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert_eq!(first_mode, InputMode::Normal);
+        assert!(joined.contains("JoinServerOnLaunchAddress=mc.example.com"));
+        assert_eq!(second_mode, InputMode::ConfirmExternalChange);
+        assert!(merged.contains("JoinWorldOnLaunch=\n"));
+        assert!(merged.contains("totalTimePlayed=60"));
+        assert_eq!(app.instances[0].world_join, None);
+    }
+}
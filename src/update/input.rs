@@ -0,0 +1,1809 @@
+//! Keys, mouse clicks and the text input dialogs, turned into the messages
+//! the other reducers handle.
+
+use crate::app::{
+    App, ClickAction, InputMode, LogLevel, LogSource, Screen, ScrollTarget, VisualRow,
+};
+use crate::command::Command;
+use crate::data::{
+    GLOBAL_SETTINGS, InstanceCfgEdit, InstanceFolder, Server, Task, find_duplicate,
+    parse_env_assignments, templates_dir,
+};
+use crate::message::Message;
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEventKind};
+use ratatui::layout::Rect;
+use std::time::{Duration, Instant};
+
+use super::create_instance::{create_drafted_instance, create_from_chosen_template, search_packs};
+use super::instances::{edit_instance_cfg, toggle_group_collapse};
+use super::mods::search_mods;
+use super::servers::{
+    export_servers, import_servers, queue_server_check, save_server_list, validate_server_address,
+};
+use super::{dispatch, expand_home, reload_data};
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::Key(key) => handle_key(app, key.code, key.modifiers),
+        Message::Mouse(mouse) => handle_mouse(app, mouse),
+        Message::InputChar(c) => {
+            app.input_buffer.push(c);
+        }
+        Message::InputBackspace => {
+            app.input_buffer.pop();
+        }
+        Message::InputConfirm => match app.input_mode {
+            InputMode::AddServerName => {
+                let name = app.input_buffer.trim().to_string();
+                if name.is_empty() {
+                    app.set_error("Server name cannot be empty".to_string());
+                } else {
+                    app.edit_server_name = name;
+                    app.input_buffer.clear();
+                    app.input_mode = InputMode::AddServerAddress;
+                }
+            }
+            InputMode::AddServerAddress => {
+                let address = app.input_buffer.trim().to_string();
+                if let Err(e) = validate_server_address(&address) {
+                    app.set_error(e);
+                } else {
+                    let duplicate =
+                        find_duplicate(&app.servers, &address, None).map(|s| s.name.clone());
+                    app.edit_server_address = address.clone();
+                    app.servers.push(Server::new(
+                        app.edit_server_name.clone(),
+                        app.edit_server_address.clone(),
+                    ));
+                    app.input_buffer.clear();
+                    app.input_mode = InputMode::Normal;
+                    if save_server_list(app) {
+                        if let Some(name) = duplicate {
+                            app.set_status(format!("Warning: same address as '{}'", name));
+                        } else {
+                            let blocklist = app.app_config.check_server_blocklist;
+                            queue_server_check(
+                                app,
+                                address,
+                                app.servers.len() - 1,
+                                blocklist,
+                                false,
+                            );
+                        }
+                    }
+                }
+            }
+            InputMode::EditServerName => {
+                let name = app.input_buffer.trim().to_string();
+                if name.is_empty() {
+                    app.set_error("Server name cannot be empty".to_string());
+                } else {
+                    app.edit_server_name = name;
+                    app.input_buffer = app.edit_server_address.clone();
+                    app.input_mode = InputMode::EditServerAddress;
+                }
+            }
+            InputMode::EditEnv => match parse_env_assignments(&app.input_buffer) {
+                Ok(env) => {
+                    if let Some(id) = app.selected_instance().map(|i| i.id.clone()) {
+                        if env.is_empty() {
+                            app.app_config.instance_env.remove(&id);
+                        } else {
+                            app.app_config.instance_env.insert(id, env);
+                        }
+                        app.save_config();
+                    }
+                    app.input_buffer.clear();
+                    app.input_mode = InputMode::Normal;
+                }
+                Err(e) => app.set_error(e),
+            },
+            InputMode::EditWrapper => {
+                let wrapper = app.input_buffer.trim().to_string();
+                if let Some(id) = app.selected_instance().map(|i| i.id.clone()) {
+                    if wrapper.is_empty() {
+                        app.app_config.instance_wrapper.remove(&id);
+                    } else {
+                        app.app_config.instance_wrapper.insert(id, wrapper);
+                    }
+                    app.save_config();
+                }
+                app.input_buffer.clear();
+                app.input_mode = InputMode::Normal;
+            }
+            InputMode::EditPrismWrapper => {
+                let wrapper = Some(app.input_buffer.trim().to_string()).filter(|w| !w.is_empty());
+                edit_instance_cfg(app, InstanceCfgEdit::WrapperCommand(wrapper));
+                app.input_buffer.clear();
+                app.input_mode = InputMode::Normal;
+            }
+            InputMode::EditPrismSetting => {
+                if let Some(def) = GLOBAL_SETTINGS.get(app.selected_setting_index) {
+                    let value = app.input_buffer.trim().to_string();
+                    if let Err(e) = app.prism_settings.set(&app.data_dir, def.key, value) {
+                        let path = app.data_dir.join("prismlauncher.cfg");
+                        app.set_error_at(
+                            format!("Failed to update prismlauncher.cfg: {}", e),
+                            &path,
+                        );
+                    }
+                }
+                app.input_buffer.clear();
+                app.input_mode = InputMode::Normal;
+            }
+            InputMode::AddTask | InputMode::EditTask => {
+                let text = app.input_buffer.trim().to_string();
+                if text.is_empty() {
+                    app.set_error("Task cannot be empty".to_string());
+                    return Ok(());
+                }
+                if app.input_mode == InputMode::AddTask {
+                    app.tasks.push(Task::new(text));
+                    app.selected_task_index = app.tasks.len() - 1;
+                } else if let Some(task) = app.tasks.get_mut(app.selected_task_index) {
+                    task.text = text;
+                }
+                app.save_tasks();
+                app.input_buffer.clear();
+                app.input_mode = InputMode::Normal;
+            }
+            InputMode::SetDataDir => {
+                let dir = expand_home(app.input_buffer.trim());
+                if !dir.is_dir() {
+                    app.set_error(format!("{} is not a folder", dir.display()));
+                    return Ok(());
+                }
+                match reload_data(app, &dir) {
+                    Ok(()) => {
+                        app.app_config.data_dir = Some(dir);
+                        app.save_config();
+                        app.set_status(format!("Using {}", app.data_dir.display()));
+                    }
+                    Err(e) => app.set_error(format!("Failed to load {}: {}", dir.display(), e)),
+                }
+            }
+            InputMode::SaveTemplate => {
+                let name = app.input_buffer.trim().to_string();
+                let Some(instance) = app.selected_instance() else {
+                    return Ok(());
+                };
+                app.commands.push(Command::SaveTemplate {
+                    instance_path: instance.path.clone(),
+                    mods_dir: instance.mods_dir(),
+                    dir: templates_dir(),
+                    name: name.clone(),
+                });
+                app.input_buffer.clear();
+                app.input_mode = InputMode::Normal;
+                app.set_status(format!("Saving template {}…", name));
+            }
+            InputMode::NewFromTemplate => {
+                let name = app.input_buffer.trim().to_string();
+                create_from_chosen_template(app, &name);
+            }
+            InputMode::NameNewInstance => {
+                let name = app.input_buffer.trim().to_string();
+                create_drafted_instance(app, &name);
+            }
+            InputMode::ExportServers => {
+                let path = expand_home(app.input_buffer.trim());
+                export_servers(app, &path);
+            }
+            InputMode::ImportServers => {
+                let path = expand_home(app.input_buffer.trim());
+                import_servers(app, &path);
+            }
+            InputMode::SearchMods => {
+                let query = app.input_buffer.trim().to_string();
+                search_mods(app, &query);
+            }
+            InputMode::SearchPacks => {
+                let query = app.input_buffer.trim().to_string();
+                search_packs(app, &query);
+            }
+            InputMode::SetAlias => {
+                let alias = app.input_buffer.trim().to_string();
+                if alias.contains(char::is_whitespace) {
+                    app.set_error("Alias cannot contain spaces".to_string());
+                } else if let Some(id) = app.selected_instance().map(|i| i.id.clone()) {
+                    match app.app_config.set_alias(&id, &alias) {
+                        Ok(()) => {
+                            app.save_config();
+                            app.input_buffer.clear();
+                            app.input_mode = InputMode::Normal;
+                        }
+                        Err(owner) => {
+                            let owner = app
+                                .instance_by_id(&owner)
+                                .map_or(owner.clone(), |i| i.name.clone());
+                            app.set_error(format!("Alias {} already belongs to {}", alias, owner));
+                        }
+                    }
+                }
+            }
+            InputMode::EditServerAddress => {
+                let address = app.input_buffer.trim().to_string();
+                if let Err(e) = validate_server_address(&address) {
+                    app.set_error(e);
+                } else {
+                    app.edit_server_address = address;
+                    app.input_buffer.clear();
+                    app.input_mode = InputMode::Normal;
+                    if let Some(server) = app.servers.get_mut(app.selected_server_index) {
+                        server.name = app.edit_server_name.clone();
+                        server.ip = app.edit_server_address.clone();
+                        save_server_list(app);
+                    }
+                }
+            }
+            _ => {}
+        },
+        Message::InputCancel => {
+            // Backing out of a conflicting save leaves the file as it is
+            if app.external_change.is_some() {
+                dispatch(app, Message::ReloadChangedFile);
+                return Ok(());
+            }
+            app.input_buffer.clear();
+            app.log_cleanup_plan.clear();
+            app.junk_cleanup_plan.clear();
+            app.dry_run_preview = None;
+            app.error_details = None;
+            app.search_index.clear();
+            app.search_results.clear();
+            app.templates.clear();
+            app.mod_hits.clear();
+            app.chosen_template = None;
+            app.pending_launch = None;
+            app.server_launch_warning = None;
+            app.input_mode = InputMode::Normal;
+        }
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+fn handle_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    // Undocumented: timing and state for performance work
+    if code == KeyCode::F(12) {
+        dispatch(app, Message::ToggleDebugOverlay);
+        return;
+    }
+
+    // Handle input modes
+    if app.input_mode != InputMode::Normal {
+        match app.input_mode {
+            InputMode::Search => match code {
+                KeyCode::Char(c) => dispatch(app, Message::SearchChar(c)),
+                KeyCode::Backspace => dispatch(app, Message::SearchBackspace),
+                KeyCode::Enter => dispatch(app, Message::SearchConfirm),
+                KeyCode::Esc => dispatch(app, Message::SearchCancel),
+                _ => {}
+            },
+            InputMode::GlobalSearch => match code {
+                KeyCode::Down | KeyCode::Tab => dispatch(app, Message::GlobalSearchMove(1)),
+                KeyCode::Up | KeyCode::BackTab => dispatch(app, Message::GlobalSearchMove(-1)),
+                KeyCode::Char('n') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    dispatch(app, Message::GlobalSearchMove(1));
+                }
+                KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
+                    dispatch(app, Message::GlobalSearchMove(-1));
+                }
+                KeyCode::Char(c) => dispatch(app, Message::GlobalSearchChar(c)),
+                KeyCode::Backspace => dispatch(app, Message::GlobalSearchBackspace),
+                KeyCode::Enter => dispatch(app, Message::GlobalSearchConfirm),
+                KeyCode::Esc => dispatch(app, Message::GlobalSearchCancel),
+                _ => {}
+            },
+            InputMode::LogSearch => match code {
+                KeyCode::Char(c) => dispatch(app, Message::LogSearchChar(c)),
+                KeyCode::Backspace => dispatch(app, Message::LogSearchBackspace),
+                KeyCode::Enter => dispatch(app, Message::LogSearchConfirm),
+                KeyCode::Esc => dispatch(app, Message::LogSearchCancel),
+                _ => {}
+            },
+            InputMode::ConfirmLogCleanup => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmLogCleanup);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::ConfirmJunkCleanup => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmJunkCleanup);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::ConfirmJavaLaunch => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmJavaLaunch);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::ConfirmLowSpaceLaunch => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmLowSpaceLaunch);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::ConfirmServerLaunch => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmServerLaunch);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::ConfirmSyncSettings => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmSyncSettings);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::PickModHit => match code {
+                KeyCode::Char(c @ '1'..='9') => {
+                    dispatch(app, Message::InstallModHit(c as usize - '1' as usize));
+                }
+                KeyCode::Esc | KeyCode::Char('q') => dispatch(app, Message::InputCancel),
+                _ => {}
+            },
+            InputMode::PickPackHit => match code {
+                KeyCode::Char(c @ '1'..='9') => {
+                    dispatch(app, Message::InstallPackHit(c as usize - '1' as usize));
+                }
+                KeyCode::Esc | KeyCode::Char('q') => dispatch(app, Message::InputCancel),
+                _ => {}
+            },
+            InputMode::PickLaunchPreset => match code {
+                KeyCode::Char(c @ '1'..='9') => {
+                    let pick = c as usize - '1' as usize;
+                    if let Some((name, _)) = app.selected_launch_presets().get(pick) {
+                        let name = name.to_string();
+                        dispatch(app, Message::LaunchWithPreset(name));
+                    }
+                }
+                KeyCode::Esc | KeyCode::Char('q') => dispatch(app, Message::InputCancel),
+                _ => {}
+            },
+            InputMode::PickLogSource => match code {
+                KeyCode::Char('i') | KeyCode::Char('1') => {
+                    dispatch(app, Message::PickLogSource(LogSource::Instance));
+                }
+                KeyCode::Char('c') | KeyCode::Char('2') => {
+                    dispatch(app, Message::PickLogSource(LogSource::CrashReports));
+                }
+                KeyCode::Char('l') | KeyCode::Char('3') => {
+                    dispatch(app, Message::PickLogSource(LogSource::Launcher));
+                }
+                KeyCode::Char('d') | KeyCode::Char('4') => {
+                    dispatch(app, Message::PickLogSource(LogSource::App));
+                }
+                KeyCode::Esc | KeyCode::Char('q') => dispatch(app, Message::InputCancel),
+                _ => {}
+            },
+            InputMode::PickInstanceFolder => match code {
+                KeyCode::Char('o') | KeyCode::Char('0') => {
+                    app.input_mode = InputMode::Normal;
+                    dispatch(app, Message::OpenInstanceFolder);
+                }
+                KeyCode::Char('m') | KeyCode::Char('1') => {
+                    dispatch(app, Message::OpenGameFolder(InstanceFolder::Mods));
+                }
+                KeyCode::Char('c') | KeyCode::Char('2') => {
+                    dispatch(app, Message::OpenGameFolder(InstanceFolder::Config));
+                }
+                KeyCode::Char('s') | KeyCode::Char('3') => {
+                    dispatch(app, Message::OpenGameFolder(InstanceFolder::Saves));
+                }
+                KeyCode::Char('i') | KeyCode::Char('4') => {
+                    dispatch(app, Message::OpenGameFolder(InstanceFolder::Screenshots));
+                }
+                KeyCode::Char('r') | KeyCode::Char('5') => {
+                    dispatch(app, Message::OpenGameFolder(InstanceFolder::ResourcePacks));
+                }
+                KeyCode::Esc | KeyCode::Char('q') => dispatch(app, Message::InputCancel),
+                _ => {}
+            },
+            InputMode::ConfirmDeleteScreenshot => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmDeleteScreenshot);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::ConfirmDeleteResourcePack => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmDeleteResourcePack);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::ConfirmDisableMod => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmDisableMod);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::DryRunPreview => match code {
+                KeyCode::Enter | KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmDryRun);
+                }
+                KeyCode::Char('j') | KeyCode::Down => {
+                    dispatch(app, Message::ScrollDryRunPreview(1))
+                }
+                KeyCode::Char('k') | KeyCode::Up => dispatch(app, Message::ScrollDryRunPreview(-1)),
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::StorageOverview => match code {
+                KeyCode::Char('r') => dispatch(app, Message::RescanStorage),
+                KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::ErrorDetails => match code {
+                KeyCode::Char('j') | KeyCode::Down => dispatch(app, Message::ScrollErrorDetails(1)),
+                KeyCode::Char('k') | KeyCode::Up => dispatch(app, Message::ScrollErrorDetails(-1)),
+                KeyCode::Char('c') => dispatch(app, Message::CopyErrorDetails),
+                KeyCode::Enter | KeyCode::Esc | KeyCode::Char('q') => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            InputMode::ConfirmExternalChange => match code {
+                KeyCode::Char('r') | KeyCode::Char('R') => {
+                    dispatch(app, Message::ReloadChangedFile);
+                }
+                KeyCode::Char('m') | KeyCode::Char('M') => {
+                    dispatch(app, Message::MergeChangedFile);
+                }
+                KeyCode::Char('o') | KeyCode::Char('O') => {
+                    dispatch(app, Message::OverwriteChangedFile);
+                }
+                KeyCode::Esc => dispatch(app, Message::InputCancel),
+                _ => {}
+            },
+            InputMode::ConfirmDelete => match code {
+                KeyCode::Char('y') | KeyCode::Char('Y') => {
+                    dispatch(app, Message::ConfirmDeleteServer);
+                }
+                KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                    dispatch(app, Message::InputCancel);
+                }
+                _ => {}
+            },
+            _ => match code {
+                KeyCode::Char(c) => dispatch(app, Message::InputChar(c)),
+                KeyCode::Backspace => dispatch(app, Message::InputBackspace),
+                KeyCode::Enter => dispatch(app, Message::InputConfirm),
+                KeyCode::Esc => dispatch(app, Message::InputCancel),
+                _ => {}
+            },
+        }
+        return;
+    }
+
+    // The tour takes over the keyboard until it is finished or skipped
+    if app.tour_step.is_some() {
+        match code {
+            KeyCode::Enter | KeyCode::Char('l' | ' ') | KeyCode::Right => {
+                dispatch(app, Message::TourStep(1));
+            }
+            KeyCode::Char('h') | KeyCode::Left | KeyCode::Backspace => {
+                dispatch(app, Message::TourStep(-1));
+            }
+            KeyCode::Esc | KeyCode::Char('q') => dispatch(app, Message::EndTour),
+            _ => {}
+        }
+        return;
+    }
+
+    // Ctrl+P searches everything from any screen
+    if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('p') {
+        dispatch(app, Message::OpenGlobalSearch);
+        return;
+    }
+
+    // Ctrl+R reads PrismLauncher's files again, e.g. after fixing one
+    if modifiers.contains(KeyModifiers::CONTROL) && code == KeyCode::Char('r') {
+        dispatch(app, Message::RetryDataFiles);
+        return;
+    }
+
+    // Alt+D previews deletes and cleanups before they run
+    if modifiers.contains(KeyModifiers::ALT) && code == KeyCode::Char('d') {
+        dispatch(app, Message::ToggleDryRun);
+        return;
+    }
+
+    // Alt+1..9 quick launches a bound instance from any screen
+    if modifiers.contains(KeyModifiers::ALT)
+        && let KeyCode::Char(c) = code
+        && let Some(slot) = c.to_digit(10).filter(|d| *d > 0)
+    {
+        dispatch(app, Message::QuickLaunch(slot as u8));
+        return;
+    }
+
+    // Normal mode keybindings
+    match app.screen {
+        Screen::Instances => handle_instances_key(app, code, modifiers),
+        Screen::Accounts => handle_accounts_key(app, code),
+        Screen::Servers => handle_servers_key(app, code),
+        Screen::Logs => handle_logs_key(app, code),
+        Screen::InstanceDetails => handle_details_key(app, code),
+        Screen::Settings => handle_settings_key(app, code),
+        Screen::CreateInstance => handle_create_instance_key(app, code),
+        Screen::Mods => handle_mods_key(app, code),
+        Screen::Screenshots => handle_screenshots_key(app, code),
+        Screen::ResourcePacks => handle_resource_packs_key(app, code),
+        Screen::Worlds => handle_worlds_key(app, code),
+        Screen::Help => handle_help_key(app, code),
+    }
+}
+
+fn rect_contains(rect: Rect, col: u16, row: u16) -> bool {
+    col >= rect.x && col < rect.x + rect.width && row >= rect.y && row < rect.y + rect.height
+}
+
+fn handle_mouse(app: &mut App, mouse: crossterm::event::MouseEvent) {
+    let col = mouse.column;
+    let row = mouse.row;
+
+    match mouse.kind {
+        MouseEventKind::Down(MouseButton::Left) => {
+            // Double-click detection
+            let now = Instant::now();
+            let is_double_click = app
+                .last_click_time
+                .map(|t| now.duration_since(t) < Duration::from_millis(400))
+                .unwrap_or(false)
+                && app.last_click_pos == (col, row);
+            app.last_click_time = Some(now);
+            app.last_click_pos = (col, row);
+
+            // Find matching click region (reverse for z-order: last registered wins)
+            let target = app
+                .click_regions
+                .iter()
+                .rev()
+                .find(|r| rect_contains(r.rect, col, row))
+                .map(|r| r.action.clone());
+
+            match target {
+                Some(ClickAction::Scrollbar(scroll_target)) => {
+                    if let Some(region) = app
+                        .click_regions
+                        .iter()
+                        .rev()
+                        .find(|r| rect_contains(r.rect, col, row))
+                    {
+                        let track = region.rect;
+                        app.scrollbar_drag = Some((scroll_target, track));
+                        scroll_to_row(app, scroll_target, track, row);
+                    }
+                }
+                Some(ClickAction::SearchResult(idx)) => {
+                    dispatch(app, Message::SelectSearchResult(idx));
+                }
+                Some(ClickAction::SwitchTab(i)) => {
+                    let screen = match i {
+                        0 => Screen::Instances,
+                        1 => Screen::Accounts,
+                        2 => Screen::Servers,
+                        3 => Screen::Logs,
+                        _ => return,
+                    };
+                    dispatch(app, Message::SwitchToScreen(screen));
+                }
+                Some(ClickAction::SelectItem(idx)) => match app.screen {
+                    // Ctrl+click launches without moving the selection
+                    Screen::Instances if mouse.modifiers.contains(KeyModifiers::CONTROL) => {
+                        dispatch(app, Message::LaunchInstanceAt(idx));
+                    }
+                    Screen::Instances => {
+                        dispatch(app, Message::SelectInstance(idx));
+                        if is_double_click {
+                            dispatch(app, Message::LaunchInstance);
+                        }
+                    }
+                    Screen::Accounts => {
+                        dispatch(app, Message::SelectAccount(idx));
+                        if is_double_click {
+                            dispatch(app, Message::ConfirmAccountSelection);
+                        }
+                    }
+                    Screen::Servers => {
+                        dispatch(app, Message::SelectServer(idx));
+                        if is_double_click {
+                            dispatch(app, Message::LaunchWithServer);
+                        }
+                    }
+                    Screen::Settings => {
+                        dispatch(app, Message::SelectSetting(idx));
+                        if is_double_click {
+                            dispatch(app, Message::EditSetting);
+                        }
+                    }
+                    Screen::Mods => {
+                        dispatch(app, Message::SelectMod(idx));
+                        if is_double_click {
+                            dispatch(app, Message::ToggleMods);
+                        }
+                    }
+                    Screen::Screenshots => {
+                        dispatch(app, Message::SelectScreenshot(idx));
+                        if is_double_click {
+                            dispatch(app, Message::OpenScreenshot);
+                        }
+                    }
+                    Screen::ResourcePacks => {
+                        dispatch(app, Message::SelectResourcePack(idx));
+                        if is_double_click {
+                            dispatch(app, Message::ToggleResourcePack);
+                        }
+                    }
+                    Screen::Worlds => {
+                        dispatch(app, Message::SelectWorld(idx));
+                        if is_double_click {
+                            dispatch(app, Message::BackupWorld);
+                        }
+                    }
+                    Screen::CreateInstance => {
+                        dispatch(app, Message::SelectDraftItem(idx));
+                        if is_double_click {
+                            dispatch(app, Message::ConfirmDraftItem);
+                        }
+                    }
+                    _ => {}
+                },
+                Some(ClickAction::GroupHeader(key)) => {
+                    toggle_group_collapse(app, &key);
+                }
+                Some(ClickAction::FooterAction(msg)) => {
+                    dispatch(app, msg);
+                }
+                Some(ClickAction::JoinCheckbox) => {
+                    dispatch(app, Message::ToggleJoinOnLaunch);
+                }
+                Some(ClickAction::JoinServer(idx)) => {
+                    dispatch(app, Message::SetJoinServer(idx));
+                }
+                Some(ClickAction::CycleAccount) => {
+                    dispatch(app, Message::CycleAccount);
+                }
+                Some(ClickAction::ToggleLogLevel(level)) => {
+                    dispatch(app, Message::ToggleLogLevel(level));
+                }
+                Some(ClickAction::GoBack) => {
+                    dispatch(app, Message::Back);
+                }
+                Some(ClickAction::DismissOverlay) => match app.screen {
+                    Screen::Help => {
+                        dispatch(app, Message::Back);
+                    }
+                    _ => {
+                        if app.error_message.is_some() || app.status_message.is_some() {
+                            app.clear_error();
+                        } else if app.input_mode != InputMode::Normal {
+                            dispatch(app, Message::InputCancel);
+                        }
+                    }
+                },
+                Some(ClickAction::SelectLogFile(idx)) => {
+                    dispatch(app, Message::SelectLog(idx));
+                    if is_double_click {
+                        dispatch(app, Message::LoadLogContent);
+                    }
+                }
+                Some(ClickAction::SelectBackup(idx)) => {
+                    dispatch(app, Message::SelectBackup(idx));
+                    if is_double_click {
+                        dispatch(app, Message::RestoreBackup);
+                    }
+                }
+                Some(ClickAction::ScrollLogPreview) | Some(ClickAction::Noop) => {}
+                None => {}
+            }
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            if let Some((scroll_target, track)) = app.scrollbar_drag {
+                scroll_to_row(app, scroll_target, track, row);
+            }
+        }
+        MouseEventKind::Up(MouseButton::Left) => {
+            app.scrollbar_drag = None;
+        }
+        MouseEventKind::Down(MouseButton::Middle) => {
+            let target = app
+                .click_regions
+                .iter()
+                .rev()
+                .find(|r| rect_contains(r.rect, col, row))
+                .map(|r| r.action.clone());
+
+            // Middle-click a server to copy its address
+            if app.screen == Screen::Servers
+                && let Some(ClickAction::SelectItem(idx)) = target
+            {
+                dispatch(app, Message::CopyServerAddress(idx));
+            }
+        }
+        MouseEventKind::ScrollUp => {
+            // Check if scrolling over log preview area
+            if app.screen == Screen::Logs {
+                let over_preview = app
+                    .click_regions
+                    .iter()
+                    .rev()
+                    .find(|r| rect_contains(r.rect, col, row))
+                    .map(|r| matches!(r.action, ClickAction::ScrollLogPreview))
+                    .unwrap_or(false);
+                if over_preview {
+                    dispatch(app, Message::ScrollLogUp(3));
+                    return;
+                }
+                let over_file_list = app
+                    .click_regions
+                    .iter()
+                    .rev()
+                    .find(|r| rect_contains(r.rect, col, row))
+                    .map(|r| matches!(r.action, ClickAction::SelectLogFile(_)))
+                    .unwrap_or(false);
+                if over_file_list && app.selected_log_index > 0 {
+                    dispatch(app, Message::SelectLog(app.selected_log_index - 1));
+                    return;
+                }
+            }
+            match app.screen {
+                Screen::Instances => {
+                    let prev_idx = app
+                        .filtered_instance_indices
+                        .iter()
+                        .position(|&idx| idx == app.selected_instance_index)
+                        .filter(|&pos| pos > 0)
+                        .and_then(|pos| app.filtered_instance_indices.get(pos - 1).copied());
+                    if let Some(idx) = prev_idx {
+                        dispatch(app, Message::SelectInstance(idx));
+                    }
+                }
+                Screen::Accounts => {
+                    let prev_idx = app
+                        .filtered_account_indices
+                        .iter()
+                        .position(|&idx| idx == app.selected_account_index)
+                        .filter(|&pos| pos > 0)
+                        .and_then(|pos| app.filtered_account_indices.get(pos - 1).copied());
+                    if let Some(idx) = prev_idx {
+                        dispatch(app, Message::SelectAccount(idx));
+                    }
+                }
+                Screen::Servers => {
+                    if let Some(idx) = app.adjacent_server_index(-1) {
+                        dispatch(app, Message::SelectServer(idx));
+                    }
+                }
+                Screen::Logs => {
+                    // Fallback: scroll log content if loaded, else navigate file list
+                    if !app.log_content.is_empty() {
+                        dispatch(app, Message::ScrollLogUp(3));
+                    } else if app.selected_log_index > 0 {
+                        dispatch(app, Message::SelectLog(app.selected_log_index - 1));
+                    }
+                }
+                Screen::Help => {
+                    dispatch(app, Message::ScrollHelpUp);
+                }
+                _ => {}
+            }
+        }
+        MouseEventKind::ScrollDown => {
+            // Check if scrolling over log preview area
+            if app.screen == Screen::Logs {
+                let over_preview = app
+                    .click_regions
+                    .iter()
+                    .rev()
+                    .find(|r| rect_contains(r.rect, col, row))
+                    .map(|r| matches!(r.action, ClickAction::ScrollLogPreview))
+                    .unwrap_or(false);
+                if over_preview {
+                    dispatch(app, Message::ScrollLogDown(3));
+                    return;
+                }
+                let over_file_list = app
+                    .click_regions
+                    .iter()
+                    .rev()
+                    .find(|r| rect_contains(r.rect, col, row))
+                    .map(|r| matches!(r.action, ClickAction::SelectLogFile(_)))
+                    .unwrap_or(false);
+                if over_file_list && app.selected_log_index + 1 < app.log_entries.len() {
+                    dispatch(app, Message::SelectLog(app.selected_log_index + 1));
+                    return;
+                }
+            }
+            match app.screen {
+                Screen::Instances => {
+                    let next_idx = app
+                        .filtered_instance_indices
+                        .iter()
+                        .position(|&idx| idx == app.selected_instance_index)
+                        .and_then(|pos| app.filtered_instance_indices.get(pos + 1).copied());
+                    if let Some(idx) = next_idx {
+                        dispatch(app, Message::SelectInstance(idx));
+                    }
+                }
+                Screen::Accounts => {
+                    let next_idx = app
+                        .filtered_account_indices
+                        .iter()
+                        .position(|&idx| idx == app.selected_account_index)
+                        .and_then(|pos| app.filtered_account_indices.get(pos + 1).copied());
+                    if let Some(idx) = next_idx {
+                        dispatch(app, Message::SelectAccount(idx));
+                    }
+                }
+                Screen::Servers => {
+                    if let Some(idx) = app.adjacent_server_index(1) {
+                        dispatch(app, Message::SelectServer(idx));
+                    }
+                }
+                Screen::Logs => {
+                    if !app.log_content.is_empty() {
+                        dispatch(app, Message::ScrollLogDown(3));
+                    } else if app.selected_log_index + 1 < app.log_entries.len() {
+                        dispatch(app, Message::SelectLog(app.selected_log_index + 1));
+                    }
+                }
+                Screen::Help => {
+                    dispatch(app, Message::ScrollHelpDown);
+                }
+                _ => {}
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Jump a list to the position under `row` on its scrollbar track
+fn scroll_to_row(app: &mut App, target: ScrollTarget, track: Rect, row: u16) {
+    let span = track.height.saturating_sub(1).max(1) as usize;
+    let offset = (row.clamp(track.y, track.y + track.height.saturating_sub(1)) - track.y) as usize;
+    // Position along a list of `len` items, rounded to the nearest
+    let pick = |len: usize| (offset * len.saturating_sub(1) + span / 2) / span;
+
+    match target {
+        ScrollTarget::Instances => {
+            let indices = &app.filtered_instance_indices;
+            if let Some(&idx) = indices.get(pick(indices.len())) {
+                dispatch(app, Message::SelectInstance(idx));
+            }
+        }
+        ScrollTarget::Accounts => {
+            let indices = &app.filtered_account_indices;
+            if let Some(&idx) = indices.get(pick(indices.len())) {
+                dispatch(app, Message::SelectAccount(idx));
+            }
+        }
+        ScrollTarget::Servers => {
+            let indices = app.visible_server_indices();
+            if let Some(&idx) = indices.get(pick(indices.len())) {
+                dispatch(app, Message::SelectServer(idx));
+            }
+        }
+        ScrollTarget::LogFiles => {
+            let idx = pick(app.log_entries.len());
+            dispatch(app, Message::SelectLog(idx));
+        }
+        ScrollTarget::LogPreview => {
+            app.log_scroll_offset = pick(app.filtered_log_content().len());
+        }
+    }
+}
+
+/// Move the instance cursor one row, onto group headers as well. Going
+/// down past the last row starts again at the top.
+fn step_instance_cursor(app: &mut App, delta: isize) {
+    let rows = app.visual_rows();
+    let Some(last) = rows.len().checked_sub(1) else {
+        return;
+    };
+    let target = match cursor_row(app, &rows) {
+        Some(pos) if delta > 0 && pos == last => 0,
+        Some(pos) => pos.saturating_add_signed(delta).min(last),
+        None => 0,
+    };
+    select_row(app, &rows[target]);
+}
+
+/// Move the instance cursor by a screenful of rows, group headers
+/// included, stopping at either end
+fn page_instances(app: &mut App, direction: isize) {
+    let rows = app.visual_rows();
+    let Some(last) = rows.len().checked_sub(1) else {
+        return;
+    };
+    let pos = cursor_row(app, &rows).unwrap_or(0);
+    let step = app.instance_page_rows.max(1) as isize * direction;
+    let target = pos.saturating_add_signed(step).min(last);
+    select_row(app, &rows[target]);
+}
+
+/// Which of `rows` the cursor is on
+fn cursor_row(app: &App, rows: &[VisualRow]) -> Option<usize> {
+    let selected_key = app.selected_group_key();
+    rows.iter().position(|row| match row {
+        VisualRow::GroupHeader { key, .. } => {
+            app.group_header_selected && selected_key.as_deref() == Some(key.as_str())
+        }
+        VisualRow::Instance(idx) => {
+            !app.group_header_selected && *idx == app.selected_instance_index
+        }
+    })
+}
+
+fn select_row(app: &mut App, row: &VisualRow) {
+    match row {
+        VisualRow::GroupHeader { key, .. } => {
+            let group = app.grouped_instances.iter().position(|g| g.key() == key);
+            if let Some(group) = group {
+                dispatch(app, Message::SelectGroup(group));
+            }
+        }
+        VisualRow::Instance(idx) => dispatch(app, Message::SelectInstance(*idx)),
+    }
+}
+
+fn handle_instances_key(app: &mut App, code: KeyCode, modifiers: KeyModifiers) {
+    // Ctrl+j/k/Up/Down for group navigation
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        match code {
+            KeyCode::Char('j') | KeyCode::Down => {
+                dispatch(app, Message::NextGroup);
+                return;
+            }
+            KeyCode::Char('k') | KeyCode::Up => {
+                dispatch(app, Message::PrevGroup);
+                return;
+            }
+            KeyCode::Char('d') => {
+                page_instances(app, 1);
+                return;
+            }
+            KeyCode::Char('u') => {
+                page_instances(app, -1);
+                return;
+            }
+            _ => {}
+        }
+    }
+
+    // Handle 2-key combo: g followed by l opens launcher logs
+    if let Some(pending) = app.pending_key {
+        app.pending_key = None;
+        if pending == 'g' && code == KeyCode::Char('l') {
+            dispatch(app, Message::OpenLauncherLogs);
+            return;
+        }
+        if pending == 'g' && code == KeyCode::Char('d') {
+            dispatch(app, Message::OpenAppLogs);
+            return;
+        }
+        // If it was 'g' followed by something else, handle 'g' as go-to-top
+        if pending == 'g'
+            && let Some(first) = app.filtered_instance_indices.first().copied()
+        {
+            dispatch(app, Message::SelectInstance(first));
+        }
+        // Don't return - process this key too if it's not 'l' or 'd'
+    }
+
+    match code {
+        // Navigation - through the rows on screen, group headers included
+        KeyCode::Char('j') | KeyCode::Down => step_instance_cursor(app, 1),
+        KeyCode::Char('k') | KeyCode::Up => step_instance_cursor(app, -1),
+        KeyCode::Char('g') => {
+            app.pending_key = Some('g');
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            if let Some(last) = app.filtered_instance_indices.last().copied() {
+                dispatch(app, Message::SelectInstance(last));
+            }
+        }
+        KeyCode::Home => {
+            if let Some(first) = app.filtered_instance_indices.first().copied() {
+                dispatch(app, Message::SelectInstance(first));
+            }
+        }
+        KeyCode::PageDown => page_instances(app, 1),
+        KeyCode::PageUp => page_instances(app, -1),
+
+        // Actions
+        KeyCode::Enter if app.group_header_selected => {
+            dispatch(app, Message::ToggleGroupCollapse);
+        }
+        KeyCode::Char('l') | KeyCode::Right if app.group_header_selected => {
+            dispatch(app, Message::LaunchGroup);
+        }
+        KeyCode::Char('l') | KeyCode::Enter | KeyCode::Right => {
+            dispatch(app, Message::LaunchInstance);
+        }
+        KeyCode::Char('x') => {
+            dispatch(app, Message::KillInstance);
+        }
+        KeyCode::Char('L') => {
+            dispatch(app, Message::OpenInstanceLogs);
+        }
+        KeyCode::Char('P') => {
+            dispatch(app, Message::OpenLaunchPresets);
+        }
+        KeyCode::Char('s') => {
+            dispatch(app, Message::OpenServerScreen);
+        }
+        KeyCode::Char('S') => {
+            dispatch(app, Message::CycleSortMode);
+        }
+        KeyCode::Char('R') => {
+            dispatch(app, Message::ToggleSortDirection);
+        }
+        KeyCode::Char('U') => {
+            dispatch(app, Message::CheckPackUpdates);
+        }
+        KeyCode::Char('y') => {
+            dispatch(app, Message::CopyLaunchCommand);
+        }
+        KeyCode::Char('A') => {
+            dispatch(app, Message::EditAlias);
+        }
+        KeyCode::Char('T') => {
+            dispatch(app, Message::SaveTemplate);
+        }
+        KeyCode::Char(' ') => {
+            dispatch(app, Message::ToggleInstanceMark);
+        }
+        KeyCode::Char('=') => {
+            dispatch(app, Message::SyncInstanceSettings);
+        }
+        KeyCode::Char('I') => {
+            dispatch(app, Message::OpenCreateInstance);
+        }
+        KeyCode::Char('m') => {
+            dispatch(app, Message::OpenModsScreen);
+        }
+        KeyCode::Char('v') => {
+            dispatch(app, Message::OpenScreenshots);
+        }
+        KeyCode::Char('p') => {
+            dispatch(app, Message::OpenResourcePacks);
+        }
+        KeyCode::Char('z') => {
+            dispatch(app, Message::OpenStorageOverview);
+        }
+        KeyCode::Char('N') => {
+            dispatch(app, Message::LowPriorityLaunch);
+        }
+        // Offered by the empty instance list
+        KeyCode::Char('n') if app.instances.is_empty() => {
+            dispatch(app, Message::OpenLauncher);
+        }
+        KeyCode::Char('r') if app.instances.is_empty() => {
+            dispatch(app, Message::ReloadData);
+        }
+        KeyCode::Char('D') if app.instances.is_empty() => {
+            dispatch(app, Message::EditDataDir);
+        }
+        KeyCode::Char('a') => {
+            dispatch(app, Message::OpenAccountScreen);
+        }
+        KeyCode::Char(',') => {
+            dispatch(app, Message::OpenSettings);
+        }
+        KeyCode::Char('c') => {
+            dispatch(app, Message::CycleAccount);
+        }
+        KeyCode::Char('i') => {
+            dispatch(app, Message::OpenInstanceDetails);
+        }
+        KeyCode::Char('o') => {
+            dispatch(app, Message::OpenInstanceFolder);
+        }
+        KeyCode::Tab => {
+            dispatch(app, Message::ToggleGroupCollapse);
+        }
+        KeyCode::Char('/') => {
+            dispatch(app, Message::StartSearch);
+        }
+        KeyCode::Esc if !app.search_query.is_empty() => {
+            dispatch(app, Message::SearchCancel);
+        }
+        KeyCode::Esc if !app.marked_instances.is_empty() => {
+            dispatch(app, Message::ClearInstanceMarks);
+        }
+        KeyCode::Char('?') => {
+            dispatch(app, Message::OpenHelp);
+        }
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+
+        _ => {}
+    }
+}
+
+fn handle_accounts_key(app: &mut App, code: KeyCode) {
+    let find_filtered_pos = |app: &App| {
+        app.filtered_account_indices
+            .iter()
+            .position(|&idx| idx == app.selected_account_index)
+    };
+
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            let next_idx = find_filtered_pos(app)
+                .and_then(|pos| app.filtered_account_indices.get(pos + 1).copied())
+                .or_else(|| app.filtered_account_indices.first().copied());
+            if let Some(idx) = next_idx {
+                dispatch(app, Message::SelectAccount(idx));
+            }
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            let prev_idx = find_filtered_pos(app)
+                .filter(|&pos| pos > 0)
+                .and_then(|pos| app.filtered_account_indices.get(pos - 1).copied())
+                .or_else(|| app.filtered_account_indices.first().copied());
+            if let Some(idx) = prev_idx {
+                dispatch(app, Message::SelectAccount(idx));
+            }
+        }
+
+        KeyCode::Char('l') | KeyCode::Enter | KeyCode::Right => {
+            dispatch(app, Message::ConfirmAccountSelection);
+        }
+
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::Back);
+        }
+
+        KeyCode::Char('/') => {
+            dispatch(app, Message::StartSearch);
+        }
+        // Offered by the empty account list
+        KeyCode::Char('n') if app.accounts.is_empty() => {
+            dispatch(app, Message::OpenLauncher);
+        }
+        KeyCode::Char('r') if app.accounts.is_empty() => {
+            dispatch(app, Message::ReloadData);
+        }
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+
+        _ => {}
+    }
+}
+
+fn handle_servers_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            if let Some(idx) = app.adjacent_server_index(1) {
+                dispatch(app, Message::SelectServer(idx));
+            }
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            if let Some(idx) = app.adjacent_server_index(-1) {
+                dispatch(app, Message::SelectServer(idx));
+            }
+        }
+
+        KeyCode::Char('l') | KeyCode::Enter | KeyCode::Right => {
+            dispatch(app, Message::LaunchWithServer);
+        }
+
+        KeyCode::Char('a') => {
+            dispatch(app, Message::AddServer);
+        }
+        KeyCode::Char('e') => {
+            dispatch(app, Message::EditServer);
+        }
+        KeyCode::Char('d') => {
+            dispatch(app, Message::DeleteServer);
+        }
+        KeyCode::Char('J') => {
+            dispatch(app, Message::SetJoinOnLaunch);
+        }
+        KeyCode::Char(' ') => {
+            dispatch(app, Message::ToggleJoinOnLaunch);
+        }
+        KeyCode::Char('F') => {
+            dispatch(app, Message::FixJoinConflict);
+        }
+        KeyCode::Char('w') => {
+            dispatch(app, Message::OpenServerWebsite);
+        }
+        KeyCode::Char('p') => {
+            dispatch(app, Message::CycleResourcePackPolicy);
+        }
+        KeyCode::Char('H') => {
+            dispatch(app, Message::ToggleHiddenServers);
+        }
+        KeyCode::Tab => {
+            dispatch(app, Message::NextLanGame);
+        }
+        KeyCode::Char('b') => {
+            dispatch(app, Message::CheckServer);
+        }
+        KeyCode::Char('P') => {
+            dispatch(app, Message::PingSelectedServer);
+        }
+        KeyCode::Char('E') => {
+            dispatch(app, Message::ExportServers);
+        }
+        KeyCode::Char('I') => {
+            dispatch(app, Message::ImportServers);
+        }
+        KeyCode::Char('n') => {
+            dispatch(app, Message::JoinLanGame);
+        }
+        KeyCode::Char('y') => {
+            dispatch(app, Message::CopyLaunchCommand);
+        }
+
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::Back);
+        }
+
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+
+        _ => {}
+    }
+}
+
+fn handle_details_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::Back);
+        }
+        KeyCode::Char('o') => {
+            dispatch(app, Message::OpenInstanceFolder);
+        }
+        KeyCode::Char('O') => {
+            dispatch(app, Message::PickInstanceFolder);
+        }
+        KeyCode::Char('b') => {
+            dispatch(app, Message::OpenWorlds);
+        }
+        KeyCode::Char('c') => {
+            dispatch(app, Message::PlanJunkCleanup);
+        }
+        KeyCode::Char('V') => {
+            dispatch(app, Message::VerifyInstance);
+        }
+        KeyCode::Char('F') => {
+            dispatch(app, Message::FixJoinConflict);
+        }
+        KeyCode::Char('r') => {
+            dispatch(app, Message::RefreshInstanceStats);
+        }
+        KeyCode::Char('p') => {
+            dispatch(app, Message::OpenPackPage);
+        }
+        KeyCode::Char('D') => {
+            dispatch(app, Message::CreateShortcut);
+        }
+        KeyCode::Char('E') => {
+            dispatch(app, Message::EditEnv);
+        }
+        KeyCode::Char('w') => {
+            dispatch(app, Message::EditWrapper);
+        }
+        KeyCode::Char('W') => {
+            dispatch(app, Message::EditPrismWrapper);
+        }
+        KeyCode::Char('j') | KeyCode::Down if app.selected_task_index + 1 < app.tasks.len() => {
+            dispatch(app, Message::SelectTask(app.selected_task_index + 1));
+        }
+        KeyCode::Char('k') | KeyCode::Up if app.selected_task_index > 0 => {
+            dispatch(app, Message::SelectTask(app.selected_task_index - 1));
+        }
+        KeyCode::Char('t') => {
+            dispatch(app, Message::AddTask);
+        }
+        KeyCode::Char('R') => {
+            dispatch(app, Message::CopyInstanceReport);
+        }
+        KeyCode::Char('T') => {
+            dispatch(app, Message::EditTask);
+        }
+        KeyCode::Char(' ') => {
+            dispatch(app, Message::ToggleTask);
+        }
+        KeyCode::Char('X') => {
+            dispatch(app, Message::DeleteTask);
+        }
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+        _ => {}
+    }
+}
+
+fn handle_settings_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Char('j') | KeyCode::Down
+            if app.selected_setting_index + 1 < GLOBAL_SETTINGS.len() =>
+        {
+            dispatch(app, Message::SelectSetting(app.selected_setting_index + 1));
+        }
+        KeyCode::Char('k') | KeyCode::Up if app.selected_setting_index > 0 => {
+            dispatch(app, Message::SelectSetting(app.selected_setting_index - 1));
+        }
+        KeyCode::Char('e') | KeyCode::Enter => {
+            dispatch(app, Message::EditSetting);
+        }
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::Back);
+        }
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+        _ => {}
+    }
+}
+
+fn handle_mods_key(app: &mut App, code: KeyCode) {
+    let selected = app.selected_mod_index;
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            dispatch(app, Message::SelectMod(selected + 1));
+        }
+        KeyCode::Char('k') | KeyCode::Up if selected > 0 => {
+            dispatch(app, Message::SelectMod(selected - 1));
+        }
+        KeyCode::Char('g') | KeyCode::Home => {
+            dispatch(app, Message::SelectMod(0));
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            if let Some(last) = app.mods.len().checked_sub(1) {
+                dispatch(app, Message::SelectMod(last));
+            }
+        }
+        KeyCode::Char(' ') => {
+            dispatch(app, Message::ToggleModMark);
+            dispatch(app, Message::SelectMod(selected + 1));
+        }
+        KeyCode::Char('e') | KeyCode::Enter => {
+            dispatch(app, Message::ToggleMods);
+        }
+        KeyCode::Char('o') => {
+            dispatch(app, Message::OpenGameFolder(InstanceFolder::Mods));
+        }
+        KeyCode::Char('a') => {
+            dispatch(app, Message::StartModSearch);
+        }
+        KeyCode::Char('u') => {
+            dispatch(app, Message::CheckModUpdates);
+        }
+        KeyCode::Char('U') => {
+            dispatch(app, Message::UpdateAllMods);
+        }
+        KeyCode::Char('w') => {
+            dispatch(app, Message::OpenModPage);
+        }
+        KeyCode::Esc if !app.marked_mods.is_empty() => {
+            dispatch(app, Message::ClearModMarks);
+        }
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::Back);
+        }
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+        _ => {}
+    }
+}
+
+fn handle_screenshots_key(app: &mut App, code: KeyCode) {
+    let selected = app.selected_screenshot_index;
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            dispatch(app, Message::SelectScreenshot(selected + 1));
+        }
+        KeyCode::Char('k') | KeyCode::Up if selected > 0 => {
+            dispatch(app, Message::SelectScreenshot(selected - 1));
+        }
+        KeyCode::Char('g') | KeyCode::Home => {
+            dispatch(app, Message::SelectScreenshot(0));
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            if let Some(last) = app.screenshots.len().checked_sub(1) {
+                dispatch(app, Message::SelectScreenshot(last));
+            }
+        }
+        KeyCode::Char('o') | KeyCode::Enter => {
+            dispatch(app, Message::OpenScreenshot);
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            dispatch(app, Message::DeleteScreenshot);
+        }
+        KeyCode::Char('O') => {
+            dispatch(app, Message::OpenGameFolder(InstanceFolder::Screenshots));
+        }
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::Back);
+        }
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+        _ => {}
+    }
+}
+
+fn handle_resource_packs_key(app: &mut App, code: KeyCode) {
+    let selected = app.selected_resource_pack_index;
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            dispatch(app, Message::SelectResourcePack(selected + 1));
+        }
+        KeyCode::Char('k') | KeyCode::Up if selected > 0 => {
+            dispatch(app, Message::SelectResourcePack(selected - 1));
+        }
+        KeyCode::Char('g') | KeyCode::Home => {
+            dispatch(app, Message::SelectResourcePack(0));
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            if let Some(last) = app.resource_packs.len().checked_sub(1) {
+                dispatch(app, Message::SelectResourcePack(last));
+            }
+        }
+        KeyCode::Char('e') | KeyCode::Char(' ') | KeyCode::Enter => {
+            dispatch(app, Message::ToggleResourcePack);
+        }
+        KeyCode::Char('d') | KeyCode::Delete => {
+            dispatch(app, Message::DeleteResourcePack);
+        }
+        KeyCode::Char('o') => {
+            dispatch(app, Message::OpenGameFolder(InstanceFolder::ResourcePacks));
+        }
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::Back);
+        }
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+        _ => {}
+    }
+}
+
+fn handle_worlds_key(app: &mut App, code: KeyCode) {
+    let backups = app.backups_focused;
+    let (selected, len) = if backups {
+        (app.selected_backup_index, app.backups.len())
+    } else {
+        (app.selected_world_index, app.worlds.len())
+    };
+    let select = |idx| {
+        if backups {
+            Message::SelectBackup(idx)
+        } else {
+            Message::SelectWorld(idx)
+        }
+    };
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            dispatch(app, select(selected + 1));
+        }
+        KeyCode::Char('k') | KeyCode::Up if selected > 0 => {
+            dispatch(app, select(selected - 1));
+        }
+        KeyCode::Char('g') | KeyCode::Home => {
+            dispatch(app, select(0));
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            if let Some(last) = len.checked_sub(1) {
+                dispatch(app, select(last));
+            }
+        }
+        KeyCode::Tab | KeyCode::BackTab => {
+            dispatch(app, Message::SwitchWorldsPane);
+        }
+        KeyCode::Char('b') => {
+            dispatch(app, Message::BackupWorld);
+        }
+        KeyCode::Char('r') => {
+            dispatch(app, Message::RestoreBackup);
+        }
+        KeyCode::Enter => {
+            if backups {
+                dispatch(app, Message::RestoreBackup);
+            } else {
+                dispatch(app, Message::BackupWorld);
+            }
+        }
+        KeyCode::Char('o') => {
+            dispatch(app, Message::OpenGameFolder(InstanceFolder::Saves));
+        }
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::Back);
+        }
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+        _ => {}
+    }
+}
+
+fn handle_create_instance_key(app: &mut App, code: KeyCode) {
+    let selected = app.draft.selected;
+    match code {
+        KeyCode::Char('j') | KeyCode::Down => {
+            dispatch(app, Message::SelectDraftItem(selected + 1));
+        }
+        KeyCode::Char('k') | KeyCode::Up if selected > 0 => {
+            dispatch(app, Message::SelectDraftItem(selected - 1));
+        }
+        KeyCode::Char('g') | KeyCode::Home => {
+            dispatch(app, Message::SelectDraftItem(0));
+        }
+        KeyCode::Char('G') | KeyCode::End => {
+            if let Some(last) = app.draft_len().checked_sub(1) {
+                dispatch(app, Message::SelectDraftItem(last));
+            }
+        }
+        KeyCode::Char('l') | KeyCode::Enter | KeyCode::Right => {
+            dispatch(app, Message::ConfirmDraftItem);
+        }
+        KeyCode::Char('s') => {
+            dispatch(app, Message::ToggleAllVersions);
+        }
+        KeyCode::Char('p') => {
+            dispatch(app, Message::StartPackSearch);
+        }
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::DraftStepBack);
+        }
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+        _ => {}
+    }
+}
+
+fn handle_help_key(app: &mut App, code: KeyCode) {
+    match code {
+        KeyCode::Esc | KeyCode::Char('q') | KeyCode::Char('?') => {
+            dispatch(app, Message::Back);
+        }
+        KeyCode::Char('j') | KeyCode::Down => {
+            dispatch(app, Message::ScrollHelpDown);
+        }
+        KeyCode::Char('k') | KeyCode::Up => {
+            dispatch(app, Message::ScrollHelpUp);
+        }
+        KeyCode::Char('t') => {
+            dispatch(app, Message::StartTour);
+        }
+        _ => {}
+    }
+}
+
+fn handle_logs_key(app: &mut App, code: KeyCode) {
+    let total = app.log_entries.len();
+
+    match code {
+        // Navigation in file list
+        KeyCode::Char('j') | KeyCode::Down if total > 0 && app.selected_log_index + 1 < total => {
+            dispatch(app, Message::SelectLog(app.selected_log_index + 1));
+        }
+        KeyCode::Char('k') | KeyCode::Up if app.selected_log_index > 0 => {
+            dispatch(app, Message::SelectLog(app.selected_log_index - 1));
+        }
+
+        // Load selected log content
+        KeyCode::Char('l') | KeyCode::Enter | KeyCode::Right => {
+            dispatch(app, Message::LoadLogContent);
+        }
+
+        // Scroll content
+        KeyCode::Char('J') | KeyCode::PageDown => {
+            dispatch(app, Message::ScrollLogDown(10));
+        }
+        KeyCode::Char('K') | KeyCode::PageUp => {
+            dispatch(app, Message::ScrollLogUp(10));
+        }
+
+        // Log search
+        KeyCode::Char('/') => {
+            dispatch(app, Message::StartLogSearch);
+        }
+        KeyCode::Char('n') => {
+            dispatch(app, Message::LogSearchNext);
+        }
+        KeyCode::Char('N') => {
+            dispatch(app, Message::LogSearchPrev);
+        }
+
+        // Log level filtering
+        KeyCode::Char('1') => {
+            dispatch(app, Message::ToggleLogLevel(LogLevel::Error));
+        }
+        KeyCode::Char('2') => {
+            dispatch(app, Message::ToggleLogLevel(LogLevel::Warn));
+        }
+        KeyCode::Char('3') => {
+            dispatch(app, Message::ToggleLogLevel(LogLevel::Info));
+        }
+        KeyCode::Char('4') => {
+            dispatch(app, Message::ToggleLogLevel(LogLevel::Debug));
+        }
+        KeyCode::Char('0') => {
+            dispatch(app, Message::ShowAllLogLevels);
+        }
+        KeyCode::Char('s') => {
+            dispatch(app, Message::CycleLogSource);
+        }
+        KeyCode::Char('i') => {
+            dispatch(app, Message::OpenLogInstancePicker);
+        }
+        KeyCode::Char('f') => {
+            dispatch(app, Message::ToggleLogFilterPanel);
+        }
+        KeyCode::Char('#') => {
+            dispatch(app, Message::ToggleLogLineNumbers);
+        }
+        KeyCode::Char('w') => {
+            dispatch(app, Message::ToggleLogWrap);
+        }
+        KeyCode::Char('F') => {
+            dispatch(app, Message::ToggleLogFollow);
+        }
+        KeyCode::Char('C') => {
+            dispatch(app, Message::PlanLogCleanup);
+        }
+
+        // Disable the mod a crash points at and relaunch
+        KeyCode::Char('D') => {
+            dispatch(app, Message::DisableSuspectedMod);
+        }
+
+        // Jump between errors and warnings
+        KeyCode::Char(']') => {
+            dispatch(
+                app,
+                Message::JumpToLogLevel {
+                    level: LogLevel::Error,
+                    forward: true,
+                },
+            );
+        }
+        KeyCode::Char('[') => {
+            dispatch(
+                app,
+                Message::JumpToLogLevel {
+                    level: LogLevel::Error,
+                    forward: false,
+                },
+            );
+        }
+        KeyCode::Char('}') => {
+            dispatch(
+                app,
+                Message::JumpToLogLevel {
+                    level: LogLevel::Warn,
+                    forward: true,
+                },
+            );
+        }
+        KeyCode::Char('{') => {
+            dispatch(
+                app,
+                Message::JumpToLogLevel {
+                    level: LogLevel::Warn,
+                    forward: false,
+                },
+            );
+        }
+        KeyCode::Char('<') => {
+            dispatch(app, Message::ResizeLogList(-5));
+        }
+        KeyCode::Char('>') => {
+            dispatch(app, Message::ResizeLogList(5));
+        }
+
+        // Open in editor
+        KeyCode::Char('e') => {
+            dispatch(app, Message::OpenLogInEditor);
+        }
+
+        // Open folder
+        KeyCode::Char('o') => {
+            dispatch(app, Message::OpenLogFolder);
+        }
+
+        // Back
+        KeyCode::Char('h') | KeyCode::Esc | KeyCode::Left => {
+            dispatch(app, Message::Back);
+        }
+
+        KeyCode::Char('q') => {
+            dispatch(app, Message::Quit);
+        }
+
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::update::tests::test_app;
+    use crossterm::event::KeyEvent;
+
+    fn key(code: KeyCode) -> Message {
+        Message::Key(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_keys_move_the_cursor_and_type_into_search() {
+        let mut app = test_app("keys", &["Alpha", "Beta"]);
+
+        assert_eq!(app.selected_instance_index, 0);
+        reduce(&mut app, key(KeyCode::Char('j'))).unwrap();
+        assert_eq!(app.selected_instance_index, 1);
+        reduce(&mut app, key(KeyCode::Char('j'))).unwrap();
+        assert!(app.group_header_selected);
+        assert!(app.selected_instance().is_none());
+
+        reduce(&mut app, key(KeyCode::Char('/'))).unwrap();
+        assert_eq!(app.input_mode, InputMode::Search);
+        reduce(&mut app, key(KeyCode::Char('x'))).unwrap();
+        reduce(&mut app, Message::InputBackspace).unwrap();
+        reduce(&mut app, Message::InputChar('l')).unwrap();
+        assert_eq!(app.input_buffer, "l");
+    }
+
+    #[test]
+    fn test_paging_counts_group_headers() {
+        let mut app = test_app("page-keys", &["Alpha", "Beta", "Gamma"]);
+        app.instance_page_rows = 2;
+
+        reduce(&mut app, key(KeyCode::PageDown)).unwrap();
+        let paged_down = app.selected_instance_index;
+        reduce(&mut app, key(KeyCode::PageUp)).unwrap();
+        let paged_up = app.selected_instance_index;
+        reduce(&mut app, key(KeyCode::PageUp)).unwrap();
+
+        // Rows are the group header, then Alpha, Beta and Gamma
+        assert_eq!(paged_down, 2);
+        assert_eq!(paged_up, 0);
+        assert!(app.group_header_selected);
+    }
+}
@@ -0,0 +1,1309 @@
+//! The instance list: loading, launching, searching, sorting and groups,
+//! tasks, templates and edits to instance.cfg.
+
+use crate::actions::{
+    Copied, LaunchOptions, copy_to_clipboard, create_shortcut, launch_command_line,
+    launch_instance, lower_priority, open_folder, open_url,
+};
+use crate::app::{App, ExternalChange, InputMode, PendingLaunch, RunningInstance, Screen};
+use crate::command::Command;
+use crate::data::{
+    FileProblem, Instance, InstanceCfgEdit, InstanceFolder, IntegrityReport, JunkItem,
+    PackPlatform, TASKS_FILE, find_instance, format_env_assignments, format_size, instance_report,
+    load_tasks,
+};
+use crate::message::Message;
+use crate::net::loaders::Loader;
+use crate::net::ping::{join_warning, protocol_version};
+use crate::schedule::Periodic;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::servers::set_servers_error;
+use super::{dispatch, show_dry_run};
+
+/// Report written to the instance folder when no clipboard is available
+const REPORT_FILE: &str = "prism-tui-report.md";
+
+/// Bad files named in a verify error; the rest are only counted
+const LISTED_BAD_FILES: usize = 20;
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::InstanceLoaded { load, instance } => {
+            if load != app.instance_load {
+                return Ok(());
+            }
+            // Changes held back for a running game aren't on disk yet
+            let mut instance = *instance;
+            for edit in app
+                .pending_cfg_edits
+                .get(&instance.id)
+                .into_iter()
+                .flatten()
+            {
+                let _ = instance.apply_cfg_edit(edit, false);
+            }
+            app.add_loaded_instance(instance);
+        }
+        Message::InstancesLoaded { load } => {
+            if load != app.instance_load {
+                return Ok(());
+            }
+            app.instances_loading = false;
+            app.place_loaded_instances();
+            app.refresh_search_files();
+            if let Some(id) = app.select_after_load.take() {
+                app.select_instance_by_id(&id);
+            }
+            // A reload from these screens left them without the server list
+            if matches!(app.screen, Screen::Servers | Screen::InstanceDetails)
+                && let Err(e) = app.load_servers_for_instance()
+            {
+                set_servers_error(app, format!("Failed to load servers: {}", e));
+            }
+            for msg in std::mem::take(&mut app.after_instances_load) {
+                dispatch(app, msg);
+            }
+        }
+        Message::ProcessScanComplete(found_pids) => {
+            app.process_scan_pending = false;
+            apply_process_scan(app, found_pids);
+        }
+        Message::PackUpdateChecked {
+            instance_id,
+            result,
+        } => {
+            app.pack_update_checks_pending = app.pack_update_checks_pending.saturating_sub(1);
+            match result {
+                Ok(Some(update)) => {
+                    app.pack_updates.insert(instance_id, update);
+                }
+                Ok(None) => {
+                    app.pack_updates.remove(&instance_id);
+                }
+                Err(e) => {
+                    let name = app
+                        .instance_by_id(&instance_id)
+                        .map(|i| i.name.clone())
+                        .unwrap_or(instance_id);
+                    app.set_error(format!("Update check failed for {}: {}", name, e));
+                }
+            }
+        }
+        Message::VersionManifestLoaded(result) => {
+            app.version_manifest_pending = false;
+            // The newer-version hint is passive, so failures are only
+            // reported to the new-instance screen, which needs the list
+            match result {
+                Ok(manifest) => app.version_manifest = Some(manifest),
+                Err(e) if app.screen == Screen::CreateInstance => {
+                    app.set_error(format!("Failed to load Minecraft versions: {}", e));
+                }
+                Err(_) => {}
+            }
+        }
+        Message::LoaderVersionsLoaded {
+            loader,
+            minecraft_version,
+            result,
+        } => {
+            let key = (loader, minecraft_version);
+            app.loader_catalogs_pending.remove(&key);
+            // Like the version manifest, the outdated-loader hint is passive
+            match result {
+                Ok(catalog) => {
+                    app.loader_catalogs.insert(key, catalog);
+                }
+                Err(e) if app.screen == Screen::CreateInstance => {
+                    app.set_error(format!("Failed to load {} versions: {}", loader.name(), e));
+                }
+                Err(_) => {}
+            }
+        }
+        Message::SelectInstance(idx) => {
+            if idx < app.visible_instance_count() {
+                app.selected_instance_index = idx;
+                app.selected_group_index = app.group_index_for_instance(idx);
+                app.group_header_selected = false;
+            }
+        }
+        Message::LaunchInstance => {
+            if let Some(instance) = app.selected_instance() {
+                let instance_id = instance.id.clone();
+                let server = instance.join_address().map(str::to_string);
+                start_instance(app, instance_id, server, false);
+            }
+        }
+        Message::LaunchInstanceAt(idx) => {
+            if let Some(instance) = app.instance_by_visual_idx(idx) {
+                let instance_id = instance.id.clone();
+                let server = instance.join_address().map(str::to_string);
+                start_instance(app, instance_id, server, false);
+            }
+        }
+        Message::OpenLaunchPresets => {
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            if app.selected_launch_presets().is_empty() {
+                let name = instance.name.clone();
+                app.set_error(format!(
+                    "No launch presets for {}; add them under [launch_presets] in config.toml",
+                    name
+                ));
+            } else {
+                app.input_mode = InputMode::PickLaunchPreset;
+            }
+        }
+        Message::LaunchWithPreset(name) => {
+            app.input_mode = InputMode::Normal;
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            let instance_id = instance.id.clone();
+            let server = instance.join_address().map(str::to_string);
+            let Some(preset) = app
+                .app_config
+                .launch_presets
+                .get(&instance_id)
+                .and_then(|presets| presets.get(&name))
+            else {
+                return Ok(());
+            };
+            let options = app.preset_launch_options(&instance_id, server, preset);
+            if start_instance_with(app, instance_id, options, false) {
+                app.set_status(format!("Launched with preset {}", name));
+            }
+        }
+        Message::QuickLaunch(slot) => {
+            let Some(target) = app.app_config.quick_launch.get(&slot.to_string()) else {
+                app.set_error(format!("No instance bound to quick launch slot {}", slot));
+                return Ok(());
+            };
+            match find_instance(&app.instances, &app.app_config.aliases, target) {
+                Some(instance) => {
+                    let instance_id = instance.id.clone();
+                    let name = instance.name.clone();
+                    let server = instance.join_address().map(str::to_string);
+                    if start_instance(app, instance_id, server, false) {
+                        app.set_status(format!("Launched {}", name));
+                    }
+                }
+                None => app.set_error(format!("Quick launch {}: no instance '{}'", slot, target)),
+            }
+        }
+        Message::LowPriorityLaunch => {
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            let instance_id = instance.id.clone();
+            let server = instance.join_address().map(str::to_string);
+            let nice = app.app_config.low_priority_nice;
+
+            let Some(running) = app.running_instances.get_mut(&instance_id) else {
+                start_instance(app, instance_id, server, true);
+                return Ok(());
+            };
+            // Already running: lower it now, or once its PID is known
+            running.low_priority = true;
+            if let Some(pid) = running.pid {
+                running.priority_lowered = true;
+                match lower_priority(pid.as_u32(), nice) {
+                    Ok(()) => app.set_status("Lowered game priority".to_string()),
+                    Err(e) => app.set_error(e.to_string()),
+                }
+            }
+        }
+        Message::KillInstance => {
+            if let Some(instance) = app.selected_instance() {
+                let id = instance.id.clone();
+                tracing::info!("Stopping {}", id);
+                if let Some(running) = app.running_instances.remove(&id)
+                    && let Some(pid) = running.pid
+                {
+                    app.commands.push(Command::KillProcess { pid });
+                }
+            }
+        }
+        Message::OpenInstanceFolder => {
+            if let Some(instance) = app.selected_instance()
+                && let Err(e) = open_folder(&instance.path)
+            {
+                app.set_error(format!("Failed to open folder: {}", e));
+            }
+        }
+        Message::PickInstanceFolder => {
+            if app.selected_instance().is_some() {
+                app.input_mode = InputMode::PickInstanceFolder;
+            }
+        }
+        Message::OpenGameFolder(folder) => {
+            app.input_mode = InputMode::Normal;
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            let path = instance.game_folder(folder);
+            if path.is_dir() {
+                if let Err(e) = open_folder(&path) {
+                    app.set_error_at(format!("Failed to open folder: {}", e), &path);
+                }
+            } else if folder == InstanceFolder::Mods && instance.mod_loader.is_none() {
+                app.set_error(format!("{} has no mod loader to load mods", instance.name));
+            } else {
+                app.set_error(format!(
+                    "{} has no {} folder yet",
+                    instance.name,
+                    folder.dir_name()
+                ));
+            }
+        }
+        Message::PlanJunkCleanup => {
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            // The game holds the natives and Fabric's cache open
+            if app.is_instance_running(&instance.id) {
+                app.set_error(format!("Close {} before cleaning it up", instance.name));
+                return Ok(());
+            }
+            let command = Command::PlanJunkCleanup {
+                instance: Box::new(instance.clone()),
+                crash_report_age: app.app_config.crash_report_retention(),
+            };
+            app.commands.push(command);
+        }
+        Message::JunkCleanupPlanned { instance_id, plan } => {
+            // The selection may have moved on while the folders were sized
+            let Some(instance) = app.selected_instance().filter(|i| i.id == instance_id) else {
+                return Ok(());
+            };
+            if plan.is_empty() {
+                app.set_status(format!("Nothing to clean up in {}", instance.name));
+            } else if app.dry_run {
+                let lines = plan.iter().map(JunkItem::describe).collect();
+                app.junk_cleanup_plan = plan;
+                show_dry_run(app, "Clean up instance", lines, Message::ConfirmJunkCleanup);
+            } else {
+                app.junk_cleanup_plan = plan;
+                app.input_mode = InputMode::ConfirmJunkCleanup;
+            }
+        }
+        Message::ConfirmJunkCleanup => {
+            let plan = std::mem::take(&mut app.junk_cleanup_plan);
+            app.input_mode = InputMode::Normal;
+            let name = app
+                .selected_instance()
+                .map(|i| i.name.clone())
+                .unwrap_or_default();
+            app.commands.push(Command::CleanJunk { name, plan });
+        }
+        Message::JunkCleaned { name, result } => {
+            match result {
+                Ok(freed) => app.set_status(format!("Freed {} in {}", format_size(freed), name)),
+                Err(e) => app.set_error(format!("Failed to clean up {}: {}", name, e)),
+            }
+            app.refresh_instance_stats(true);
+        }
+        Message::VerifyInstance => {
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            let status = format!("Verifying the libraries and assets of {}...", instance.name);
+            let command = Command::VerifyInstance {
+                name: instance.name.clone(),
+                instance_path: instance.path.clone(),
+                data_dir: app.data_dir.clone(),
+            };
+            app.set_status(status);
+            app.commands.push(command);
+        }
+        Message::InstanceVerified { name, result } => match result {
+            Ok(report) => report_integrity(app, &name, &report),
+            Err(e) => app.set_error(format!("Failed to verify {}: {}", name, e)),
+        },
+        Message::CopyLaunchCommand => {
+            if let Some(instance) = app.selected_instance() {
+                // On the Servers screen the selected server is used, elsewhere
+                // the instance's enabled join-on-launch server
+                let server = if app.screen == Screen::Servers {
+                    app.selected_server().map(|s| s.ip.clone())
+                } else {
+                    instance.join_address().map(str::to_string)
+                };
+                let command =
+                    launch_command_line(&instance.id, &app.launch_options(&instance.id, server));
+                match copy_to_clipboard(&command) {
+                    Ok(_) => app.set_status(format!("Copied: {}", command)),
+                    Err(e) => app.set_error(format!("Failed to copy launch command: {}", e)),
+                }
+            }
+        }
+        Message::CreateShortcut => {
+            if let Some(instance) = app.selected_instance() {
+                let options = app.launch_options(&instance.id, None);
+                match create_shortcut(instance, &app.data_dir, &options) {
+                    Ok(path) => app.set_status(format!("Created shortcut {}", path.display())),
+                    Err(e) => app.set_error(format!("Failed to create shortcut: {}", e)),
+                }
+            }
+        }
+        Message::CopyInstanceReport => {
+            if let Some(instance) = app.selected_instance() {
+                let report = instance_report(instance, &app.prism_settings);
+                let osc52 = "Asked the terminal to copy the report, which may not have worked";
+                match copy_to_clipboard(&report) {
+                    Ok(Copied::Tool) => app.set_status("Copied instance report".to_string()),
+                    Ok(Copied::Terminal) if app.read_only => app.set_status(osc52.to_string()),
+                    Err(e) if app.read_only => {
+                        app.set_error(format!("Failed to copy report: {}", e));
+                    }
+                    // Without a clipboard tool, leave the report in the
+                    // instance folder as well
+                    copied => {
+                        let path = instance.path.join(REPORT_FILE);
+                        match std::fs::write(&path, report) {
+                            Ok(()) if copied.is_ok() => {
+                                app.set_status(format!("{}; also wrote {}", osc52, path.display()))
+                            }
+                            Ok(()) => app.set_status(format!("Wrote {}", path.display())),
+                            Err(e) => {
+                                app.set_error_at(format!("Failed to write report: {}", e), &path)
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Message::EditEnv => {
+            if let Some(instance) = app.selected_instance() {
+                app.input_buffer = format_env_assignments(&app.app_config.env_for(&instance.id));
+                app.input_mode = InputMode::EditEnv;
+            }
+        }
+        Message::EditWrapper => {
+            if let Some(instance) = app.selected_instance() {
+                app.input_buffer = app.app_config.wrapper_for(&instance.id).join(" ");
+                app.input_mode = InputMode::EditWrapper;
+            }
+        }
+        Message::EditPrismWrapper => {
+            if let Some(instance) = app.selected_instance() {
+                app.input_buffer = instance.wrapper_command.clone().unwrap_or_default();
+                app.input_mode = InputMode::EditPrismWrapper;
+            }
+        }
+        Message::SelectTask(idx) => {
+            if idx < app.tasks.len() {
+                app.selected_task_index = idx;
+            }
+        }
+        Message::AddTask => {
+            if app.selected_instance().is_some() {
+                app.input_buffer.clear();
+                app.input_mode = InputMode::AddTask;
+            }
+        }
+        Message::EditTask => {
+            if let Some(task) = app.tasks.get(app.selected_task_index) {
+                app.input_buffer = task.text.clone();
+                app.input_mode = InputMode::EditTask;
+            }
+        }
+        Message::ToggleTask => {
+            if let Some(task) = app.tasks.get_mut(app.selected_task_index) {
+                task.done = !task.done;
+                app.save_tasks();
+            }
+        }
+        Message::DeleteTask => {
+            if app.selected_task_index < app.tasks.len() {
+                app.tasks.remove(app.selected_task_index);
+                app.selected_task_index = app
+                    .selected_task_index
+                    .min(app.tasks.len().saturating_sub(1));
+                app.save_tasks();
+            }
+        }
+        Message::EditAlias => {
+            if app.selected_instance().is_some() {
+                app.input_buffer = app.selected_instance_alias().unwrap_or("").to_string();
+                app.input_mode = InputMode::SetAlias;
+            }
+        }
+        Message::ToggleInstanceMark => {
+            if let Some(id) = app.selected_instance().map(|i| i.id.clone())
+                && !app.marked_instances.remove(&id)
+            {
+                app.marked_instances.insert(id);
+            }
+        }
+        Message::ClearInstanceMarks => {
+            app.marked_instances.clear();
+        }
+        Message::SyncInstanceSettings => {
+            let Some(source) = app.selected_instance() else {
+                return Ok(());
+            };
+            let source_id = source.id.clone();
+            if app.marked_instances.iter().all(|id| *id == source_id) {
+                app.set_error(
+                    "Mark the instances to copy to with Space, then select the one to copy from"
+                        .to_string(),
+                );
+            } else {
+                app.input_mode = InputMode::ConfirmSyncSettings;
+            }
+        }
+        Message::ConfirmSyncSettings => {
+            app.input_mode = InputMode::Normal;
+            let Some(source) = app.selected_instance() else {
+                return Ok(());
+            };
+            let settings = match source.java_settings() {
+                Ok(settings) => settings,
+                Err(e) => {
+                    let path = source.path.join("instance.cfg");
+                    app.set_error_at(format!("Failed to read settings: {}", e), &path);
+                    return Ok(());
+                }
+            };
+            let source_id = source.id.clone();
+            let edit = InstanceCfgEdit::Settings(settings);
+            let targets: Vec<String> = app
+                .marked_instances
+                .iter()
+                .filter(|id| **id != source_id)
+                .cloned()
+                .collect();
+
+            // Like `edit_instance_cfg`, held back for running games
+            let (mut written, mut held) = (0, 0);
+            let mut failed = Vec::new();
+            for id in targets {
+                let defer = app.running_instances.contains_key(&id)
+                    && app.app_config.defer_running_cfg_edits;
+                let Some(instance) = app.instance_by_id_mut(&id) else {
+                    continue;
+                };
+                match instance.apply_cfg_edit(&edit, !defer) {
+                    Ok(()) if defer => {
+                        app.pending_cfg_edits
+                            .entry(id)
+                            .or_default()
+                            .push(edit.clone());
+                        held += 1;
+                    }
+                    Ok(()) => written += 1,
+                    Err(e) => failed.push(format!("{}: {}", instance.name, e)),
+                }
+            }
+            if let Some(first) = failed.first() {
+                app.set_error(format!(
+                    "Failed to update {} instance(s), e.g. {}",
+                    failed.len(),
+                    first
+                ));
+            } else if held > 0 {
+                app.set_status(format!(
+                    "Copied settings to {} instances; {} running, saving when they exit",
+                    written + held,
+                    held
+                ));
+            } else {
+                app.marked_instances.clear();
+                app.set_status(format!("Copied settings to {} instances", written));
+            }
+        }
+        Message::SaveTemplate => {
+            if let Some(instance) = app.selected_instance() {
+                app.input_buffer = instance.name.clone();
+                app.input_mode = InputMode::SaveTemplate;
+            }
+        }
+        Message::TemplateSaved { name, result } => match result {
+            Ok(()) => app.set_status(format!("Saved template {}", name)),
+            Err(e) => app.set_error(format!("Failed to save template: {}", e)),
+        },
+        Message::OpenInstanceDetails => {
+            if app.selected_instance().is_some() {
+                // Servers are needed to check the join-on-launch target
+                if let Err(e) = app.load_servers_for_instance() {
+                    set_servers_error(app, format!("Failed to load servers: {}", e));
+                }
+                app.refresh_instance_stats(false);
+                let path = app.selected_instance().map(|i| i.path.clone());
+                match path.as_deref().map(load_tasks).unwrap_or(Ok(Vec::new())) {
+                    Ok(tasks) => app.tasks = tasks,
+                    Err(e) => {
+                        app.tasks.clear();
+                        let file = path.unwrap_or_default().join(TASKS_FILE);
+                        app.set_error_at(format!("Failed to load tasks: {}", e), &file);
+                    }
+                }
+                app.selected_task_index = 0;
+                let instance = app.selected_instance();
+                let loader = instance.and_then(|i| i.mod_loader.as_deref());
+                match (loader.map(Loader::from_name), instance) {
+                    (None, _) => queue_version_manifest_fetch(app),
+                    (Some(Some(loader)), Some(instance)) => {
+                        let minecraft_version = instance.minecraft_version.clone();
+                        queue_loader_catalog_fetch(app, loader, minecraft_version);
+                    }
+                    _ => {}
+                }
+                app.previous_screen = Some(app.screen);
+                app.screen = Screen::InstanceDetails;
+            }
+        }
+        Message::RefreshInstanceStats => {
+            app.refresh_instance_stats(true);
+        }
+        Message::CheckPackUpdates => {
+            queue_pack_update_checks(app);
+        }
+        Message::OpenPackPage => {
+            let url = app
+                .selected_instance()
+                .and_then(|i| i.managed_pack.as_ref())
+                .and_then(|p| p.url());
+            match url {
+                Some(url) => {
+                    if let Err(e) = open_url(&url) {
+                        app.set_error(format!("Failed to open pack page: {}", e));
+                    }
+                }
+                None => app.set_error("Instance has no pack page".to_string()),
+            }
+        }
+        Message::ConfirmJavaLaunch => {
+            app.input_mode = InputMode::Normal;
+            if let Some(launch) = app.pending_launch.take() {
+                ping_then_launch(app, launch);
+            }
+        }
+        Message::ConfirmLowSpaceLaunch => {
+            app.input_mode = InputMode::Normal;
+            if let Some(launch) = app.pending_launch.take() {
+                launch_now(app, launch);
+            }
+        }
+        Message::ServerPinged {
+            address,
+            result,
+            for_launch: true,
+        } => {
+            // Dropped if the launch was cancelled or replaced meanwhile
+            let Some(launch) = app
+                .pending_launch
+                .take_if(|launch| launch.options.server.as_deref() == Some(address.as_str()))
+            else {
+                return Ok(());
+            };
+            let minecraft_version = app
+                .instance_by_id(&launch.instance_id)
+                .map(|i| i.minecraft_version.clone())
+                .unwrap_or_default();
+            match join_warning(&address, &minecraft_version, &result) {
+                // Don't take over a dialog the user is typing in
+                Some(warning) if app.input_mode != InputMode::Normal => {
+                    app.set_error(format!("Launch cancelled: {}", warning));
+                }
+                Some(warning) => {
+                    app.status_message = None;
+                    app.server_launch_warning = Some(warning);
+                    app.pending_launch = Some(launch);
+                    app.input_mode = InputMode::ConfirmServerLaunch;
+                }
+                None => {
+                    app.status_message = None;
+                    check_space_then_launch(app, launch);
+                }
+            }
+        }
+        Message::ConfirmServerLaunch => {
+            app.input_mode = InputMode::Normal;
+            app.server_launch_warning = None;
+            if let Some(launch) = app.pending_launch.take() {
+                check_space_then_launch(app, launch);
+            }
+        }
+        // Search
+        Message::StartSearch => {
+            app.input_mode = InputMode::Search;
+            app.input_buffer.clear();
+        }
+        Message::SearchChar(c) => {
+            app.input_buffer.push(c);
+            app.update_search(app.input_buffer.clone());
+        }
+        Message::SearchBackspace => {
+            app.input_buffer.pop();
+            app.update_search(app.input_buffer.clone());
+        }
+        Message::SearchConfirm => {
+            app.input_mode = InputMode::Normal;
+        }
+        Message::SearchCancel => {
+            app.input_buffer.clear();
+            app.clear_search();
+            app.input_mode = InputMode::Normal;
+        }
+        // Sorting
+        Message::CycleSortMode => {
+            app.sort_mode = app.sort_mode.next();
+            app.sort_and_group_instances();
+            app.selected_instance_index = 0;
+            app.selected_group_index = app.group_index_for_instance(0);
+            app.group_header_selected = false;
+            app.save_config();
+        }
+        Message::ToggleSortDirection => {
+            app.sort_ascending = !app.sort_ascending;
+            app.sort_and_group_instances();
+            app.selected_instance_index = 0;
+            app.selected_group_index = app.group_index_for_instance(0);
+            app.group_header_selected = false;
+            app.save_config();
+        }
+        // Collapsible groups
+        Message::SelectGroup(group_idx) => {
+            if group_idx < app.grouped_instances.len() {
+                app.selected_group_index = group_idx;
+                app.group_header_selected = true;
+            }
+        }
+        Message::LaunchGroup => {
+            let first = app
+                .grouped_instances
+                .get(app.selected_group_index)
+                .and_then(|g| g.indices.first())
+                .and_then(|&idx| app.instances.get(idx));
+            if let Some(instance) = first {
+                let instance_id = instance.id.clone();
+                let server = instance.join_address().map(str::to_string);
+                start_instance(app, instance_id, server, false);
+            }
+        }
+        Message::ToggleGroupCollapse => {
+            if let Some(key) = app.selected_group_key() {
+                toggle_group_collapse(app, &key);
+            }
+        }
+        Message::NextGroup => {
+            let count = app.grouped_instances.len();
+            if count > 0 {
+                app.selected_group_index = (app.selected_group_index + 1) % count;
+                match app.first_instance_in_group(app.selected_group_index) {
+                    Some(first) => {
+                        app.selected_instance_index = first;
+                        app.group_header_selected = false;
+                    }
+                    None => app.group_header_selected = true,
+                }
+            }
+        }
+        Message::PrevGroup => {
+            let count = app.grouped_instances.len();
+            if count > 0 {
+                if app.selected_group_index == 0 {
+                    app.selected_group_index = count - 1;
+                } else {
+                    app.selected_group_index -= 1;
+                }
+                match app.first_instance_in_group(app.selected_group_index) {
+                    Some(first) => {
+                        app.selected_instance_index = first;
+                        app.group_header_selected = false;
+                    }
+                    None => app.group_header_selected = true,
+                }
+            }
+        }
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+/// Launch an instance with the active account and its environment
+/// overrides, joining `server` if given.
+/// Returns whether the launch was started.
+pub(super) fn start_instance(
+    app: &mut App,
+    instance_id: String,
+    server: Option<String>,
+    low_priority: bool,
+) -> bool {
+    let options = app.launch_options(&instance_id, server);
+    start_instance_with(app, instance_id, options, low_priority)
+}
+
+fn start_instance_with(
+    app: &mut App,
+    instance_id: String,
+    options: LaunchOptions,
+    low_priority: bool,
+) -> bool {
+    let launch = PendingLaunch {
+        instance_id,
+        options,
+        low_priority,
+    };
+    // A too-old Java fails at startup with a class version error, so ask first
+    let java_mismatch = app
+        .instance_by_id(&launch.instance_id)
+        .and_then(|i| i.java_mismatch(&app.prism_settings));
+    if java_mismatch.is_some() && !app.is_instance_running(&launch.instance_id) {
+        app.pending_launch = Some(launch);
+        app.input_mode = InputMode::ConfirmJavaLaunch;
+        return false;
+    }
+    ping_then_launch(app, launch)
+}
+
+fn ping_then_launch(app: &mut App, launch: PendingLaunch) -> bool {
+    // Booting the game only to read "connection refused" is slow, so ask the
+    // server first; the launch goes on when it answers
+    if app.app_config.ping_before_join
+        && !app.is_instance_running(&launch.instance_id)
+        && let Some(address) = launch.options.server.clone()
+    {
+        let protocol = app
+            .instance_by_id(&launch.instance_id)
+            .and_then(|i| protocol_version(&i.minecraft_version));
+        app.set_status(format!("Checking {}...", address));
+        app.commands.push(Command::PingServer {
+            address,
+            protocol,
+            for_launch: true,
+        });
+        app.pending_launch = Some(launch);
+        return false;
+    }
+    check_space_then_launch(app, launch)
+}
+
+fn check_space_then_launch(app: &mut App, launch: PendingLaunch) -> bool {
+    // Running out of space mid-session can corrupt worlds, so ask first
+    app.refresh_free_space();
+    if app.disk_space_low() && !app.is_instance_running(&launch.instance_id) {
+        app.pending_launch = Some(launch);
+        app.input_mode = InputMode::ConfirmLowSpaceLaunch;
+        return false;
+    }
+    launch_now(app, launch)
+}
+
+fn launch_now(app: &mut App, launch: PendingLaunch) -> bool {
+    let PendingLaunch {
+        instance_id,
+        options,
+        low_priority,
+    } = launch;
+    if app.is_instance_running(&instance_id) {
+        app.set_error("Instance is already running".into());
+        return false;
+    }
+
+    tracing::info!("Launching {}", instance_id);
+    if let Err(e) = launch_instance(&instance_id, &options) {
+        app.set_error(format!("Launch failed: {}", e));
+        return false;
+    }
+    app.running_instances.insert(
+        instance_id,
+        RunningInstance {
+            pid: None,
+            launched_at: Instant::now(),
+            low_priority: low_priority || app.app_config.launch_low_priority,
+            priority_lowered: false,
+        },
+    );
+    // Find the new game's process without waiting out the interval
+    app.scheduler.run_soon(Periodic::ProcessScan);
+    true
+}
+
+pub(super) fn toggle_group_collapse(app: &mut App, key: &str) {
+    let selected_id = app.selected_instance().map(|i| i.id.clone());
+    let on_group = app.selected_group_key().as_deref() == Some(key);
+    if app.collapsed_groups.contains(key) {
+        app.collapsed_groups.remove(key);
+    } else {
+        app.collapsed_groups.insert(key.to_string());
+    }
+    let count = app.visible_instance_count();
+    app.filtered_instance_indices = (0..count).collect();
+
+    // Keep the cursor in place: on the header of a group it was in, else on
+    // the same instance, whose position moves with the groups above it
+    if on_group && (app.group_header_selected || app.collapsed_groups.contains(key)) {
+        dispatch(app, Message::SelectGroup(app.selected_group_index));
+    } else if let Some(idx) = selected_id.and_then(|id| app.visual_index_of(&id)) {
+        app.selected_instance_index = idx;
+    }
+    if app.selected_instance_index >= count {
+        app.selected_instance_index = count.saturating_sub(1);
+    }
+}
+
+/// Query the platform API for every managed Modrinth pack, and every
+/// CurseForge pack when there is an API key.
+/// Each result arrives as `Message::PackUpdateChecked`.
+fn queue_pack_update_checks(app: &mut App) {
+    if app.pack_update_checks_pending > 0 {
+        return;
+    }
+
+    let curseforge_key = app.curseforge_key();
+    for instance in &app.instances {
+        let Some(pack) = instance.managed_pack.as_ref().filter(|p| {
+            let checkable = match p.platform {
+                PackPlatform::Modrinth => true,
+                PackPlatform::CurseForge => curseforge_key.is_some(),
+                _ => false,
+            };
+            checkable && !p.id.is_empty()
+        }) else {
+            continue;
+        };
+
+        app.pack_update_checks_pending += 1;
+        app.commands.push(Command::CheckPackUpdate {
+            instance_id: instance.id.clone(),
+            platform: pack.platform.clone(),
+            project_id: pack.id.clone(),
+            version_id: pack.version_id.clone(),
+            curseforge_key: curseforge_key.clone(),
+        });
+    }
+}
+
+/// Load the Mojang version manifest (cached on disk) if it isn't loaded yet.
+/// The result arrives as `Message::VersionManifestLoaded`.
+pub(super) fn queue_version_manifest_fetch(app: &mut App) {
+    if app.version_manifest.is_some() || app.version_manifest_pending {
+        return;
+    }
+
+    app.version_manifest_pending = true;
+    app.commands.push(Command::FetchVersionManifest);
+}
+
+/// Fetch the version catalog of `loader` for `minecraft_version` unless it
+/// is loaded or on its way. The result arrives as
+/// `Message::LoaderVersionsLoaded`.
+pub(super) fn queue_loader_catalog_fetch(app: &mut App, loader: Loader, minecraft_version: String) {
+    let key = (loader, minecraft_version.clone());
+    if app.loader_catalogs.contains_key(&key) || !app.loader_catalogs_pending.insert(key) {
+        return;
+    }
+
+    app.commands.push(Command::FetchLoaderVersions {
+        loader,
+        minecraft_version,
+    });
+}
+
+/// Scan for Java processes off the update loop so large process tables
+/// don't stall rendering. Results arrive as `Message::ProcessScanComplete`.
+pub(super) fn queue_process_scan(app: &mut App) {
+    let instances = app
+        .instances
+        .iter()
+        .map(|i| (i.id.clone(), i.path.clone()))
+        .collect();
+
+    app.process_scan_pending = true;
+    app.commands.push(Command::ScanProcesses { instances });
+}
+
+/// Apply the result of a process scan to the tracked running instances.
+/// Updates PIDs for tracked instances and removes entries where the game has stopped.
+fn apply_process_scan(app: &mut App, found_pids: HashMap<String, sysinfo::Pid>) {
+    let mut to_remove = Vec::new();
+    let mut errors = Vec::new();
+    for (id, running) in app.running_instances.iter_mut() {
+        if let Some(&pid) = found_pids.get(id.as_str()) {
+            running.pid = Some(pid);
+            if running.low_priority && !running.priority_lowered {
+                // Only try once so a failing renice isn't repeated every scan
+                running.priority_lowered = true;
+                if let Err(e) = lower_priority(pid.as_u32(), app.app_config.low_priority_nice) {
+                    errors.push(e.to_string());
+                }
+            }
+        } else if running.pid.is_some() {
+            // Had a PID but Java process is gone — game exited
+            to_remove.push(id.clone());
+        } else if running.launched_at.elapsed() > Duration::from_secs(30) {
+            // Never found a Java process and it's been too long — give up
+            to_remove.push(id.clone());
+        }
+        // else: recently launched, still waiting for Java to start
+    }
+
+    for id in to_remove {
+        tracing::info!("{} is no longer running", id);
+        app.running_instances.remove(&id);
+    }
+    if let Some(e) = errors.pop() {
+        app.set_error(e);
+    }
+    write_pending_cfg_edits(app);
+}
+
+/// Change the selected instance's instance.cfg. PrismLauncher rewrites the
+/// file when a game exits, so while one runs the change is held back until
+/// then, or written with a warning when deferring is turned off.
+pub(super) fn edit_instance_cfg(app: &mut App, edit: InstanceCfgEdit) {
+    let Some(instance) = app.selected_instance() else {
+        return;
+    };
+    let id = instance.id.clone();
+    let name = instance.name.clone();
+    let running = app.running_instances.contains_key(&id);
+    let defer = running && app.app_config.defer_running_cfg_edits;
+
+    if !defer && instance.cfg_changed_on_disk(&edit) {
+        app.external_change = Some(ExternalChange::InstanceCfg(edit));
+        app.input_mode = InputMode::ConfirmExternalChange;
+        return;
+    }
+
+    if let Some(instance) = app.selected_instance_mut()
+        && let Err(e) = instance.apply_cfg_edit(&edit, !defer)
+    {
+        let path = instance.path.join("instance.cfg");
+        app.set_error_at(format!("Failed to update config: {}", e), &path);
+        return;
+    }
+    if defer {
+        app.pending_cfg_edits.entry(id).or_default().push(edit);
+        app.set_status(format!(
+            "{} is running; saving the change when it exits",
+            name
+        ));
+    } else if running {
+        app.set_error(format!(
+            "{} is running; PrismLauncher may overwrite this change when it exits",
+            name
+        ));
+    }
+}
+
+/// Read the selected instance from disk again, keeping its group
+pub(super) fn reload_selected_instance(app: &mut App) -> crate::error::Result<()> {
+    let Some(instance) = app.selected_instance_mut() else {
+        return Ok(());
+    };
+    let groups = instance
+        .group
+        .iter()
+        .map(|group| (instance.id.clone(), group.clone()))
+        .collect();
+    *instance = Instance::load(instance.path.clone(), &groups)?;
+    Ok(())
+}
+
+/// Sum up a verify run: a notice when every file matched, otherwise an
+/// error whose details name the bad files, relative to the data directory
+fn report_integrity(app: &mut App, name: &str, report: &IntegrityReport) {
+    let mut problems: Vec<String> = report
+        .bad
+        .iter()
+        .take(LISTED_BAD_FILES)
+        .map(|file| {
+            let path = file.path.strip_prefix(&app.data_dir).unwrap_or(&file.path);
+            let problem = match file.problem {
+                FileProblem::Missing => "missing",
+                FileProblem::Corrupt => "corrupt",
+            };
+            format!("{} ({})", path.display(), problem)
+        })
+        .collect();
+    if let Some(more) = report
+        .bad
+        .len()
+        .checked_sub(LISTED_BAD_FILES)
+        .filter(|n| *n > 0)
+    {
+        problems.push(format!("{} more", more));
+    }
+    if !report.uncached.is_empty() {
+        problems.push(format!(
+            "no metadata cached for {}",
+            report.uncached.join(", ")
+        ));
+    }
+
+    if problems.is_empty() {
+        app.set_status(format!(
+            "All {} libraries and assets of {} are intact",
+            report.checked, name
+        ));
+    } else if report.bad.is_empty() {
+        app.set_error(format!(
+            "{} could not be fully verified: {}",
+            name,
+            problems.join(": ")
+        ));
+    } else {
+        app.set_error(format!(
+            "{} of {} files of {} are missing or corrupt: {}",
+            report.bad.len(),
+            report.checked,
+            name,
+            problems.join(": ")
+        ));
+    }
+}
+
+/// Write the instance.cfg changes held back for games that have exited
+pub fn write_pending_cfg_edits(app: &mut App) {
+    let ready: Vec<String> = app
+        .pending_cfg_edits
+        .keys()
+        .filter(|id| !app.running_instances.contains_key(*id))
+        .cloned()
+        .collect();
+    for id in ready {
+        let edits = app.pending_cfg_edits.remove(&id).unwrap_or_default();
+        let Some(instance) = app.instance_by_id_mut(&id) else {
+            continue;
+        };
+        let name = instance.name.clone();
+        match edits
+            .iter()
+            .try_for_each(|edit| instance.apply_cfg_edit(edit, true))
+        {
+            Ok(()) => app.set_status(format!("Saved held back changes for {}", name)),
+            Err(e) => app.set_error(format!("Failed to update config for {}: {}", name, e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::update::tests::test_app;
+
+    #[test]
+    fn test_loaded_instances_wait_to_be_placed() {
+        let mut app = test_app("placing", &["Alpha", "Beta"]);
+        let mut extra = app.instances[0].clone();
+        extra.id = "Aardvark".to_string();
+        extra.name = "Aardvark".to_string();
+        let load = app.instance_load;
+
+        reduce(
+            &mut app,
+            Message::InstanceLoaded {
+                load,
+                instance: Box::new(extra),
+            },
+        )
+        .unwrap();
+        let listed_early = app.instance_by_id("Aardvark").is_some();
+        let selected_early = app.selected_instance().map(|i| i.id.clone());
+        app.place_loaded_instances();
+
+        assert!(!listed_early);
+        assert_eq!(app.grouped_instances[0].indices.len(), 3);
+        assert!(app.instance_by_id("Aardvark").is_some());
+        assert_eq!(
+            app.selected_instance().map(|i| i.id.clone()),
+            selected_early
+        );
+    }
+
+    #[test]
+    fn test_search_narrows_and_widens_again() {
+        let mut app = test_app("search", &["Alpha", "Beta", "Gamma"]);
+
+        reduce(&mut app, Message::StartSearch).unwrap();
+        reduce(&mut app, Message::SearchChar('a')).unwrap();
+        assert_eq!(app.filtered_instance_indices.len(), 3);
+        reduce(&mut app, Message::SearchChar('m')).unwrap();
+        assert_eq!(app.filtered_instance_indices.len(), 1);
+        assert_eq!(
+            app.selected_instance().map(|i| i.name.as_str()),
+            Some("Gamma")
+        );
+        reduce(&mut app, Message::SearchBackspace).unwrap();
+        assert_eq!(app.filtered_instance_indices.len(), 3);
+        reduce(&mut app, Message::SearchCancel).unwrap();
+        assert_eq!(app.input_mode, InputMode::Normal);
+    }
+
+    #[test]
+    fn test_missing_game_folders_say_why() {
+        let mut app = test_app("folders", &["Alpha"]);
+
+        reduce(&mut app, Message::PickInstanceFolder).unwrap();
+        assert_eq!(app.input_mode, InputMode::PickInstanceFolder);
+        reduce(&mut app, Message::OpenGameFolder(InstanceFolder::Mods)).unwrap();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.error_message.as_deref(),
+            Some("Alpha has no mod loader to load mods")
+        );
+        reduce(&mut app, Message::OpenGameFolder(InstanceFolder::Saves)).unwrap();
+        assert_eq!(
+            app.error_message.as_deref(),
+            Some("Alpha has no saves folder yet")
+        );
+    }
+
+    #[test]
+    fn test_too_old_java_asks_before_launching() {
+        let mut app = test_app("java", &["Alpha"]);
+        app.instances[0].minecraft_version = "1.20.5".to_string();
+        app.instances[0]
+            .setting_overrides
+            .insert("JavaVersion".to_string(), "17.0.8".to_string());
+        let id = app.instances[0].id.clone();
+
+        assert!(!start_instance(&mut app, id, None, false));
+        assert_eq!(app.input_mode, InputMode::ConfirmJavaLaunch);
+        assert!(app.pending_launch.is_some());
+        assert_eq!(
+            app.instances[0]
+                .java_mismatch(&app.prism_settings)
+                .map(|m| m.label()),
+            Some("needs Java 21, set to Java 17".to_string())
+        );
+    }
+
+    #[test]
+    fn test_offline_server_asks_before_launching() {
+        let mut app = test_app("ping", &["Alpha"]);
+        app.app_config.ping_before_join = true;
+        let id = app.instances[0].id.clone();
+
+        assert!(!start_instance(
+            &mut app,
+            id,
+            Some("mc.example.com".into()),
+            false
+        ));
+        assert_eq!(
+            app.commands,
+            vec![Command::PingServer {
+                address: "mc.example.com".into(),
+                protocol: None,
+                for_launch: true,
+            }]
+        );
+        // A late answer for another server is ignored
+        let refused = Err("connection refused".to_string());
+        reduce(
+            &mut app,
+            Message::ServerPinged {
+                address: "other.example.com".into(),
+                result: refused.clone(),
+                for_launch: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        reduce(
+            &mut app,
+            Message::ServerPinged {
+                address: "mc.example.com".into(),
+                result: refused,
+                for_launch: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(app.input_mode, InputMode::ConfirmServerLaunch);
+        assert_eq!(
+            app.server_launch_warning.as_deref(),
+            Some("mc.example.com did not answer: connection refused")
+        );
+        assert!(app.pending_launch.is_some());
+    }
+
+    #[test]
+    fn test_verify_errors_name_the_bad_files() {
+        let mut app = test_app("verify", &["Alpha"]);
+        let bad = (0..LISTED_BAD_FILES + 2)
+            .map(|n| crate::data::integrity::BadFile {
+                path: app.data_dir.join(format!("libraries/lib-{}.jar", n)),
+                problem: FileProblem::Missing,
+            })
+            .collect();
+        let report = IntegrityReport {
+            checked: 100,
+            bad,
+            uncached: Vec::new(),
+        };
+
+        reduce(
+            &mut app,
+            Message::InstanceVerified {
+                name: "Alpha".to_string(),
+                result: Ok(report),
+            },
+        )
+        .unwrap();
+        let error = app.error_message.clone().unwrap();
+        assert!(error.starts_with("22 of 100 files of Alpha are missing or corrupt: "));
+        assert!(error.contains(": libraries/lib-0.jar (missing): "));
+        assert!(error.ends_with(": 2 more"));
+
+        reduce(
+            &mut app,
+            Message::InstanceVerified {
+                name: "Alpha".to_string(),
+                result: Ok(IntegrityReport {
+                    checked: 100,
+                    ..Default::default()
+                }),
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("All 100 libraries and assets of Alpha are intact")
+        );
+    }
+
+    #[test]
+    fn test_junk_cleanup_is_planned_and_applied_in_the_background() {
+        let mut app = test_app("junk", &["Alpha"]);
+        let id = app.instances[0].id.clone();
+        let item = JunkItem {
+            kind: crate::data::JunkKind::Natives,
+            path: app.instances[0].path.join("natives"),
+            size: 2048,
+        };
+
+        reduce(&mut app, Message::PlanJunkCleanup).unwrap();
+        let planning = app.commands.drain(..).collect::<Vec<_>>();
+        reduce(
+            &mut app,
+            Message::JunkCleanupPlanned {
+                instance_id: id,
+                plan: vec![item.clone()],
+            },
+        )
+        .unwrap();
+        let mode = app.input_mode;
+        reduce(&mut app, Message::ConfirmJunkCleanup).unwrap();
+
+        assert!(matches!(
+            planning.as_slice(),
+            [Command::PlanJunkCleanup { instance, .. }] if instance.name == "Alpha"
+        ));
+        assert_eq!(mode, InputMode::ConfirmJunkCleanup);
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert_eq!(
+            app.commands,
+            [Command::CleanJunk {
+                name: "Alpha".to_string(),
+                plan: vec![item],
+            }]
+        );
+    }
+}
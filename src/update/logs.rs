@@ -0,0 +1,486 @@
+//! The log viewer: picking sources, searching and filtering, and cleanup.
+
+use crate::actions::{open_folder, open_in_editor};
+use crate::app::{App, InputMode, LOG_LIST_MAX_PERCENT, LOG_LIST_MIN_PERCENT, LogSource, Screen};
+use crate::command::Command;
+use crate::data::{
+    CleanupItem, analyze_log, disable_mod, find_mod_file, load_log_content, load_log_entries,
+    plan_log_cleanup, search_items,
+};
+use crate::message::Message;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use super::{dispatch, show_dry_run};
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::OpenInstanceLogs => {
+            if app.selected_instance().is_some() {
+                open_logs(app, LogSource::Instance);
+            }
+        }
+        Message::OpenLauncherLogs => {
+            open_logs(app, LogSource::Launcher);
+        }
+        Message::OpenAppLogs => {
+            open_logs(app, LogSource::App);
+        }
+        Message::OpenLogsTab => {
+            if app.screen == Screen::Logs {
+                return Ok(());
+            }
+            match app.last_log_source {
+                Some(source) if !source.is_per_instance() || app.selected_instance().is_some() => {
+                    open_logs(app, source);
+                }
+                _ => app.input_mode = InputMode::PickLogSource,
+            }
+        }
+        Message::PickLogSource(source) => {
+            app.input_mode = InputMode::Normal;
+            if source.is_per_instance() && app.selected_instance().is_none() {
+                app.set_error("Select an instance first".to_string());
+            } else {
+                open_logs(app, source);
+            }
+        }
+        Message::CycleLogSource => {
+            let mut source = app.log_source.next();
+            if app.selected_instance().is_none() {
+                while source.is_per_instance() {
+                    source = source.next();
+                }
+            }
+            reopen_logs(app, source);
+        }
+        Message::OpenLogInstancePicker => {
+            app.search_index = app.build_log_instance_index();
+            app.input_buffer.clear();
+            app.search_results = search_items(&app.search_index, "");
+            app.selected_search_result = 0;
+            app.input_mode = InputMode::GlobalSearch;
+        }
+        Message::SelectLog(idx) => {
+            if idx < app.log_entries.len() {
+                app.selected_log_index = idx;
+                app.log_content.clear();
+                app.log_hints.clear();
+                app.suspect_mod = None;
+                app.log_scroll_offset = 0;
+            }
+        }
+        Message::LoadLogContent => {
+            if let Some(entry) = app.log_entries.get(app.selected_log_index) {
+                match load_log_content(&entry.path) {
+                    Ok(content) => {
+                        app.log_hints = analyze_log(&content);
+                        app.suspect_mod = suspected_mod_file(app);
+                        app.log_content = content;
+                        app.log_scroll_offset = 0;
+                        app.log_loaded_size = fs::metadata(&entry.path).ok().map(|m| m.len());
+                        // Re-run search if active
+                        if !app.log_search_query.is_empty() {
+                            app.update_log_search();
+                        }
+                        app.note_log_file();
+                    }
+                    Err(e) => {
+                        app.set_error(format!("Failed to load log content: {}", e));
+                    }
+                }
+            }
+        }
+        Message::ScrollLogUp(amount) => {
+            app.log_scroll_offset = app.log_scroll_offset.saturating_sub(amount);
+        }
+        Message::ScrollLogDown(amount) => {
+            let max_offset = app.filtered_log_content().len().saturating_sub(1);
+            app.log_scroll_offset = (app.log_scroll_offset + amount).min(max_offset);
+        }
+        Message::OpenLogInEditor => {
+            if let Some(entry) = app.log_entries.get(app.selected_log_index)
+                && let Err(e) = open_in_editor(&entry.path)
+            {
+                app.set_error(format!("Failed to open editor: {}", e));
+            }
+        }
+        Message::OpenLogFolder => {
+            if let Some(entry) = app.log_entries.get(app.selected_log_index)
+                && let Some(parent) = entry.path.parent()
+                && let Err(e) = open_folder(parent)
+            {
+                app.set_error(format!("Failed to open folder: {}", e));
+            }
+        }
+        // Log search
+        Message::StartLogSearch => {
+            app.input_mode = InputMode::LogSearch;
+            app.log_search_query.clear();
+            app.log_search_matches.clear();
+            app.log_search_current = 0;
+        }
+        Message::LogSearchChar(c) => {
+            app.log_search_query.push(c);
+            app.update_log_search();
+        }
+        Message::LogSearchBackspace => {
+            app.log_search_query.pop();
+            app.update_log_search();
+        }
+        Message::LogSearchConfirm => {
+            app.input_mode = InputMode::Normal;
+        }
+        Message::LogSearchCancel => {
+            app.log_search_query.clear();
+            app.log_search_matches.clear();
+            app.log_search_current = 0;
+            app.input_mode = InputMode::Normal;
+        }
+        Message::LogSearchNext => {
+            app.log_search_next();
+        }
+        Message::LogSearchPrev => {
+            app.log_search_prev();
+        }
+        // Log level filtering
+        Message::ToggleLogLevel(level) => {
+            if app.log_level_filter.contains(&level) {
+                app.log_level_filter.remove(&level);
+            } else {
+                app.log_level_filter.insert(level);
+            }
+        }
+        Message::ShowAllLogLevels => {
+            app.log_level_filter.clear();
+        }
+        Message::ToggleLogFilterPanel => {
+            app.show_log_filter_panel = !app.show_log_filter_panel;
+        }
+        Message::ToggleLogWrap => {
+            app.log_wrap = !app.log_wrap;
+        }
+        Message::ToggleLogFollow => {
+            app.log_follow = !app.log_follow;
+            if app.log_follow {
+                app.set_status("Following the log; new lines show as they are written".to_string());
+            }
+        }
+        Message::PlanLogCleanup => {
+            let retention = match app.log_source {
+                LogSource::Instance | LogSource::CrashReports => app
+                    .selected_instance()
+                    .and_then(|i| app.app_config.log_retention_for(&i.id)),
+                LogSource::Launcher => app.app_config.global_log_retention(),
+                LogSource::App => {
+                    app.set_status("prism-tui removes its own logs after a week".to_string());
+                    return Ok(());
+                }
+            };
+            let Some(max_age) = retention else {
+                app.set_error("No log retention configured (log_retention_days)".to_string());
+                return Ok(());
+            };
+            let plan = plan_log_cleanup(
+                &app.log_entries,
+                max_age,
+                app.app_config.log_retention_compress,
+                SystemTime::now(),
+            );
+            if plan.is_empty() {
+                app.set_status("No logs to clean up".to_string());
+            } else if app.dry_run {
+                let lines = plan.iter().map(CleanupItem::describe).collect();
+                app.log_cleanup_plan = plan;
+                show_dry_run(app, "Clean up logs", lines, Message::ConfirmLogCleanup);
+            } else {
+                app.log_cleanup_plan = plan;
+                app.input_mode = InputMode::ConfirmLogCleanup;
+            }
+        }
+        Message::ConfirmLogCleanup => {
+            let planned = std::mem::take(&mut app.log_cleanup_plan);
+            app.input_mode = InputMode::Normal;
+            app.commands.push(Command::CleanLogs {
+                planned,
+                targets: Vec::new(),
+                compress: app.app_config.log_retention_compress,
+            });
+        }
+        Message::DisableSuspectedMod => {
+            if let Some(path) = &app.suspect_mod
+                && app.dry_run
+            {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                let instance = app.selected_instance().map_or("", |i| i.name.as_str());
+                let lines = vec![
+                    format!("Rename {} to {}.disabled", name, name),
+                    format!("Launch {} again", instance),
+                ];
+                show_dry_run(app, "Disable mod", lines, Message::ConfirmDisableMod);
+            } else if app.suspect_mod.is_some() {
+                app.input_mode = InputMode::ConfirmDisableMod;
+            } else {
+                app.set_error("No installed mod is suspected in this log".to_string());
+            }
+        }
+        Message::ConfirmDisableMod => {
+            app.input_mode = InputMode::Normal;
+            let Some(path) = app.suspect_mod.take() else {
+                return Ok(());
+            };
+            if let Err(e) = disable_mod(&path) {
+                app.set_error_at(format!("Failed to disable mod: {}", e), &path);
+                return Ok(());
+            }
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            app.set_status(format!("Disabled {}", name));
+            dispatch(app, Message::LaunchInstance);
+        }
+        Message::CleanAllLogs => {
+            let targets: Vec<_> = app
+                .instances
+                .iter()
+                .filter_map(|i| {
+                    let max_age = app.app_config.log_retention_for(&i.id)?;
+                    Some((i.name.clone(), i.logs_dir(), max_age))
+                })
+                .collect();
+            if targets.is_empty() {
+                return Ok(());
+            }
+            let compress = app.app_config.log_retention_compress;
+            if app.dry_run {
+                let lines: Vec<String> = targets
+                    .iter()
+                    .flat_map(|(name, dir, max_age)| {
+                        let entries = load_log_entries(dir).unwrap_or_default();
+                        plan_log_cleanup(&entries, *max_age, compress, SystemTime::now())
+                            .into_iter()
+                            .map(move |item| format!("{}: {}", name, item.describe()))
+                    })
+                    .collect();
+                if lines.is_empty() {
+                    app.set_status("No logs to clean up".to_string());
+                } else {
+                    show_dry_run(app, "Clean up all logs", lines, Message::CleanAllLogs);
+                }
+                return Ok(());
+            }
+            let targets: Vec<_> = targets
+                .into_iter()
+                .map(|(_, dir, max_age)| (dir, max_age))
+                .collect();
+            app.commands.push(Command::CleanLogs {
+                planned: Vec::new(),
+                targets,
+                compress,
+            });
+        }
+        Message::LogCleanupFinished(result) => {
+            match result {
+                Ok(0) => {}
+                Ok(count) => app.set_status(format!("Cleaned up {} old logs", count)),
+                Err(e) => app.set_error(format!("Failed to clean up logs: {}", e)),
+            }
+            // Reload the list, keeping the source and filters
+            if app.screen == Screen::Logs
+                && let Ok(entries) = app.load_log_entries_for(app.log_source)
+            {
+                app.log_entries = entries;
+                app.selected_log_index = 0;
+                app.log_content.clear();
+                app.log_hints.clear();
+                app.suspect_mod = None;
+                app.log_scroll_offset = 0;
+            }
+        }
+        Message::JumpToLogLevel { level, forward } => {
+            match app.adjacent_log_level_line(level, forward) {
+                Some(pos) => app.log_scroll_offset = pos,
+                None if !app.log_content.is_empty() => {
+                    let direction = if forward { "below" } else { "above" };
+                    app.set_status(format!("No {} lines {}", level.label(), direction));
+                }
+                None => {}
+            }
+        }
+        Message::ToggleLogLineNumbers => {
+            app.app_config.log_line_numbers = !app.app_config.log_line_numbers;
+            app.save_config();
+        }
+        Message::ResizeLogList(delta) => {
+            app.log_list_percent = app
+                .log_list_percent
+                .saturating_add_signed(delta)
+                .clamp(LOG_LIST_MIN_PERCENT, LOG_LIST_MAX_PERCENT);
+        }
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+/// Jar of the first hinted mod that is installed in the selected instance
+fn suspected_mod_file(app: &App) -> Option<PathBuf> {
+    if !app.log_source.is_per_instance() {
+        return None;
+    }
+    let mods_dir = app.selected_instance()?.mods_dir();
+    app.log_hints
+        .iter()
+        .filter_map(|h| h.suspect.as_deref())
+        .find_map(|id| find_mod_file(&mods_dir, id))
+}
+
+/// Load the previewed file again when it grew since it was loaded. The
+/// preview keeps to its end while following.
+pub(super) fn refresh_followed_log(app: &mut App) {
+    let Some(entry) = app.log_entries.get(app.selected_log_index) else {
+        return;
+    };
+    if app.log_content.is_empty() {
+        return;
+    }
+    let size = fs::metadata(&entry.path).ok().map(|m| m.len());
+    if size == app.log_loaded_size {
+        return;
+    }
+    match load_log_content(&entry.path) {
+        Ok(content) => {
+            app.log_hints = analyze_log(&content);
+            app.log_content = content;
+            app.log_loaded_size = size;
+            if !app.log_search_query.is_empty() {
+                app.update_log_search();
+            }
+        }
+        Err(e) => {
+            app.log_follow = false;
+            app.set_error(format!("Stopped following the log: {}", e));
+        }
+    }
+}
+
+/// Switch what the Logs screen shows, keeping the level filters and
+/// running the search again on the new file
+pub(super) fn reopen_logs(app: &mut App, source: LogSource) {
+    let query = std::mem::take(&mut app.log_search_query);
+    open_logs(app, source);
+    app.log_search_query = query;
+    app.update_log_search();
+}
+
+/// Switch to the Logs screen for a folder and load its newest file, or the
+/// one last viewed from the same source
+pub(super) fn open_logs(app: &mut App, source: LogSource) {
+    match app.load_log_entries_for(source) {
+        Ok(entries) => {
+            app.log_entries = entries;
+            app.selected_log_index = 0;
+            app.log_content.clear();
+            app.log_hints.clear();
+            app.suspect_mod = None;
+            app.log_scroll_offset = 0;
+            app.log_source = source;
+            app.last_log_source = Some(source);
+            app.log_search_query.clear();
+            app.log_search_matches.clear();
+            if app.screen != Screen::Logs {
+                app.previous_screen = Some(app.screen);
+                app.screen = Screen::Logs;
+            }
+
+            // Back where this session left off, else latest.log (listed
+            // first) or the file last read
+            let id = app
+                .selected_instance()
+                .filter(|_| source.is_per_instance())
+                .map(|i| i.id.clone());
+            let position = app.log_positions.get(&(source, id)).cloned();
+            let last = match &position {
+                Some(position) => Some(position.file.as_str()),
+                None if app.app_config.open_latest_log => {
+                    app.log_entries.first().map(|e| e.name.as_str())
+                }
+                None => app.last_log_file(),
+            }
+            .and_then(|name| app.log_entries.iter().position(|e| e.name == name));
+            if let Some(idx) = last {
+                app.selected_log_index = idx;
+                dispatch(app, Message::LoadLogContent);
+                if let Some(position) = position {
+                    let last_line = app.filtered_log_content().len().saturating_sub(1);
+                    app.log_scroll_offset = position.scroll.min(last_line);
+                }
+            }
+        }
+        Err(e) => {
+            app.set_error(format!("Failed to load logs: {}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::LogLevel;
+    use crate::update::tests::test_app;
+    use crate::update::update;
+
+    #[test]
+    fn test_jump_to_log_level_skips_to_the_next_match() {
+        let mut app = test_app("logs", &[]);
+        app.log_content = ["[INFO] start", "[ERROR] one", "[INFO] more", "[ERROR] two"]
+            .map(String::from)
+            .to_vec();
+        let jump = |forward| Message::JumpToLogLevel {
+            level: LogLevel::Error,
+            forward,
+        };
+
+        reduce(&mut app, jump(true)).unwrap();
+        assert_eq!(app.log_scroll_offset, 1);
+        reduce(&mut app, jump(true)).unwrap();
+        assert_eq!(app.log_scroll_offset, 3);
+        reduce(&mut app, jump(true)).unwrap();
+        assert_eq!(app.log_scroll_offset, 3);
+        assert!(app.status_message.is_some());
+        reduce(&mut app, jump(false)).unwrap();
+        assert_eq!(app.log_scroll_offset, 1);
+    }
+
+    #[test]
+    fn test_follow_reloads_and_view_settings_save_on_leaving() {
+        let mut app = test_app("logs-follow", &["Alpha"]);
+        // Keeps the test from writing the real config file
+        app.read_only = true;
+        let logs = app.instances[0].logs_dir();
+        fs::create_dir_all(&logs).unwrap();
+        fs::write(logs.join("latest.log"), "[INFO] start\n").unwrap();
+
+        update(&mut app, Message::OpenInstanceLogs);
+        update(&mut app, Message::LoadLogContent);
+        update(&mut app, Message::ToggleLogWrap);
+        update(&mut app, Message::ToggleLogFollow);
+        let saved_early = app.app_config.log_view.wrap;
+        fs::write(logs.join("latest.log"), "[INFO] start\n[INFO] joined\n").unwrap();
+        refresh_followed_log(&mut app);
+        let lines = app.log_content.len();
+        update(&mut app, Message::Back);
+        fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert!(!saved_early);
+        assert_eq!(lines, 2);
+        assert!(app.app_config.log_view.wrap);
+        assert!(app.app_config.log_view.follow);
+        assert_eq!(
+            app.app_config
+                .log_view
+                .instance_files
+                .get("Alpha")
+                .map(String::as_str),
+            Some("latest.log")
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Applying messages to the app state. Each domain has its own reducer in a
+//! submodule; `update` does the work common to every message and then
+//! passes it along to them.
+
+mod accounts;
+mod create_instance;
+mod general;
+mod input;
+mod instances;
+mod logs;
+mod mods;
+mod resourcepacks;
+mod screenshots;
+mod servers;
+mod worlds;
+
+use crate::app::{App, DryRunPreview, InputMode, Screen};
+use crate::cli::StartScreen;
+use crate::command::Command;
+use crate::data::PrismConfig;
+use crate::message::Message;
+use crate::net::metrics::{self, InstanceState, SharedState};
+use crossterm::event::KeyCode;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+pub use instances::write_pending_cfg_edits;
+
+/// A reducer handles the messages of its domain and hands any other message
+/// back as `Err` for the next one to try
+type Reducer = fn(&mut App, Message) -> Result<(), Message>;
+
+const REDUCERS: [Reducer; 11] = [
+    input::reduce,
+    instances::reduce,
+    servers::reduce,
+    logs::reduce,
+    accounts::reduce,
+    create_instance::reduce,
+    mods::reduce,
+    screenshots::reduce,
+    resourcepacks::reduce,
+    worlds::reduce,
+    general::reduce,
+];
+
+/// Apply `msg` to the app, returning the side effects it asked for
+pub fn update(app: &mut App, msg: Message) -> Vec<Command> {
+    dispatch(app, msg);
+    std::mem::take(&mut app.commands)
+}
+
+/// Apply `msg` to the app. Reducers call this for follow-up messages; their
+/// commands queue up on the app until `update` returns them.
+fn dispatch(app: &mut App, msg: Message) {
+    // Enter on an error toast opens the whole error instead
+    if let Message::Key(key) = &msg
+        && key.code == KeyCode::Enter
+        && app.error_message.is_some()
+        && app.input_mode == InputMode::Normal
+    {
+        dispatch(app, Message::ShowErrorDetails);
+        return;
+    }
+    // Clear error on any input except Tick and background results
+    if !msg.is_background() && !matches!(msg, Message::ShowErrorDetails) {
+        app.clear_error();
+    }
+    if !matches!(msg, Message::Tick) {
+        tracing::trace!(?msg);
+    }
+    app.debug_stats.record_message(&msg);
+    if !msg.is_background() {
+        app.remember_position();
+    }
+    // Moving around while instances load keeps the cursor where it was put
+    if matches!(msg, Message::Key(_)) {
+        app.select_after_load = None;
+    }
+    if app.read_only && msg.is_mutating() {
+        app.set_error("Not available in read-only mode".to_string());
+        return;
+    }
+
+    let on_logs = app.screen == Screen::Logs;
+    run_reducers(app, msg);
+    // The log viewer's preferences are written once, on the way out
+    if on_logs && (app.screen != Screen::Logs || !app.running) {
+        app.save_log_view();
+    }
+}
+
+/// Hand `msg` to the first reducer that handles it
+fn run_reducers(app: &mut App, msg: Message) {
+    let mut msg = msg;
+    for reduce in REDUCERS {
+        match reduce(app, msg) {
+            Ok(()) => return,
+            Err(unhandled) => msg = unhandled,
+        }
+    }
+    tracing::warn!(?msg, "no reducer handled message");
+}
+
+/// Open one of the screens that can be requested from outside the TUI
+pub fn open_screen(app: &mut App, screen: StartScreen) {
+    let msg = match screen {
+        StartScreen::Instances => {
+            app.screen = Screen::Instances;
+            return;
+        }
+        StartScreen::Details => Message::OpenInstanceDetails,
+        StartScreen::Servers => Message::OpenServerScreen,
+        StartScreen::Accounts => Message::OpenAccountScreen,
+        StartScreen::Logs => Message::OpenInstanceLogs,
+        StartScreen::LauncherLogs => Message::OpenLauncherLogs,
+        StartScreen::AppLogs => Message::OpenAppLogs,
+        StartScreen::Settings => Message::OpenSettings,
+    };
+    dispatch(app, msg);
+}
+
+/// Load instances and accounts again from `data_dir`, in place, so running
+/// games, listeners and the screen carry over
+fn reload_data(app: &mut App, data_dir: &Path) -> crate::error::Result<()> {
+    let config = PrismConfig::load(data_dir)?;
+    app.load_data(config)?;
+    app.clear_error();
+    if let Some(error) = app.groups_error.clone().or(app.accounts_error.clone()) {
+        app.set_error(error);
+    }
+    Ok(())
+}
+
+/// Paths typed by hand may start with `~/`
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Start the HTTP metrics endpoint when `metrics_address` is configured
+pub fn start_metrics_server(app: &mut App) {
+    let Some(address) = app.app_config.metrics_address.clone() else {
+        return;
+    };
+    let state = SharedState::default();
+    match metrics::serve(&address, Arc::clone(&state)) {
+        Ok(()) => {
+            app.metrics = Some(state);
+            publish_metrics(app);
+        }
+        Err(e) => app.set_error(format!("Metrics endpoint on {} failed: {}", address, e)),
+    }
+}
+
+/// Hand the metrics endpoint the current instance list and running games,
+/// when they changed since the last time
+fn publish_metrics(app: &mut App) {
+    let Some(metrics) = &app.metrics else {
+        return;
+    };
+    let sessions = |id: &str| {
+        app.running_instances
+            .get(id)
+            .map(|r| (r.launched_at, r.pid))
+    };
+    let mut hasher = DefaultHasher::new();
+    for instance in &app.instances {
+        (&instance.id, &instance.name, sessions(&instance.id)).hash(&mut hasher);
+    }
+    let hash = hasher.finish();
+    if app.metrics_published == Some(hash) {
+        return;
+    }
+    let states = app
+        .instances
+        .iter()
+        .map(|i| InstanceState {
+            id: i.id.clone(),
+            name: i.name.clone(),
+            session: sessions(&i.id),
+        })
+        .collect();
+    if let Ok(mut shared) = metrics.lock() {
+        *shared = states;
+        app.metrics_published = Some(hash);
+    }
+}
+
+/// List what an action would do and wait for confirmation before `confirm`
+/// carries it out
+fn show_dry_run(app: &mut App, title: &str, lines: Vec<String>, confirm: Message) {
+    app.dry_run_preview = Some(DryRunPreview {
+        title: title.to_string(),
+        lines,
+        scroll: 0,
+        confirm,
+    });
+    app.input_mode = InputMode::DryRunPreview;
+}
+
+#[cfg(test)]
+pub(super) mod tests {
+    use super::*;
+    use crate::data::{AppConfig, Server};
+    use std::fs;
+    use tokio::sync::mpsc;
+
+    /// An app over a throwaway data directory with one instance per name,
+    /// fully loaded, on default settings and past the first-run tour. The
+    /// directory is removed again before returning.
+    pub(super) fn test_app(test: &str, names: &[&str]) -> App {
+        let data_dir = crate::data::test_dir(&format!("update-{}", test));
+        for name in names {
+            let dir = data_dir.join("instances").join(name);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(
+                dir.join("instance.cfg"),
+                format!("[General]\nname={}\n", name),
+            )
+            .unwrap();
+        }
+        fs::create_dir_all(data_dir.join("instances")).unwrap();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut app = App::new(PrismConfig::load(&data_dir).unwrap(), tx).unwrap();
+        while let Some(msg) = rx.blocking_recv() {
+            let done = matches!(msg, Message::InstancesLoaded { .. });
+            update(&mut app, msg);
+            if done {
+                break;
+            }
+        }
+        fs::remove_dir_all(&data_dir).unwrap();
+        app.app_config = AppConfig::default();
+        app.read_only = false;
+        app.tour_step = None;
+        app.clear_error();
+        app
+    }
+
+    #[test]
+    fn test_update_hands_messages_to_their_reducer() {
+        let mut app = test_app("dispatch", &["Alpha"]);
+
+        assert!(matches!(
+            accounts::reduce(&mut app, Message::Quit),
+            Err(Message::Quit)
+        ));
+        assert!(app.running);
+        update(&mut app, Message::Quit);
+        assert!(!app.running);
+    }
+
+    #[test]
+    fn test_update_returns_the_commands_a_message_queued() {
+        let mut app = test_app("commands", &["Alpha"]);
+        app.servers = ["Hub", "Survival"]
+            .map(|name| Server::new(name.to_string(), format!("{}.example.com", name)))
+            .to_vec();
+        app.selected_server_index = 1;
+
+        let commands = update(&mut app, Message::CheckServer);
+
+        assert_eq!(
+            commands,
+            [Command::CheckServer {
+                address: "Survival.example.com".to_string(),
+                others: vec![("Hub".to_string(), "Hub.example.com".to_string())],
+                check_blocklist: true,
+                report_ok: true,
+            }]
+        );
+        assert!(app.commands.is_empty());
+        assert!(update(&mut app, Message::Tick).is_empty());
+    }
+
+    #[test]
+    fn test_read_only_stops_mutating_messages() {
+        let mut app = test_app("read-only", &["Alpha"]);
+        app.read_only = true;
+
+        update(&mut app, Message::AddServer);
+
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(app.error_message.is_some());
+    }
+
+    #[test]
+    fn test_reload_keeps_the_screen_and_running_games() {
+        let mut app = test_app("reload", &["Alpha"]);
+        let other = crate::data::test_dir("reload");
+        fs::create_dir_all(other.join("instances")).unwrap();
+        app.screen = Screen::Settings;
+        app.running_instances.insert(
+            app.instances[0].id.clone(),
+            crate::app::RunningInstance {
+                pid: None,
+                launched_at: std::time::Instant::now(),
+                low_priority: false,
+                priority_lowered: false,
+            },
+        );
+
+        let reloaded = reload_data(&mut app, &other);
+        fs::remove_dir_all(&other).unwrap();
+
+        assert!(reloaded.is_ok());
+        assert_eq!(app.data_dir, other);
+        assert!(app.instances.is_empty());
+        assert!(app.instances_loading);
+        assert_eq!(app.screen, Screen::Settings);
+        assert_eq!(app.running_instances.len(), 1);
+    }
+}
@@ -0,0 +1,367 @@
+//! The mods screen: an instance's mods folder, turned on and off by renaming,
+//! and new mods found on Modrinth or CurseForge.
+
+use crate::actions::open_url;
+use crate::app::{App, InputMode, Screen};
+use crate::command::Command;
+use crate::data::{Instance, list_mods, set_mod_enabled};
+use crate::message::Message;
+use crate::net::loaders::Loader;
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::OpenModsScreen => {
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            app.mods = list_mods(&instance.mods_dir());
+            app.selected_mod_index = 0;
+            app.marked_mods.clear();
+            app.mod_updates.clear();
+            app.previous_screen = Some(app.screen);
+            app.screen = Screen::Mods;
+        }
+        Message::SelectMod(idx) => {
+            if idx < app.mods.len() {
+                app.selected_mod_index = idx;
+            }
+        }
+        Message::ToggleModMark => {
+            if let Some(name) = app.mods.get(app.selected_mod_index).map(|m| m.name.clone())
+                && !app.marked_mods.remove(&name)
+            {
+                app.marked_mods.insert(name);
+            }
+        }
+        Message::ClearModMarks => {
+            app.marked_mods.clear();
+        }
+        Message::ToggleMods => toggle_mods(app),
+        Message::StartModSearch => {
+            app.input_buffer.clear();
+            app.input_mode = InputMode::SearchMods;
+        }
+        Message::ModSearchDone(result) => {
+            // The search was for the instance shown; leaving drops it
+            if app.screen != Screen::Mods || app.input_mode != InputMode::Normal {
+                return Ok(());
+            }
+            app.status_message = None;
+            match result {
+                Ok(hits) if hits.is_empty() => app.set_error("No mods found".to_string()),
+                Ok(hits) => {
+                    app.mod_hits = hits;
+                    app.input_mode = InputMode::PickModHit;
+                }
+                Err(e) => app.set_error(format!("Mod search failed: {}", e)),
+            }
+        }
+        Message::InstallModHit(idx) => {
+            let Some(hit) = app.mod_hits.get(idx).cloned() else {
+                return Ok(());
+            };
+            app.input_mode = InputMode::Normal;
+            let Some((instance, loader)) = app.selected_instance().and_then(with_loader) else {
+                return Ok(());
+            };
+            let status = format!("Installing {}...", hit.name);
+            let command = Command::InstallMod {
+                hit,
+                minecraft_version: instance.minecraft_version.clone(),
+                loader,
+                mods_dir: instance.mods_dir(),
+                curseforge_key: app.curseforge_key(),
+            };
+            app.set_status(status);
+            app.commands.push(command);
+        }
+        Message::ModInstalled { name, result } => match result {
+            Ok(file_name) => {
+                if app.screen == Screen::Mods
+                    && let Some(mods_dir) = app.selected_instance().map(|i| i.mods_dir())
+                {
+                    app.mods = list_mods(&mods_dir);
+                    if let Some(idx) = app.mods.iter().position(|m| m.name == file_name) {
+                        app.selected_mod_index = idx;
+                    }
+                }
+                app.set_status(format!("Installed {} ({})", name, file_name));
+            }
+            Err(e) => app.set_error(format!("Failed to install {}: {}", name, e)),
+        },
+        Message::OpenModPage => {
+            let (Some(instance), Some(file)) = (
+                app.selected_instance(),
+                app.mods.get(app.selected_mod_index),
+            ) else {
+                return Ok(());
+            };
+            let path = instance.mods_dir().join(file.file_name());
+            let name = file.name.clone();
+            app.set_status(format!("Looking up {} on Modrinth...", name));
+            app.commands.push(Command::FindModPage { name, path });
+        }
+        Message::ModPageFound { name, result } => {
+            app.status_message = None;
+            match result {
+                Ok(Some(url)) => {
+                    if let Err(e) = open_url(&url) {
+                        app.set_error(format!("Failed to open {}: {}", url, e));
+                    }
+                }
+                Ok(None) => app.set_error(format!("{} isn't on Modrinth", name)),
+                Err(e) => app.set_error(format!("Failed to look up {}: {}", name, e)),
+            }
+        }
+        Message::CheckModUpdates => {
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            let Some((instance, loader)) = with_loader(instance) else {
+                app.set_error(format!(
+                    "Mods can't be checked for {}'s loader",
+                    instance.name
+                ));
+                return Ok(());
+            };
+            let command = Command::CheckModUpdates {
+                mods_dir: instance.mods_dir(),
+                minecraft_version: instance.minecraft_version.clone(),
+                loader,
+            };
+            app.set_status("Checking Modrinth for mod updates...".to_string());
+            app.commands.push(command);
+        }
+        Message::ModUpdatesChecked(result) => {
+            // The check was for the instance shown; leaving drops it
+            if app.screen != Screen::Mods {
+                return Ok(());
+            }
+            match result {
+                Ok(updates) if updates.is_empty() => {
+                    app.mod_updates.clear();
+                    app.set_status("All mods on Modrinth are up to date".to_string());
+                }
+                Ok(updates) => {
+                    let count = updates.len();
+                    app.mod_updates = updates;
+                    app.set_status(format!(
+                        "{} {} an update, press U to update all",
+                        count,
+                        if count == 1 { "mod has" } else { "mods have" }
+                    ));
+                }
+                Err(e) => app.set_error(format!("Mod update check failed: {}", e)),
+            }
+        }
+        Message::UpdateAllMods => {
+            if app.mod_updates.is_empty() {
+                app.set_error("No mod updates, press u to check".to_string());
+                return Ok(());
+            }
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            // The game holds its jars open and would load a half-swapped set
+            if app.is_instance_running(&instance.id) {
+                app.set_error(format!("Close {} before updating its mods", instance.name));
+                return Ok(());
+            }
+            let mods_dir = instance.mods_dir();
+            let updates = app.mod_updates.clone();
+            app.set_status(format!("Updating {} mods...", updates.len()));
+            app.commands.push(Command::UpdateMods { updates, mods_dir });
+        }
+        Message::ModsUpdated { updated, errors } => {
+            app.mod_updates
+                .retain(|u| !updated.contains(&u.installed.name));
+            let mods_dir = app.selected_instance().map(|i| i.mods_dir());
+            if app.screen == Screen::Mods
+                && let Some(mods_dir) = &mods_dir
+            {
+                app.mods = list_mods(mods_dir);
+                app.selected_mod_index =
+                    app.selected_mod_index.min(app.mods.len().saturating_sub(1));
+            }
+            let Some(first) = errors.first() else {
+                app.set_status(format!(
+                    "Updated {} mods, old versions kept as .bak",
+                    updated.len()
+                ));
+                return Ok(());
+            };
+            let message = format!(
+                "Updated {} mods, {} failed ({})",
+                updated.len(),
+                errors.len(),
+                first
+            );
+            match mods_dir {
+                Some(mods_dir) => app.set_error_at(message, &mods_dir),
+                None => app.set_error(message),
+            }
+        }
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+/// The instance with its loader, for searching and downloading mods
+fn with_loader(instance: &Instance) -> Option<(&Instance, Loader)> {
+    let loader = Loader::from_name(instance.mod_loader.as_deref()?)?;
+    Some((instance, loader))
+}
+
+/// Search Modrinth, and CurseForge with a key, for mods that run on the
+/// selected instance
+pub(super) fn search_mods(app: &mut App, query: &str) {
+    if query.is_empty() {
+        app.set_error("Type what to search for".to_string());
+        return;
+    }
+    let Some(instance) = app.selected_instance() else {
+        return;
+    };
+    let Some((instance, loader)) = with_loader(instance) else {
+        app.set_error(format!(
+            "Mods can't be searched for {}'s loader",
+            instance.name
+        ));
+        return;
+    };
+    let command = Command::SearchMods {
+        query: query.to_string(),
+        minecraft_version: instance.minecraft_version.clone(),
+        loader,
+        curseforge_key: app.curseforge_key(),
+    };
+    app.input_buffer.clear();
+    app.input_mode = InputMode::Normal;
+    app.set_status(format!("Searching for {}...", query));
+    app.commands.push(command);
+}
+
+/// Flip the marked mods, or the selected one when none are marked, then
+/// read the folder again
+fn toggle_mods(app: &mut App) {
+    let Some(mods_dir) = app.selected_instance().map(|i| i.mods_dir()) else {
+        return;
+    };
+    let targets: Vec<_> = if app.marked_mods.is_empty() {
+        app.mods
+            .get(app.selected_mod_index)
+            .cloned()
+            .into_iter()
+            .collect()
+    } else {
+        app.mods
+            .iter()
+            .filter(|m| app.marked_mods.contains(&m.name))
+            .cloned()
+            .collect()
+    };
+    let (mut enabled, mut disabled) = (0, 0);
+    let mut failure = None;
+    for file in &targets {
+        match set_mod_enabled(&mods_dir, file, !file.enabled) {
+            Ok(()) if file.enabled => disabled += 1,
+            Ok(()) => enabled += 1,
+            Err(e) => {
+                failure.get_or_insert(format!("Failed to toggle {}: {}", file.name, e));
+            }
+        }
+    }
+
+    let selected = app.mods.get(app.selected_mod_index).map(|m| m.name.clone());
+    app.mods = list_mods(&mods_dir);
+    app.selected_mod_index = app
+        .mods
+        .iter()
+        .position(|m| Some(&m.name) == selected.as_ref())
+        .unwrap_or(0);
+    app.marked_mods.clear();
+
+    match failure {
+        Some(message) => app.set_error_at(message, &mods_dir),
+        None => match (targets.as_slice(), enabled, disabled) {
+            ([file], 1, _) => app.set_status(format!("Enabled {}", file.name)),
+            ([file], _, 1) => app.set_status(format!("Disabled {}", file.name)),
+            _ => app.set_status(format!("Enabled {} mods, disabled {}", enabled, disabled)),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::ModFile;
+    use crate::net::mod_source::{ModHit, ModSource};
+    use crate::update::tests::test_app;
+
+    #[test]
+    fn test_marks_follow_the_selection() {
+        let mut app = test_app("mods", &["Alpha"]);
+        app.mods = ["a.jar", "b.jar", "c.jar"]
+            .map(|name| ModFile {
+                name: name.to_string(),
+                enabled: true,
+            })
+            .to_vec();
+
+        reduce(&mut app, Message::SelectMod(1)).unwrap();
+        reduce(&mut app, Message::ToggleModMark).unwrap();
+        reduce(&mut app, Message::SelectMod(5)).unwrap();
+        reduce(&mut app, Message::ToggleModMark).unwrap();
+        assert!(app.marked_mods.is_empty());
+        reduce(&mut app, Message::SelectMod(2)).unwrap();
+        reduce(&mut app, Message::ToggleModMark).unwrap();
+        let mut marked: Vec<&str> = app.marked_mods.iter().map(String::as_str).collect();
+        marked.sort();
+        assert_eq!(marked, ["c.jar"]);
+    }
+
+    #[test]
+    fn test_mods_screen_opens_without_a_loader() {
+        let mut app = test_app("mods-no-loader", &["Alpha"]);
+
+        reduce(&mut app, Message::OpenModsScreen).unwrap();
+        assert_eq!(app.screen, Screen::Mods);
+        assert!(app.error_message.is_none());
+    }
+
+    #[test]
+    fn test_picked_search_result_is_installed_for_the_instance() {
+        let mut app = test_app("mod-search", &["Alpha"]);
+        app.instances[0].mod_loader = Some("Fabric".to_string());
+        app.instances[0].minecraft_version = "1.20.1".to_string();
+        app.screen = Screen::Mods;
+
+        search_mods(&mut app, "sodium");
+        assert_eq!(
+            app.commands,
+            vec![Command::SearchMods {
+                query: "sodium".to_string(),
+                minecraft_version: "1.20.1".to_string(),
+                loader: Loader::Fabric,
+                curseforge_key: None,
+            }]
+        );
+        let hit = ModHit {
+            source: ModSource::Modrinth,
+            id: "AANobbMI".to_string(),
+            name: "Sodium".to_string(),
+            summary: String::new(),
+            downloads: 80_000_000,
+        };
+        reduce(&mut app, Message::ModSearchDone(Ok(vec![hit.clone()]))).unwrap();
+        assert_eq!(app.input_mode, InputMode::PickModHit);
+
+        app.commands.clear();
+        reduce(&mut app, Message::InstallModHit(0)).unwrap();
+        assert_eq!(app.input_mode, InputMode::Normal);
+        assert!(matches!(
+            &app.commands[..],
+            [Command::InstallMod { hit: installed, loader: Loader::Fabric, .. }] if *installed == hit
+        ));
+    }
+}
@@ -0,0 +1,198 @@
+//! The resource packs screen: an instance's `resourcepacks/` folder, turned
+//! on and off through the game's options.txt.
+
+use crate::app::{App, InputMode, Screen};
+use crate::data::{
+    InstanceFolder, delete_resource_pack, load_resource_packs, resource_pack_format,
+    set_resource_pack_enabled,
+};
+use crate::message::Message;
+
+use super::show_dry_run;
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::OpenResourcePacks => {
+            if app.selected_instance().is_none() {
+                return Ok(());
+            }
+            reload_packs(app);
+            app.selected_resource_pack_index = 0;
+            app.resource_pack_scroll = 0;
+            app.previous_screen = Some(app.screen);
+            app.screen = Screen::ResourcePacks;
+        }
+        Message::SelectResourcePack(idx) => {
+            if idx < app.resource_packs.len() {
+                app.selected_resource_pack_index = idx;
+            }
+        }
+        Message::ToggleResourcePack => {
+            if refuse_while_running(app, "changing") {
+                return Ok(());
+            }
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            let Some(pack) = app.resource_packs.get(app.selected_resource_pack_index) else {
+                return Ok(());
+            };
+            let options = instance.options_file();
+            let game_format = resource_pack_format(&instance.minecraft_version);
+            let (name, enabled) = (pack.name.clone(), !pack.enabled);
+            if let Err(e) = set_resource_pack_enabled(&options, pack, enabled, game_format) {
+                app.set_error_at(format!("Failed to update options.txt: {}", e), &options);
+                return Ok(());
+            }
+            reload_packs(app);
+            if enabled {
+                app.set_status(format!("Enabled {}", name));
+            } else {
+                app.set_status(format!("Disabled {}", name));
+            }
+        }
+        Message::DeleteResourcePack => {
+            if refuse_while_running(app, "deleting") {
+                return Ok(());
+            }
+            let Some(pack) = app.resource_packs.get(app.selected_resource_pack_index) else {
+                return Ok(());
+            };
+            if app.dry_run {
+                let line = format!("Delete resourcepacks/{}", pack.name);
+                show_dry_run(
+                    app,
+                    "Delete resource pack",
+                    vec![line],
+                    Message::ConfirmDeleteResourcePack,
+                );
+            } else {
+                app.input_mode = InputMode::ConfirmDeleteResourcePack;
+            }
+        }
+        Message::ConfirmDeleteResourcePack => {
+            app.input_mode = InputMode::Normal;
+            if refuse_while_running(app, "deleting") {
+                return Ok(());
+            }
+            let idx = app.selected_resource_pack_index;
+            let Some(pack) = app.resource_packs.get(idx) else {
+                return Ok(());
+            };
+            match delete_resource_pack(pack) {
+                Ok(()) => {
+                    let pack = app.resource_packs.remove(idx);
+                    app.selected_resource_pack_index =
+                        idx.min(app.resource_packs.len().saturating_sub(1));
+                    app.set_status(format!("Deleted {}", pack.name));
+                }
+                Err(e) => {
+                    let path = pack.path.clone();
+                    app.set_error_at(format!("Failed to delete resource pack: {}", e), &path);
+                }
+            }
+        }
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+/// Show an error and return true while the selected instance's game is
+/// open: it writes options.txt back when it closes and holds its packs open
+fn refuse_while_running(app: &mut App, action: &str) -> bool {
+    let Some(instance) = app.selected_instance() else {
+        return false;
+    };
+    if !app.is_instance_running(&instance.id) {
+        return false;
+    }
+    app.set_error(format!(
+        "Close {} before {} its resource packs",
+        instance.name, action
+    ));
+    true
+}
+
+/// Read the folder and options.txt again, keeping the selection in range
+fn reload_packs(app: &mut App) {
+    let Some(instance) = app.selected_instance() else {
+        return;
+    };
+    app.resource_packs = load_resource_packs(
+        &instance.game_folder(InstanceFolder::ResourcePacks),
+        &instance.options_file(),
+    );
+    app.selected_resource_pack_index = app
+        .selected_resource_pack_index
+        .min(app.resource_packs.len().saturating_sub(1));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::RunningInstance;
+    use crate::update::tests::test_app;
+    use std::fs;
+    use std::time::Instant;
+
+    #[test]
+    fn test_toggle_and_delete_the_selected_pack() {
+        let mut app = test_app("resourcepacks", &["Alpha"]);
+        let dir = app.instances[0].game_folder(InstanceFolder::ResourcePacks);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Faithful.zip"), "not really a zip").unwrap();
+        fs::write(dir.join("readme.txt"), "not a pack").unwrap();
+        let options = app.instances[0].options_file();
+
+        reduce(&mut app, Message::OpenResourcePacks).unwrap();
+        let listed = app.resource_packs.len();
+        reduce(&mut app, Message::ToggleResourcePack).unwrap();
+        let enabled = app.resource_packs[0].enabled;
+        let written = fs::read_to_string(&options).unwrap();
+        reduce(&mut app, Message::DeleteResourcePack).unwrap();
+        let mode = app.input_mode;
+        reduce(&mut app, Message::ConfirmDeleteResourcePack).unwrap();
+        let left = dir.join("Faithful.zip").exists();
+        fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert_eq!(listed, 1);
+        assert!(enabled);
+        assert!(written.contains("resourcePacks:[\"vanilla\",\"file/Faithful.zip\"]"));
+        assert_eq!(mode, InputMode::ConfirmDeleteResourcePack);
+        assert!(!left);
+        assert!(app.resource_packs.is_empty());
+        assert_eq!(app.screen, Screen::ResourcePacks);
+    }
+
+    #[test]
+    fn test_delete_is_refused_while_the_game_runs() {
+        let mut app = test_app("resourcepacks-running", &["Alpha"]);
+        let dir = app.instances[0].game_folder(InstanceFolder::ResourcePacks);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Faithful.zip"), "not really a zip").unwrap();
+        reduce(&mut app, Message::OpenResourcePacks).unwrap();
+        app.running_instances.insert(
+            app.instances[0].id.clone(),
+            RunningInstance {
+                pid: None,
+                launched_at: Instant::now(),
+                low_priority: false,
+                priority_lowered: false,
+            },
+        );
+
+        reduce(&mut app, Message::DeleteResourcePack).unwrap();
+        let mode = app.input_mode;
+        reduce(&mut app, Message::ConfirmDeleteResourcePack).unwrap();
+        let left = dir.join("Faithful.zip").exists();
+        fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert_eq!(mode, InputMode::Normal);
+        assert!(left);
+        assert_eq!(app.resource_packs.len(), 1);
+        assert_eq!(
+            app.error_message.as_deref(),
+            Some("Close Alpha before deleting its resource packs")
+        );
+    }
+}
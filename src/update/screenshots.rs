@@ -0,0 +1,105 @@
+//! The screenshots screen: an instance's `screenshots/` folder, previewed
+//! in terminals that can draw images.
+
+use crate::actions::open_file;
+use crate::app::{App, InputMode, Screen};
+use crate::data::{InstanceFolder, delete_screenshot, load_screenshots};
+use crate::message::Message;
+
+use super::show_dry_run;
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::OpenScreenshots => {
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            app.screenshots = load_screenshots(&instance.game_folder(InstanceFolder::Screenshots));
+            app.selected_screenshot_index = 0;
+            app.screenshot_scroll = 0;
+            app.previous_screen = Some(app.screen);
+            app.screen = Screen::Screenshots;
+        }
+        Message::SelectScreenshot(idx) => {
+            if idx < app.screenshots.len() {
+                app.selected_screenshot_index = idx;
+            }
+        }
+        Message::OpenScreenshot => {
+            if let Some(screenshot) = app.screenshots.get(app.selected_screenshot_index)
+                && let Err(e) = open_file(&screenshot.path)
+            {
+                let path = screenshot.path.clone();
+                app.set_error_at(e.to_string(), &path);
+            }
+        }
+        Message::DeleteScreenshot => {
+            let Some(screenshot) = app.screenshots.get(app.selected_screenshot_index) else {
+                return Ok(());
+            };
+            if app.dry_run {
+                let line = format!("Delete screenshots/{}", screenshot.name);
+                show_dry_run(
+                    app,
+                    "Delete screenshot",
+                    vec![line],
+                    Message::ConfirmDeleteScreenshot,
+                );
+            } else {
+                app.input_mode = InputMode::ConfirmDeleteScreenshot;
+            }
+        }
+        Message::ConfirmDeleteScreenshot => {
+            app.input_mode = InputMode::Normal;
+            let idx = app.selected_screenshot_index;
+            let Some(screenshot) = app.screenshots.get(idx) else {
+                return Ok(());
+            };
+            match delete_screenshot(screenshot) {
+                Ok(()) => {
+                    let screenshot = app.screenshots.remove(idx);
+                    app.selected_screenshot_index =
+                        idx.min(app.screenshots.len().saturating_sub(1));
+                    app.set_status(format!("Deleted {}", screenshot.name));
+                }
+                Err(e) => {
+                    let path = screenshot.path.clone();
+                    app.set_error_at(format!("Failed to delete screenshot: {}", e), &path);
+                }
+            }
+        }
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::update::tests::test_app;
+    use std::fs;
+
+    #[test]
+    fn test_delete_screenshot_keeps_the_selection_in_range() {
+        let mut app = test_app("screenshots", &["Alpha"]);
+        let dir = app.instances[0].game_folder(InstanceFolder::Screenshots);
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("2024-05-01_10.00.00.png"), "not really a png").unwrap();
+        fs::write(dir.join("notes.txt"), "not a screenshot").unwrap();
+
+        reduce(&mut app, Message::OpenScreenshots).unwrap();
+        let listed = app.screenshots.len();
+        reduce(&mut app, Message::DeleteScreenshot).unwrap();
+        let mode = app.input_mode;
+        reduce(&mut app, Message::ConfirmDeleteScreenshot).unwrap();
+        let left = dir.join("2024-05-01_10.00.00.png").exists();
+        fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert_eq!(listed, 1);
+        assert_eq!(mode, InputMode::ConfirmDeleteScreenshot);
+        assert!(!left);
+        assert!(app.screenshots.is_empty());
+        assert_eq!(app.selected_screenshot_index, 0);
+        assert_eq!(app.screen, Screen::Screenshots);
+    }
+}
@@ -0,0 +1,526 @@
+//! The server list of the selected instance, server checks and LAN games.
+
+use crate::actions::{copy_to_clipboard, find_url, open_url};
+use crate::app::{App, ExternalChange, InputMode, Screen};
+use crate::command::Command;
+use crate::data::{
+    Instance, InstanceCfgEdit, JoinConflict, find_duplicate, load_servers, merge_servers,
+    save_servers,
+};
+use crate::error::PrismError;
+use crate::message::Message;
+use crate::net::lan;
+use crate::net::ping::protocol_version;
+use std::path::Path;
+
+use super::instances::{edit_instance_cfg, start_instance};
+use super::{dispatch, show_dry_run};
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::CopyServerAddress(idx) => {
+            if let Some(server) = app.servers.get(idx) {
+                let address = server.ip.clone();
+                match copy_to_clipboard(&address) {
+                    Ok(_) => app.set_status(format!("Copied: {}", address)),
+                    Err(e) => app.set_error(format!("Failed to copy address: {}", e)),
+                }
+            }
+        }
+        Message::SelectServer(idx) => {
+            if idx < app.servers.len() {
+                app.selected_server_index = idx;
+            }
+        }
+        Message::AddServer => {
+            app.input_mode = InputMode::AddServerName;
+            app.input_buffer.clear();
+            app.edit_server_name.clear();
+            app.edit_server_address.clear();
+        }
+        Message::EditServer => {
+            if let Some(server) = app.selected_server().cloned() {
+                app.edit_server_name = server.name.clone();
+                app.edit_server_address = server.ip.clone();
+                app.input_buffer = server.name;
+                app.input_mode = InputMode::EditServerName;
+            }
+        }
+        Message::ToggleHiddenServers => {
+            app.show_hidden_servers = !app.show_hidden_servers;
+            app.clamp_server_selection();
+        }
+        Message::DeleteServer => {
+            if let Some(server) = app.selected_server() {
+                if app.dry_run {
+                    let line = format!("Remove '{}' ({}) from servers.dat", server.name, server.ip);
+                    show_dry_run(
+                        app,
+                        "Delete server",
+                        vec![line],
+                        Message::ConfirmDeleteServer,
+                    );
+                } else {
+                    app.input_mode = InputMode::ConfirmDelete;
+                }
+            }
+        }
+        Message::ConfirmDeleteServer => {
+            if app.selected_server_index < app.servers.len() {
+                app.servers.remove(app.selected_server_index);
+                if app.servers.is_empty() {
+                    app.selected_server_index = 0;
+                } else if app.selected_server_index >= app.servers.len() {
+                    app.selected_server_index = app.servers.len() - 1;
+                }
+                app.clamp_server_selection();
+                app.input_mode = InputMode::Normal;
+                save_server_list(app);
+            } else {
+                app.input_mode = InputMode::Normal;
+            }
+        }
+        Message::SetJoinOnLaunch => {
+            dispatch(app, Message::SetJoinServer(app.selected_server_index));
+        }
+        Message::SetJoinServer(idx) => {
+            if let Some(server) = app.servers.get(idx).cloned()
+                && let Some(instance) = app.selected_instance()
+            {
+                let currently_set = instance
+                    .server_join
+                    .as_ref()
+                    .map(|sj| sj.enabled && sj.address == server.ip)
+                    .unwrap_or(false);
+                edit_instance_cfg(
+                    app,
+                    InstanceCfgEdit::ServerJoin {
+                        enabled: !currently_set,
+                        address: Some(server.ip),
+                    },
+                );
+            }
+        }
+        Message::ToggleJoinOnLaunch => {
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            // Nothing set up yet: start with the selected server
+            match instance.server_join.as_ref() {
+                Some(sj) => {
+                    let edit = InstanceCfgEdit::ServerJoin {
+                        enabled: !sj.enabled,
+                        address: Some(sj.address.clone()),
+                    };
+                    edit_instance_cfg(app, edit);
+                }
+                None => dispatch(app, Message::SetJoinOnLaunch),
+            }
+        }
+        Message::FixJoinConflict => {
+            let Some(conflict) = app.join_conflict() else {
+                return Ok(());
+            };
+            if let Some(instance) = app.selected_instance() {
+                let edit = match conflict {
+                    JoinConflict::WorldAndServer => InstanceCfgEdit::ClearWorldJoin,
+                    JoinConflict::MissingServer => InstanceCfgEdit::ServerJoin {
+                        enabled: false,
+                        address: instance.server_join.as_ref().map(|sj| sj.address.clone()),
+                    },
+                };
+                edit_instance_cfg(app, edit);
+            }
+        }
+        Message::CycleResourcePackPolicy => {
+            if let Some(server) = app.servers.get_mut(app.selected_server_index) {
+                server.accept_textures = server.accept_textures.next();
+                save_server_list(app);
+            }
+        }
+        Message::OpenServerWebsite => {
+            if let Some(server) = app.selected_server() {
+                match find_url(&server.name).map(str::to_string) {
+                    Some(url) => {
+                        if let Err(e) = open_url(&url) {
+                            app.set_error(format!("Failed to open website: {}", e));
+                        }
+                    }
+                    None => app.set_error("No website link in the server's name".to_string()),
+                }
+            }
+        }
+        Message::LaunchWithServer => {
+            if let (Some(instance), Some(server)) = (app.selected_instance(), app.selected_server())
+            {
+                let instance_id = instance.id.clone();
+                let server_addr = server.ip.clone();
+                start_instance(app, instance_id, Some(server_addr), false);
+            }
+        }
+        Message::CheckServer => {
+            if let Some(server) = app.selected_server() {
+                let address = server.ip.clone();
+                let idx = app.selected_server_index;
+                match find_duplicate(&app.servers, &address, Some(idx)) {
+                    Some(dup) => app.set_status(format!("Warning: same address as '{}'", dup.name)),
+                    None => queue_server_check(app, address, idx, true, true),
+                }
+            }
+        }
+        Message::PingSelectedServer => {
+            if let Some(server) = app.selected_server() {
+                let address = server.ip.clone();
+                let protocol = app
+                    .selected_instance()
+                    .and_then(|i| protocol_version(&i.minecraft_version));
+                app.set_status(format!("Pinging {}...", address));
+                app.commands.push(Command::PingServer {
+                    address,
+                    protocol,
+                    for_launch: false,
+                });
+            }
+        }
+        Message::ServerPinged {
+            address,
+            result,
+            for_launch: false,
+        } => match result {
+            Ok(status) => {
+                let mut text = format!("{}: {}", address, status.summary());
+                if let Some(instance) = app.selected_instance()
+                    && status.mismatches(&instance.minecraft_version) == Some(true)
+                {
+                    text.push_str(&format!(
+                        "; {} is {} and can't join",
+                        instance.name, instance.minecraft_version
+                    ));
+                }
+                app.set_status(text);
+            }
+            Err(e) => app.set_error(format!("{} did not answer: {}", address, e)),
+        },
+        Message::ServerCheckComplete {
+            address,
+            warnings,
+            report_ok,
+        } => {
+            if !warnings.is_empty() {
+                app.set_status(format!("{}: {}", address, warnings.join("; ")));
+            } else if report_ok {
+                app.set_status(format!("{}: no problems found", address));
+            }
+        }
+        Message::NextLanGame => {
+            if !app.lan_games.is_empty() {
+                app.selected_lan_index = (app.selected_lan_index + 1) % app.lan_games.len();
+            }
+        }
+        Message::JoinLanGame => {
+            if let (Some(instance), Some(game)) = (app.selected_instance(), app.selected_lan_game())
+            {
+                let instance_id = instance.id.clone();
+                let address = game.address.clone();
+                start_instance(app, instance_id, Some(address), false);
+            }
+        }
+        Message::LanGameDiscovered(game) => {
+            app.record_lan_game(game);
+        }
+        Message::LanDiscoveryFailed(e) => {
+            app.lan_listener = None;
+            app.lan_error = Some(e);
+        }
+        Message::ExportServers => {
+            if let Some(instance) = app.selected_instance() {
+                if app.servers.is_empty() {
+                    app.set_error(format!("{} has no servers to export", instance.name));
+                } else {
+                    app.input_buffer = format!("~/{} servers.dat", instance.name);
+                    app.input_mode = InputMode::ExportServers;
+                }
+            }
+        }
+        Message::ImportServers => {
+            if let Some(error) = &app.servers_error {
+                // Saving the merged list would replace the unreadable file
+                let error = format!("{}; fix it before importing", error);
+                set_servers_error(app, error);
+            } else if app.selected_instance().is_some() {
+                app.input_buffer.clear();
+                app.input_mode = InputMode::ImportServers;
+            }
+        }
+        Message::OpenServerScreen => {
+            if app.selected_instance().is_some() {
+                // A corrupt servers.dat still leaves LAN games and launching
+                if let Err(e) = app.load_servers_for_instance() {
+                    set_servers_error(app, e.to_string());
+                }
+                app.previous_screen = Some(app.screen);
+                app.screen = Screen::Servers;
+                start_lan_discovery(app);
+            }
+        }
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+/// Write the server list to `path`, as servers.dat inside it if it is a
+/// folder
+pub(super) fn export_servers(app: &mut App, path: &Path) {
+    let path = if path.is_dir() {
+        path.join("servers.dat")
+    } else {
+        path.to_path_buf()
+    };
+    match save_servers(&path, &app.servers) {
+        Ok(()) => {
+            app.input_buffer.clear();
+            app.input_mode = InputMode::Normal;
+            app.set_status(format!(
+                "Exported {} servers to {}",
+                app.servers.len(),
+                path.display()
+            ));
+        }
+        Err(e) => app.set_error_at(format!("Failed to export servers: {}", e), &path),
+    }
+}
+
+/// Add the servers in the servers.dat at `path` that aren't listed yet
+pub(super) fn import_servers(app: &mut App, path: &Path) {
+    if !path.is_file() {
+        app.set_error(format!("{} is not a file", path.display()));
+        return;
+    }
+    let imported = match load_servers(&path.to_path_buf()) {
+        Ok(imported) => imported,
+        Err(e) => {
+            app.set_error_at(format!("Failed to read servers: {}", e), path);
+            return;
+        }
+    };
+    app.input_buffer.clear();
+    app.input_mode = InputMode::Normal;
+    let total = imported.len();
+    let added = merge_servers(&mut app.servers, imported);
+    if added == 0 {
+        app.set_status(format!("All {} servers are already listed", total));
+    } else if save_server_list(app) {
+        app.set_status(format!(
+            "Imported {} servers ({} already listed)",
+            added,
+            total - added
+        ));
+    }
+}
+
+/// Validate a Minecraft server address
+pub(super) fn validate_server_address(address: &str) -> Result<(), String> {
+    if address.is_empty() {
+        return Err("Server address cannot be empty".to_string());
+    }
+
+    if address.contains(' ') {
+        return Err("Server address cannot contain spaces".to_string());
+    }
+
+    let parts: Vec<&str> = address.rsplitn(2, ':').collect();
+    let host = if parts.len() == 2 {
+        if parts[0].parse::<u16>().is_err() {
+            return Err("Invalid port number".to_string());
+        }
+        parts[1]
+    } else {
+        address
+    };
+
+    if host.is_empty() {
+        return Err("Server hostname cannot be empty".to_string());
+    }
+
+    Ok(())
+}
+
+/// Resolve a server and compare it with the other servers' resolved
+/// addresses, optionally checking Mojang's blocklist too. Warnings arrive as
+/// `Message::ServerCheckComplete`.
+pub(super) fn queue_server_check(
+    app: &mut App,
+    address: String,
+    server_idx: usize,
+    check_blocklist: bool,
+    report_ok: bool,
+) {
+    let others = app
+        .servers
+        .iter()
+        .enumerate()
+        .filter(|(idx, _)| *idx != server_idx)
+        .map(|(_, s)| (s.name.clone(), s.ip.clone()))
+        .collect();
+    app.commands.push(Command::CheckServer {
+        address,
+        others,
+        check_blocklist,
+        report_ok,
+    });
+}
+
+/// Listen for LAN world announcements while the Servers screen is open.
+/// Games arrive as `Message::LanGameDiscovered`.
+fn start_lan_discovery(app: &mut App) {
+    if app.lan_listener.is_some() {
+        return;
+    }
+
+    let tx = app.message_tx.clone();
+    app.lan_error = None;
+    app.lan_listener = Some(tokio::spawn(async move {
+        let socket = match lan::bind() {
+            Ok(socket) => socket,
+            Err(e) => {
+                let _ = tx.send(Message::LanDiscoveryFailed(e.to_string()));
+                return;
+            }
+        };
+        loop {
+            match lan::next_game(&socket).await {
+                Ok(game) => {
+                    if tx.send(Message::LanGameDiscovered(game)).is_err() {
+                        return;
+                    }
+                }
+                Err(e) => {
+                    let _ = tx.send(Message::LanDiscoveryFailed(e.to_string()));
+                    return;
+                }
+            }
+        }
+    }));
+}
+
+pub(super) fn stop_lan_discovery(app: &mut App) {
+    if let Some(listener) = app.lan_listener.take() {
+        listener.abort();
+    }
+    app.lan_games.clear();
+    app.selected_lan_index = 0;
+}
+
+/// Save the server list, or ask how to go on when servers.dat was changed
+/// by PrismLauncher or the game since it was loaded. Returns whether the
+/// list was written.
+pub(super) fn save_server_list(app: &mut App) -> bool {
+    match app.save_servers_for_instance(false) {
+        Ok(()) => true,
+        Err(PrismError::ChangedOnDisk(_)) => {
+            app.external_change = Some(ExternalChange::Servers);
+            app.input_mode = InputMode::ConfirmExternalChange;
+            false
+        }
+        Err(e) => {
+            set_servers_error(app, format!("Failed to save servers: {}", e));
+            false
+        }
+    }
+}
+
+/// Report a servers.dat failure, naming the selected instance's file
+pub(super) fn set_servers_error(app: &mut App, msg: String) {
+    match app.selected_instance().map(Instance::servers_dat_path) {
+        Some(path) => app.set_error_at(msg, &path),
+        None => app.set_error(msg),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::Server;
+    use crate::net::ping::ServerStatus;
+    use crate::update::tests::test_app;
+
+    #[test]
+    fn test_hiding_servers_moves_the_selection_off_hidden_ones() {
+        let mut app = test_app("servers", &["Alpha"]);
+        app.servers = ["Hub", "Secret", "Survival"]
+            .map(|name| Server::new(name.to_string(), format!("{}.example.com", name)))
+            .to_vec();
+        app.servers[1].hidden = true;
+        app.show_hidden_servers = true;
+
+        reduce(&mut app, Message::SelectServer(1)).unwrap();
+        reduce(&mut app, Message::SelectServer(7)).unwrap();
+        assert_eq!(app.selected_server_index, 1);
+        reduce(&mut app, Message::ToggleHiddenServers).unwrap();
+        assert_eq!(
+            app.selected_server().map(|s| s.name.as_str()),
+            Some("Survival")
+        );
+    }
+
+    #[test]
+    fn test_ping_flags_a_version_the_instance_cannot_join() {
+        let mut app = test_app("server-ping", &["Alpha"]);
+        app.instances[0].minecraft_version = "1.20.1".to_string();
+        let status = ServerStatus {
+            version_name: "Velocity 3.3.0".to_string(),
+            protocol: 765,
+            players_online: 3,
+            players_max: 20,
+        };
+
+        reduce(
+            &mut app,
+            Message::ServerPinged {
+                address: "mc.example.com".to_string(),
+                result: Ok(status),
+                for_launch: false,
+            },
+        )
+        .unwrap();
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some(
+                "mc.example.com: 1.20.3-1.20.4 (protocol 765), 3/20 players; \
+                 Alpha is 1.20.1 and can't join"
+            )
+        );
+    }
+
+    #[test]
+    fn test_validate_server_address_valid() {
+        assert!(validate_server_address("mc.hypixel.net").is_ok());
+        assert!(validate_server_address("play.example.com:25565").is_ok());
+        assert!(validate_server_address("192.168.1.1").is_ok());
+        assert!(validate_server_address("192.168.1.1:25565").is_ok());
+        assert!(validate_server_address("localhost").is_ok());
+        assert!(validate_server_address("localhost:25565").is_ok());
+    }
+
+    #[test]
+    fn test_validate_server_address_empty() {
+        assert!(validate_server_address("").is_err());
+    }
+
+    #[test]
+    fn test_validate_server_address_spaces() {
+        assert!(validate_server_address("example .com").is_err());
+        assert!(validate_server_address(" example.com").is_err());
+    }
+
+    #[test]
+    fn test_validate_server_address_invalid_port() {
+        assert!(validate_server_address("example.com:invalid").is_err());
+        assert!(validate_server_address("example.com:99999").is_err());
+    }
+
+    #[test]
+    fn test_validate_server_address_empty_host() {
+        assert!(validate_server_address(":25565").is_err());
+    }
+}
@@ -0,0 +1,239 @@
+//! The worlds screen: an instance's `saves/` next to the backups FTB Backups,
+//! Simple Backups and prism-tui itself keep of them.
+
+use crate::app::{App, Screen};
+use crate::command::Command;
+use crate::data::{InstanceFolder, list_worlds};
+use crate::message::Message;
+
+pub(super) fn reduce(app: &mut App, msg: Message) -> Result<(), Message> {
+    match msg {
+        Message::OpenWorlds => {
+            if app.selected_instance().is_none() {
+                return Ok(());
+            }
+            reload_worlds(app);
+            app.backups.clear();
+            find_backups(app);
+            app.selected_world_index = 0;
+            app.world_scroll = 0;
+            app.selected_backup_index = 0;
+            app.backup_scroll = 0;
+            app.backups_focused = false;
+            app.previous_screen = Some(app.screen);
+            app.screen = Screen::Worlds;
+        }
+        Message::SelectWorld(idx) => {
+            if idx < app.worlds.len() {
+                app.selected_world_index = idx;
+                app.backups_focused = false;
+            }
+        }
+        Message::SelectBackup(idx) => {
+            if idx < app.backups.len() {
+                app.selected_backup_index = idx;
+                app.backups_focused = true;
+            }
+        }
+        Message::SwitchWorldsPane => {
+            app.backups_focused = !app.backups_focused;
+        }
+        Message::BackupsFound {
+            instance_id,
+            backups,
+        } => {
+            // Another instance may have been opened meanwhile
+            if app.selected_instance().map(|i| i.id.as_str()) != Some(instance_id.as_str()) {
+                return Ok(());
+            }
+            app.backups = backups;
+            app.backups_loading = false;
+            app.selected_backup_index = app
+                .selected_backup_index
+                .min(app.backups.len().saturating_sub(1));
+        }
+        Message::BackupWorld => {
+            let Some(instance) = app.selected_instance() else {
+                return Ok(());
+            };
+            // A running game writes to its world while the zip is read
+            if app.is_instance_running(&instance.id) {
+                app.set_error(format!("Close {} before backing up a world", instance.name));
+                return Ok(());
+            }
+            let Some(world) = app.worlds.get(app.selected_world_index).cloned() else {
+                return Ok(());
+            };
+            if let Some(game_dir) = instance.minecraft_dir() {
+                let world_dir = game_dir.join(InstanceFolder::Saves.dir_name()).join(&world);
+                app.set_status(format!("Backing up {}...", world));
+                app.commands.push(Command::BackupWorld {
+                    world_dir,
+                    game_dir,
+                });
+            }
+        }
+        Message::WorldBackedUp { world, result } => match result {
+            Ok(file_name) => {
+                app.set_status(format!("Backed up {} to backups/{}", world, file_name));
+                if app.screen == Screen::Worlds {
+                    find_backups(app);
+                }
+            }
+            Err(e) => app.set_error(format!("Failed to back up {}: {}", world, e)),
+        },
+        Message::RestoreBackup => {
+            let Some(backup) = app.backups.get(app.selected_backup_index).cloned() else {
+                return Ok(());
+            };
+            if let Some(instance) = app.selected_instance() {
+                let saves_dir = instance.game_folder(InstanceFolder::Saves);
+                app.set_status(format!("Restoring {}...", backup.world));
+                app.commands
+                    .push(Command::RestoreBackup { backup, saves_dir });
+            }
+        }
+        Message::BackupRestored { world, result } => match result {
+            Ok(folder) => {
+                app.set_status(format!("Restored {} to saves/{}", world, folder));
+                if app.screen == Screen::Worlds {
+                    reload_worlds(app);
+                }
+            }
+            Err(e) => app.set_error(format!("Failed to restore {}: {}", world, e)),
+        },
+        msg => return Err(msg),
+    }
+    Ok(())
+}
+
+/// Read saves/ again, keeping the selection in range
+fn reload_worlds(app: &mut App) {
+    let Some(instance) = app.selected_instance() else {
+        return;
+    };
+    app.worlds = list_worlds(&instance.game_folder(InstanceFolder::Saves));
+    app.selected_world_index = app
+        .selected_world_index
+        .min(app.worlds.len().saturating_sub(1));
+}
+
+/// Read the backup archives in the background; opening each one's listing
+/// takes a while when there are many or large ones
+fn find_backups(app: &mut App) {
+    let Some(instance) = app.selected_instance() else {
+        return;
+    };
+    let Some(game_dir) = instance.minecraft_dir() else {
+        app.backups_loading = false;
+        return;
+    };
+    let command = Command::FindBackups {
+        instance_id: instance.id.clone(),
+        game_dir,
+    };
+    app.backups_loading = true;
+    app.commands.push(command);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::RunningInstance;
+    use crate::data::WorldBackup;
+    use crate::data::backups::BackupMod;
+    use crate::update::tests::test_app;
+    use std::fs;
+    use std::time::Instant;
+
+    #[test]
+    fn test_any_world_can_be_backed_up_and_any_backup_restored() {
+        let mut app = test_app("worlds", &["Alpha"]);
+        let saves = app.instances[0].game_folder(InstanceFolder::Saves);
+        for n in 0..12 {
+            let world = saves.join(format!("World {:02}", n));
+            fs::create_dir_all(&world).unwrap();
+            fs::write(world.join("level.dat"), "level").unwrap();
+        }
+        let game_dir = app.instances[0].minecraft_dir().unwrap();
+        let backups: Vec<WorldBackup> = (0..12)
+            .map(|n| WorldBackup {
+                path: game_dir.join(format!("backups/World {:02}.zip", n)),
+                source: BackupMod::PrismTui,
+                world: format!("World {:02}", n),
+                size: 100,
+                modified: None,
+            })
+            .collect();
+
+        reduce(&mut app, Message::OpenWorlds).unwrap();
+        let finding = std::mem::take(&mut app.commands);
+        let loading = app.backups_loading;
+        let found = Message::BackupsFound {
+            instance_id: app.instances[0].id.clone(),
+            backups: backups.clone(),
+        };
+        reduce(&mut app, found).unwrap();
+        reduce(&mut app, Message::SelectWorld(11)).unwrap();
+        reduce(&mut app, Message::BackupWorld).unwrap();
+        reduce(&mut app, Message::SelectBackup(10)).unwrap();
+        reduce(&mut app, Message::RestoreBackup).unwrap();
+        let worlds = app.worlds.len();
+        fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert_eq!(app.screen, Screen::Worlds);
+        assert_eq!(
+            finding,
+            [Command::FindBackups {
+                instance_id: "Alpha".to_string(),
+                game_dir: game_dir.clone(),
+            }]
+        );
+        assert!(loading && !app.backups_loading);
+        assert_eq!(worlds, 12);
+        assert!(app.backups_focused);
+        assert_eq!(
+            app.commands,
+            [
+                Command::BackupWorld {
+                    world_dir: saves.join(&app.worlds[11]),
+                    game_dir,
+                },
+                Command::RestoreBackup {
+                    backup: backups[10].clone(),
+                    saves_dir: saves,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_backing_up_waits_for_the_game_to_close() {
+        let mut app = test_app("worlds-running", &["Alpha"]);
+        let world = app.instances[0]
+            .game_folder(InstanceFolder::Saves)
+            .join("New World");
+        fs::create_dir_all(&world).unwrap();
+        fs::write(world.join("level.dat"), "level").unwrap();
+        reduce(&mut app, Message::OpenWorlds).unwrap();
+        app.commands.clear();
+        app.running_instances.insert(
+            app.instances[0].id.clone(),
+            RunningInstance {
+                pid: None,
+                launched_at: Instant::now(),
+                low_priority: false,
+                priority_lowered: false,
+            },
+        );
+
+        reduce(&mut app, Message::BackupWorld).unwrap();
+        fs::remove_dir_all(&app.data_dir).unwrap();
+
+        assert!(app.commands.is_empty());
+        assert_eq!(
+            app.error_message.as_deref(),
+            Some("Close Alpha before backing up a world")
+        );
+    }
+}
@@ -1,7 +1,9 @@
-use crate::app::{App, ClickAction, InputMode};
+use crate::app::{App, ClickAction, InputMode, ScrollTarget};
 use crate::message::Message;
 use crate::theme::ui;
-use crate::view::{SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar, render_scrollbar};
+use crate::view::{
+    SELECTED_PREFIX, UNSELECTED_PREFIX, empty_state_lines, render_footer_bar, render_scrollbar,
+};
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 
@@ -24,9 +26,9 @@ fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
     let back_text = "[Esc] Back";
     let back_x_offset = "Select Account".len() + 2; // title + "  "
     let mut spans = vec![
-        Span::styled("Select Account", Style::default().fg(ui::PRIMARY).bold()),
+        Span::styled("Select Account", Style::default().fg(ui::primary()).bold()),
         Span::raw("  "),
-        Span::styled(back_text, Style::default().fg(ui::MUTED)),
+        Span::styled(back_text, Style::default().fg(ui::muted())),
     ];
 
     // Register click region for back button (area.x + 1 for border + offset)
@@ -41,13 +43,13 @@ fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
     // Show search query if active
     if !app.search_query.is_empty() || app.input_mode == InputMode::Search {
         spans.push(Span::raw("  "));
-        spans.push(Span::styled("/", Style::default().fg(ui::HIGHLIGHT)));
+        spans.push(Span::styled("/", Style::default().fg(ui::highlight())));
         spans.push(Span::styled(
             &app.input_buffer,
-            Style::default().fg(ui::HIGHLIGHT),
+            Style::default().fg(ui::highlight()),
         ));
         if app.input_mode == InputMode::Search {
-            spans.push(Span::styled("_", Style::default().fg(ui::HIGHLIGHT)));
+            spans.push(Span::styled("_", Style::default().fg(ui::highlight())));
         }
     }
 
@@ -83,7 +85,7 @@ fn render_account_list(app: &mut App, frame: &mut Frame, area: Rect) {
 
             let style = if is_selected {
                 Style::default()
-                    .fg(ui::PRIMARY)
+                    .fg(ui::primary())
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
@@ -94,9 +96,9 @@ fn render_account_list(app: &mut App, frame: &mut Frame, area: Rect) {
                 Span::styled(
                     active_marker,
                     if is_active {
-                        Style::default().fg(ui::ACTIVE)
+                        Style::default().fg(ui::active())
                     } else {
-                        Style::default().fg(ui::MUTED)
+                        Style::default().fg(ui::muted())
                     },
                 ),
                 Span::raw(" "),
@@ -117,15 +119,31 @@ fn render_account_list(app: &mut App, frame: &mut Frame, area: Rect) {
 
     let total_items = items.len();
 
-    let list = if items.is_empty() {
-        let msg = if !app.search_query.is_empty() {
-            "No matches. Press Esc to clear search."
-        } else {
-            "No accounts found. Add accounts in PrismLauncher."
-        };
+    let list = if app.accounts.is_empty() {
+        let message = app.accounts_error.clone().unwrap_or_else(|| {
+            format!(
+                "No accounts found in {}",
+                app.data_dir.join("accounts.json").display()
+            )
+        });
+        let lines = empty_state_lines(
+            app,
+            area,
+            message,
+            &[
+                (
+                    "n",
+                    "Add an account in PrismLauncher",
+                    Message::OpenLauncher,
+                ),
+                ("r", "Reload once it's added", Message::ReloadData),
+            ],
+        );
+        List::new(lines.into_iter().map(ListItem::new))
+    } else if items.is_empty() {
         List::new(vec![ListItem::new(Span::styled(
-            format!("  {}", msg),
-            Style::default().fg(ui::MUTED),
+            "  No matches. Press Esc to clear search.",
+            Style::default().fg(ui::muted()),
         ))])
     } else {
         List::new(items)
@@ -157,11 +175,13 @@ fn render_account_list(app: &mut App, frame: &mut Frame, area: Rect) {
         .position(|&idx| idx == app.selected_account_index)
         .unwrap_or(0);
     render_scrollbar(
+        app,
         frame,
         area,
         total_items,
         inner_height,
         selected_pos.saturating_sub(inner_height / 2),
+        ScrollTarget::Accounts,
     );
 }
 
@@ -0,0 +1,163 @@
+use crate::app::{App, CreateStep};
+use crate::message::Message;
+use crate::net::mojang::VersionType;
+use crate::theme::ui;
+use crate::view::{
+    SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar, render_list, render_list_frame,
+    scroll_to_keep_visible,
+};
+use ratatui::prelude::*;
+use ratatui::widgets::ListItem;
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    // What has been picked so far, e.g. "1.20.1 > Fabric"
+    let draft = &app.draft;
+    let mut picked: Vec<&str> = draft.minecraft_version.iter().map(String::as_str).collect();
+    if draft.step == CreateStep::LoaderVersion
+        && let Some(loader) = draft.loader
+    {
+        picked.push(loader.name());
+    }
+    let mut notes = Vec::new();
+    if !picked.is_empty() {
+        notes.push(Span::styled(
+            picked.join(" > "),
+            Style::default().fg(ui::text()),
+        ));
+    }
+    let (content, footer) = render_list_frame(app, frame, area, "New Instance", notes);
+
+    render_step_list(app, frame, content);
+    render_footer(app, frame, footer);
+}
+
+/// The rows of the current step as (label, note) pairs, or why there are
+/// none
+pub(super) fn step_rows(app: &App) -> Result<Vec<(String, Option<&'static str>)>, String> {
+    let draft = &app.draft;
+    let rows: Vec<_> = match draft.step {
+        CreateStep::Template => std::iter::once(("Blank instance".to_string(), None))
+            .chain(
+                app.templates
+                    .iter()
+                    .map(|t| (format!("{} ({})", t.name, t.summary()), Some("template"))),
+            )
+            .collect(),
+        CreateStep::MinecraftVersion => app
+            .draft_versions()
+            .into_iter()
+            .map(|v| {
+                let note = match v.kind {
+                    VersionType::Release => None,
+                    VersionType::Snapshot => Some("snapshot"),
+                    VersionType::OldBeta | VersionType::OldAlpha => Some("old"),
+                };
+                (v.id.clone(), note)
+            })
+            .collect(),
+        CreateStep::Loader => app
+            .draft_loaders()
+            .into_iter()
+            .map(|l| (l.map_or("Vanilla", |l| l.name()).to_string(), None))
+            .collect(),
+        CreateStep::LoaderVersion => app
+            .draft_loader_versions()
+            .into_iter()
+            .flatten()
+            .map(|v| (v.version.clone(), (!v.stable).then_some("unstable")))
+            .collect(),
+    };
+    if !rows.is_empty() {
+        return Ok(rows);
+    }
+
+    let loader_pending = match (draft.loader, &draft.minecraft_version) {
+        (Some(loader), Some(version)) => app
+            .loader_catalogs_pending
+            .contains(&(loader, version.clone())),
+        _ => false,
+    };
+    Err(match draft.step {
+        CreateStep::MinecraftVersion if app.version_manifest_pending => "Loading…".to_string(),
+        CreateStep::MinecraftVersion => "Minecraft versions could not be loaded".to_string(),
+        CreateStep::LoaderVersion if loader_pending => "Loading…".to_string(),
+        _ => format!(
+            "No {} versions for Minecraft {}",
+            draft.loader.map_or("", |l| l.name()),
+            draft.minecraft_version.as_deref().unwrap_or_default()
+        ),
+    })
+}
+
+fn render_step_list(app: &mut App, frame: &mut Frame, area: Rect) {
+    let title = match app.draft.step {
+        CreateStep::Template => "Start From",
+        CreateStep::MinecraftVersion if app.draft.all_versions => "Minecraft Version (all)",
+        CreateStep::MinecraftVersion => "Minecraft Version (releases)",
+        CreateStep::Loader => "Mod Loader",
+        CreateStep::LoaderVersion => "Loader Version",
+    };
+    let (rows, empty) = match step_rows(app) {
+        Ok(rows) => (rows, String::new()),
+        Err(message) => (Vec::new(), message),
+    };
+
+    let selected = app.draft.selected;
+    let scroll = scroll_to_keep_visible(
+        app.draft.scroll,
+        selected,
+        rows.len(),
+        area.height.saturating_sub(2) as usize,
+    );
+    app.draft.scroll = scroll;
+
+    let items: Vec<ListItem> = rows
+        .into_iter()
+        .enumerate()
+        .skip(scroll)
+        .map(|(idx, (label, note))| {
+            let is_selected = idx == selected;
+            let prefix = if is_selected {
+                SELECTED_PREFIX
+            } else {
+                UNSELECTED_PREFIX
+            };
+            let style = if is_selected {
+                Style::default()
+                    .fg(ui::primary())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            let mut spans = vec![Span::styled(prefix, style), Span::styled(label, style)];
+            if let Some(note) = note {
+                spans.push(Span::styled(
+                    format!(" [{}]", note),
+                    Style::default().fg(ui::muted()),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    render_list(app, frame, area, title.to_string(), items, scroll, &empty);
+}
+
+fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
+    let keys: &[(&str, &str, Option<Message>)] = match app.draft.step {
+        CreateStep::MinecraftVersion => &[
+            ("j/k", "Nav", None),
+            ("l/Enter", "Select", Some(Message::ConfirmDraftItem)),
+            ("s", "Snapshots", Some(Message::ToggleAllVersions)),
+            ("p", "Modpack", Some(Message::StartPackSearch)),
+            ("h/Esc", "Back", Some(Message::DraftStepBack)),
+        ],
+        _ => &[
+            ("j/k", "Nav", None),
+            ("l/Enter", "Select", Some(Message::ConfirmDraftItem)),
+            ("p", "Modpack", Some(Message::StartPackSearch)),
+            ("h/Esc", "Back", Some(Message::DraftStepBack)),
+        ],
+    };
+    render_footer_bar(app, frame, area, keys);
+}
@@ -0,0 +1,151 @@
+use crate::dashboard::Dashboard;
+use crate::theme::{self, ui};
+use crate::view::truncate;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
+
+/// Launches listed in a printed snapshot; the live view fills the screen
+const SNAPSHOT_RECENT: usize = 8;
+
+/// Rows a snapshot needs: the header, then both boxes with their borders
+pub fn dashboard_height(dashboard: &Dashboard) -> u16 {
+    let running = dashboard.running.len().max(1);
+    let recent = dashboard.recent_launches().len().clamp(1, SNAPSHOT_RECENT);
+    (1 + running + 2 + recent + 2) as u16
+}
+
+pub fn render_dashboard(dashboard: &Dashboard, area: Rect, buf: &mut Buffer) {
+    let running_height = dashboard.running.len().max(1) as u16 + 2;
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Length(running_height),
+            Constraint::Min(3),
+        ])
+        .split(area);
+
+    let muted = Style::default().fg(ui::muted());
+    let header = Line::from(vec![
+        Span::styled(
+            " Prism TUI",
+            Style::default()
+                .fg(ui::primary())
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(
+            format!(
+                "  memory {} / {}  updated {}",
+                format_memory(dashboard.used_memory),
+                format_memory(dashboard.total_memory),
+                dashboard.updated.format("%H:%M:%S")
+            ),
+            muted,
+        ),
+    ]);
+    Paragraph::new(header).render(chunks[0], buf);
+
+    render_running(dashboard, chunks[1], buf);
+    render_recent(dashboard, chunks[2], buf);
+
+    if theme::is_plain() {
+        theme::downgrade(buf);
+    }
+}
+
+fn render_running(dashboard: &Dashboard, area: Rect, buf: &mut Buffer) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(format!("Running ({})", dashboard.running.len()));
+    let muted = Style::default().fg(ui::muted());
+
+    if dashboard.running.is_empty() {
+        Paragraph::new(Span::styled(" Nothing running", muted))
+            .block(block)
+            .render(area, buf);
+        return;
+    }
+
+    let rows: Vec<Row> = dashboard
+        .running
+        .iter()
+        .map(|game| {
+            Row::new(vec![
+                Cell::from(Line::from(vec![
+                    Span::styled(" ● ", Style::default().fg(ui::active())),
+                    Span::styled(truncate(&game.name, 30), Style::default().fg(ui::text())),
+                ])),
+                Cell::from(Span::styled(game.minecraft_version.clone(), muted)),
+                Cell::from(format!("{:>5.1}% cpu", game.cpu)),
+                Cell::from(format_memory(game.memory)),
+                Cell::from(Span::styled(format_uptime(game.uptime), muted)),
+                Cell::from(Span::styled(format!("pid {}", game.pid), muted)),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Min(20),
+        Constraint::Length(10),
+        Constraint::Length(11),
+        Constraint::Length(10),
+        Constraint::Length(8),
+        Constraint::Length(11),
+    ];
+    Widget::render(Table::new(rows, widths).block(block), area, buf);
+}
+
+fn render_recent(dashboard: &Dashboard, area: Rect, buf: &mut Buffer) {
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Recent launches");
+    let muted = Style::default().fg(ui::muted());
+    let recent = dashboard.recent_launches();
+
+    if recent.is_empty() {
+        Paragraph::new(Span::styled(" No launches yet", muted))
+            .block(block)
+            .render(area, buf);
+        return;
+    }
+
+    let rows: Vec<Row> = recent
+        .iter()
+        .map(|instance| {
+            Row::new(vec![
+                Cell::from(Span::styled(
+                    format!("   {}", truncate(&instance.name, 30)),
+                    Style::default().fg(ui::text()),
+                )),
+                Cell::from(Span::styled(instance.minecraft_version.clone(), muted)),
+                Cell::from(instance.formatted_last_launch()),
+                Cell::from(Span::styled(instance.formatted_playtime(), muted)),
+            ])
+        })
+        .collect();
+    let widths = [
+        Constraint::Min(20),
+        Constraint::Length(10),
+        Constraint::Length(18),
+        Constraint::Length(12),
+    ];
+    Widget::render(Table::new(rows, widths).block(block), area, buf);
+}
+
+fn format_memory(bytes: u64) -> String {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    if bytes as f64 >= GIB {
+        format!("{:.1} GB", bytes as f64 / GIB)
+    } else {
+        format!("{} MB", bytes / (1024 * 1024))
+    }
+}
+
+fn format_uptime(seconds: u64) -> String {
+    let hours = seconds / 3600;
+    let minutes = (seconds % 3600) / 60;
+    if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
@@ -0,0 +1,125 @@
+use crate::app::App;
+use crate::theme::ui;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use std::time::{Duration, Instant};
+
+/// Frame timing and a summary of the app state, toggled with F12
+pub fn render(app: &App, frame: &mut Frame, area: Rect) {
+    let stats = &app.debug_stats;
+    let ms = |d: Duration| format!("{:.1}ms", d.as_secs_f64() * 1000.0);
+
+    let rows = [
+        (
+            "render",
+            format!(
+                "{} avg {} max {}",
+                ms(stats.last_render),
+                ms(stats.avg_render),
+                ms(stats.max_render)
+            ),
+        ),
+        ("frames", stats.frames.to_string()),
+        (
+            "messages",
+            format!(
+                "{} input, {} background, {} queued",
+                stats.input_messages, stats.background_messages, stats.queued
+            ),
+        ),
+        ("tick", ms(app.tick_rate())),
+        (
+            "schedule",
+            app.scheduler
+                .upcoming(Instant::now())
+                .into_iter()
+                .map(|(task, wait)| format!("{} in {:.1}s", task.label(), wait.as_secs_f64()))
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+        ("clicks", format!("{} regions", app.click_regions.len())),
+        ("screen", format!("{:?} ({:?})", app.screen, app.input_mode)),
+        (
+            "instances",
+            format!(
+                "{} total, {} shown, {} groups, #{} selected",
+                app.instances.len(),
+                app.filtered_instance_indices.len(),
+                app.grouped_instances.len(),
+                app.selected_instance_index
+            ),
+        ),
+        (
+            "running",
+            format!(
+                "{} games, {} held cfg edits",
+                app.running_instances.len(),
+                app.pending_cfg_edits.len()
+            ),
+        ),
+        (
+            "servers",
+            format!(
+                "{} ({} hidden), {} LAN",
+                app.servers.len(),
+                app.hidden_server_count(),
+                app.lan_games.len()
+            ),
+        ),
+        (
+            "logs",
+            format!(
+                "{} files, {} lines, {} matches",
+                app.log_entries.len(),
+                app.log_content.len(),
+                app.log_search_matches.len()
+            ),
+        ),
+        (
+            "search",
+            format!(
+                "{} indexed, {} results",
+                app.search_index.len(),
+                app.search_results.len()
+            ),
+        ),
+        (
+            "network",
+            format!(
+                "{} pack checks, {} loader catalogs pending",
+                app.pack_update_checks_pending,
+                app.loader_catalogs_pending.len()
+            ),
+        ),
+    ];
+
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(format!("{:<10}", label), Style::default().fg(ui::muted())),
+                Span::styled(value, Style::default().fg(ui::text())),
+            ])
+        })
+        .collect();
+
+    let width = 60.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let overlay_area = Rect {
+        x: area.x + area.width - width,
+        y: area.y,
+        width,
+        height,
+    };
+
+    frame.render_widget(Clear, overlay_area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Debug ")
+                .border_style(Style::default().fg(ui::warning())),
+        ),
+        overlay_area,
+    );
+}
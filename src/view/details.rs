@@ -1,7 +1,9 @@
 use crate::app::App;
+use crate::data::{InstanceStats, SettingSource, format_env_assignments, format_size};
 use crate::message::Message;
+use crate::net::modrinth::{PackUpdate, ProjectVersion};
 use crate::theme::ui;
-use crate::view::render_footer_bar;
+use crate::view::{SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar};
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
 
@@ -16,6 +18,16 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
 }
 
 fn render_details(app: &mut App, frame: &mut Frame, area: Rect) {
+    let conflict = app.join_conflict();
+    let stats = app.selected_instance_stats();
+    let newer_release = app
+        .selected_instance()
+        .and_then(|i| app.newer_minecraft_release(i))
+        .map(|v| v.to_string());
+    let newer_loader = app
+        .selected_instance()
+        .and_then(|i| app.newer_loader_version(i))
+        .map(|v| v.version.clone());
     let instance = match app.selected_instance() {
         Some(i) => i,
         None => {
@@ -25,59 +37,106 @@ fn render_details(app: &mut App, frame: &mut Frame, area: Rect) {
                         .borders(Borders::ALL)
                         .title("Instance Details"),
                 )
-                .style(Style::default().fg(ui::MUTED));
+                .style(Style::default().fg(ui::muted()));
             frame.render_widget(empty, area);
             return;
         }
     };
 
+    let pack_text = instance
+        .managed_pack
+        .as_ref()
+        .map(|p| {
+            let name = if p.name.is_empty() { &p.id } else { &p.name };
+            if p.version_name.is_empty() {
+                format!("{} ({})", name, p.platform.label())
+            } else {
+                format!("{} {} ({})", name, p.version_name, p.platform.label())
+            }
+        })
+        .unwrap_or_else(|| "Not a managed pack".to_string());
+
     let mut lines: Vec<Line> = vec![
         Line::from(vec![
-            Span::styled("  Name:           ", Style::default().fg(ui::MUTED)),
-            Span::styled(&instance.name, Style::default().fg(ui::TEXT).bold()),
+            Span::styled("  Name:           ", Style::default().fg(ui::muted())),
+            Span::styled(&instance.name, Style::default().fg(ui::text()).bold()),
         ]),
         Line::from(vec![
-            Span::styled("  Path:           ", Style::default().fg(ui::MUTED)),
+            Span::styled("  Path:           ", Style::default().fg(ui::muted())),
             Span::styled(
                 instance.path.display().to_string(),
-                Style::default().fg(ui::TEXT),
+                Style::default().fg(ui::text()),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Version:        ", Style::default().fg(ui::MUTED)),
-            Span::styled(&instance.minecraft_version, Style::default().fg(ui::TEXT)),
+            Span::styled("  Version:        ", Style::default().fg(ui::muted())),
+            Span::styled(&instance.minecraft_version, Style::default().fg(ui::text())),
+            Span::styled(
+                newer_release
+                    .map(|v| format!("  ({} available)", v))
+                    .unwrap_or_default(),
+                Style::default().fg(ui::highlight()),
+            ),
         ]),
         Line::from(vec![
-            Span::styled("  Mod Loader:     ", Style::default().fg(ui::MUTED)),
+            Span::styled("  Mod Loader:     ", Style::default().fg(ui::muted())),
+            Span::styled(
+                instance
+                    .mod_loader_display()
+                    .unwrap_or_else(|| "None".to_string()),
+                Style::default().fg(ui::text()),
+            ),
             Span::styled(
-                instance.mod_loader.as_deref().unwrap_or("None"),
-                Style::default().fg(ui::TEXT),
+                newer_loader
+                    .map(|v| format!("  [outdated: {} available]", v))
+                    .unwrap_or_default(),
+                Style::default().fg(ui::highlight()),
             ),
         ]),
+    ];
+    if let Some(label) = instance.missing_loader_label() {
+        lines.push(Line::from(vec![
+            Span::styled("                  ", Style::default().fg(ui::muted())),
+            Span::styled(
+                format!("[! {}]", label),
+                Style::default().fg(ui::warning()).bold(),
+            ),
+            Span::styled(
+                " install one in PrismLauncher",
+                Style::default().fg(ui::muted()),
+            ),
+        ]));
+    }
+    lines.extend([
+        Line::from(vec![
+            Span::styled("  Pack:           ", Style::default().fg(ui::muted())),
+            Span::styled(pack_text, Style::default().fg(ui::text())),
+        ]),
         Line::from(vec![
-            Span::styled("  Group:          ", Style::default().fg(ui::MUTED)),
+            Span::styled("  Group:          ", Style::default().fg(ui::muted())),
             Span::styled(
                 instance.group.as_deref().unwrap_or("Ungrouped"),
-                Style::default().fg(ui::TEXT),
+                Style::default().fg(ui::text()),
             ),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("  Playtime:       ", Style::default().fg(ui::MUTED)),
+            Span::styled("  Playtime:       ", Style::default().fg(ui::muted())),
             Span::styled(
                 instance.formatted_playtime_full(),
-                Style::default().fg(ui::ACTIVE),
+                Style::default().fg(ui::active()),
             ),
         ]),
         Line::from(vec![
-            Span::styled("  Last Launch:    ", Style::default().fg(ui::MUTED)),
+            Span::styled("  Last Launch:    ", Style::default().fg(ui::muted())),
             Span::styled(
-                instance.formatted_last_launch(),
-                Style::default().fg(ui::TEXT),
+                instance.formatted_last_launch_full(),
+                Style::default().fg(ui::text()),
             ),
         ]),
         Line::from(""),
-    ];
+    ]);
+    lines.splice(2..2, storage_lines(app));
 
     // Server Join
     let join_text = instance
@@ -93,32 +152,137 @@ fn render_details(app: &mut App, frame: &mut Frame, area: Rect) {
         .unwrap_or_else(|| "Not configured".to_string());
 
     lines.push(Line::from(vec![
-        Span::styled("  Join on Launch: ", Style::default().fg(ui::MUTED)),
-        Span::styled(join_text, Style::default().fg(ui::TEXT)),
+        Span::styled("  Join on Launch: ", Style::default().fg(ui::muted())),
+        Span::styled(join_text, Style::default().fg(ui::text())),
     ]));
 
+    if let Some(world) = &instance.world_join {
+        lines.push(Line::from(vec![
+            Span::styled("  Join World:     ", Style::default().fg(ui::muted())),
+            Span::styled(world.as_str(), Style::default().fg(ui::text())),
+        ]));
+    }
+
+    if let Some(conflict) = conflict {
+        lines.push(Line::from(vec![
+            Span::styled("                  ", Style::default().fg(ui::muted())),
+            Span::styled(
+                format!("[! {}]", conflict.label()),
+                Style::default().fg(ui::warning()).bold(),
+            ),
+            Span::styled(
+                format!(" F: {}", conflict.fix_label()),
+                Style::default().fg(ui::muted()),
+            ),
+        ]));
+    }
+
+    let env = app.app_config.env_for(&instance.id);
+    lines.push(Line::from(vec![
+        Span::styled("  Environment:    ", Style::default().fg(ui::muted())),
+        if env.is_empty() {
+            Span::styled("None (E to edit)", Style::default().fg(ui::muted()))
+        } else {
+            Span::styled(
+                format_env_assignments(&env),
+                Style::default().fg(ui::text()),
+            )
+        },
+    ]));
+
+    let wrapper = app.app_config.wrapper_for(&instance.id);
+    lines.push(Line::from(vec![
+        Span::styled("  Wrapper:        ", Style::default().fg(ui::muted())),
+        if wrapper.is_empty() {
+            Span::styled("None (w to edit)", Style::default().fg(ui::muted()))
+        } else {
+            Span::styled(wrapper.join(" "), Style::default().fg(ui::text()))
+        },
+    ]));
+    let mut prism_wrapper = vec![Span::styled(
+        "  Prism Wrapper:  ",
+        Style::default().fg(ui::muted()),
+    )];
+    match (
+        &instance.wrapper_command,
+        app.prism_settings.get("WrapperCommand"),
+    ) {
+        (Some(command), _) => prism_wrapper.push(Span::styled(
+            command.as_str(),
+            Style::default().fg(ui::text()),
+        )),
+        (None, Some(command)) => {
+            prism_wrapper.extend(setting_spans(command.to_string(), SettingSource::Global))
+        }
+        (None, None) => prism_wrapper.push(Span::styled(
+            "None (W to edit)",
+            Style::default().fg(ui::muted()),
+        )),
+    }
+    lines.push(Line::from(prism_wrapper));
+
+    // Launcher settings as they apply to this instance
+    let setting = |key| instance.effective_setting(&app.prism_settings, key);
+    let java_warning = instance
+        .java_mismatch(&app.prism_settings)
+        .map(|m| m.label());
+    for (label, value, warning) in [
+        (
+            "  Memory:         ",
+            instance.memory_setting(&app.prism_settings),
+            None,
+        ),
+        ("  Java:           ", setting("JavaPath"), java_warning),
+        ("  JVM Args:       ", setting("JvmArgs"), None),
+    ] {
+        let mut spans = vec![Span::styled(label, Style::default().fg(ui::muted()))];
+        match value {
+            Some((value, source)) => spans.extend(setting_spans(value, source)),
+            None => spans.push(Span::styled("Default", Style::default().fg(ui::muted()))),
+        }
+        lines.push(Line::from(spans));
+        if let Some(warning) = warning {
+            lines.push(Line::from(vec![
+                Span::styled("                  ", Style::default().fg(ui::muted())),
+                Span::styled(
+                    format!("[! {}]", warning),
+                    Style::default().fg(ui::warning()).bold(),
+                ),
+            ]));
+        }
+    }
+
     lines.push(Line::from(""));
 
-    // Counts
-    let mods = instance.mods_count();
-    let saves = instance.saves_count();
-    let packs = instance.resource_packs_count();
+    // Counts (cached, press r to refresh)
+    let count = |f: fn(&InstanceStats) -> usize| {
+        stats
+            .as_ref()
+            .map(|s| f(s).to_string())
+            .unwrap_or_else(|| "-".to_string())
+    };
 
     lines.push(Line::from(vec![
-        Span::styled("  Mods:           ", Style::default().fg(ui::MUTED)),
-        Span::styled(format!("{}", mods), Style::default().fg(ui::TEXT)),
+        Span::styled("  Mods:           ", Style::default().fg(ui::muted())),
+        Span::styled(count(|s| s.mods), Style::default().fg(ui::text())),
     ]));
 
     lines.push(Line::from(vec![
-        Span::styled("  Saves:          ", Style::default().fg(ui::MUTED)),
-        Span::styled(format!("{}", saves), Style::default().fg(ui::TEXT)),
+        Span::styled("  Saves:          ", Style::default().fg(ui::muted())),
+        Span::styled(count(|s| s.saves), Style::default().fg(ui::text())),
     ]));
 
     lines.push(Line::from(vec![
-        Span::styled("  Resource Packs: ", Style::default().fg(ui::MUTED)),
-        Span::styled(format!("{}", packs), Style::default().fg(ui::TEXT)),
+        Span::styled("  Resource Packs: ", Style::default().fg(ui::muted())),
+        Span::styled(count(|s| s.resource_packs), Style::default().fg(ui::text())),
     ]));
 
+    render_tasks(app, &mut lines);
+
+    if let Some(update) = app.pack_updates.get(&instance.id) {
+        render_pack_update(update, &mut lines);
+    }
+
     let title = format!("Instance Details: {}", instance.name);
     let details = Paragraph::new(lines)
         .block(Block::default().borders(Borders::ALL).title(title))
@@ -127,11 +291,174 @@ fn render_details(app: &mut App, frame: &mut Frame, area: Rect) {
     frame.render_widget(details, area);
 }
 
+/// Maximum changelog lines shown per pack version
+const CHANGELOG_LINES: usize = 8;
+
+/// Maximum number of newer versions listed in the changelog section
+const CHANGELOG_VERSIONS: usize = 5;
+
+fn render_tasks<'a>(app: &'a App, lines: &mut Vec<Line<'a>>) {
+    lines.push(Line::from(""));
+    if app.tasks.is_empty() {
+        lines.push(Line::from(vec![
+            Span::styled("  Tasks:          ", Style::default().fg(ui::muted())),
+            Span::styled("None (t to add)", Style::default().fg(ui::muted())),
+        ]));
+        return;
+    }
+
+    let open = app.tasks.iter().filter(|t| !t.done).count();
+    lines.push(Line::from(vec![
+        Span::styled("  Tasks:          ", Style::default().fg(ui::muted())),
+        Span::styled(
+            format!("{} open of {}", open, app.tasks.len()),
+            Style::default().fg(ui::text()),
+        ),
+    ]));
+    for (idx, task) in app.tasks.iter().enumerate() {
+        let prefix = if idx == app.selected_task_index {
+            SELECTED_PREFIX
+        } else {
+            UNSELECTED_PREFIX
+        };
+        let (check, style) = if task.done {
+            ("[x] ", Style::default().fg(ui::muted()).crossed_out())
+        } else {
+            ("[ ] ", Style::default().fg(ui::text()))
+        };
+        lines.push(Line::from(vec![
+            Span::raw("  "),
+            Span::styled(prefix, Style::default().fg(ui::primary())),
+            Span::styled(check, Style::default().fg(ui::active())),
+            Span::styled(task.text.as_str(), style),
+        ]));
+    }
+}
+
+/// The real folder of a symlinked instance and the free space on its drive
+fn storage_lines(app: &App) -> Vec<Line<'static>> {
+    let Some(storage) = app.selected_instance_storage() else {
+        return Vec::new();
+    };
+    let mut lines = Vec::new();
+    if let Some(real) = &storage.real_path {
+        lines.push(Line::from(vec![
+            Span::styled("  Stored at:      ", Style::default().fg(ui::muted())),
+            Span::styled(real.display().to_string(), Style::default().fg(ui::text())),
+            Span::styled("  (symlink)", Style::default().fg(ui::muted())),
+        ]));
+    }
+    if let (Some(available), Some(mount)) = (storage.available, &storage.mount_point) {
+        lines.push(Line::from(vec![
+            Span::styled("  Free space:     ", Style::default().fg(ui::muted())),
+            Span::styled(
+                format!("{} on {}", format_size(available), mount.display()),
+                Style::default().fg(ui::text()),
+            ),
+            Span::styled(
+                if storage.external {
+                    "  (not the launcher's drive)"
+                } else {
+                    ""
+                },
+                Style::default().fg(ui::highlight()),
+            ),
+        ]));
+    }
+    lines
+}
+
+/// A setting value followed by where it comes from
+fn setting_spans<'a>(value: String, source: SettingSource) -> Vec<Span<'a>> {
+    vec![
+        Span::styled(value, Style::default().fg(ui::text())),
+        Span::styled(
+            format!(" ({})", source.label()),
+            Style::default().fg(ui::muted()),
+        ),
+    ]
+}
+
+fn render_pack_update<'a>(update: &'a PackUpdate, lines: &mut Vec<Line<'a>>) {
+    let latest = update
+        .latest()
+        .map(|v| v.version_number.as_str())
+        .unwrap_or("?");
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled("  Pack Update:    ", Style::default().fg(ui::muted())),
+        Span::styled(
+            format!(
+                "{} available ({} newer)",
+                latest,
+                update.newer_versions.len()
+            ),
+            Style::default().fg(ui::highlight()).bold(),
+        ),
+    ]));
+
+    for version in update.newer_versions.iter().take(CHANGELOG_VERSIONS) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {} ({})", version.version_number, published_date(version)),
+            Style::default().fg(ui::primary()),
+        )));
+        let changelog = version.changelog.as_deref().unwrap_or("").trim();
+        if changelog.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "    No changelog",
+                Style::default().fg(ui::muted()),
+            )));
+            continue;
+        }
+        for line in changelog
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .take(CHANGELOG_LINES)
+        {
+            lines.push(Line::from(Span::styled(
+                format!("    {}", line.trim_end()),
+                Style::default().fg(ui::text()),
+            )));
+        }
+    }
+}
+
+fn published_date(version: &ProjectVersion) -> &str {
+    version
+        .date_published
+        .split('T')
+        .next()
+        .unwrap_or(&version.date_published)
+}
+
 fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
     let keys: &[(&str, &str, Option<Message>)] = &[
         ("h/Esc", "Back", Some(Message::Back)),
         ("o", "Open Folder", Some(Message::OpenInstanceFolder)),
+        ("O", "Subfolder", Some(Message::PickInstanceFolder)),
+        ("b", "Worlds", Some(Message::OpenWorlds)),
+        ("c", "Clean", Some(Message::PlanJunkCleanup)),
+        ("V", "Verify", Some(Message::VerifyInstance)),
+        ("r", "Refresh", Some(Message::RefreshInstanceStats)),
+        ("D", "Shortcut", Some(Message::CreateShortcut)),
+        ("E", "Env", Some(Message::EditEnv)),
+        ("w", "Wrapper", Some(Message::EditWrapper)),
+        ("t", "Add Task", Some(Message::AddTask)),
+        ("R", "Report", Some(Message::CopyInstanceReport)),
         ("q", "Quit", Some(Message::Quit)),
     ];
-    render_footer_bar(app, frame, area, keys);
+    let mut keys = keys.to_vec();
+    if app.join_conflict().is_some() {
+        keys.insert(2, ("F", "Fix Join", Some(Message::FixJoinConflict)));
+    }
+    let has_pack_page = app
+        .selected_instance()
+        .and_then(|i| i.managed_pack.as_ref())
+        .is_some_and(|p| p.url().is_some());
+    if has_pack_page {
+        keys.insert(2, ("p", "Pack Page", Some(Message::OpenPackPage)));
+    }
+    render_footer_bar(app, frame, area, &keys);
 }
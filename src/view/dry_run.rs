@@ -0,0 +1,67 @@
+use crate::app::{App, ClickAction};
+use crate::i18n::tr;
+use crate::theme::ui;
+use crate::view::centered_rect;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+/// The files a destructive action would touch, with Enter to go ahead
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let Some(count) = app.dry_run_preview.as_ref().map(|p| p.lines.len()) else {
+        return;
+    };
+
+    let width = 80.min(area.width.saturating_sub(4));
+    let height = (count as u16 + 2)
+        .clamp(5, 20)
+        .min(area.height.saturating_sub(2));
+    let preview_area = centered_rect(width, height, area);
+
+    // Nothing runs on a stray click; outside cancels like Esc
+    app.register_click(area, ClickAction::DismissOverlay);
+    app.register_click(preview_area, ClickAction::Noop);
+
+    let Some(preview) = &app.dry_run_preview else {
+        return;
+    };
+
+    let lines: Vec<Line> = preview
+        .lines
+        .iter()
+        .skip(preview.scroll)
+        .map(|line| Line::styled(line.as_str(), Style::default().fg(ui::text())))
+        .collect();
+    let keys = Line::from(vec![
+        Span::styled(" Enter", Style::default().fg(ui::active())),
+        Span::styled(
+            format!(" {}  ", tr("run")),
+            Style::default().fg(ui::muted()),
+        ),
+        Span::styled("j/k", Style::default().fg(ui::active())),
+        Span::styled(
+            format!(" {}  ", tr("scroll")),
+            Style::default().fg(ui::muted()),
+        ),
+        Span::styled("Esc", Style::default().fg(ui::active())),
+        Span::styled(
+            format!(" {} ", tr("cancel")),
+            Style::default().fg(ui::muted()),
+        ),
+    ]);
+
+    let dialog = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(
+                " {}: {} ({}) ",
+                tr("Dry run"),
+                tr(&preview.title),
+                count
+            ))
+            .title_bottom(keys.right_aligned())
+            .border_style(Style::default().fg(ui::warning())),
+    );
+
+    frame.render_widget(Clear, preview_area);
+    frame.render_widget(dialog, preview_area);
+}
@@ -0,0 +1,94 @@
+use crate::app::{App, ClickAction};
+use crate::i18n::tr;
+use crate::theme::ui;
+use crate::view::centered_rect;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+/// The whole error behind a toast: each cause on its own line, the file it
+/// is about and what usually fixes it
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let Some(details) = &app.error_details else {
+        return;
+    };
+    let scroll = details.scroll as u16;
+
+    let mut lines: Vec<Line> = details
+        .chain
+        .iter()
+        .enumerate()
+        .map(|(depth, cause)| {
+            let marker = if depth == 0 { "" } else { "└ " };
+            Line::styled(
+                format!(
+                    "{}{}{}",
+                    "  ".repeat(depth.saturating_sub(1)),
+                    marker,
+                    cause
+                ),
+                Style::default().fg(ui::error()),
+            )
+        })
+        .collect();
+    if let Some(path) = &details.path {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("{} ", tr("File:")),
+                Style::default().fg(ui::muted()),
+            ),
+            Span::styled(path.display().to_string(), Style::default().fg(ui::text())),
+        ]));
+    }
+    if let Some(remedy) = details.remedy {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(format!("{} ", tr("Try:")), Style::default().fg(ui::muted())),
+            Span::styled(tr(remedy), Style::default().fg(ui::text())),
+        ]));
+    }
+
+    // Only as tall as the wrapped text needs
+    let width = 80.min(area.width.saturating_sub(4));
+    let inner = width.saturating_sub(2).max(1) as usize;
+    let rows: usize = lines.iter().map(|l| l.width().max(1).div_ceil(inner)).sum();
+    let height = (rows as u16 + 2)
+        .clamp(5, 16)
+        .min(area.height.saturating_sub(2));
+    let details_area = centered_rect(width, height, area);
+
+    app.register_click(area, ClickAction::DismissOverlay);
+    app.register_click(details_area, ClickAction::Noop);
+
+    let keys = Line::from(vec![
+        Span::styled(" c", Style::default().fg(ui::active())),
+        Span::styled(
+            format!(" {}  ", tr("copy")),
+            Style::default().fg(ui::muted()),
+        ),
+        Span::styled("j/k", Style::default().fg(ui::active())),
+        Span::styled(
+            format!(" {}  ", tr("scroll")),
+            Style::default().fg(ui::muted()),
+        ),
+        Span::styled("Esc", Style::default().fg(ui::active())),
+        Span::styled(
+            format!(" {} ", tr("close")),
+            Style::default().fg(ui::muted()),
+        ),
+    ]);
+
+    let dialog = Paragraph::new(lines)
+        .wrap(Wrap { trim: false })
+        .scroll((scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(" {} ", tr("Error")))
+                .title_bottom(keys.right_aligned())
+                .border_style(Style::default().fg(ui::error())),
+        );
+
+    frame.render_widget(Clear, details_area);
+    frame.render_widget(dialog, details_area);
+}
@@ -1,4 +1,5 @@
 use crate::app::{App, ClickAction};
+use crate::i18n::tr;
 use crate::theme::ui;
 use crate::view::centered_rect;
 use ratatui::prelude::*;
@@ -23,6 +24,10 @@ const NAVIGATION: &[HelpEntry] = &[
         key: "g/G / Home/End",
         description: "Go to top/bottom",
     },
+    HelpEntry {
+        key: "PgUp/PgDn",
+        description: "Page instances (also Ctrl+u/d)",
+    },
     HelpEntry {
         key: "l/Enter",
         description: "Select/Launch",
@@ -35,6 +40,10 @@ const NAVIGATION: &[HelpEntry] = &[
         key: "Ctrl+j/k",
         description: "Jump to next/prev group",
     },
+    HelpEntry {
+        key: "Alt+1..9",
+        description: "Quick launch bound instance",
+    },
 ];
 
 const INSTANCE_KEYS: &[HelpEntry] = &[
@@ -62,22 +71,94 @@ const INSTANCE_KEYS: &[HelpEntry] = &[
         key: "L",
         description: "Instance logs",
     },
+    HelpEntry {
+        key: "P",
+        description: "Launch with a preset",
+    },
     HelpEntry {
         key: "gl",
         description: "Launcher logs",
     },
+    HelpEntry {
+        key: "gd",
+        description: "prism-tui's own log",
+    },
     HelpEntry {
         key: "Tab",
         description: "Collapse/expand group",
     },
+    HelpEntry {
+        key: "Group: Enter/l",
+        description: "Collapse/expand, launch first",
+    },
     HelpEntry {
         key: "x",
         description: "Kill running instance",
     },
+    HelpEntry {
+        key: "U",
+        description: "Check modpack updates",
+    },
+    HelpEntry {
+        key: "y",
+        description: "Copy launch command",
+    },
+    HelpEntry {
+        key: "A",
+        description: "Set instance alias",
+    },
+    HelpEntry {
+        key: "Space",
+        description: "Mark instance (Esc unmarks all)",
+    },
+    HelpEntry {
+        key: "=",
+        description: "Copy memory/Java settings to marked",
+    },
+    HelpEntry {
+        key: "T",
+        description: "Save instance as a template",
+    },
+    HelpEntry {
+        key: "I",
+        description: "New instance from a template, a version or a modpack",
+    },
+    HelpEntry {
+        key: "m",
+        description: "Turn mods on/off",
+    },
+    HelpEntry {
+        key: "v",
+        description: "Browse screenshots",
+    },
+    HelpEntry {
+        key: "p",
+        description: "Manage resource packs",
+    },
+    HelpEntry {
+        key: "z",
+        description: "Data directory storage overview",
+    },
+    HelpEntry {
+        key: "N",
+        description: "Launch/renice at low priority",
+    },
+    HelpEntry {
+        key: ",",
+        description: "Launcher settings",
+    },
+    HelpEntry {
+        key: "c",
+        description: "Switch to next account",
+    },
     HelpEntry {
         key: "/",
         description: "Start search",
     },
+    HelpEntry {
+        key: "n/r/D",
+        description: "No instances: open launcher/reload/data dir",
+    },
 ];
 
 const SERVER_KEYS: &[HelpEntry] = &[
@@ -97,6 +178,122 @@ const SERVER_KEYS: &[HelpEntry] = &[
         key: "J",
         description: "Set join-on-launch",
     },
+    HelpEntry {
+        key: "Space",
+        description: "Turn join-on-launch on/off",
+    },
+    HelpEntry {
+        key: "F",
+        description: "Fix join-on-launch conflict",
+    },
+    HelpEntry {
+        key: "w",
+        description: "Open website linked in name",
+    },
+    HelpEntry {
+        key: "y",
+        description: "Copy launch command for server",
+    },
+    HelpEntry {
+        key: "p",
+        description: "Cycle resource pack prompt",
+    },
+    HelpEntry {
+        key: "H",
+        description: "Show/hide hidden servers",
+    },
+    HelpEntry {
+        key: "b",
+        description: "Check for duplicates/blocklist",
+    },
+    HelpEntry {
+        key: "P",
+        description: "Ping for version and players",
+    },
+    HelpEntry {
+        key: "E / I",
+        description: "Export/import servers.dat",
+    },
+    HelpEntry {
+        key: "n / Tab",
+        description: "Join LAN world / next LAN world",
+    },
+];
+
+const MOD_KEYS: &[HelpEntry] = &[
+    HelpEntry {
+        key: "Space",
+        description: "Mark mod (Esc unmarks all)",
+    },
+    HelpEntry {
+        key: "e/Enter",
+        description: "Enable/disable marked or selected mods",
+    },
+    HelpEntry {
+        key: "o",
+        description: "Open mods folder",
+    },
+    HelpEntry {
+        key: "a",
+        description: "Add a mod from Modrinth or CurseForge",
+    },
+    HelpEntry {
+        key: "u / U",
+        description: "Check mods for updates / update all",
+    },
+    HelpEntry {
+        key: "w",
+        description: "Open the mod's Modrinth page",
+    },
+];
+
+const RESOURCE_PACK_KEYS: &[HelpEntry] = &[
+    HelpEntry {
+        key: "e/Enter",
+        description: "Turn the resource pack on/off",
+    },
+    HelpEntry {
+        key: "d",
+        description: "Delete resource pack",
+    },
+    HelpEntry {
+        key: "o",
+        description: "Open resource packs folder",
+    },
+];
+
+const WORLD_KEYS: &[HelpEntry] = &[
+    HelpEntry {
+        key: "Tab",
+        description: "Switch between worlds and backups",
+    },
+    HelpEntry {
+        key: "b",
+        description: "Back up the selected world",
+    },
+    HelpEntry {
+        key: "r",
+        description: "Restore the selected backup",
+    },
+    HelpEntry {
+        key: "o",
+        description: "Open saves folder",
+    },
+];
+
+const SCREENSHOT_KEYS: &[HelpEntry] = &[
+    HelpEntry {
+        key: "o/Enter",
+        description: "Open in the image viewer",
+    },
+    HelpEntry {
+        key: "d",
+        description: "Delete screenshot",
+    },
+    HelpEntry {
+        key: "O",
+        description: "Open screenshots folder",
+    },
 ];
 
 const LOG_KEYS: &[HelpEntry] = &[
@@ -120,6 +317,50 @@ const LOG_KEYS: &[HelpEntry] = &[
         key: "0",
         description: "Show all levels",
     },
+    HelpEntry {
+        key: "f",
+        description: "Show/hide level filter panel",
+    },
+    HelpEntry {
+        key: "s",
+        description: "Switch log source",
+    },
+    HelpEntry {
+        key: "i",
+        description: "Show another instance's logs",
+    },
+    HelpEntry {
+        key: "#",
+        description: "Toggle line numbers",
+    },
+    HelpEntry {
+        key: "w",
+        description: "Wrap long lines",
+    },
+    HelpEntry {
+        key: "F",
+        description: "Follow the file as it grows",
+    },
+    HelpEntry {
+        key: "C",
+        description: "Clean up old logs",
+    },
+    HelpEntry {
+        key: "D",
+        description: "Disable suspected mod, relaunch",
+    },
+    HelpEntry {
+        key: "[/]",
+        description: "Prev/next error",
+    },
+    HelpEntry {
+        key: "{/}",
+        description: "Prev/next warning",
+    },
+    HelpEntry {
+        key: "</>",
+        description: "Narrow/widen file list",
+    },
     HelpEntry {
         key: "e",
         description: "Open in editor",
@@ -135,6 +376,22 @@ const GLOBAL_KEYS: &[HelpEntry] = &[
         key: "?",
         description: "Show/hide this help",
     },
+    HelpEntry {
+        key: "Ctrl+P",
+        description: "Search everything",
+    },
+    HelpEntry {
+        key: "Ctrl+R",
+        description: "Reload PrismLauncher's files",
+    },
+    HelpEntry {
+        key: "Alt+D",
+        description: "Toggle dry run",
+    },
+    HelpEntry {
+        key: "Enter",
+        description: "Show the whole error",
+    },
     HelpEntry {
         key: "q",
         description: "Quit",
@@ -154,6 +411,22 @@ const HELP_SECTIONS: &[HelpSection] = &[
         title: "Server List",
         entries: SERVER_KEYS,
     },
+    HelpSection {
+        title: "Mods",
+        entries: MOD_KEYS,
+    },
+    HelpSection {
+        title: "Resource Packs",
+        entries: RESOURCE_PACK_KEYS,
+    },
+    HelpSection {
+        title: "Worlds",
+        entries: WORLD_KEYS,
+    },
+    HelpSection {
+        title: "Screenshots",
+        entries: SCREENSHOT_KEYS,
+    },
     HelpSection {
         title: "Log Viewer",
         entries: LOG_KEYS,
@@ -164,6 +437,18 @@ const HELP_SECTIONS: &[HelpSection] = &[
     },
 ];
 
+/// The keybindings as plain lines, for the screen reader mode
+pub fn text_lines() -> Vec<String> {
+    let mut lines = Vec::new();
+    for section in HELP_SECTIONS {
+        lines.push(format!("{}:", tr(section.title)));
+        for entry in section.entries {
+            lines.push(format!("  {}: {}", entry.key, tr(entry.description)));
+        }
+    }
+    lines
+}
+
 pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
     let help_width = 55.min(area.width.saturating_sub(4));
     let help_height = 40.min(area.height.saturating_sub(4));
@@ -178,16 +463,27 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
 
     let mut lines: Vec<Line> = Vec::new();
     lines.push(Line::from(Span::styled(
-        " Keybindings",
-        Style::default().fg(ui::PRIMARY).bold(),
+        format!(" {}", tr("Keybindings")),
+        Style::default().fg(ui::primary()).bold(),
     )));
+    lines.push(Line::from(vec![
+        Span::styled(
+            format!(" {} ", tr("Press")),
+            Style::default().fg(ui::muted()),
+        ),
+        Span::styled("t", Style::default().fg(ui::active())),
+        Span::styled(
+            format!(" {}", tr("for a quick tour")),
+            Style::default().fg(ui::muted()),
+        ),
+    ]));
     lines.push(Line::from(""));
 
     for section in HELP_SECTIONS {
         lines.push(Line::from(Span::styled(
-            format!(" {}", section.title),
+            format!(" {}", tr(section.title)),
             Style::default()
-                .fg(ui::HIGHLIGHT)
+                .fg(ui::highlight())
                 .add_modifier(Modifier::BOLD),
         )));
 
@@ -195,9 +491,9 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
             lines.push(Line::from(vec![
                 Span::styled(
                     format!("  {:<16}", entry.key),
-                    Style::default().fg(ui::ACTIVE),
+                    Style::default().fg(ui::active()),
                 ),
-                Span::styled(entry.description, Style::default().fg(ui::TEXT)),
+                Span::styled(tr(entry.description), Style::default().fg(ui::text())),
             ]));
         }
         lines.push(Line::from(""));
@@ -218,12 +514,14 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
 
     let title = if total_lines > inner_height {
         format!(
-            "Help (j/k to scroll, {}/{})",
+            "{} ({}, {}/{})",
+            tr("Help"),
+            tr("j/k to scroll"),
             scroll_offset + 1,
             total_lines
         )
     } else {
-        "Help".to_string()
+        tr("Help").to_string()
     };
 
     let help = Paragraph::new(visible_lines)
@@ -231,7 +529,7 @@ pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(title)
-                .border_style(Style::default().fg(ui::HELP_BORDER)),
+                .border_style(Style::default().fg(ui::help_border())),
         )
         .wrap(Wrap { trim: false });
 
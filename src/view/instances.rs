@@ -1,8 +1,11 @@
-use crate::app::{App, ClickAction, InputMode, VisualRow};
+use crate::app::{App, ClickAction, InputMode, ScrollTarget, VisualRow};
+use crate::data::Instance;
+use crate::data::format;
 use crate::message::Message;
 use crate::theme::ui;
 use crate::view::{
-    SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar, render_scrollbar, truncate,
+    SELECTED_PREFIX, UNSELECTED_PREFIX, empty_state_lines, render_footer_bar, render_scrollbar,
+    truncate,
 };
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table};
@@ -35,24 +38,34 @@ fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
         if app.sort_ascending { "▲" } else { "▼" }
     );
 
+    // Clicking the account segment switches to the next account
+    let title = "Prism-TUI";
+    let account_region = Rect {
+        x: area.x + 1 + title.len() as u16 + 1,
+        y: area.y,
+        width: account_text.chars().count() as u16,
+        height: area.height,
+    };
+    app.register_click(account_region, ClickAction::CycleAccount);
+
     let mut spans = vec![
-        Span::styled("Prism-TUI", Style::default().fg(ui::PRIMARY).bold()),
+        Span::styled(title, Style::default().fg(ui::primary()).bold()),
         Span::raw(" "),
-        Span::styled(account_text, Style::default().fg(ui::ACTIVE)),
+        Span::styled(account_text, Style::default().fg(ui::active())),
         Span::raw(" "),
-        Span::styled(sort_text, Style::default().fg(ui::MUTED)),
+        Span::styled(sort_text, Style::default().fg(ui::muted())),
     ];
 
     // Show search query if active
     if !app.search_query.is_empty() || app.input_mode == InputMode::Search {
         spans.push(Span::raw("  "));
-        spans.push(Span::styled("/", Style::default().fg(ui::HIGHLIGHT)));
+        spans.push(Span::styled("/", Style::default().fg(ui::highlight())));
         spans.push(Span::styled(
             &app.input_buffer,
-            Style::default().fg(ui::HIGHLIGHT),
+            Style::default().fg(ui::highlight()),
         ));
         if app.input_mode == InputMode::Search {
-            spans.push(Span::styled("_", Style::default().fg(ui::HIGHLIGHT)));
+            spans.push(Span::styled("_", Style::default().fg(ui::highlight())));
         }
     }
 
@@ -63,9 +76,18 @@ fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
 
 fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
     let width = area.width;
-    let inner_height = area.height.saturating_sub(2) as usize;
+
+    let show_loader_version = app.app_config.show_loader_version;
+    let loader_width: u16 = if show_loader_version { 18 } else { 10 };
 
     let visual = app.visual_rows();
+    // Name the columns once there are enough of them to mix up
+    let header_height: u16 = if width >= 80 && !visual.is_empty() {
+        1
+    } else {
+        0
+    };
+    let inner_height = area.height.saturating_sub(2 + header_height) as usize;
     let mut rows: Vec<Row> = Vec::new();
     let mut selected_row: Option<usize> = None;
     let selected_group_key = app.selected_group_key();
@@ -86,13 +108,18 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
                 let is_selected_group = selected_group_key.as_deref() == Some(group_name);
                 let prefix = if is_selected_group { ">" } else { " " };
                 let header_text = format!("{} {} {} ({})", prefix, indicator, group_name, count);
-                let style = if is_selected_group {
+                let style = if is_selected_group && app.group_header_selected {
+                    selected_row = Some(row_idx);
                     Style::default()
-                        .fg(ui::PRIMARY)
+                        .fg(ui::primary())
+                        .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+                } else if is_selected_group {
+                    Style::default()
+                        .fg(ui::primary())
                         .add_modifier(Modifier::BOLD)
                 } else {
                     Style::default()
-                        .fg(ui::HIGHLIGHT)
+                        .fg(ui::highlight())
                         .add_modifier(Modifier::BOLD)
                 };
                 rows.push(Row::new(vec![Cell::from(Span::styled(header_text, style))]).height(1));
@@ -104,7 +131,12 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
                 };
 
                 let is_running = app.is_instance_running(&instance.id);
-                let is_selected = *visual_idx == app.selected_instance_index;
+                let low_priority = app
+                    .running_instances
+                    .get(&instance.id)
+                    .is_some_and(|r| r.low_priority);
+                let is_selected =
+                    *visual_idx == app.selected_instance_index && !app.group_header_selected;
                 let prefix = if is_selected {
                     SELECTED_PREFIX
                 } else {
@@ -117,14 +149,14 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
 
                 let style = if is_selected {
                     Style::default()
-                        .fg(ui::PRIMARY)
+                        .fg(ui::primary())
                         .add_modifier(Modifier::BOLD)
                 } else {
                     Style::default()
                 };
 
-                let muted = Style::default().fg(ui::MUTED);
-                let active_style = Style::default().fg(ui::ACTIVE);
+                let muted = Style::default().fg(ui::muted());
+                let active_style = Style::default().fg(ui::active());
 
                 let join_indicator = instance
                     .server_join
@@ -134,26 +166,64 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
                     .unwrap_or("");
 
                 let running_prefix = if is_running { "● " } else { "" };
+                let has_update = app.pack_updates.contains_key(&instance.id);
+                let update_badge = if has_update { " ↑" } else { "" };
+                // Jars in mods/ with no loader to run them
+                let loader_badge = if instance.unloaded_mods > 0 { " !" } else { "" };
+                let alias_suffix = app
+                    .app_config
+                    .alias_for(&instance.id)
+                    .map(|a| format!(" ({})", a))
+                    .unwrap_or_default();
+
+                let marked = app.marked_instances.contains(&instance.id);
+                let mark_prefix = if marked { "+ " } else { "" };
 
                 let name_cell = |max_len: usize| -> Cell<'_> {
+                    let mut spans = vec![Span::styled(prefix, style)];
+                    if marked {
+                        spans.push(Span::styled(
+                            mark_prefix,
+                            Style::default().fg(ui::highlight()).bold(),
+                        ));
+                    }
                     if is_running {
-                        Cell::from(Line::from(vec![
-                            Span::styled(prefix, style),
-                            Span::styled("● ", Style::default().fg(ui::ACTIVE)),
-                            Span::styled(
-                                truncate(
-                                    &instance.name,
-                                    max_len.saturating_sub(running_prefix.len()),
-                                ),
-                                style,
-                            ),
-                        ]))
-                    } else {
-                        Cell::from(Span::styled(
-                            format!("{}{}", prefix, truncate(&instance.name, max_len)),
-                            style,
-                        ))
+                        // Low priority games get a dimmer dot
+                        let dot_color = if low_priority {
+                            ui::highlight()
+                        } else {
+                            ui::active()
+                        };
+                        spans.push(Span::styled("● ", Style::default().fg(dot_color)));
+                    }
+                    spans.push(Span::styled(
+                        truncate(
+                            &instance.name,
+                            max_len
+                                .saturating_sub(running_prefix.chars().count())
+                                .saturating_sub(mark_prefix.len())
+                                .saturating_sub(update_badge.chars().count())
+                                .saturating_sub(loader_badge.chars().count())
+                                .saturating_sub(alias_suffix.chars().count()),
+                        ),
+                        style,
+                    ));
+                    if !alias_suffix.is_empty() {
+                        spans.push(Span::styled(alias_suffix.clone(), muted));
                     }
+                    if has_update {
+                        spans.push(Span::styled(
+                            update_badge,
+                            Style::default().fg(ui::highlight()).bold(),
+                        ));
+                    }
+                    if !loader_badge.is_empty() {
+                        spans.push(Span::styled(
+                            loader_badge,
+                            Style::default().fg(ui::warning()).bold(),
+                        ));
+                    }
+                    Cell::from(Line::from(spans))
                 };
 
                 let cells = if width < 60 {
@@ -173,6 +243,7 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
                             truncate(&instance.minecraft_version, 12),
                             muted,
                         )),
+                        Cell::from(Span::styled(instance.last_played(), muted)),
                         Cell::from(Span::styled(instance.formatted_playtime(), muted)),
                     ]
                 } else {
@@ -183,9 +254,20 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
                             muted,
                         )),
                         Cell::from(Span::styled(
-                            instance.mod_loader.as_deref().unwrap_or("-"),
+                            if show_loader_version {
+                                instance
+                                    .mod_loader_display()
+                                    .map(|l| truncate(&l, loader_width as usize))
+                                    .unwrap_or_else(|| "-".to_string())
+                            } else {
+                                instance
+                                    .mod_loader
+                                    .clone()
+                                    .unwrap_or_else(|| "-".to_string())
+                            },
                             muted,
                         )),
+                        Cell::from(Span::styled(instance.last_played(), muted)),
                         Cell::from(Span::styled(instance.formatted_playtime(), muted)),
                         Cell::from(Span::styled(truncate(join_indicator, 20), active_style)),
                     ]
@@ -198,16 +280,40 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
 
     let total_visible = rows.len();
 
-    if rows.is_empty() {
-        let msg = if !app.search_query.is_empty() {
-            "No matches. Press Esc to clear search."
-        } else {
-            "No instances found. Add instances in PrismLauncher."
-        };
+    if rows.is_empty() && app.instances_loading {
         rows.push(
             Row::new(vec![Cell::from(Span::styled(
-                format!("  {}", msg),
-                Style::default().fg(ui::MUTED),
+                "  Loading instances...",
+                Style::default().fg(ui::muted()),
+            ))])
+            .height(1),
+        );
+    } else if rows.is_empty() && app.instances.is_empty() {
+        let message = format!("No instances found in {}", app.data_dir.display());
+        let lines = empty_state_lines(
+            app,
+            area,
+            message,
+            &[
+                (
+                    "n",
+                    "Create an instance in PrismLauncher",
+                    Message::OpenLauncher,
+                ),
+                ("r", "Reload once it's created", Message::ReloadData),
+                ("D", "Use a different data directory", Message::EditDataDir),
+            ],
+        );
+        rows.extend(
+            lines
+                .into_iter()
+                .map(|line| Row::new(vec![Cell::from(line)])),
+        );
+    } else if rows.is_empty() {
+        rows.push(
+            Row::new(vec![Cell::from(Span::styled(
+                "  No matches. Press Esc to clear search.",
+                Style::default().fg(ui::muted()),
             ))])
             .height(1),
         );
@@ -219,6 +325,12 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
             app.filtered_instance_count(),
             app.total_instance_count()
         )
+    } else if app.instances_loading {
+        format!(
+            "Instances (loading {}/{})",
+            app.instances.len(),
+            app.instances_expected
+        )
     } else {
         "Instances".to_string()
     };
@@ -232,26 +344,67 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
         vec![
             Constraint::Min(20),
             Constraint::Length(14),
+            Constraint::Length(9),
             Constraint::Length(12),
         ]
     } else {
         vec![
             Constraint::Min(20),
             Constraint::Length(14),
-            Constraint::Length(10),
+            Constraint::Length(loader_width),
+            Constraint::Length(9),
             Constraint::Length(12),
             Constraint::Length(22),
         ]
     };
 
-    let table = Table::new(rows, widths).block(Block::default().borders(Borders::ALL).title(title));
+    let mut block = Block::default().borders(Borders::ALL).title(title);
+    if !app.instances.is_empty() {
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                format!(" {} ", summary(app)),
+                Style::default().fg(ui::muted()),
+            ))
+            .right_aligned(),
+        );
+    }
+    // Keep the selection on screen, scrolling as little as needed
+    let scroll = selected_row.map_or(0, |sel| {
+        app.instance_scroll
+            .min(total_visible.saturating_sub(inner_height))
+            .min(sel)
+            .max((sel + 1).saturating_sub(inner_height))
+    });
+    app.instance_scroll = scroll;
+    app.instance_page_rows = inner_height;
+    let mut table = Table::new(rows.into_iter().skip(scroll), widths).block(block);
+    if header_height > 0 {
+        let labels: &[&str] = if width < 100 {
+            &["  Name", "Version", "Played", "Playtime"]
+        } else {
+            &[
+                "  Name",
+                "Version",
+                "Loader",
+                "Played",
+                "Playtime",
+                "Auto-join",
+            ]
+        };
+        let header_style = Style::default().fg(ui::muted()).bold();
+        table = table.header(Row::new(
+            labels
+                .iter()
+                .map(|label| Cell::from(Span::styled(*label, header_style))),
+        ));
+    }
 
     frame.render_widget(table, area);
 
     // Register click regions for visible rows
-    // Content starts at area.y + 1 (top border)
-    for (row_idx, vrow) in visual.iter().enumerate() {
-        let row_y = area.y + 1 + row_idx as u16;
+    // Content starts below the top border and the column names
+    for (row_idx, vrow) in visual.iter().enumerate().skip(scroll) {
+        let row_y = area.y + 1 + header_height + (row_idx - scroll) as u16;
         if row_y >= area.y + area.height.saturating_sub(1) {
             break; // past visible area (bottom border)
         }
@@ -272,13 +425,20 @@ fn render_instance_table(app: &mut App, frame: &mut Frame, area: Rect) {
     }
 
     // Scrollbar
-    if let Some(sel) = selected_row {
+    if selected_row.is_some() {
+        let below_header = Rect {
+            y: area.y + header_height,
+            height: area.height.saturating_sub(header_height),
+            ..area
+        };
         render_scrollbar(
+            app,
             frame,
-            area,
+            below_header,
             total_visible,
             inner_height,
-            sel.saturating_sub(inner_height / 2),
+            scroll,
+            ScrollTarget::Instances,
         );
     }
 }
@@ -303,10 +463,16 @@ fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
         ];
         if selected_running {
             keys.push(("x", "Kill", Some(Message::KillInstance)));
+            keys.push(("N", "Low Prio", Some(Message::LowPriorityLaunch)));
+        }
+        if !app.marked_instances.is_empty() {
+            keys.push(("=", "Copy Settings", Some(Message::SyncInstanceSettings)));
+            keys.push(("Esc", "Unmark", Some(Message::ClearInstanceMarks)));
         }
         keys.extend_from_slice(&[
             ("/", "Search", Some(Message::StartSearch)),
             ("S", "Sort", Some(Message::CycleSortMode)),
+            ("U", "Updates", Some(Message::CheckPackUpdates)),
             ("s", "Servers", Some(Message::OpenServerScreen)),
             ("a", "Account", Some(Message::OpenAccountScreen)),
             ("i", "Details", Some(Message::OpenInstanceDetails)),
@@ -317,3 +483,37 @@ fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
         render_footer_bar(app, frame, area, &keys);
     }
 }
+
+/// Counts for the instances shown, e.g. "12 instances, 1 running, 340h 5m played"
+fn summary(app: &App) -> String {
+    let shown: Vec<&Instance> = app
+        .filtered_instance_indices
+        .iter()
+        .filter_map(|&idx| app.instances.get(idx))
+        .collect();
+    let running = shown
+        .iter()
+        .filter(|i| app.running_instances.contains_key(&i.id))
+        .count();
+    let playtime: u64 = shown.iter().map(|i| i.total_time_played).sum();
+
+    let noun = if app.instances.len() == 1 {
+        "instance"
+    } else {
+        "instances"
+    };
+    let mut parts = vec![if app.search_query.is_empty() {
+        format!("{} {}", shown.len(), noun)
+    } else {
+        format!("{} of {} {}", shown.len(), app.instances.len(), noun)
+    }];
+    parts.push(format!("{} running", running));
+    if !app.marked_instances.is_empty() {
+        parts.push(format!("{} marked", app.marked_instances.len()));
+    }
+    parts.push(format!("{} played", format::playtime_full(playtime)));
+    if !app.search_query.is_empty() {
+        parts.push(format!("filter '{}'", app.search_query));
+    }
+    parts.join(", ")
+}
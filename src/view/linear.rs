@@ -0,0 +1,270 @@
+//! Linear output for screen readers: instead of redrawing the screen, a line
+//! is printed whenever the focused item, an open prompt or a message changes.
+
+use crate::app::{App, InputMode, Screen};
+use crate::data::GLOBAL_SETTINGS;
+use crate::i18n::tr;
+use crate::view::{create_instance, dialog_text, help};
+
+/// Remembers what was last announced so only changes are spoken
+#[derive(Default)]
+pub struct Announcer {
+    /// Focus, prompt and message, in that order
+    last: [Option<String>; 3],
+}
+
+impl Announcer {
+    /// Lines describing whatever changed since the previous call
+    pub fn changes(&mut self, app: &App) -> Vec<String> {
+        let current = [Some(focus(app)), prompt(app), message(app)];
+        let mut lines = Vec::new();
+        for (last, now) in self.last.iter_mut().zip(current) {
+            if *last != now {
+                lines.extend(now.iter().flat_map(|text| text.lines().map(str::to_string)));
+                *last = now;
+            }
+        }
+        lines
+    }
+}
+
+/// Position within a list, e.g. "3 of 12"
+fn position(index: usize, count: usize) -> String {
+    format!("{} of {}", index + 1, count)
+}
+
+fn focus(app: &App) -> String {
+    if app.input_mode == InputMode::GlobalSearch {
+        let result = app
+            .search_results
+            .get(app.selected_search_result)
+            .and_then(|&i| app.search_index.get(i));
+        return match result {
+            Some(item) => format!(
+                "{} '{}': {}, {}, {}",
+                tr("Search"),
+                app.input_buffer,
+                item.label,
+                tr(item.kind.label()),
+                position(app.selected_search_result, app.search_results.len())
+            ),
+            None => format!("{} '{}': no results", tr("Search"), app.input_buffer),
+        };
+    }
+
+    match app.screen {
+        Screen::Instances => {
+            let filter = if app.search_query.is_empty() {
+                String::new()
+            } else {
+                format!(" matching '{}'", app.search_query)
+            };
+            if app.group_header_selected
+                && let Some(group) = app.grouped_instances.get(app.selected_group_index)
+            {
+                let state = if app.selected_group_collapsed() {
+                    "collapsed"
+                } else {
+                    "expanded"
+                };
+                return format!(
+                    "{}{}: group {}, {}, {} instances",
+                    tr("Instances"),
+                    filter,
+                    group.key(),
+                    state,
+                    group.indices.len()
+                );
+            }
+            match app.selected_instance() {
+                Some(instance) => {
+                    let mut parts = vec![instance.name.clone(), instance.minecraft_version.clone()];
+                    parts.extend(instance.mod_loader_display());
+                    if app.running_instances.contains_key(&instance.id) {
+                        parts.push("running".to_string());
+                    }
+                    parts.push(position(
+                        app.selected_instance_index,
+                        app.visible_instance_count(),
+                    ));
+                    format!("{}{}: {}", tr("Instances"), filter, parts.join(", "))
+                }
+                None if app.instances.is_empty() => {
+                    format!("{}: none found, press r to reload", tr("Instances"))
+                }
+                None => format!("{}{}: nothing selected", tr("Instances"), filter),
+            }
+        }
+        Screen::Accounts => match app.selected_account() {
+            Some(account) => format!(
+                "{}: {}{}, {}",
+                tr("Accounts"),
+                account.username,
+                if account.is_active { ", active" } else { "" },
+                position(app.selected_account_index, app.accounts.len())
+            ),
+            None => format!("{}: none found", tr("Accounts")),
+        },
+        Screen::Servers => {
+            let visible = app.visible_server_indices();
+            let instance = app.selected_instance().map_or("", |i| i.name.as_str());
+            match app.selected_server() {
+                Some(server) => format!(
+                    "{} ({}): {}, {}, {}",
+                    tr("Servers"),
+                    instance,
+                    server.name,
+                    server.ip,
+                    position(
+                        visible
+                            .iter()
+                            .position(|&i| i == app.selected_server_index)
+                            .unwrap_or_default(),
+                        visible.len()
+                    )
+                ),
+                None => format!("{} ({}): none, press a to add one", tr("Servers"), instance),
+            }
+        }
+        Screen::Logs => {
+            let Some(entry) = app.log_entries.get(app.selected_log_index) else {
+                return format!("{}: no log files", tr("Logs"));
+            };
+            let lines = app.filtered_log_content();
+            match lines.get(app.log_scroll_offset) {
+                Some((number, text)) => format!(
+                    "{}: {}, line {}: {}",
+                    tr("Logs"),
+                    entry.name,
+                    number + 1,
+                    text
+                ),
+                None => format!(
+                    "{}: {}, {}",
+                    tr("Logs"),
+                    entry.name,
+                    position(app.selected_log_index, app.log_entries.len())
+                ),
+            }
+        }
+        Screen::InstanceDetails => match app.selected_instance() {
+            Some(instance) => format!(
+                "{}: {}, Minecraft {}, played {}, last launched {}",
+                tr("Instance details"),
+                instance.name,
+                instance.minecraft_version,
+                instance.formatted_playtime(),
+                instance.formatted_last_launch()
+            ),
+            None => tr("Instance details").to_string(),
+        },
+        Screen::Settings => match GLOBAL_SETTINGS.get(app.selected_setting_index) {
+            Some(def) => format!(
+                "{}: {}: {}, {}",
+                tr("Launcher settings"),
+                def.label,
+                app.prism_settings.get(def.key).unwrap_or("default"),
+                position(app.selected_setting_index, GLOBAL_SETTINGS.len())
+            ),
+            None => tr("Launcher settings").to_string(),
+        },
+        Screen::Mods => match app.mods.get(app.selected_mod_index) {
+            Some(file) => format!(
+                "{}: {}, {}{}, {}",
+                tr("Mods"),
+                file.name,
+                if file.enabled {
+                    tr("enabled")
+                } else {
+                    tr("disabled")
+                },
+                if app.marked_mods.contains(&file.name) {
+                    format!(", {}", tr("marked"))
+                } else {
+                    String::new()
+                },
+                position(app.selected_mod_index, app.mods.len())
+            ),
+            None => tr("Mods").to_string(),
+        },
+        Screen::Screenshots => match app.screenshots.get(app.selected_screenshot_index) {
+            Some(screenshot) => format!(
+                "{}: {}, {}, {}",
+                tr("Screenshots"),
+                screenshot.name,
+                screenshot.summary(),
+                position(app.selected_screenshot_index, app.screenshots.len())
+            ),
+            None => tr("Screenshots").to_string(),
+        },
+        Screen::ResourcePacks => match app.resource_packs.get(app.selected_resource_pack_index) {
+            Some(pack) => format!(
+                "{}: {}, {}{}, {}",
+                tr("Resource packs"),
+                pack.name,
+                if pack.enabled {
+                    tr("enabled")
+                } else {
+                    tr("disabled")
+                },
+                pack.description
+                    .as_ref()
+                    .map(|d| format!(", {}", d))
+                    .unwrap_or_default(),
+                position(app.selected_resource_pack_index, app.resource_packs.len())
+            ),
+            None => tr("Resource packs").to_string(),
+        },
+        Screen::Worlds if app.backups_focused => match app.backups.get(app.selected_backup_index) {
+            Some(backup) => format!(
+                "{}: {}, {}",
+                tr("Backups"),
+                backup.summary(),
+                position(app.selected_backup_index, app.backups.len())
+            ),
+            None => tr("Backups").to_string(),
+        },
+        Screen::Worlds => match app.worlds.get(app.selected_world_index) {
+            Some(world) => format!(
+                "{}: {}, {}",
+                tr("Worlds"),
+                world,
+                position(app.selected_world_index, app.worlds.len())
+            ),
+            None => tr("Worlds").to_string(),
+        },
+        Screen::CreateInstance => match create_instance::step_rows(app) {
+            Ok(rows) => match rows.get(app.draft.selected) {
+                Some((label, note)) => format!(
+                    "{}: {}{}, {}",
+                    tr("New instance"),
+                    label,
+                    note.map(|n| format!(" ({})", n)).unwrap_or_default(),
+                    position(app.draft.selected, rows.len())
+                ),
+                None => tr("New instance").to_string(),
+            },
+            Err(message) => format!("{}: {}", tr("New instance"), message),
+        },
+        Screen::Help => {
+            let mut lines = vec![format!("{}, Esc to close", tr("Help"))];
+            lines.extend(help::text_lines());
+            lines.join("\n")
+        }
+    }
+}
+
+fn prompt(app: &App) -> Option<String> {
+    let (title, content) = dialog_text(app)?;
+    // Drop the drawn cursor
+    let content = content.strip_suffix('_').unwrap_or(&content);
+    Some(format!("{}: {}", title, content))
+}
+
+fn message(app: &App) -> Option<String> {
+    match (&app.error_message, &app.status_message) {
+        (Some(error), _) => Some(format!("{}: {}", tr("Error"), error)),
+        (None, Some(status)) => Some(status.clone()),
+        (None, None) => None,
+    }
+}
@@ -1,11 +1,14 @@
-use crate::app::{App, ClickAction, InputMode, LogLevel, LogSource};
+use crate::app::{
+    App, ClickAction, InputMode, LogLevel, LogSource, ScrollTarget, detect_log_level,
+};
+use crate::data::CleanupAction;
 use crate::message::Message;
 use crate::theme::ui;
 use crate::view::{
     SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar, render_scrollbar, truncate,
 };
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
 
 pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
     let chunks = Layout::default()
@@ -31,21 +34,29 @@ fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
                 "Logs: Instance".to_string()
             }
         }
+        LogSource::CrashReports => match app.selected_instance() {
+            Some(instance) => format!("Crash reports: {}", instance.name),
+            None => "Crash reports".to_string(),
+        },
         LogSource::Launcher => "Logs: Launcher".to_string(),
+        LogSource::App => "Logs: prism-tui".to_string(),
     };
 
-    let mut spans = vec![Span::styled(title, Style::default().fg(ui::PRIMARY).bold())];
+    let mut spans = vec![Span::styled(
+        title,
+        Style::default().fg(ui::primary()).bold(),
+    )];
 
     // Show log search if active
     if !app.log_search_query.is_empty() || app.input_mode == InputMode::LogSearch {
         spans.push(Span::raw("  "));
-        spans.push(Span::styled("/", Style::default().fg(ui::HIGHLIGHT)));
+        spans.push(Span::styled("/", Style::default().fg(ui::highlight())));
         spans.push(Span::styled(
             &app.log_search_query,
-            Style::default().fg(ui::HIGHLIGHT),
+            Style::default().fg(ui::highlight()),
         ));
         if app.input_mode == InputMode::LogSearch {
-            spans.push(Span::styled("_", Style::default().fg(ui::HIGHLIGHT)));
+            spans.push(Span::styled("_", Style::default().fg(ui::highlight())));
         }
         if !app.log_search_matches.is_empty() {
             spans.push(Span::styled(
@@ -54,7 +65,7 @@ fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
                     app.log_search_current + 1,
                     app.log_search_matches.len()
                 ),
-                Style::default().fg(ui::MUTED),
+                Style::default().fg(ui::muted()),
             ));
         }
     }
@@ -62,19 +73,14 @@ fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
     // Show active log level filters
     if !app.log_level_filter.is_empty() {
         spans.push(Span::raw("  "));
-        let filter_text: Vec<&str> = [
-            LogLevel::Error,
-            LogLevel::Warn,
-            LogLevel::Info,
-            LogLevel::Debug,
-        ]
-        .iter()
-        .filter(|l| app.log_level_filter.contains(l))
-        .map(|l| l.label())
-        .collect();
+        let filter_text: Vec<&str> = LogLevel::ALL
+            .iter()
+            .filter(|l| app.log_level_filter.contains(l))
+            .map(|l| l.label())
+            .collect();
         spans.push(Span::styled(
             format!("[{}]", filter_text.join(",")),
-            Style::default().fg(ui::WARNING),
+            Style::default().fg(ui::warning()),
         ));
     }
 
@@ -84,14 +90,126 @@ fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
 }
 
 fn render_content(app: &mut App, frame: &mut Frame, area: Rect) {
-    // Split into file list (30%) and content preview (70%)
+    // Split into file list and content preview, resizable with </>
+    let list_percent = app.log_list_percent;
     let chunks = Layout::default()
         .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(30), Constraint::Percentage(70)])
+        .constraints([
+            Constraint::Percentage(list_percent),
+            Constraint::Percentage(100 - list_percent),
+        ])
         .split(area);
 
-    render_file_list(app, frame, chunks[0]);
-    render_log_preview(app, frame, chunks[1]);
+    if app.show_log_filter_panel {
+        let left = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Min(0),
+                Constraint::Length(LogLevel::ALL.len() as u16 + 2),
+            ])
+            .split(chunks[0]);
+        render_file_list(app, frame, left[0]);
+        render_level_filter(app, frame, left[1]);
+    } else {
+        render_file_list(app, frame, chunks[0]);
+    }
+
+    // Recognized crash causes sit above the preview
+    if app.log_hints.is_empty() {
+        render_log_preview(app, frame, chunks[1]);
+    } else {
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(app.log_hints.len() as u16 + 2),
+                Constraint::Min(0),
+            ])
+            .split(chunks[1]);
+        render_hints(app, frame, right[0]);
+        render_log_preview(app, frame, right[1]);
+    }
+}
+
+fn render_hints(app: &App, frame: &mut Frame, area: Rect) {
+    let items: Vec<ListItem> = app
+        .log_hints
+        .iter()
+        .map(|hint| {
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    format!(" [{}] ", hint.kind.label()),
+                    Style::default().fg(ui::error()),
+                ),
+                Span::raw(hint.message.clone()),
+                Span::styled(
+                    format!(" (line {})", hint.line + 1),
+                    Style::default().fg(ui::muted()),
+                ),
+            ]))
+        })
+        .collect();
+
+    let title = match app.suspect_mod.as_ref().and_then(|p| p.file_name()) {
+        Some(name) => format!("Possible causes (D: disable {})", name.to_string_lossy()),
+        None => "Possible causes".to_string(),
+    };
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+
+    frame.render_widget(list, area);
+}
+
+fn render_level_filter(app: &mut App, frame: &mut Frame, area: Rect) {
+    let items: Vec<ListItem> = LogLevel::ALL
+        .iter()
+        .enumerate()
+        .map(|(idx, level)| {
+            let checked = app.log_level_filter.contains(level);
+            ListItem::new(Line::from(vec![
+                Span::styled(
+                    if checked { " [x] " } else { " [ ] " },
+                    Style::default().fg(if checked { ui::active() } else { ui::muted() }),
+                ),
+                Span::styled(
+                    format!("{} ", idx + 1),
+                    Style::default().fg(ui::highlight()),
+                ),
+                Span::styled(level.label(), level_style(*level)),
+            ]))
+        })
+        .collect();
+
+    // With nothing checked every level is shown
+    let title = if app.log_level_filter.is_empty() {
+        "Levels (all)"
+    } else {
+        "Levels"
+    };
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+
+    frame.render_widget(list, area);
+
+    for (idx, level) in LogLevel::ALL.iter().enumerate() {
+        let row_y = area.y + 1 + idx as u16;
+        if row_y >= area.y + area.height.saturating_sub(1) {
+            break;
+        }
+        let row_rect = Rect {
+            x: area.x,
+            y: row_y,
+            width: area.width,
+            height: 1,
+        };
+        app.register_click(row_rect, ClickAction::ToggleLogLevel(*level));
+    }
+}
+
+fn level_style(level: LogLevel) -> Style {
+    match level {
+        LogLevel::Error => Style::default().fg(ui::log_error()).bold(),
+        LogLevel::Warn => Style::default().fg(ui::log_warn()),
+        LogLevel::Info => Style::default().fg(ui::log_info()),
+        LogLevel::Debug => Style::default().fg(ui::log_debug()),
+    }
 }
 
 fn render_file_list(app: &mut App, frame: &mut Frame, area: Rect) {
@@ -111,18 +229,34 @@ fn render_file_list(app: &mut App, frame: &mut Frame, area: Rect) {
 
             let style = if is_selected {
                 Style::default()
-                    .fg(ui::PRIMARY)
+                    .fg(ui::primary())
                     .add_modifier(Modifier::BOLD)
             } else {
                 Style::default()
             };
 
-            ListItem::new(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(prefix, style),
                 Span::styled(truncate(&entry.name, 20), style),
                 Span::raw(" "),
-                Span::styled(entry.formatted_size(), Style::default().fg(ui::MUTED)),
-            ]))
+                Span::styled(entry.formatted_size(), Style::default().fg(ui::muted())),
+            ];
+            // Dry-run preview while a cleanup is awaiting confirmation
+            let planned = app
+                .log_cleanup_plan
+                .iter()
+                .find(|i| i.entry.path == entry.path);
+            if let Some(item) = planned {
+                spans.push(match item.action {
+                    CleanupAction::Delete => {
+                        Span::styled(" [delete]", Style::default().fg(ui::error()))
+                    }
+                    CleanupAction::Compress => {
+                        Span::styled(" [gzip]", Style::default().fg(ui::warning()))
+                    }
+                });
+            }
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
@@ -149,16 +283,22 @@ fn render_file_list(app: &mut App, frame: &mut Frame, area: Rect) {
 
     // Scrollbar
     render_scrollbar(
+        app,
         frame,
         area,
         total_items,
         inner_height,
         app.selected_log_index.saturating_sub(inner_height / 2),
+        ScrollTarget::LogFiles,
     );
 }
 
 fn render_log_preview(app: &mut App, frame: &mut Frame, area: Rect) {
     let inner_height = area.height.saturating_sub(2) as usize;
+    if app.log_follow {
+        let total = app.filtered_log_content().len();
+        app.log_scroll_offset = total.saturating_sub(inner_height);
+    }
 
     let filtered_content = app.filtered_log_content();
     let total_lines = filtered_content.len();
@@ -166,6 +306,10 @@ fn render_log_preview(app: &mut App, frame: &mut Frame, area: Rect) {
     let search_match_set: std::collections::HashSet<usize> =
         app.log_search_matches.iter().copied().collect();
 
+    // Line numbers refer to the file, so they stay stable while filtering
+    let show_line_numbers = app.app_config.log_line_numbers;
+    let number_width = app.log_content.len().to_string().len();
+
     let visible_lines: Vec<Line> = filtered_content
         .iter()
         .skip(app.log_scroll_offset)
@@ -174,39 +318,60 @@ fn render_log_preview(app: &mut App, frame: &mut Frame, area: Rect) {
             let is_search_match = search_match_set.contains(original_idx);
 
             // Basic log level highlighting
-            let mut style = if line.contains("ERROR") || line.contains("[ERROR]") {
-                Style::default().fg(ui::ERROR)
-            } else if line.contains("WARN") || line.contains("[WARN]") {
-                Style::default().fg(ui::WARNING)
-            } else if line.contains("INFO") || line.contains("[INFO]") {
-                Style::default().fg(ui::INFO)
-            } else if line.contains("DEBUG") || line.contains("[DEBUG]") {
-                Style::default().fg(ui::DEBUG)
-            } else {
-                Style::default()
-            };
+            let level = detect_log_level(line);
+            let mut style = level.map(level_style).unwrap_or_default();
 
             if is_search_match {
-                style = style.bg(ui::HIGHLIGHT).fg(Color::Black);
+                style = style.bg(ui::highlight()).fg(Color::Black);
             }
 
-            Line::from(Span::styled(line.as_str(), style))
+            let mut spans = Vec::new();
+            if show_line_numbers {
+                spans.push(Span::styled(
+                    format!("{:>width$} ", original_idx + 1, width = number_width),
+                    Style::default().fg(ui::muted()),
+                ));
+                // A letter as well as a color, so levels don't rely on color alone
+                spans.push(Span::styled(
+                    level.map_or(" ", |l| &l.label()[..1]),
+                    level.map(level_style).unwrap_or_default(),
+                ));
+                spans.push(Span::raw(" "));
+            }
+            spans.push(Span::styled(line.as_str(), style));
+            Line::from(spans)
         })
         .collect();
 
+    let modified = app
+        .log_entries
+        .get(app.selected_log_index)
+        .and_then(|entry| entry.formatted_modified())
+        .map(|time| format!(", {}", time))
+        .unwrap_or_default();
+    let modes: String = [(app.log_wrap, ", wrapped"), (app.log_follow, ", following")]
+        .iter()
+        .filter(|(on, _)| *on)
+        .map(|(_, label)| *label)
+        .collect();
     let title = if app.log_content.is_empty() {
-        "Preview (press Enter to load)".to_string()
+        format!("Preview (press Enter to load){}", modified)
     } else {
         format!(
-            "Preview ({}-{}/{})",
+            "Preview ({}-{}/{}{}){}",
             app.log_scroll_offset + 1,
             (app.log_scroll_offset + inner_height).min(total_lines),
-            total_lines
+            total_lines,
+            modes,
+            modified
         )
     };
 
-    let preview =
+    let mut preview =
         Paragraph::new(visible_lines).block(Block::default().borders(Borders::ALL).title(title));
+    if app.log_wrap {
+        preview = preview.wrap(Wrap { trim: false });
+    }
 
     frame.render_widget(preview, area);
 
@@ -215,11 +380,13 @@ fn render_log_preview(app: &mut App, frame: &mut Frame, area: Rect) {
 
     // Scrollbar for preview
     render_scrollbar(
+        app,
         frame,
         area,
         total_lines,
         inner_height,
         app.log_scroll_offset,
+        ScrollTarget::LogPreview,
     );
 }
 
@@ -232,18 +399,29 @@ fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
         ];
         render_footer_bar(app, frame, area, keys);
     } else {
-        let keys: &[(&str, &str, Option<Message>)] = &[
+        let mut keys: Vec<(&str, &str, Option<Message>)> = vec![
             ("j/k", "Nav", None),
             ("l/Enter", "Load", Some(Message::LoadLogContent)),
             ("J/K", "Scroll", None),
             ("/", "Search", Some(Message::StartLogSearch)),
             ("n/N", "Next/Prev", None),
+            ("[/]", "Errors", None),
             ("1-4", "Filter", None),
+            ("s", "Source", Some(Message::CycleLogSource)),
+            ("i", "Instance", Some(Message::OpenLogInstancePicker)),
+            ("f", "Levels", Some(Message::ToggleLogFilterPanel)),
+            ("#", "Lines", Some(Message::ToggleLogLineNumbers)),
+            ("w", "Wrap", Some(Message::ToggleLogWrap)),
+            ("F", "Follow", Some(Message::ToggleLogFollow)),
+            ("C", "Clean", Some(Message::PlanLogCleanup)),
             ("0", "All", Some(Message::ShowAllLogLevels)),
             ("e", "Editor", Some(Message::OpenLogInEditor)),
             ("o", "Folder", Some(Message::OpenLogFolder)),
             ("h/Esc", "Back", Some(Message::Back)),
         ];
-        render_footer_bar(app, frame, area, keys);
+        if app.suspect_mod.is_some() {
+            keys.insert(0, ("D", "Disable mod", Some(Message::DisableSuspectedMod)));
+        }
+        render_footer_bar(app, frame, area, &keys);
     }
 }
@@ -1,23 +1,46 @@
 mod accounts;
+mod create_instance;
+mod dashboard;
+mod debug;
 mod details;
+mod dry_run;
+mod error_details;
 mod help;
 mod instances;
+mod linear;
 mod logs;
+mod mods;
+mod resourcepacks;
+mod screenshots;
+mod search;
 mod servers;
-
-use crate::app::{App, ClickAction, InputMode, Screen};
+mod settings;
+mod storage;
+mod tour;
+mod worlds;
+
+use crate::app::{App, ClickAction, ExternalChange, InputMode, Screen, ScrollTarget};
+use crate::data::{CleanupAction, InstanceFolder, JunkKind, format_size};
+use crate::error::error_chain;
+use crate::i18n::tr;
 use crate::message::Message;
-use crate::theme::ui;
+use crate::theme::{self, ui};
 use ratatui::prelude::*;
 use ratatui::widgets::{
-    Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Tabs,
+    Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
+    ScrollbarState, Tabs,
 };
 
+pub use dashboard::{dashboard_height, render_dashboard};
+pub use linear::Announcer;
+pub use tour::TOUR_STEP_COUNT;
+
 pub(crate) const SELECTED_PREFIX: &str = " > ";
 pub(crate) const UNSELECTED_PREFIX: &str = "   ";
 
 pub fn render(app: &mut App, frame: &mut Frame) {
     app.click_regions.clear();
+    app.image_preview = None;
     let area = frame.area();
 
     // Split into tab bar + content
@@ -35,6 +58,12 @@ pub fn render(app: &mut App, frame: &mut Frame) {
         Screen::Servers => servers::render(app, frame, content_area),
         Screen::Logs => logs::render(app, frame, content_area),
         Screen::InstanceDetails => details::render(app, frame, content_area),
+        Screen::Settings => settings::render(app, frame, content_area),
+        Screen::CreateInstance => create_instance::render(app, frame, content_area),
+        Screen::Mods => mods::render(app, frame, content_area),
+        Screen::Screenshots => screenshots::render(app, frame, content_area),
+        Screen::ResourcePacks => resourcepacks::render(app, frame, content_area),
+        Screen::Worlds => worlds::render(app, frame, content_area),
         Screen::Help => {
             instances::render(app, frame, content_area);
             help::render(app, frame, content_area);
@@ -42,24 +71,78 @@ pub fn render(app: &mut App, frame: &mut Frame) {
     }
 
     // Render input dialog overlay (but not for search or log search, which are rendered inline)
-    if app.input_mode != InputMode::Normal
+    if app.input_mode == InputMode::GlobalSearch {
+        search::render(app, frame, area);
+    } else if app.input_mode == InputMode::DryRunPreview {
+        dry_run::render(app, frame, area);
+    } else if app.input_mode == InputMode::ErrorDetails {
+        error_details::render(app, frame, area);
+    } else if app.input_mode == InputMode::StorageOverview {
+        storage::render(app, frame, area);
+    } else if app.input_mode != InputMode::Normal
         && app.input_mode != InputMode::Search
         && app.input_mode != InputMode::LogSearch
     {
         render_input_dialog(app, frame, area);
     }
 
-    // Render error message if present
+    if app.tour_step.is_some() {
+        tour::render(app, frame, area);
+    }
+
+    // Render error message if present, otherwise any status notice
     if let Some(ref error) = app.error_message {
         let error = error.clone();
-        render_error(&error, app, frame, area);
+        let hint = format!(" Enter: {} ", tr("details"));
+        render_toast(
+            tr("Error"),
+            &error,
+            Some(&hint),
+            ui::error(),
+            app,
+            frame,
+            area,
+        );
+    } else if let Some(ref status) = app.status_message {
+        let status = status.clone();
+        render_toast(tr("Info"), &status, None, ui::active(), app, frame, area);
+    }
+
+    if app.show_debug_overlay {
+        debug::render(app, frame, area);
+    }
+
+    if theme::is_plain() {
+        theme::downgrade(frame.buffer_mut());
     }
 }
 
 fn render_tab_bar(app: &mut App, frame: &mut Frame, area: Rect) {
-    let titles = vec!["Instances", "Accounts", "Servers", "Logs"];
+    // Live counts, and a dot while any game is running
+    let mut instances = vec![Span::raw(format!(
+        "{} ({})",
+        tr("Instances"),
+        app.instances.len()
+    ))];
+    if !app.running_instances.is_empty() {
+        instances.insert(0, Span::styled("● ", Style::default().fg(ui::active())));
+    }
+    let titles = vec![
+        Line::from(instances),
+        Line::from(format!("{} ({})", tr("Accounts"), app.accounts.len())),
+        Line::from(tr("Servers")),
+        Line::from(tr("Logs")),
+    ];
     let selected = match app.screen {
-        Screen::Instances | Screen::InstanceDetails | Screen::Help => 0,
+        Screen::Instances
+        | Screen::InstanceDetails
+        | Screen::Settings
+        | Screen::CreateInstance
+        | Screen::Mods
+        | Screen::Screenshots
+        | Screen::ResourcePacks
+        | Screen::Worlds
+        | Screen::Help => 0,
         Screen::Accounts => 1,
         Screen::Servers => 2,
         Screen::Logs => 3,
@@ -67,18 +150,46 @@ fn render_tab_bar(app: &mut App, frame: &mut Frame, area: Rect) {
 
     let tabs = Tabs::new(titles.clone())
         .select(selected)
-        .style(Style::default().fg(ui::MUTED))
-        .highlight_style(Style::default().fg(ui::PRIMARY).bold())
+        .style(Style::default().fg(ui::muted()))
+        .highlight_style(Style::default().fg(ui::primary()).bold())
         .divider(" | ");
 
     frame.render_widget(tabs, area);
+    let mut modes = Vec::new();
+    if app.dry_run {
+        modes.push(tr("dry run"));
+    }
+    if app.read_only {
+        modes.push(tr("read-only"));
+    }
+    let mut marker = Vec::new();
+    if !modes.is_empty() {
+        marker.push(Span::styled(
+            format!("{}  ", modes.join(", ")),
+            Style::default().fg(ui::warning()),
+        ));
+    }
+    // Free space where the worlds are, in warning colors once it runs low
+    if let Some(free) = app.data_dir_free_space {
+        let color = if app.disk_space_low() {
+            ui::warning()
+        } else {
+            ui::muted()
+        };
+        marker.push(Span::styled(
+            format!("{} {} ", format_size(free), tr("free")),
+            Style::default().fg(color),
+        ));
+    }
+    frame.render_widget(Line::from(marker).right_aligned(), area);
 
     // Register click regions for each tab
-    // Tabs widget renders: " Title0 | Title1 | Title2 | Title3 "
-    // Each title is preceded by a space and followed by divider " | " (3 chars), except the last
+    // Tabs widget renders: " Title0  |  Title1  |  Title2  |  Title3 "
+    // Each title is padded by a space on both sides, with the " | " divider
+    // between them
     let mut x = area.x + 1; // initial padding
     for (i, title) in titles.iter().enumerate() {
-        let title_width = title.len() as u16;
+        let title_width = title.width() as u16;
         let region = Rect {
             x,
             y: area.y,
@@ -88,14 +199,17 @@ fn render_tab_bar(app: &mut App, frame: &mut Frame, area: Rect) {
         app.register_click(region, ClickAction::SwitchTab(i));
         x += title_width;
         if i < titles.len() - 1 {
-            x += 3; // " | " divider
+            x += 5; // padding, " | " divider, padding
         }
     }
 }
 
 fn render_input_dialog(app: &mut App, frame: &mut Frame, area: Rect) {
+    let Some((title, content)) = dialog_text(app) else {
+        return;
+    };
     let dialog_width = 50.min(area.width.saturating_sub(4));
-    let dialog_height = 5;
+    let dialog_height = (content.lines().count() as u16 + 2).max(5);
 
     let dialog_area = centered_rect(dialog_width, dialog_height, area);
 
@@ -105,34 +219,314 @@ fn render_input_dialog(app: &mut App, frame: &mut Frame, area: Rect) {
 
     frame.render_widget(Clear, dialog_area);
 
+    let dialog = Paragraph::new(content)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .border_style(Style::default().fg(ui::dialog_border())),
+        )
+        .style(Style::default().fg(ui::text()));
+
+    frame.render_widget(dialog, dialog_area);
+}
+
+/// Title and body of the open input dialog, including what has been typed
+pub(crate) fn dialog_text(app: &App) -> Option<(&'static str, String)> {
     let (title, prompt) = match app.input_mode {
         InputMode::AddServerName => ("Add Server", "Server name:"),
         InputMode::AddServerAddress => ("Add Server", "Server address:"),
         InputMode::EditServerName => ("Edit Server", "Server name:"),
         InputMode::EditServerAddress => ("Edit Server", "Server address:"),
+        InputMode::ExportServers => ("Export Servers", "Write servers.dat to:"),
+        InputMode::ImportServers => ("Import Servers", "Add servers from:"),
         InputMode::ConfirmDelete => ("Confirm Delete", "Delete this server? (y/n)"),
-        InputMode::Normal | InputMode::Search | InputMode::LogSearch => return,
+        InputMode::ConfirmLogCleanup => ("Clean Up Logs", ""),
+        InputMode::ConfirmJunkCleanup => ("Clean Up Instance", ""),
+        InputMode::ConfirmDisableMod => ("Disable Mod", ""),
+        InputMode::ConfirmDeleteScreenshot => ("Delete Screenshot", ""),
+        InputMode::ConfirmDeleteResourcePack => ("Delete Resource Pack", ""),
+        InputMode::ConfirmExternalChange => ("Changed on Disk", ""),
+        InputMode::PickLogSource => ("Open Logs", ""),
+        InputMode::PickInstanceFolder => ("Open Folder", ""),
+        InputMode::PickLaunchPreset => ("Launch Preset", ""),
+        InputMode::ConfirmJavaLaunch => ("Java Version", ""),
+        InputMode::ConfirmLowSpaceLaunch => ("Low Disk Space", ""),
+        InputMode::ConfirmServerLaunch => ("Server Check", ""),
+        InputMode::ConfirmSyncSettings => ("Copy Settings", ""),
+        InputMode::SaveTemplate => ("Save Template", "Template name:"),
+        InputMode::SearchMods => ("Add Mod", "Search for:"),
+        InputMode::PickModHit => ("Add Mod", ""),
+        InputMode::SearchPacks => ("Install Modpack", "Search for:"),
+        InputMode::PickPackHit => ("Install Modpack", ""),
+        InputMode::NewFromTemplate | InputMode::NameNewInstance => ("New Instance", "Name:"),
+        InputMode::DryRunPreview => ("Dry run", ""),
+        InputMode::ErrorDetails => ("Error", ""),
+        InputMode::StorageOverview => ("Storage", ""),
+        InputMode::SetAlias => ("Instance Alias", "Alias (empty clears):"),
+        InputMode::SetDataDir => ("Data Directory", "PrismLauncher data folder:"),
+        InputMode::EditEnv => ("Environment", "KEY=value ... (empty clears):"),
+        InputMode::EditWrapper => ("Launch Wrapper", "Run prismlauncher through:"),
+        InputMode::EditPrismWrapper => ("Prism Wrapper", "WrapperCommand (empty clears):"),
+        InputMode::EditPrismSetting => ("Launcher Setting", "Value (empty resets):"),
+        InputMode::AddTask => ("Add Task", "Task:"),
+        InputMode::EditTask => ("Edit Task", "Task:"),
+        InputMode::Normal | InputMode::Search | InputMode::LogSearch | InputMode::GlobalSearch => {
+            return None;
+        }
     };
 
-    let content = if app.input_mode == InputMode::ConfirmDelete {
+    let (title, prompt) = (tr(title), tr(prompt));
+    let content = if app.input_mode == InputMode::ConfirmLogCleanup {
+        log_cleanup_summary(app)
+    } else if app.input_mode == InputMode::ConfirmJunkCleanup {
+        junk_cleanup_summary(app)
+    } else if app.input_mode == InputMode::ConfirmDisableMod {
+        let name = app
+            .suspect_mod
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .unwrap_or_default()
+            .to_string_lossy();
+        format!("Disable {} and relaunch? (y/n)", name)
+    } else if app.input_mode == InputMode::ConfirmDeleteScreenshot {
+        let name = app
+            .screenshots
+            .get(app.selected_screenshot_index)
+            .map(|s| s.name.as_str())
+            .unwrap_or_default();
+        format!("Delete {}? (y/n)", name)
+    } else if app.input_mode == InputMode::ConfirmDeleteResourcePack {
+        let name = app
+            .resource_packs
+            .get(app.selected_resource_pack_index)
+            .map(|p| p.name.as_str())
+            .unwrap_or_default();
+        format!("Delete {}? (y/n)", name)
+    } else if let Some(preview) = &app.dry_run_preview {
+        let mut lines = vec![tr(&preview.title).to_string()];
+        lines.extend(preview.lines.iter().cloned());
+        lines.push(tr("Enter to run, Esc to cancel").to_string());
+        lines.join("\n")
+    } else if let Some(details) = &app.error_details {
+        details.to_text()
+    } else if app.input_mode == InputMode::StorageOverview {
+        storage::storage_lines(app).join("\n")
+    } else if app.input_mode == InputMode::PickLogSource {
+        log_source_choices(app)
+    } else if app.input_mode == InputMode::PickInstanceFolder {
+        instance_folder_choices(app)
+    } else if app.input_mode == InputMode::PickLaunchPreset {
+        launch_preset_choices(app)
+    } else if app.input_mode == InputMode::ConfirmJavaLaunch {
+        java_prompt(app)
+    } else if app.input_mode == InputMode::ConfirmLowSpaceLaunch {
+        low_space_prompt(app)
+    } else if app.input_mode == InputMode::ConfirmServerLaunch {
+        server_launch_prompt(app)
+    } else if app.input_mode == InputMode::ConfirmSyncSettings {
+        sync_settings_prompt(app)
+    } else if matches!(
+        app.input_mode,
+        InputMode::PickModHit | InputMode::PickPackHit
+    ) {
+        mod_hit_choices(app)
+    } else if app.input_mode == InputMode::ConfirmExternalChange {
+        external_change_prompt(app)
+    } else if app.input_mode == InputMode::ConfirmDelete {
         prompt.to_string()
     } else {
         format!("{} {}_", prompt, app.input_buffer)
     };
+    Some((title, content))
+}
 
-    let dialog = Paragraph::new(content)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(title)
-                .border_style(Style::default().fg(ui::DIALOG_BORDER)),
-        )
-        .style(Style::default().fg(ui::TEXT));
+/// The log folders the Logs tab can open
+fn log_source_choices(app: &App) -> String {
+    let instance = match app.selected_instance() {
+        Some(instance) => format!("{} {}", tr("i: logs of"), instance.name),
+        None => tr("i: instance logs (none selected)").to_string(),
+    };
+    [
+        instance.as_str(),
+        tr("c: its crash reports"),
+        tr("l: PrismLauncher's logs"),
+        tr("d: prism-tui's own log"),
+    ]
+    .join("\n")
+}
 
-    frame.render_widget(dialog, dialog_area);
+/// Folders of the selected instance, noting the ones that aren't there yet
+fn instance_folder_choices(app: &App) -> String {
+    let Some(instance) = app.selected_instance() else {
+        return String::new();
+    };
+    let choices = [
+        (InstanceFolder::Mods, tr("m: mods")),
+        (InstanceFolder::Config, tr("c: config")),
+        (InstanceFolder::Saves, tr("s: saves")),
+        (InstanceFolder::Screenshots, tr("i: screenshots")),
+        (InstanceFolder::ResourcePacks, tr("r: resource packs")),
+    ];
+    let mut lines = vec![tr("o: the instance folder").to_string()];
+    lines.extend(choices.into_iter().map(|(folder, label)| {
+        if instance.game_folder(folder).is_dir() {
+            label.to_string()
+        } else if folder == InstanceFolder::Mods && instance.mod_loader.is_none() {
+            format!("{} {}", label, tr("(no mod loader)"))
+        } else {
+            format!("{} {}", label, tr("(none yet)"))
+        }
+    }));
+    lines.join("\n")
+}
+
+/// The selected instance's presets, numbered for picking
+fn launch_preset_choices(app: &App) -> String {
+    app.selected_launch_presets()
+        .iter()
+        .take(9)
+        .enumerate()
+        .map(|(i, (name, preset))| format!("{}: {} ({})", i + 1, name, preset.summary()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Launch warning when the instance's Java is older than its Minecraft needs
+fn java_prompt(app: &App) -> String {
+    let Some(instance) = app
+        .pending_launch
+        .as_ref()
+        .and_then(|launch| app.instance_by_id(&launch.instance_id))
+    else {
+        return String::new();
+    };
+    let Some(mismatch) = instance.java_mismatch(&app.prism_settings) else {
+        return String::new();
+    };
+    format!(
+        "Minecraft {} needs Java {} or newer,\nbut {} is set to use Java {}.\nLaunch anyway? (y/n)",
+        instance.minecraft_version, mismatch.required, instance.name, mismatch.configured
+    )
+}
+
+/// Launch warning when the data directory's drive is nearly full
+fn low_space_prompt(app: &App) -> String {
+    format!(
+        "Only {} free for PrismLauncher's data.\nA full disk can corrupt worlds.\nLaunch anyway? (y/n)",
+        format_size(app.data_dir_free_space.unwrap_or(0))
+    )
+}
+
+/// The ping warning one error layer per line, as the dialog doesn't wrap
+fn server_launch_prompt(app: &App) -> String {
+    let warning = app.server_launch_warning.as_deref().unwrap_or_default();
+    format!(
+        "{}.\nLaunch anyway? (y/n)",
+        error_chain(warning).join(":\n")
+    )
+}
+
+/// "Copy the memory, Java and JVM settings of X to 3 marked instances? (y/n)"
+fn sync_settings_prompt(app: &App) -> String {
+    let Some(source) = app.selected_instance() else {
+        return String::new();
+    };
+    let count = app
+        .marked_instances
+        .iter()
+        .filter(|id| **id != source.id)
+        .count();
+    format!(
+        "Copy the memory, Java and JVM settings of {}\nto {} marked instances? (y/n)",
+        source.name, count
+    )
+}
+
+/// Mods or modpacks found on Modrinth and CurseForge, numbered for
+/// installing
+fn mod_hit_choices(app: &App) -> String {
+    app.mod_hits
+        .iter()
+        .take(9)
+        .enumerate()
+        .map(|(i, hit)| format!("{}: {}", i + 1, hit.summary()))
+        .collect::<Vec<_>>()
+        .join("\n")
 }
 
-fn render_error(error: &str, app: &mut App, frame: &mut Frame, area: Rect) {
+/// What changed under us and the ways to go on
+fn external_change_prompt(app: &App) -> String {
+    match app.external_change {
+        Some(ExternalChange::InstanceCfg(_)) => format!(
+            "{}\n{}",
+            tr("instance.cfg was changed by another program."),
+            tr("r: reload, m: apply your change on top")
+        ),
+        _ => format!(
+            "{}\n{}",
+            tr("servers.dat was changed by another program."),
+            tr("r: reload, m: merge lists, o: overwrite")
+        ),
+    }
+}
+
+/// "Delete 3, Compress 2 logs (12.0 MB)? (y/n)" for the pending cleanup plan
+fn log_cleanup_summary(app: &App) -> String {
+    let plan = &app.log_cleanup_plan;
+    let count = |action| plan.iter().filter(|i| i.action == action).count();
+    let size: u64 = plan.iter().map(|i| i.entry.size).sum();
+
+    let mut parts = Vec::new();
+    let deleted = count(CleanupAction::Delete);
+    if deleted > 0 {
+        parts.push(format!("Delete {}", deleted));
+    }
+    let compressed = count(CleanupAction::Compress);
+    if compressed > 0 {
+        parts.push(format!("Compress {}", compressed));
+    }
+    format!("{} logs ({})? (y/n)", parts.join(", "), format_size(size))
+}
+
+/// e.g. "Delete 3 crash reports, Fabric cache, natives" and the space freed
+fn junk_cleanup_summary(app: &App) -> String {
+    let plan = &app.junk_cleanup_plan;
+    let has = |kind| plan.iter().any(|i| i.kind == kind);
+    let size: u64 = plan.iter().map(|i| i.size).sum();
+
+    let mut parts = Vec::new();
+    let reports = plan
+        .iter()
+        .filter(|i| i.kind == JunkKind::CrashReport)
+        .count();
+    match reports {
+        0 => {}
+        1 => parts.push("1 crash report".to_string()),
+        n => parts.push(format!("{} crash reports", n)),
+    }
+    if has(JunkKind::FabricCache) {
+        parts.push("Fabric cache".to_string());
+    }
+    if has(JunkKind::Natives) {
+        parts.push("natives".to_string());
+    }
+    format!(
+        "Delete {}\nto free {}? (y/n)",
+        parts.join(", "),
+        format_size(size)
+    )
+}
+
+fn render_toast(
+    title: &str,
+    error: &str,
+    hint: Option<&str>,
+    color: Color,
+    app: &mut App,
+    frame: &mut Frame,
+    area: Rect,
+) {
     let error_width = (error.len() as u16 + 4).min(area.width.saturating_sub(4));
     let error_height = 3;
 
@@ -149,14 +543,16 @@ fn render_error(error: &str, app: &mut App, frame: &mut Frame, area: Rect) {
 
     frame.render_widget(Clear, error_area);
 
+    let mut block = Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(color));
+    if let Some(hint) = hint {
+        block = block.title_bottom(Line::from(hint).right_aligned());
+    }
     let error_widget = Paragraph::new(error)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Error")
-                .border_style(Style::default().fg(ui::ERROR)),
-        )
-        .style(Style::default().fg(ui::ERROR));
+        .block(block)
+        .style(Style::default().fg(color));
 
     frame.render_widget(error_widget, error_area);
 }
@@ -178,12 +574,53 @@ pub(crate) fn truncate(s: &str, max_len: usize) -> String {
     }
 }
 
+/// Lines for a list with nothing in it: what is missing, then one clickable
+/// line per action. `area` is the bordered list the lines are drawn in.
+pub(crate) fn empty_state_lines(
+    app: &mut App,
+    area: Rect,
+    message: String,
+    actions: &[(&'static str, &'static str, Message)],
+) -> Vec<Line<'static>> {
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("  {}", message),
+            Style::default().fg(ui::muted()),
+        )),
+        Line::from(""),
+    ];
+    for (key, label, message) in actions {
+        let row = area.y + 1 + lines.len() as u16;
+        if row < area.bottom().saturating_sub(1) {
+            let rect = Rect {
+                x: area.x + 1,
+                y: row,
+                width: area.width.saturating_sub(2),
+                height: 1,
+            };
+            app.register_click(rect, ClickAction::FooterAction(message.clone()));
+        }
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:>3}  ", key),
+                Style::default()
+                    .fg(ui::highlight())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(*label, Style::default().fg(ui::text())),
+        ]));
+    }
+    lines
+}
+
 pub(crate) fn render_scrollbar(
+    app: &mut App,
     frame: &mut Frame,
     area: Rect,
     total_items: usize,
     visible_items: usize,
     offset: usize,
+    target: ScrollTarget,
 ) {
     if total_items > visible_items {
         let scrollbar_area = Rect {
@@ -201,6 +638,9 @@ pub(crate) fn render_scrollbar(
             .end_symbol(Some("▼"));
 
         frame.render_stateful_widget(scrollbar, scrollbar_area, &mut scrollbar_state);
+
+        // Registered last so it wins over the row regions underneath
+        app.register_click(scrollbar_area, ClickAction::Scrollbar(target));
     }
 }
 
@@ -217,16 +657,17 @@ pub(crate) fn render_footer_bar(
 
     for (i, (key, action, msg)) in keys.iter().enumerate() {
         if i > 0 {
-            spans.push(Span::styled("  ", Style::default().fg(ui::MUTED)));
+            spans.push(Span::styled("  ", Style::default().fg(ui::muted())));
             inner_x += 2;
         }
-        let key_len = key.len() as u16;
-        let action_text = format!(" {}", action);
-        let action_len = action_text.len() as u16;
-        let total_len = key_len + action_len;
+        let key_span = Span::styled(*key, Style::default().fg(ui::highlight()));
+        let action_span =
+            Span::styled(format!(" {}", tr(action)), Style::default().fg(ui::muted()));
+        // Display width, since translations are not always ASCII
+        let total_len = (key_span.width() + action_span.width()) as u16;
 
-        spans.push(Span::styled(*key, Style::default().fg(ui::HIGHLIGHT)));
-        spans.push(Span::styled(action_text, Style::default().fg(ui::MUTED)));
+        spans.push(key_span);
+        spans.push(action_span);
 
         if let Some(m) = msg {
             // Register click region: area.x + 1 (left border) + inner_x
@@ -246,3 +687,114 @@ pub(crate) fn render_footer_bar(
 
     frame.render_widget(footer, area);
 }
+
+/// Splits a sub-screen into header, content and footer and draws the
+/// header: the title, a clickable "[Esc] Back", then `notes`. Returns the
+/// content and footer areas.
+pub(crate) fn render_list_frame(
+    app: &mut App,
+    frame: &mut Frame,
+    area: Rect,
+    title: &str,
+    notes: Vec<Span<'_>>,
+) -> (Rect, Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+    let header_area = chunks[0];
+
+    let back_text = "[Esc] Back";
+    let back_x_offset = title.chars().count() + 2;
+    let mut spans = vec![
+        Span::styled(title, Style::default().fg(ui::primary()).bold()),
+        Span::raw("  "),
+        Span::styled(back_text, Style::default().fg(ui::muted())),
+    ];
+    for note in notes {
+        spans.push(Span::raw("  "));
+        spans.push(note);
+    }
+
+    let back_region = Rect {
+        x: header_area.x + 1 + back_x_offset as u16,
+        y: header_area.y,
+        width: back_text.len() as u16,
+        height: header_area.height,
+    };
+    app.register_click(back_region, ClickAction::GoBack);
+
+    let header = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL));
+    frame.render_widget(header, header_area);
+
+    (chunks[1], chunks[2])
+}
+
+/// The first row to show so that `selected` stays within `height` rows,
+/// scrolling as little as needed from `scroll`
+pub(crate) fn scroll_to_keep_visible(
+    scroll: usize,
+    selected: usize,
+    len: usize,
+    height: usize,
+) -> usize {
+    scroll
+        .min(len.saturating_sub(height))
+        .min(selected)
+        .max((selected + 1).saturating_sub(height))
+}
+
+/// Draws `items`, already skipped past `scroll`, in a bordered list titled
+/// `title` and makes each shown row select its item. With no items, `empty`
+/// is shown instead.
+pub(crate) fn render_list(
+    app: &mut App,
+    frame: &mut Frame,
+    area: Rect,
+    title: String,
+    items: Vec<ListItem<'_>>,
+    scroll: usize,
+    empty: &str,
+) {
+    let block = Block::default().borders(Borders::ALL).title(title);
+    if items.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            format!("  {}", empty),
+            Style::default().fg(ui::muted()),
+        ))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let shown = items.len().min(area.height.saturating_sub(2) as usize);
+    frame.render_widget(List::new(items).block(block), area);
+
+    for row in 0..shown {
+        let row_rect = Rect {
+            x: area.x,
+            y: area.y + 1 + row as u16,
+            width: area.width,
+            height: 1,
+        };
+        app.register_click(row_rect, ClickAction::SelectItem(scroll + row));
+    }
+}
+
+/// Right-aligns `count`, such as "3 packs, 2 enabled", inside a footer bar
+pub(crate) fn render_footer_count(frame: &mut Frame, area: Rect, count: String) {
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(3),
+        height: 1,
+    };
+    frame.render_widget(
+        Line::from(Span::styled(count, Style::default().fg(ui::muted()))).right_aligned(),
+        inner,
+    );
+}
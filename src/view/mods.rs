@@ -0,0 +1,139 @@
+use crate::app::App;
+use crate::data::InstanceFolder;
+use crate::message::Message;
+use crate::theme::ui;
+use crate::view::{
+    SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar, render_footer_count, render_list,
+    render_list_frame, scroll_to_keep_visible,
+};
+use ratatui::prelude::*;
+use ratatui::widgets::ListItem;
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let title = match app.selected_instance() {
+        Some(instance) => format!("Mods of {}", instance.name),
+        None => "Mods".to_string(),
+    };
+    // Without a loader the game starts as plain Minecraft and skips mods/
+    let note = if app
+        .selected_instance()
+        .is_some_and(|i| i.mod_loader.is_none())
+    {
+        Span::styled(
+            "No mod loader: these mods won't load",
+            Style::default().fg(ui::warning()),
+        )
+    } else {
+        Span::styled(
+            "Changes apply the next time the game starts",
+            Style::default().fg(ui::muted()),
+        )
+    };
+    let (content, footer) = render_list_frame(app, frame, area, &title, vec![note]);
+
+    render_mod_list(app, frame, content);
+    render_footer(app, frame, footer);
+}
+
+fn render_mod_list(app: &mut App, frame: &mut Frame, area: Rect) {
+    let title = app
+        .selected_instance()
+        .map(|i| i.mods_dir().display().to_string())
+        .unwrap_or_default();
+
+    let selected = app.selected_mod_index;
+    let scroll = scroll_to_keep_visible(
+        app.mod_scroll,
+        selected,
+        app.mods.len(),
+        area.height.saturating_sub(2) as usize,
+    );
+    app.mod_scroll = scroll;
+
+    let items: Vec<ListItem> = app
+        .mods
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .map(|(idx, file)| {
+            let is_selected = idx == selected;
+            let prefix = if is_selected {
+                SELECTED_PREFIX
+            } else {
+                UNSELECTED_PREFIX
+            };
+            let style = match (is_selected, file.enabled) {
+                (true, _) => Style::default()
+                    .fg(ui::primary())
+                    .add_modifier(Modifier::BOLD),
+                (false, true) => Style::default(),
+                (false, false) => Style::default().fg(ui::muted()),
+            };
+            let mut spans = vec![Span::styled(prefix, style)];
+            if app.marked_mods.contains(&file.name) {
+                spans.push(Span::styled(
+                    "+ ",
+                    Style::default().fg(ui::highlight()).bold(),
+                ));
+            }
+            spans.push(Span::styled(
+                if file.enabled { "[x] " } else { "[ ] " },
+                style,
+            ));
+            spans.push(Span::styled(file.name.clone(), style));
+            if let Some(update) = app.mod_updates.iter().find(|u| u.installed == *file) {
+                spans.push(Span::styled(
+                    format!("  -> {}", update.version),
+                    Style::default().fg(ui::highlight()),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    render_list(
+        app,
+        frame,
+        area,
+        title,
+        items,
+        scroll,
+        "No mods yet. Press o to open the mods folder.",
+    );
+}
+
+fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
+    let mut keys: Vec<(&str, &str, Option<Message>)> = vec![
+        ("j/k", "Nav", None),
+        ("Space", "Mark", Some(Message::ToggleModMark)),
+        ("e/Enter", "On/Off", Some(Message::ToggleMods)),
+        ("a", "Add", Some(Message::StartModSearch)),
+        ("u", "Updates", Some(Message::CheckModUpdates)),
+        ("w", "Website", Some(Message::OpenModPage)),
+        (
+            "o",
+            "Open Folder",
+            Some(Message::OpenGameFolder(InstanceFolder::Mods)),
+        ),
+    ];
+    if !app.mod_updates.is_empty() {
+        keys.push(("U", "Update All", Some(Message::UpdateAllMods)));
+    }
+    if app.marked_mods.is_empty() {
+        keys.push(("h/Esc", "Back", Some(Message::Back)));
+    } else {
+        keys.push(("Esc", "Unmark", Some(Message::ClearModMarks)));
+    }
+    render_footer_bar(app, frame, area, &keys);
+
+    // e.g. "42 mods, 40 enabled, 3 updates, 2 marked"
+    let enabled = app.mods.iter().filter(|m| m.enabled).count();
+    let mut count = format!("{} mods, {} enabled", app.mods.len(), enabled);
+    if !app.mod_updates.is_empty() {
+        count.push_str(&format!(", {} updates", app.mod_updates.len()));
+    }
+    if !app.marked_mods.is_empty() {
+        count.push_str(&format!(", {} marked", app.marked_mods.len()));
+    }
+    render_footer_count(frame, area, count);
+}
@@ -0,0 +1,155 @@
+use crate::app::App;
+use crate::data::InstanceFolder;
+use crate::message::Message;
+use crate::theme::ui;
+use crate::view::{
+    SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar, render_footer_count, render_list,
+    render_list_frame, scroll_to_keep_visible,
+};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, ListItem, Paragraph, Wrap};
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let title = match app.selected_instance() {
+        Some(instance) => format!("Resource Packs of {}", instance.name),
+        None => "Resource Packs".to_string(),
+    };
+    let note = Span::styled(
+        "Changes apply the next time the game starts",
+        Style::default().fg(ui::muted()),
+    );
+    let (content, footer) = render_list_frame(app, frame, area, &title, vec![note]);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(content);
+
+    render_pack_list(app, frame, columns[0]);
+    render_pack_details(app, frame, columns[1]);
+    render_footer(app, frame, footer);
+}
+
+fn render_pack_list(app: &mut App, frame: &mut Frame, area: Rect) {
+    let title = app
+        .selected_instance()
+        .map(|i| {
+            i.game_folder(InstanceFolder::ResourcePacks)
+                .display()
+                .to_string()
+        })
+        .unwrap_or_default();
+
+    let selected = app.selected_resource_pack_index;
+    let scroll = scroll_to_keep_visible(
+        app.resource_pack_scroll,
+        selected,
+        app.resource_packs.len(),
+        area.height.saturating_sub(2) as usize,
+    );
+    app.resource_pack_scroll = scroll;
+
+    let items: Vec<ListItem> = app
+        .resource_packs
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .map(|(idx, pack)| {
+            let is_selected = idx == selected;
+            let prefix = if is_selected {
+                SELECTED_PREFIX
+            } else {
+                UNSELECTED_PREFIX
+            };
+            let style = match (is_selected, pack.enabled) {
+                (true, _) => Style::default()
+                    .fg(ui::primary())
+                    .add_modifier(Modifier::BOLD),
+                (false, true) => Style::default(),
+                (false, false) => Style::default().fg(ui::muted()),
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(if pack.enabled { "[x] " } else { "[ ] " }, style),
+                Span::styled(pack.name.clone(), style),
+            ]))
+        })
+        .collect();
+
+    render_list(
+        app,
+        frame,
+        area,
+        title,
+        items,
+        scroll,
+        "No resource packs yet. Press o to open the folder.",
+    );
+}
+
+fn render_pack_details(app: &App, frame: &mut Frame, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title("pack.mcmeta");
+    let Some(pack) = app.resource_packs.get(app.selected_resource_pack_index) else {
+        frame.render_widget(block, area);
+        return;
+    };
+
+    let field = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("{:<13}", label), Style::default().fg(ui::muted())),
+            Span::raw(value),
+        ])
+    };
+    let mut lines = vec![
+        Line::from(Span::styled(
+            pack.name.as_str(),
+            Style::default().fg(ui::primary()).bold(),
+        )),
+        Line::default(),
+        field(
+            "Pack format",
+            pack.pack_format
+                .map(|f| f.to_string())
+                .unwrap_or_else(|| "unknown".to_string()),
+        ),
+        field(
+            "Stored as",
+            if pack.is_dir { "folder" } else { "zip" }.to_string(),
+        ),
+        field(
+            "State",
+            if pack.enabled { "enabled" } else { "disabled" }.to_string(),
+        ),
+        Line::default(),
+    ];
+    match &pack.description {
+        Some(description) => lines.push(Line::raw(description.as_str())),
+        None => lines.push(Line::from(Span::styled(
+            "No pack.mcmeta, or it couldn't be read",
+            Style::default().fg(ui::muted()),
+        ))),
+    }
+
+    let details = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(details, area);
+}
+
+fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
+    let keys: Vec<(&str, &str, Option<Message>)> = vec![
+        ("j/k", "Nav", None),
+        ("e/Enter", "On/Off", Some(Message::ToggleResourcePack)),
+        ("d", "Delete", Some(Message::DeleteResourcePack)),
+        (
+            "o",
+            "Open Folder",
+            Some(Message::OpenGameFolder(InstanceFolder::ResourcePacks)),
+        ),
+        ("h/Esc", "Back", Some(Message::Back)),
+    ];
+    render_footer_bar(app, frame, area, &keys);
+
+    let enabled = app.resource_packs.iter().filter(|p| p.enabled).count();
+    let count = format!("{} packs, {} enabled", app.resource_packs.len(), enabled);
+    render_footer_count(frame, area, count);
+}
@@ -0,0 +1,155 @@
+use crate::app::{App, InputMode};
+use crate::data::InstanceFolder;
+use crate::message::Message;
+use crate::theme::ui;
+use crate::tui::ImagePreview;
+use crate::view::{
+    SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar, render_footer_count, render_list,
+    render_list_frame, scroll_to_keep_visible,
+};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, ListItem, Paragraph, Wrap};
+
+/// Rows kept free above the bottom of the screen, where toasts show up
+/// and an image would cover them
+const TOAST_MARGIN: u16 = 5;
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let title = match app.selected_instance() {
+        Some(instance) => format!("Screenshots of {}", instance.name),
+        None => "Screenshots".to_string(),
+    };
+    let (content, footer) = render_list_frame(app, frame, area, &title, Vec::new());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(content);
+
+    render_screenshot_list(app, frame, columns[0]);
+    render_preview(app, frame, columns[1]);
+    render_footer(app, frame, footer);
+}
+
+fn render_screenshot_list(app: &mut App, frame: &mut Frame, area: Rect) {
+    let title = app
+        .selected_instance()
+        .map(|i| {
+            i.game_folder(InstanceFolder::Screenshots)
+                .display()
+                .to_string()
+        })
+        .unwrap_or_default();
+
+    let selected = app.selected_screenshot_index;
+    let scroll = scroll_to_keep_visible(
+        app.screenshot_scroll,
+        selected,
+        app.screenshots.len(),
+        area.height.saturating_sub(2) as usize,
+    );
+    app.screenshot_scroll = scroll;
+
+    let items: Vec<ListItem> = app
+        .screenshots
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .map(|(idx, screenshot)| {
+            let (prefix, style) = if idx == selected {
+                (
+                    SELECTED_PREFIX,
+                    Style::default()
+                        .fg(ui::primary())
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                (UNSELECTED_PREFIX, Style::default())
+            };
+            ListItem::new(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(screenshot.name.clone(), style),
+            ]))
+        })
+        .collect();
+
+    render_list(
+        app,
+        frame,
+        area,
+        title,
+        items,
+        scroll,
+        "No screenshots yet. Press F2 in game to take one.",
+    );
+}
+
+fn render_preview(app: &mut App, frame: &mut Frame, area: Rect) {
+    let block = Block::default().borders(Borders::ALL).title("Preview");
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let Some(screenshot) = app.screenshots.get(app.selected_screenshot_index) else {
+        return;
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            screenshot.name.as_str(),
+            Style::default().fg(ui::primary()).bold(),
+        )),
+        Line::from(Span::styled(
+            screenshot.summary(),
+            Style::default().fg(ui::muted()),
+        )),
+    ];
+    if app.image_protocol.is_none() {
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled(
+            "No image preview in this terminal; kitty, Ghostty, iTerm2 and WezTerm show one.",
+            Style::default().fg(ui::muted()),
+        )));
+    }
+    let text_height = lines.len() as u16 + 1;
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+
+    // Dialogs and overlays are drawn as text, which the image would hide
+    let covered =
+        app.input_mode != InputMode::Normal || app.tour_step.is_some() || app.show_debug_overlay;
+    let bottom = frame.area().height.saturating_sub(TOAST_MARGIN);
+    let image_area = Rect {
+        x: inner.x,
+        y: inner.y + text_height,
+        width: inner.width,
+        height: bottom
+            .min(inner.bottom())
+            .saturating_sub(inner.y + text_height),
+    };
+    if !covered
+        && app.image_protocol.is_some()
+        && !image_area.is_empty()
+        && let Some(dimensions) = screenshot.dimensions
+    {
+        app.image_preview = Some(ImagePreview {
+            path: screenshot.path.clone(),
+            dimensions,
+            area: image_area,
+        });
+    }
+}
+
+fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
+    let keys: Vec<(&str, &str, Option<Message>)> = vec![
+        ("j/k", "Nav", None),
+        ("o/Enter", "View", Some(Message::OpenScreenshot)),
+        ("d", "Delete", Some(Message::DeleteScreenshot)),
+        (
+            "O",
+            "Open Folder",
+            Some(Message::OpenGameFolder(InstanceFolder::Screenshots)),
+        ),
+        ("h/Esc", "Back", Some(Message::Back)),
+    ];
+    render_footer_bar(app, frame, area, &keys);
+
+    let count = format!("{} screenshots", app.screenshots.len());
+    render_footer_count(frame, area, count);
+}
@@ -0,0 +1,107 @@
+use crate::app::{App, ClickAction};
+use crate::theme::ui;
+use crate::view::{SELECTED_PREFIX, UNSELECTED_PREFIX, centered_rect};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let width = 70.min(area.width.saturating_sub(4));
+    let height = 24.min(area.height.saturating_sub(4));
+    let search_area = centered_rect(width, height, area);
+
+    // Click outside dismisses, click inside absorbs
+    app.register_click(area, ClickAction::DismissOverlay);
+    app.register_click(search_area, ClickAction::Noop);
+
+    frame.render_widget(Clear, search_area);
+
+    // Query line, then results under a header per group
+    let mut lines: Vec<Line> = vec![
+        Line::from(vec![
+            Span::styled(" > ", Style::default().fg(ui::highlight())),
+            Span::styled(app.input_buffer.as_str(), Style::default().fg(ui::text())),
+            Span::styled("_", Style::default().fg(ui::highlight())),
+        ]),
+        Line::from(""),
+    ];
+    // Line of each result, to scroll the selection into view and register clicks
+    let mut result_lines = Vec::new();
+    let mut group = None;
+    for (pos, &idx) in app.search_results.iter().enumerate() {
+        let item = &app.search_index[idx];
+        if group != Some(item.kind) {
+            group = Some(item.kind);
+            lines.push(Line::from(Span::styled(
+                format!(" {}", item.kind.label()),
+                Style::default()
+                    .fg(ui::highlight())
+                    .add_modifier(Modifier::BOLD),
+            )));
+        }
+        let selected = pos == app.selected_search_result;
+        let (prefix, style) = if selected {
+            (
+                SELECTED_PREFIX,
+                Style::default()
+                    .fg(ui::primary())
+                    .add_modifier(Modifier::BOLD),
+            )
+        } else {
+            (UNSELECTED_PREFIX, Style::default().fg(ui::text()))
+        };
+        result_lines.push(lines.len());
+        lines.push(Line::from(vec![
+            Span::styled(prefix, style),
+            Span::styled(item.label.as_str(), style),
+            Span::styled(
+                format!("  {}", item.detail.trim()),
+                Style::default().fg(ui::muted()),
+            ),
+        ]));
+    }
+    if app.search_results.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "   No matches",
+            Style::default().fg(ui::muted()),
+        )));
+    }
+
+    // Keep the query line and scroll the results under it
+    let inner_height = height.saturating_sub(2) as usize;
+    let selected_line = result_lines
+        .get(app.selected_search_result)
+        .copied()
+        .unwrap_or(0);
+    let scroll = (selected_line + 1).saturating_sub(inner_height);
+    let query: Vec<Line> = lines.drain(..2).collect();
+    let visible: Vec<Line> = query
+        .into_iter()
+        .chain(lines.into_iter().skip(scroll))
+        .take(inner_height)
+        .collect();
+
+    let widget = Paragraph::new(visible).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Search (Enter to open, Esc to close)")
+            .border_style(Style::default().fg(ui::dialog_border())),
+    );
+
+    frame.render_widget(widget, search_area);
+
+    for (pos, &line) in result_lines.iter().enumerate() {
+        let Some(row) = (line - 2).checked_sub(scroll).map(|r| r + 2) else {
+            continue;
+        };
+        if row >= inner_height {
+            break;
+        }
+        let rect = Rect {
+            x: search_area.x + 1,
+            y: search_area.y + 1 + row as u16,
+            width: search_area.width.saturating_sub(2),
+            height: 1,
+        };
+        app.register_click(rect, ClickAction::SearchResult(pos));
+    }
+}
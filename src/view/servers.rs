@@ -1,4 +1,6 @@
-use crate::app::{App, ClickAction};
+use crate::actions::find_url;
+use crate::app::{App, ClickAction, ScrollTarget};
+use crate::data::ResourcePackPolicy;
 use crate::message::Message;
 use crate::theme::ui;
 use crate::view::{
@@ -7,21 +9,31 @@ use crate::view::{
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 
+/// Rows shown in the LAN section before it stops growing
+const MAX_LAN_ROWS: usize = 4;
+
+/// After each server's address; clicking one sets it as the join target
+const JOIN_MARKER: &str = "[J]";
+const NO_JOIN_MARKER: &str = "[ ]";
+
 pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let lan_rows = app.lan_games.len().clamp(1, MAX_LAN_ROWS) as u16;
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(3), // Header
-            Constraint::Length(3), // Join on launch status
-            Constraint::Min(0),    // Server list
-            Constraint::Length(3), // Footer
+            Constraint::Length(3),            // Header
+            Constraint::Length(3),            // Join on launch status
+            Constraint::Min(0),               // Server list
+            Constraint::Length(lan_rows + 2), // LAN games
+            Constraint::Length(3),            // Footer
         ])
         .split(area);
 
     render_header(app, frame, chunks[0]);
     render_join_status(app, frame, chunks[1]);
     render_server_list(app, frame, chunks[2]);
-    render_footer(app, frame, chunks[3]);
+    render_lan_games(app, frame, chunks[3]);
+    render_footer(app, frame, chunks[4]);
 }
 
 fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
@@ -34,10 +46,10 @@ fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
     let back_x_offset = instance_name.len() + " - Servers".len() + 2;
 
     let header = Paragraph::new(Line::from(vec![
-        Span::styled(instance_name, Style::default().fg(ui::PRIMARY).bold()),
-        Span::styled(" - Servers", Style::default().fg(ui::PRIMARY)),
+        Span::styled(instance_name, Style::default().fg(ui::primary()).bold()),
+        Span::styled(" - Servers", Style::default().fg(ui::primary())),
         Span::raw("  "),
-        Span::styled(back_text, Style::default().fg(ui::MUTED)),
+        Span::styled(back_text, Style::default().fg(ui::muted())),
     ]))
     .block(Block::default().borders(Borders::ALL));
 
@@ -61,63 +73,124 @@ fn render_join_status(app: &mut App, frame: &mut Frame, area: Rect) {
         .unwrap_or((false, "None"));
 
     let checkbox = if enabled { "[x]" } else { "[ ]" };
+    let conflict = app.join_conflict();
+    let matched = app
+        .servers
+        .iter()
+        .find(|s| s.ip == address)
+        .map(|s| s.name.clone());
 
-    let status = Paragraph::new(Line::from(vec![
-        Span::raw("Join on Launch: "),
+    let label = "Join on Launch: ";
+    let mut spans = vec![
+        Span::raw(label),
         Span::styled(
             checkbox,
             if enabled {
-                Style::default().fg(ui::ACTIVE)
+                Style::default().fg(ui::active())
             } else {
-                Style::default().fg(ui::MUTED)
+                Style::default().fg(ui::muted())
             },
         ),
         Span::raw(" "),
         Span::styled(
             address,
             if enabled {
-                Style::default().fg(ui::ACTIVE)
+                Style::default().fg(ui::active())
             } else {
-                Style::default().fg(ui::MUTED)
+                Style::default().fg(ui::muted())
             },
         ),
-    ]))
-    .block(Block::default().borders(Borders::ALL));
+    ];
+    if let Some(name) = matched {
+        spans.push(Span::styled(
+            format!(" ({})", name),
+            Style::default().fg(ui::muted()),
+        ));
+    }
+
+    let mut fix_region = None;
+    if let Some(conflict) = conflict {
+        spans.push(Span::raw("  "));
+        spans.push(Span::styled(
+            format!("[! {}]", conflict.label()),
+            Style::default().fg(ui::warning()).bold(),
+        ));
+        let fix = Span::styled(
+            format!(" F: {}", conflict.fix_label()),
+            Style::default().fg(ui::muted()),
+        );
+        let x = spans.iter().map(Span::width).sum::<usize>() as u16;
+        fix_region = Some((x, fix.width() as u16));
+        spans.push(fix);
+    }
+
+    let status = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL));
 
     frame.render_widget(status, area);
 
-    // Register click region for the join checkbox area
-    app.register_click(area, ClickAction::JoinCheckbox);
+    // Only the checkbox toggles; the conflict fix is its own target
+    let inner_x = area.x + 1;
+    app.register_click(
+        Rect {
+            x: inner_x + label.len() as u16,
+            y: area.y + 1,
+            width: checkbox.len() as u16,
+            height: 1,
+        },
+        ClickAction::JoinCheckbox,
+    );
+    if let Some((x, width)) = fix_region {
+        app.register_click(
+            Rect {
+                x: inner_x + x,
+                y: area.y + 1,
+                width,
+                height: 1,
+            },
+            ClickAction::FooterAction(Message::FixJoinConflict),
+        );
+    }
 }
 
 fn render_server_list(app: &mut App, frame: &mut Frame, area: Rect) {
     let inner_height = area.height.saturating_sub(2) as usize;
 
-    let join_address = app
+    let join = app
         .selected_instance()
         .and_then(|i| i.server_join.as_ref())
-        .filter(|sj| sj.enabled)
-        .map(|sj| sj.address.as_str());
+        .map(|sj| (sj.address.as_str(), sj.enabled));
 
-    let items: Vec<ListItem> = app
-        .servers
+    let visible = app.visible_server_indices();
+    let items: Vec<ListItem> = visible
         .iter()
-        .enumerate()
-        .map(|(idx, server)| {
+        .map(|&idx| {
+            let server = &app.servers[idx];
             let is_selected = idx == app.selected_server_index;
-            let is_join_server = join_address.map(|a| a == server.ip).unwrap_or(false);
+            let join_state = join.filter(|(a, _)| *a == server.ip).map(|(_, on)| on);
 
             let prefix = if is_selected {
                 SELECTED_PREFIX
             } else {
                 UNSELECTED_PREFIX
             };
-            let join_marker = if is_join_server { " [J]" } else { "" };
+            // A disabled join target keeps its marker, dimmed
+            let (join_marker, join_style) = match join_state {
+                Some(true) => (JOIN_MARKER, Style::default().fg(ui::active())),
+                Some(false) => (JOIN_MARKER, Style::default().fg(ui::muted())),
+                None => (NO_JOIN_MARKER, Style::default().fg(ui::muted())),
+            };
+            let pack_marker = match server.accept_textures {
+                ResourcePackPolicy::Prompt => String::new(),
+                policy => format!(" [RP: {}]", policy.label()),
+            };
+            let hidden_marker = if server.hidden { " [hidden]" } else { "" };
 
             let style = if is_selected {
                 Style::default()
-                    .fg(ui::PRIMARY)
+                    .fg(ui::primary())
                     .add_modifier(Modifier::BOLD)
+            } else if server.hidden {
+                Style::default().fg(ui::muted())
             } else {
                 Style::default()
             };
@@ -125,29 +198,48 @@ fn render_server_list(app: &mut App, frame: &mut Frame, area: Rect) {
             ListItem::new(Line::from(vec![
                 Span::styled(prefix, style),
                 Span::styled(format!("{:<20}", truncate(&server.name, 20)), style),
-                Span::styled(&server.ip, Style::default().fg(ui::MUTED)),
-                Span::styled(join_marker, Style::default().fg(ui::ACTIVE)),
+                Span::styled(&server.ip, Style::default().fg(ui::muted())),
+                Span::raw(" "),
+                Span::styled(join_marker, join_style),
+                Span::styled(pack_marker, Style::default().fg(ui::muted())),
+                Span::styled(hidden_marker, Style::default().fg(ui::muted()).italic()),
             ]))
         })
         .collect();
 
     let total_items = items.len();
+    let hidden_count = app.hidden_server_count();
+    let title = if hidden_count > 0 && !app.show_hidden_servers {
+        format!("Servers ({} hidden)", hidden_count)
+    } else {
+        "Servers".to_string()
+    };
 
-    let list = if items.is_empty() {
+    let list = if let Some(error) = &app.servers_error {
         List::new(vec![ListItem::new(Span::styled(
-            "  No servers. Press 'a' to add one.",
-            Style::default().fg(ui::MUTED),
+            format!("  {}", error),
+            Style::default().fg(ui::error()),
+        ))])
+    } else if items.is_empty() {
+        let msg = if hidden_count > 0 {
+            "  No visible servers. Press 'H' to show hidden ones."
+        } else {
+            "  No servers. Press 'a' to add one."
+        };
+        List::new(vec![ListItem::new(Span::styled(
+            msg,
+            Style::default().fg(ui::muted()),
         ))])
     } else {
         List::new(items)
     }
-    .block(Block::default().borders(Borders::ALL).title("Servers"));
+    .block(Block::default().borders(Borders::ALL).title(title));
 
     frame.render_widget(list, area);
 
     // Register click regions for each visible server item
-    for idx in 0..app.servers.len() {
-        let row_y = area.y + 1 + idx as u16;
+    for (row, &idx) in visible.iter().enumerate() {
+        let row_y = area.y + 1 + row as u16;
         if row_y >= area.y + area.height.saturating_sub(1) {
             break;
         }
@@ -158,18 +250,81 @@ fn render_server_list(app: &mut App, frame: &mut Frame, area: Rect) {
             height: 1,
         };
         app.register_click(row_rect, ClickAction::SelectItem(idx));
+
+        // The marker after the address picks this server to join
+        let server = &app.servers[idx];
+        let name = Span::raw(format!("{:<20}", truncate(&server.name, 20)));
+        let marker_x = UNSELECTED_PREFIX.len() + name.width() + Span::raw(&server.ip).width() + 1;
+        let marker_rect = Rect {
+            x: area.x + 1 + marker_x as u16,
+            y: row_y,
+            width: JOIN_MARKER.len() as u16,
+            height: 1,
+        };
+        if marker_rect.right() < area.right() {
+            app.register_click(marker_rect, ClickAction::JoinServer(idx));
+        }
     }
 
     // Scrollbar
+    let selected_row = visible
+        .iter()
+        .position(|&i| i == app.selected_server_index)
+        .unwrap_or(0);
     render_scrollbar(
+        app,
         frame,
         area,
         total_items,
         inner_height,
-        app.selected_server_index.saturating_sub(inner_height / 2),
+        selected_row.saturating_sub(inner_height / 2),
+        ScrollTarget::Servers,
     );
 }
 
+fn render_lan_games(app: &mut App, frame: &mut Frame, area: Rect) {
+    let items: Vec<ListItem> = if let Some(e) = &app.lan_error {
+        vec![ListItem::new(Span::styled(
+            format!("  LAN discovery unavailable: {}", e),
+            Style::default().fg(ui::error()),
+        ))]
+    } else if app.lan_games.is_empty() {
+        vec![ListItem::new(Span::styled(
+            "  Listening for LAN worlds...",
+            Style::default().fg(ui::muted()),
+        ))]
+    } else {
+        app.lan_games
+            .iter()
+            .enumerate()
+            .map(|(idx, discovered)| {
+                let is_selected = idx == app.selected_lan_index;
+                let prefix = if is_selected {
+                    SELECTED_PREFIX
+                } else {
+                    UNSELECTED_PREFIX
+                };
+                let style = if is_selected {
+                    Style::default().fg(ui::active()).bold()
+                } else {
+                    Style::default()
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(prefix, style),
+                    Span::styled(
+                        format!("{:<20}", truncate(&discovered.game.motd, 20)),
+                        style,
+                    ),
+                    Span::styled(&discovered.game.address, Style::default().fg(ui::muted())),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title("LAN"));
+    frame.render_widget(list, area);
+}
+
 fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
     let keys: &[(&str, &str, Option<Message>)] = &[
         ("j/k", "Nav", None),
@@ -178,7 +333,38 @@ fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
         ("a", "Add", Some(Message::AddServer)),
         ("e", "Edit", Some(Message::EditServer)),
         ("d", "Del", Some(Message::DeleteServer)),
+        ("p", "Packs", Some(Message::CycleResourcePackPolicy)),
         ("h/Esc", "Back", Some(Message::Back)),
     ];
-    render_footer_bar(app, frame, area, keys);
+    let mut keys = keys.to_vec();
+    if app
+        .selected_server()
+        .is_some_and(|s| find_url(&s.name).is_some())
+    {
+        keys.insert(
+            keys.len() - 1,
+            ("w", "Website", Some(Message::OpenServerWebsite)),
+        );
+    }
+    if !app.lan_games.is_empty() {
+        keys.insert(2, ("n", "Join LAN", Some(Message::JoinLanGame)));
+        if app.lan_games.len() > 1 {
+            keys.insert(3, ("Tab", "Next LAN", Some(Message::NextLanGame)));
+        }
+    }
+    if app.hidden_server_count() > 0 {
+        let label = if app.show_hidden_servers {
+            "Hide Hidden"
+        } else {
+            "Show Hidden"
+        };
+        keys.insert(
+            keys.len() - 1,
+            ("H", label, Some(Message::ToggleHiddenServers)),
+        );
+    }
+    if app.join_conflict().is_some() {
+        keys.insert(3, ("F", "Fix Join", Some(Message::FixJoinConflict)));
+    }
+    render_footer_bar(app, frame, area, &keys);
 }
@@ -0,0 +1,126 @@
+use crate::app::{App, ClickAction};
+use crate::data::GLOBAL_SETTINGS;
+use crate::message::Message;
+use crate::theme::ui;
+use crate::view::{SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+
+const LABEL_WIDTH: usize = 18;
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+
+    render_header(app, frame, chunks[0]);
+    render_setting_list(app, frame, chunks[1]);
+    render_footer(app, frame, chunks[2]);
+}
+
+fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
+    let title = "Launcher Settings";
+    let back_text = "[Esc] Back";
+    let back_x_offset = title.len() + 2;
+    let spans = vec![
+        Span::styled(title, Style::default().fg(ui::primary()).bold()),
+        Span::raw("  "),
+        Span::styled(back_text, Style::default().fg(ui::muted())),
+        Span::raw("  "),
+        Span::styled(
+            "Changes apply when PrismLauncher restarts",
+            Style::default().fg(ui::muted()),
+        ),
+    ];
+
+    let back_region = Rect {
+        x: area.x + 1 + back_x_offset as u16,
+        y: area.y,
+        width: back_text.len() as u16,
+        height: area.height,
+    };
+    app.register_click(back_region, ClickAction::GoBack);
+
+    let header = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL));
+
+    frame.render_widget(header, area);
+}
+
+fn render_setting_list(app: &mut App, frame: &mut Frame, area: Rect) {
+    let items: Vec<ListItem> = GLOBAL_SETTINGS
+        .iter()
+        .enumerate()
+        .map(|(idx, def)| {
+            let is_selected = idx == app.selected_setting_index;
+            let prefix = if is_selected {
+                SELECTED_PREFIX
+            } else {
+                UNSELECTED_PREFIX
+            };
+            let style = if is_selected {
+                Style::default()
+                    .fg(ui::primary())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+
+            let mut spans = vec![
+                Span::styled(prefix, style),
+                Span::styled(format!("{:<width$}", def.label, width = LABEL_WIDTH), style),
+                match (
+                    app.prism_settings.resolved_path(&app.data_dir, def.key),
+                    app.prism_settings.get(def.key),
+                ) {
+                    (Some(path), _) => {
+                        Span::styled(path.display().to_string(), Style::default().fg(ui::text()))
+                    }
+                    (None, Some(value)) => {
+                        Span::styled(value.to_string(), Style::default().fg(ui::text()))
+                    }
+                    (None, None) => Span::styled("Default", Style::default().fg(ui::muted())),
+                },
+            ];
+            if !def.editable {
+                spans.push(Span::styled(
+                    " [read-only]",
+                    Style::default().fg(ui::muted()),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+
+    let title = app.data_dir.join("prismlauncher.cfg").display().to_string();
+    let list = List::new(items).block(Block::default().borders(Borders::ALL).title(title));
+
+    frame.render_widget(list, area);
+
+    for idx in 0..GLOBAL_SETTINGS.len() {
+        let row_y = area.y + 1 + idx as u16;
+        if row_y >= area.y + area.height.saturating_sub(1) {
+            break;
+        }
+        let row_rect = Rect {
+            x: area.x,
+            y: row_y,
+            width: area.width,
+            height: 1,
+        };
+        app.register_click(row_rect, ClickAction::SelectItem(idx));
+    }
+}
+
+fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
+    let keys: &[(&str, &str, Option<Message>)] = &[
+        ("j/k", "Nav", None),
+        ("e/Enter", "Edit", Some(Message::EditSetting)),
+        ("h/Esc", "Back", Some(Message::Back)),
+    ];
+    render_footer_bar(app, frame, area, keys);
+}
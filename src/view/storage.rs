@@ -0,0 +1,113 @@
+use crate::app::{App, ClickAction};
+use crate::data::{format, format_size};
+use crate::i18n::tr;
+use crate::theme::ui;
+use crate::view::centered_rect;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+/// Instances listed by name; the smaller ones are added up on one line
+const LISTED_INSTANCES: usize = 8;
+
+/// Where the space in the data directory goes: each large instance, then
+/// the shared libraries, assets and icons
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let lines = storage_lines(app);
+    let width = 64.min(area.width.saturating_sub(4));
+    let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(2));
+    let panel_area = centered_rect(width, height, area);
+
+    app.register_click(area, ClickAction::DismissOverlay);
+    app.register_click(panel_area, ClickAction::Noop);
+
+    let lines: Vec<Line> = lines
+        .into_iter()
+        .map(|line| {
+            // Instances are indented under their total
+            let color = if line.starts_with("  ") {
+                ui::muted()
+            } else {
+                ui::text()
+            };
+            Line::styled(line, Style::default().fg(color))
+        })
+        .collect();
+    let keys = Line::from(vec![
+        Span::styled(" r", Style::default().fg(ui::active())),
+        Span::styled(
+            format!(" {}  ", tr("rescan")),
+            Style::default().fg(ui::muted()),
+        ),
+        Span::styled("Esc", Style::default().fg(ui::active())),
+        Span::styled(
+            format!(" {} ", tr("close")),
+            Style::default().fg(ui::muted()),
+        ),
+    ]);
+
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!(" {} ", tr("Storage")))
+            .title_bottom(keys.right_aligned())
+            .border_style(Style::default().fg(ui::primary())),
+    );
+
+    frame.render_widget(Clear, panel_area);
+    frame.render_widget(panel, panel_area);
+}
+
+/// The overview as text, also read out in the screen reader mode
+pub fn storage_lines(app: &App) -> Vec<String> {
+    let mut lines = vec![app.data_dir.display().to_string()];
+    let Some(usage) = &app.storage_usage else {
+        lines.push(tr("Adding up folder sizes...").to_string());
+        return lines;
+    };
+    let total = usage.total();
+    let scanned = if app.storage_scanning {
+        tr("scanning again...").to_string()
+    } else {
+        let scanned_at = chrono::DateTime::<chrono::Local>::from(usage.scanned_at);
+        format!("{} {}", tr("scanned"), format::age(scanned_at))
+    };
+    lines.push(format!(
+        "{:<28}{:>10}  {}",
+        tr("Total"),
+        format_size(total),
+        scanned
+    ));
+    lines.push(String::new());
+
+    let row = |label: &str, size: u64| {
+        let label: String = label.chars().take(27).collect();
+        format!(
+            "{:<28}{:>10}  {:>3}%",
+            label,
+            format_size(size),
+            percent(size, total)
+        )
+    };
+    lines.push(row(tr("Instances"), usage.instances_total()));
+    for (name, size) in usage.instances.iter().take(LISTED_INSTANCES) {
+        lines.push(row(&format!("  {}", name), *size));
+    }
+    let rest = &usage.instances[usage.instances.len().min(LISTED_INSTANCES)..];
+    if !rest.is_empty() {
+        let size = rest.iter().map(|(_, size)| size).sum();
+        lines.push(row(&format!("  {} {}", rest.len(), tr("more")), size));
+    }
+    for (label, size) in [
+        ("Libraries", usage.libraries),
+        ("Assets", usage.assets),
+        ("Icons", usage.icons),
+        ("Other", usage.other),
+    ] {
+        lines.push(row(tr(label), size));
+    }
+    lines
+}
+
+fn percent(size: u64, total: u64) -> u64 {
+    (size * 100).checked_div(total).unwrap_or(0)
+}
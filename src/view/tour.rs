@@ -0,0 +1,103 @@
+use crate::app::{App, ClickAction};
+use crate::i18n::tr;
+use crate::message::Message;
+use crate::theme::ui;
+use crate::view::centered_rect;
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap};
+
+struct TourStep {
+    title: &'static str,
+    body: &'static str,
+}
+
+const STEPS: &[TourStep] = &[
+    TourStep {
+        title: "Welcome to prism-tui",
+        body: "Your PrismLauncher instances, accounts and servers, in the terminal. \
+               This short tour covers the essentials. You can take it again any time \
+               from the help screen (?).",
+    },
+    TourStep {
+        title: "Tabs",
+        body: "The bar at the top switches between Instances, Accounts, Servers and \
+               Logs. Click a tab or use the keys in each screen's footer: a for \
+               accounts, s for servers, L for logs. h or Esc goes back.",
+    },
+    TourStep {
+        title: "Launching",
+        body: "Move with j/k or the arrow keys and press l or Enter to launch the \
+               selected instance with the account shown in the header. Press c to \
+               switch accounts, i for instance details, and x to stop a running game.",
+    },
+    TourStep {
+        title: "Search",
+        body: "Press / to filter the current list as you type. Ctrl+P searches \
+               instances, accounts, servers and log files at once and jumps to the \
+               result.",
+    },
+    TourStep {
+        title: "Servers",
+        body: "Press s on an instance to manage its servers: a adds one, l joins it, \
+               and J makes the instance join it every time it launches. Worlds opened \
+               to LAN show up here too.",
+    },
+];
+
+/// Number of tour steps, for moving through them
+pub const TOUR_STEP_COUNT: usize = STEPS.len();
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let Some(index) = app.tour_step else {
+        return;
+    };
+    let step = &STEPS[index.min(STEPS.len() - 1)];
+
+    let width = 60.min(area.width.saturating_sub(4));
+    let height = 11.min(area.height.saturating_sub(2));
+    let tour_area = centered_rect(width, height, area);
+
+    // The tour is modal: clicks outside do nothing, clicks inside advance
+    app.register_click(area, ClickAction::Noop);
+    app.register_click(tour_area, ClickAction::FooterAction(Message::TourStep(1)));
+
+    frame.render_widget(Clear, tour_area);
+
+    let last = index + 1 == STEPS.len();
+    let lines = vec![
+        Line::from(""),
+        Line::from(Span::styled(tr(step.body), Style::default().fg(ui::text()))),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("Enter", Style::default().fg(ui::active())),
+            Span::styled(
+                format!(" {}  ", tr(if last { "finish" } else { "next" })),
+                Style::default().fg(ui::muted()),
+            ),
+            Span::styled("h", Style::default().fg(ui::active())),
+            Span::styled(
+                format!(" {}  ", tr("back")),
+                Style::default().fg(ui::muted()),
+            ),
+            Span::styled("Esc", Style::default().fg(ui::active())),
+            Span::styled(format!(" {}", tr("skip")), Style::default().fg(ui::muted())),
+        ]),
+    ];
+
+    let tour = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!(
+                    " {} ({}/{}) ",
+                    tr(step.title),
+                    index + 1,
+                    STEPS.len()
+                ))
+                .border_style(Style::default().fg(ui::help_border()))
+                .padding(Padding::horizontal(1)),
+        )
+        .wrap(Wrap { trim: true });
+
+    frame.render_widget(tour, tour_area);
+}
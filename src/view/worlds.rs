@@ -0,0 +1,213 @@
+use crate::app::{App, ClickAction};
+use crate::data::InstanceFolder;
+use crate::message::Message;
+use crate::theme::ui;
+use crate::view::{SELECTED_PREFIX, UNSELECTED_PREFIX, render_footer_bar};
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
+
+pub fn render(app: &mut App, frame: &mut Frame, area: Rect) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(area);
+    let content = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[1]);
+
+    render_header(app, frame, chunks[0]);
+    render_world_list(app, frame, content[0]);
+    render_backup_list(app, frame, content[1]);
+    render_footer(app, frame, chunks[2]);
+}
+
+fn render_header(app: &mut App, frame: &mut Frame, area: Rect) {
+    let title = match app.selected_instance() {
+        Some(instance) => format!("Worlds of {}", instance.name),
+        None => "Worlds".to_string(),
+    };
+    let back_text = "[Esc] Back";
+    let back_x_offset = title.chars().count() + 2;
+    let spans = vec![
+        Span::styled(title, Style::default().fg(ui::primary()).bold()),
+        Span::raw("  "),
+        Span::styled(back_text, Style::default().fg(ui::muted())),
+        Span::raw("  "),
+        Span::styled(
+            "Restored worlds go next to the current ones",
+            Style::default().fg(ui::muted()),
+        ),
+    ];
+
+    let back_region = Rect {
+        x: area.x + 1 + back_x_offset as u16,
+        y: area.y,
+        width: back_text.len() as u16,
+        height: area.height,
+    };
+    app.register_click(back_region, ClickAction::GoBack);
+
+    let header = Paragraph::new(Line::from(spans)).block(Block::default().borders(Borders::ALL));
+
+    frame.render_widget(header, area);
+}
+
+/// The pane's block, its border lit while keys move through it
+fn pane(title: String, focused: bool) -> Block<'static> {
+    let border = if focused { ui::primary() } else { ui::muted() };
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border))
+        .title(title)
+}
+
+/// First row to show so `selected` stays on screen, scrolling as little as
+/// needed
+fn scroll_to(scroll: usize, selected: usize, len: usize, height: usize) -> usize {
+    scroll
+        .min(len.saturating_sub(height))
+        .min(selected)
+        .max((selected + 1).saturating_sub(height))
+}
+
+fn row_style(selected: bool, focused: bool) -> (&'static str, Style) {
+    match (selected, focused) {
+        (true, true) => (
+            SELECTED_PREFIX,
+            Style::default()
+                .fg(ui::primary())
+                .add_modifier(Modifier::BOLD),
+        ),
+        (true, false) => (SELECTED_PREFIX, Style::default().fg(ui::text())),
+        (false, _) => (UNSELECTED_PREFIX, Style::default().fg(ui::text())),
+    }
+}
+
+fn render_world_list(app: &mut App, frame: &mut Frame, area: Rect) {
+    let focused = !app.backups_focused;
+    let block = pane("saves".to_string(), focused);
+
+    if app.worlds.is_empty() {
+        let empty = Paragraph::new(Span::styled(
+            "  No worlds yet",
+            Style::default().fg(ui::muted()),
+        ))
+        .block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let selected = app.selected_world_index;
+    let scroll = scroll_to(app.world_scroll, selected, app.worlds.len(), inner_height);
+    app.world_scroll = scroll;
+
+    let items: Vec<ListItem> = app
+        .worlds
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .map(|(idx, world)| {
+            let (prefix, style) = row_style(idx == selected, focused);
+            ListItem::new(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(world.as_str(), style),
+            ]))
+        })
+        .collect();
+    let shown = items.len().min(inner_height);
+
+    frame.render_widget(List::new(items).block(block), area);
+
+    for row in 0..shown {
+        let row_rect = Rect {
+            x: area.x,
+            y: area.y + 1 + row as u16,
+            width: area.width,
+            height: 1,
+        };
+        app.register_click(row_rect, ClickAction::SelectItem(scroll + row));
+    }
+}
+
+fn render_backup_list(app: &mut App, frame: &mut Frame, area: Rect) {
+    let focused = app.backups_focused;
+    let block = pane("Backups".to_string(), focused);
+
+    if app.backups.is_empty() {
+        let text = if app.backups_loading {
+            "  Reading backups..."
+        } else {
+            "  No FTB Backups, Simple Backups or prism-tui backups yet"
+        };
+        let empty =
+            Paragraph::new(Span::styled(text, Style::default().fg(ui::muted()))).block(block);
+        frame.render_widget(empty, area);
+        return;
+    }
+
+    let inner_height = area.height.saturating_sub(2) as usize;
+    let selected = app.selected_backup_index;
+    let scroll = scroll_to(app.backup_scroll, selected, app.backups.len(), inner_height);
+    app.backup_scroll = scroll;
+
+    let items: Vec<ListItem> = app
+        .backups
+        .iter()
+        .enumerate()
+        .skip(scroll)
+        .map(|(idx, backup)| {
+            let (prefix, style) = row_style(idx == selected, focused);
+            ListItem::new(Line::from(vec![
+                Span::styled(prefix, style),
+                Span::styled(backup.summary(), style),
+            ]))
+        })
+        .collect();
+    let shown = items.len().min(inner_height);
+
+    frame.render_widget(List::new(items).block(block), area);
+
+    for row in 0..shown {
+        let row_rect = Rect {
+            x: area.x,
+            y: area.y + 1 + row as u16,
+            width: area.width,
+            height: 1,
+        };
+        app.register_click(row_rect, ClickAction::SelectBackup(scroll + row));
+    }
+}
+
+fn render_footer(app: &mut App, frame: &mut Frame, area: Rect) {
+    let keys: Vec<(&str, &str, Option<Message>)> = vec![
+        ("j/k", "Nav", None),
+        ("Tab", "Switch", Some(Message::SwitchWorldsPane)),
+        ("b", "Back Up", Some(Message::BackupWorld)),
+        ("r", "Restore", Some(Message::RestoreBackup)),
+        (
+            "o",
+            "Open Folder",
+            Some(Message::OpenGameFolder(InstanceFolder::Saves)),
+        ),
+        ("h/Esc", "Back", Some(Message::Back)),
+    ];
+    render_footer_bar(app, frame, area, &keys);
+
+    let count = format!("{} worlds, {} backups", app.worlds.len(), app.backups.len());
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(3),
+        height: 1,
+    };
+    frame.render_widget(
+        Line::from(Span::styled(count, Style::default().fg(ui::muted()))).right_aligned(),
+        inner,
+    );
+}